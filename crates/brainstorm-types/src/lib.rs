@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+pub mod ops;
+
 pub const RESIZE_HANDLE_SIZE: f64 = 8.0;
 
 /// Char-safe filename truncation for display labels.
@@ -14,9 +16,48 @@ pub fn truncate_filename(filename: &str) -> String {
         filename.to_string()
     }
 }
+
+/// Char-safe middle elision for display labels: keeps the start and end of `s`
+/// and replaces the middle with `…`, so the result is at most `max_chars` chars.
+/// Strings that already fit come back unchanged.
+///
+/// URL-aware: when `s` has a `scheme://host` prefix that fits in two thirds of
+/// the budget, the whole prefix is kept so the host stays readable, and the
+/// rest of the budget goes to the tail (`https://example.com/…/final-page`).
+pub fn elide_middle(s: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() <= max_chars {
+        return s.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    // Room left after the ellipsis, split between head and tail.
+    let budget = max_chars - 1;
+    let half = budget.div_ceil(2);
+    let host_prefix = s.find("://").map(|i| {
+        let after = i + 3;
+        let end = s[after..].find('/').map_or(s.len(), |j| after + j);
+        s[..end].chars().count()
+    });
+    let head = match host_prefix {
+        Some(prefix) if prefix <= budget * 2 / 3 => prefix.max(half),
+        _ => half,
+    };
+    let tail = budget - head;
+    let mut out: String = chars[..head].iter().collect();
+    out.push('…');
+    out.extend(&chars[chars.len() - tail..]);
+    out
+}
+
 pub const MIN_NODE_WIDTH: f64 = 50.0;
 pub const MIN_NODE_HEIGHT: f64 = 30.0;
 
+/// Size [`Node::new`] gives a node.
+pub const DEFAULT_NODE_WIDTH: f64 = 200.0;
+pub const DEFAULT_NODE_HEIGHT: f64 = 100.0;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ResizeHandle {
     TopLeft,
@@ -225,12 +266,111 @@ impl std::fmt::Display for ValidationError {
 
 impl std::error::Error for ValidationError {}
 
+/// Error `export_pdf` returns instead of replacing an existing `board.pdf`; the
+/// frontend matches on it to ask before exporting again with `overwrite`.
+pub const PDF_EXISTS_ERROR: &str = "board.pdf already exists";
+
+/// Default link-preview request timeout, in seconds (the "Preview timeout"
+/// setting, and the desktop client before the frontend pushes its settings).
+pub const DEFAULT_PREVIEW_TIMEOUT_SECS: u32 = 10;
+
+/// Shortest and longest link-preview timeouts, in seconds, the setting accepts.
+pub const MIN_PREVIEW_TIMEOUT_SECS: u32 = 1;
+pub const MAX_PREVIEW_TIMEOUT_SECS: u32 = 120;
+
+/// Clamp a requested link-preview timeout into
+/// `MIN_PREVIEW_TIMEOUT_SECS..=MAX_PREVIEW_TIMEOUT_SECS`.
+pub fn clamp_preview_timeout_secs(secs: u64) -> u32 {
+    secs.clamp(
+        MIN_PREVIEW_TIMEOUT_SECS.into(),
+        MAX_PREVIEW_TIMEOUT_SECS.into(),
+    ) as u32
+}
+
+/// Decimal places node coordinates and sizes are rounded to on save.
+pub const SAVE_COORD_DECIMALS: u32 = 2;
+
+/// Round every node's `x`, `y`, `width` and `height` to `decimals` places, so
+/// sub-pixel drag jitter doesn't bloat `board.json` or churn diffs. Applied on
+/// the save path only; loading reads whatever precision the file holds.
+/// Idempotent, and non-finite values pass through unchanged for `validate`.
+pub fn round_board_coords(board: &mut Board, decimals: u32) {
+    let scale = 10f64.powi(decimals as i32);
+    let round = |v: f64| (v * scale).round() / scale;
+    for node in &mut board.nodes {
+        node.x = round(node.x);
+        node.y = round(node.y);
+        node.width = round(node.width);
+        node.height = round(node.height);
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct Board {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<u32>,
     pub nodes: Vec<Node>,
     pub edges: Vec<Edge>,
+    /// Per-board preferences. Omitted from `board.json` while all defaults, so
+    /// boards that never set one serialize exactly as before.
+    #[serde(default, skip_serializing_if = "BoardSettings::is_default")]
+    pub settings: BoardSettings,
+}
+
+/// Preferences that belong to one board and travel with its `board.json`, as
+/// opposed to the per-user UI settings kept in localStorage.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BoardSettings {
+    /// Border color given to nodes created on this board (double-click, image
+    /// paste, edge dragged to empty canvas). `None` leaves the type default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_node_color: Option<String>,
+    /// Whether edges have a direction. `false` makes the board an association
+    /// map: no arrowheads, `a–b` and `b–a` count as the same edge, and the graph
+    /// helpers in [`ops`] ignore direction. Defaults to `true` (absent key).
+    #[serde(default = "default_directed", skip_serializing_if = "is_directed")]
+    pub directed: bool,
+    /// Groups laid out as an auto-stacked column: their members sit top to
+    /// bottom, evenly spaced, in the order of their `y`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stacked_groups: Vec<String>,
+}
+
+fn default_directed() -> bool {
+    true
+}
+
+fn is_directed(directed: &bool) -> bool {
+    *directed
+}
+
+impl Default for BoardSettings {
+    fn default() -> Self {
+        Self {
+            default_node_color: None,
+            directed: true,
+            stacked_groups: Vec::new(),
+        }
+    }
+}
+
+impl BoardSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether `group` is laid out as an auto-stacked column.
+    pub fn is_stacked(&self, group: &str) -> bool {
+        self.stacked_groups.iter().any(|g| g == group)
+    }
+
+    /// Style a freshly created node with the board's defaults. A color the node
+    /// already carries wins.
+    pub fn apply_defaults(&self, node: &mut Node) {
+        if node.color.is_none() {
+            node.color = self.default_node_color.clone();
+        }
+    }
 }
 
 impl Board {
@@ -242,6 +382,13 @@ impl Board {
         self.version.unwrap_or(CURRENT_BOARD_VERSION)
     }
 
+    /// The board's version when it's newer than [`CURRENT_BOARD_VERSION`], i.e.
+    /// it was written by a newer build and may hold data this one can't show.
+    pub fn newer_version(&self) -> Option<u32> {
+        let version = self.schema_version();
+        (version > CURRENT_BOARD_VERSION).then_some(version)
+    }
+
     /// Structurally validate the board, returning every problem found (empty Vec
     /// == clean). Pure and side-effect-free so it can run anywhere — the CLI
     /// (`brainstorm validate`), the file-watcher reload path, or tests.
@@ -341,6 +488,36 @@ impl Board {
         });
         dropped
     }
+    /// Ids of every node reachable from `root` by following edges in their
+    /// `from_node -> to_node` direction, excluding `root` itself. Cycle-safe: each
+    /// node is visited once, and a cycle back to `root` does not include it.
+    pub fn downstream_nodes(&self, root: &str) -> std::collections::HashSet<String> {
+        let mut reached: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut stack = vec![root];
+        while let Some(current) = stack.pop() {
+            for edge in self.edges.iter().filter(|e| e.from_node == current) {
+                if edge.to_node != root && reached.insert(edge.to_node.clone()) {
+                    stack.push(edge.to_node.as_str());
+                }
+            }
+        }
+        reached
+    }
+
+    /// Ids of nodes hidden by a collapsed ancestor: the union of
+    /// [`Board::downstream_nodes`] over every `collapsed` node that is not itself
+    /// hidden. Walking in board order and skipping already-hidden roots keeps two
+    /// collapsed nodes on a cycle from hiding each other.
+    pub fn collapsed_hidden(&self) -> std::collections::HashSet<String> {
+        let mut hidden: std::collections::HashSet<String> = std::collections::HashSet::new();
+        for node in self.nodes.iter().filter(|n| n.collapsed) {
+            if !hidden.contains(&node.id) {
+                hidden.extend(self.downstream_nodes(&node.id));
+            }
+        }
+        hidden
+    }
+
     /// Fill in zero `width`/`height` on freshly-loaded nodes using text-based
     /// auto-sizing. Agents (and hand-edited `board.json` files) may omit the
     /// dimensions entirely; `#[serde(default)]` deserializes those to `0.0`,
@@ -371,6 +548,11 @@ pub struct Node {
     #[serde(default)]
     pub height: f64,
     pub text: String,
+    /// Human-readable label shown on the canvas and in tooltips in place of
+    /// `text`, which stays the machine value (URL, image path, content). `None`
+    /// (omitted from the JSON) derives the label from `text` as before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     #[serde(default)]
     pub node_type: NodeType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -383,6 +565,29 @@ pub struct Node {
     pub group: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub priority: Option<u8>,
+    /// Collapsed nodes hide their downstream subtree on the canvas. Omitted from
+    /// the JSON when `false`, so older boards load (and re-save) unchanged.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub collapsed: bool,
+    /// Render a text-like node's content as markdown on the canvas (as `md`
+    /// nodes are) without changing its type; the stored text stays the raw
+    /// source. Omitted from the JSON when `false`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub render_markdown: bool,
+    /// Unix millis of the last text/metadata edit made in the app (see
+    /// [`touch`]). Geometry changes don't count. Omitted when unset, so older
+    /// boards load unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    /// Keys this build doesn't know (e.g. written by a newer version), kept
+    /// verbatim so loading and re-saving the board doesn't drop them.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Stamp `node` as edited at `now_ms` (unix millis).
+pub fn touch(node: &mut Node, now_ms: i64) {
+    node.updated_at = Some(now_ms);
 }
 
 impl Node {
@@ -391,18 +596,42 @@ impl Node {
             id,
             x,
             y,
-            width: 200.0,
-            height: 100.0,
+            width: DEFAULT_NODE_WIDTH,
+            height: DEFAULT_NODE_HEIGHT,
             text,
+            title: None,
             node_type: NodeType::Text,
             color: None,
             tags: Vec::new(),
             status: None,
             group: None,
             priority: None,
+            collapsed: false,
+            render_markdown: false,
+            updated_at: None,
+            extra: Default::default(),
         }
     }
 
+    /// Whether this is a plain text/idea/note node flagged to show its text as
+    /// rendered markdown. Other types ignore the flag: `md` nodes always render
+    /// markdown, and image/link nodes draw their own content.
+    pub fn renders_markdown_inline(&self) -> bool {
+        self.render_markdown
+            && matches!(
+                self.node_type,
+                NodeType::Text | NodeType::Idea | NodeType::Note
+            )
+    }
+
+    /// The node's own label, when it has a non-blank `title`.
+    pub fn display_title(&self) -> Option<&str> {
+        self.title
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+    }
+
     pub fn contains_point(&self, px: f64, py: f64) -> bool {
         px >= self.x && px <= self.x + self.width && py >= self.y && py <= self.y + self.height
     }
@@ -472,6 +701,78 @@ pub struct Edge {
     pub to_node: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// Side of the source node the edge leaves from. `None` (every edge made
+    /// before ports existed) lets the renderer pick the border point facing
+    /// the other node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_port: Option<Port>,
+    /// Side of the target node the edge enters; `None` as for `from_port`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_port: Option<Port>,
+}
+
+impl Edge {
+    /// `(from_port, to_port)`, the shape the edge geometry helpers take.
+    pub fn ports(&self) -> (Option<Port>, Option<Port>) {
+        (self.from_port, self.to_port)
+    }
+}
+
+/// A named side of a node that an edge can be pinned to, so diagram edges
+/// leave and enter where the author put them. Serializes lowercase
+/// (`"top"`, `"right"`, …).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Port {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Port {
+    /// Unit vector pointing out of the node through this side (y down).
+    pub fn normal(self) -> (f64, f64) {
+        match self {
+            Port::Top => (0.0, -1.0),
+            Port::Bottom => (0.0, 1.0),
+            Port::Left => (-1.0, 0.0),
+            Port::Right => (1.0, 0.0),
+        }
+    }
+}
+
+/// Where an edge attaches to `port` of a node with geometry `(x, y, width,
+/// height)`: the midpoint of that side.
+pub fn port_anchor((x, y, width, height): (f64, f64, f64, f64), port: Port) -> (f64, f64) {
+    match port {
+        Port::Top => (x + width / 2.0, y),
+        Port::Bottom => (x + width / 2.0, y + height),
+        Port::Left => (x, y + height / 2.0),
+        Port::Right => (x + width, y + height / 2.0),
+    }
+}
+
+/// The side of the node at `(x, y, width, height)` nearest to `(px, py)`,
+/// measured to each side's line, so a point inside a wide node still picks
+/// the left or right side when it sits close to one. Ties go top, bottom,
+/// left, right in that order.
+pub fn nearest_port((x, y, width, height): (f64, f64, f64, f64), (px, py): (f64, f64)) -> Port {
+    [
+        (Port::Top, (py - y).abs()),
+        (Port::Bottom, (py - (y + height)).abs()),
+        (Port::Left, (px - x).abs()),
+        (Port::Right, (px - (x + width)).abs()),
+    ]
+    .into_iter()
+    .fold((Port::Top, f64::INFINITY), |best, (port, d)| {
+        if d < best.1 {
+            (port, d)
+        } else {
+            best
+        }
+    })
+    .0
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -595,7 +896,328 @@ pub fn fit_camera(
     }
 }
 
+/// First line of a node's text, trimmed, used as its label in graph exports.
+/// Falls back to the node id when the text is blank so every vertex is named.
+fn export_label(node: &Node) -> &str {
+    match node.text.lines().next().map(str::trim) {
+        Some(line) if !line.is_empty() => line,
+        _ => node.id.as_str(),
+    }
+}
+
+/// Escape a string for use inside a double-quoted Graphviz DOT ID.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a comma, quote, or line
+/// break (embedded quotes are doubled); other fields pass through unchanged.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Render the board as a Graphviz `digraph` (a plain `graph` with `--` edges
+/// when the board is undirected). Vertices are keyed by node id and labeled
+/// with the first line of their text; edge labels carry over when set. An empty
+/// board yields a valid, empty graph. Pure so it's shared by the UI download
+/// and the headless `export` CLI.
+pub fn board_to_dot(board: &Board) -> String {
+    let (kind, arrow) = if board.settings.directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let mut out = format!("{kind} board {{\n");
+    for node in &board.nodes {
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\"];\n",
+            dot_escape(&node.id),
+            dot_escape(export_label(node))
+        ));
+    }
+    for edge in &board.edges {
+        out.push_str(&format!(
+            "  \"{}\" {arrow} \"{}\"",
+            dot_escape(&edge.from_node),
+            dot_escape(&edge.to_node)
+        ));
+        if let Some(label) = edge.label.as_deref().filter(|l| !l.is_empty()) {
+            out.push_str(&format!(" [label=\"{}\"]", dot_escape(label)));
+        }
+        out.push_str(";\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Render the board's edges as CSV with a `from,to,label` header. Endpoints are
+/// the first line of each node's text (the id for blank or missing nodes); an
+/// unlabeled edge has an empty `label` column.
+pub fn board_to_csv(board: &Board) -> String {
+    let labels: std::collections::HashMap<&str, &str> = board
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), export_label(n)))
+        .collect();
+    let name = |id: &str| csv_field(labels.get(id).copied().unwrap_or(id));
+    let mut out = String::from("from,to,label\n");
+    for edge in &board.edges {
+        out.push_str(&format!(
+            "{},{},{}\n",
+            name(&edge.from_node),
+            name(&edge.to_node),
+            csv_field(edge.label.as_deref().unwrap_or(""))
+        ));
+    }
+    out
+}
+
+/// Render the subtree downstream of `root` (following outgoing edges) as a
+/// standalone markdown document. Each node becomes a section whose heading level
+/// is its depth (the root is `#`, capped at `######`): the heading is the first
+/// line of its text and any remaining lines follow as a paragraph. `md` nodes
+/// contribute their raw content instead. Children appear in edge order. Each node
+/// is written once, so cycles and shared descendants can't repeat or loop. An
+/// unknown `root` yields an empty string.
+pub fn subtree_to_markdown(board: &Board, root: &str) -> String {
+    let nodes: std::collections::HashMap<&str, &Node> =
+        board.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    if !nodes.contains_key(root) {
+        return String::new();
+    }
+
+    let mut sections: Vec<String> = Vec::new();
+    let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    push_subtree_sections(board, &nodes, root, &mut visited, &mut sections);
+
+    let mut out = sections.join("\n\n");
+    out.push('\n');
+    out
+}
+
+/// Render the whole board as one markdown outline: every root (a node with no
+/// incoming edge) in board order becomes a top-level `#` section followed by
+/// its subtree, as in [`subtree_to_markdown`]. Nodes only reachable through a
+/// cycle are then emitted as roots of their own, so every node appears exactly
+/// once. An empty board yields an empty string.
+pub fn board_to_markdown(board: &Board) -> String {
+    let nodes: std::collections::HashMap<&str, &Node> =
+        board.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let has_parent: std::collections::HashSet<&str> = board
+        .edges
+        .iter()
+        .filter(|e| e.from_node != e.to_node && nodes.contains_key(e.from_node.as_str()))
+        .map(|e| e.to_node.as_str())
+        .collect();
+
+    let mut sections: Vec<String> = Vec::new();
+    let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let roots = board
+        .nodes
+        .iter()
+        .filter(|n| !has_parent.contains(n.id.as_str()))
+        .chain(&board.nodes);
+    for root in roots {
+        if !visited.contains(root.id.as_str()) {
+            push_subtree_sections(board, &nodes, &root.id, &mut visited, &mut sections);
+        }
+    }
+
+    if sections.is_empty() {
+        return String::new();
+    }
+    let mut out = sections.join("\n\n");
+    out.push('\n');
+    out
+}
+
+/// Depth-first walk shared by the markdown exports: append a section for
+/// `root` and each not-yet-`visited` descendant (children in edge order).
+fn push_subtree_sections<'a>(
+    board: &'a Board,
+    nodes: &std::collections::HashMap<&'a str, &'a Node>,
+    root: &'a str,
+    visited: &mut std::collections::HashSet<&'a str>,
+    sections: &mut Vec<String>,
+) {
+    let mut stack: Vec<(&str, usize)> = vec![(root, 1)];
+    while let Some((id, depth)) = stack.pop() {
+        let Some(node) = nodes.get(id) else {
+            continue;
+        };
+        if !visited.insert(id) {
+            continue;
+        }
+        sections.push(markdown_section(node, depth));
+        // Push in reverse so children pop (and render) in edge order.
+        for edge in board.edges.iter().rev().filter(|e| e.from_node == id) {
+            if !visited.contains(edge.to_node.as_str()) {
+                stack.push((edge.to_node.as_str(), depth + 1));
+            }
+        }
+    }
+}
+
+/// One node's section for [`subtree_to_markdown`].
+fn markdown_section(node: &Node, depth: usize) -> String {
+    if node.node_type == NodeType::Md {
+        return node.text.trim().to_string();
+    }
+    let hashes = "#".repeat(depth.min(6));
+    let body = node
+        .text
+        .split_once('\n')
+        .map(|(_, rest)| rest.trim())
+        .unwrap_or_default();
+    if body.is_empty() {
+        format!("{hashes} {}", export_label(node))
+    } else {
+        format!("{hashes} {}\n\n{body}", export_label(node))
+    }
+}
+
+/// One node of a JSON Canvas (`.canvas`, as written by Obsidian) file. Only the
+/// fields the import maps are read; the rest are ignored.
+#[derive(Deserialize)]
+struct CanvasNode {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CanvasEdge {
+    id: String,
+    from_node: String,
+    to_node: String,
+    #[serde(default)]
+    from_side: Option<Port>,
+    #[serde(default)]
+    to_side: Option<Port>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CanvasFile {
+    #[serde(default)]
+    nodes: Vec<CanvasNode>,
+    #[serde(default)]
+    edges: Vec<CanvasEdge>,
+}
+
+/// JSON Canvas preset colors `"1"`–`"6"` (red, orange, yellow, green, cyan,
+/// purple) as hex; any other value is already a hex color and passes through.
+fn canvas_color(color: &str) -> String {
+    const PRESETS: [&str; 6] = [
+        "#ef4444", "#f97316", "#eab308", "#22c55e", "#06b6d4", "#8b5cf6",
+    ];
+    color
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| i.checked_sub(1))
+        .and_then(|i| PRESETS.get(i))
+        .map_or_else(|| color.to_string(), |c| c.to_string())
+}
+
+/// Convert a JSON Canvas document into a board. `text` nodes become text nodes,
+/// `link` nodes link nodes, and `file` nodes image nodes for image files or link
+/// nodes (pointing at the path) otherwise. A `group` doesn't become a node:
+/// every node lying inside its rectangle gets its label (or id) as `group`,
+/// the innermost group winning. Edges whose endpoints were dropped are
+/// discarded. Errors only when the text isn't a canvas document.
+pub fn board_from_canvas(raw: &str) -> Result<Board, String> {
+    const IMAGE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg"];
+    let canvas: CanvasFile =
+        serde_json::from_str(raw).map_err(|e| format!("invalid canvas JSON: {e}"))?;
+
+    let (groups, items): (Vec<&CanvasNode>, Vec<&CanvasNode>) =
+        canvas.nodes.iter().partition(|n| n.kind == "group");
+    let mut nodes = Vec::new();
+    for item in items {
+        let (node_type, text) = match item.kind.as_str() {
+            "text" => (NodeType::Text, item.text.clone().unwrap_or_default()),
+            "link" => (NodeType::Link, item.url.clone().unwrap_or_default()),
+            "file" => {
+                let path = item.file.clone().unwrap_or_default();
+                let is_image = path.rsplit_once('.').is_some_and(|(_, ext)| {
+                    IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+                });
+                if is_image {
+                    (NodeType::Image, path)
+                } else {
+                    (NodeType::Link, path)
+                }
+            }
+            _ => continue,
+        };
+        // Smallest enclosing group, so nested groups assign the inner one.
+        let group = groups
+            .iter()
+            .filter(|g| {
+                item.x >= g.x
+                    && item.y >= g.y
+                    && item.x + item.width <= g.x + g.width
+                    && item.y + item.height <= g.y + g.height
+            })
+            .min_by(|a, b| (a.width * a.height).total_cmp(&(b.width * b.height)))
+            .map(|g| g.label.clone().unwrap_or_else(|| g.id.clone()));
+        let mut node = Node::new(item.id.clone(), item.x, item.y, text);
+        node.node_type = node_type;
+        node.width = item.width;
+        node.height = item.height;
+        node.color = item.color.as_deref().map(canvas_color);
+        node.group = group;
+        nodes.push(node);
+    }
+
+    let ids: std::collections::HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+    let edges = canvas
+        .edges
+        .iter()
+        .filter(|e| ids.contains(e.from_node.as_str()) && ids.contains(e.to_node.as_str()))
+        .map(|e| Edge {
+            id: e.id.clone(),
+            from_node: e.from_node.clone(),
+            to_node: e.to_node.clone(),
+            label: e.label.clone().filter(|l| !l.is_empty()),
+            from_port: e.from_side,
+            to_port: e.to_side,
+        })
+        .collect();
+
+    Ok(Board {
+        version: None,
+        nodes,
+        edges,
+        settings: Default::default(),
+    })
+}
+
 #[cfg(test)]
+// Range assertions spell out both bounds (`w >= a && w <= b`) on purpose.
+#[allow(clippy::manual_range_contains)]
 mod tests {
     use super::*;
 
@@ -862,12 +1484,17 @@ mod tests {
                 width: 80.0,
                 height: 40.0,
                 text: "".to_string(),
+                title: None,
                 node_type: NodeType::Text,
                 color: None,
                 tags: Vec::new(),
                 status: None,
                 group: None,
                 priority: None,
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
             assert_eq!(node.center(), (-260.0, -80.0));
         }
@@ -1039,12 +1666,17 @@ mod tests {
                         width: 200.0,
                         height: 100.0,
                         text: "Second".to_string(),
+                        title: None,
                         node_type: NodeType::Idea,
                         color: None,
                         tags: Vec::new(),
                         status: None,
                         group: None,
                         priority: None,
+                        collapsed: false,
+                        render_markdown: false,
+                        updated_at: None,
+                        extra: Default::default(),
                     },
                 ],
                 edges: vec![Edge {
@@ -1052,7 +1684,10 @@ mod tests {
                     from_node: "n1".to_string(),
                     to_node: "n2".to_string(),
                     label: None,
+                    from_port: None,
+                    to_port: None,
                 }],
+                settings: Default::default(),
             };
 
             let json = serde_json::to_string(&board).unwrap();
@@ -1126,14 +1761,20 @@ mod tests {
                     width: 321.0,
                     height: 123.0,
                     text: "Sized".to_string(),
+                    title: None,
                     node_type: NodeType::Text,
                     color: None,
                     tags: Vec::new(),
                     status: None,
                     group: None,
                     priority: None,
+                    collapsed: false,
+                    render_markdown: false,
+                    updated_at: None,
+                    extra: Default::default(),
                 }],
                 edges: vec![],
+                settings: Default::default(),
             };
 
             board.apply_auto_size();
@@ -1155,14 +1796,20 @@ mod tests {
                     width: 275.0,
                     height: 0.0,
                     text: "Half".to_string(),
+                    title: None,
                     node_type: NodeType::Text,
                     color: None,
                     tags: Vec::new(),
                     status: None,
                     group: None,
                     priority: None,
+                    collapsed: false,
+                    render_markdown: false,
+                    updated_at: None,
+                    extra: Default::default(),
                 }],
                 edges: vec![],
+                settings: Default::default(),
             };
 
             board.apply_auto_size();
@@ -1203,12 +1850,17 @@ mod tests {
                 width: 200.0,
                 height: 100.0,
                 text: "Meta node".to_string(),
+                title: None,
                 node_type: NodeType::Idea,
                 color: Some("#ff6600".to_string()),
                 tags: vec!["urgent".to_string(), "pricing".to_string()],
                 status: Some("in-progress".to_string()),
                 group: Some("cluster-a".to_string()),
                 priority: Some(2),
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
             let json = serde_json::to_string(&node).unwrap();
             let deserialized: Node = serde_json::from_str(&json).unwrap();
@@ -1224,6 +1876,95 @@ mod tests {
             assert!(!json.contains("status"));
             assert!(!json.contains("group"));
             assert!(!json.contains("priority"));
+            assert!(!json.contains("collapsed"));
+        }
+
+        #[test]
+        fn collapsed_round_trips_and_defaults_false() {
+            let mut node = Node::new("n1".to_string(), 0.0, 0.0, "Root".to_string());
+            node.collapsed = true;
+            let json = serde_json::to_string(&node).unwrap();
+            assert!(json.contains("\"collapsed\":true"));
+            let back: Node = serde_json::from_str(&json).unwrap();
+            assert!(back.collapsed);
+
+            // Boards written before the field existed load expanded.
+            let old = r#"{"id":"n2","x":0,"y":0,"text":"Old"}"#;
+            let node: Node = serde_json::from_str(old).unwrap();
+            assert!(!node.collapsed);
+        }
+
+        #[test]
+        fn unknown_fields_survive_a_round_trip() {
+            let json = r#"{"id":"n1","x":0,"y":0,"text":"Future","shape":"hexagon","style":{"glow":true}}"#;
+            let node: Node = serde_json::from_str(json).unwrap();
+            assert_eq!(node.extra["shape"], "hexagon");
+
+            let out = serde_json::to_value(&node).unwrap();
+            assert_eq!(out["shape"], "hexagon");
+            assert_eq!(out["style"]["glow"], true);
+            let back: Node = serde_json::from_value(out).unwrap();
+            assert_eq!(back, node);
+        }
+
+        #[test]
+        fn known_fields_do_not_leak_into_extra() {
+            let json = r#"{"id":"n1","x":1,"y":2,"width":3,"height":4,"text":"t","tags":["a"],"priority":2}"#;
+            let node: Node = serde_json::from_str(json).unwrap();
+            assert!(node.extra.is_empty());
+            assert_eq!(node.priority, Some(2));
+            let out = serde_json::to_string(&node).unwrap();
+            assert!(!out.contains("extra"));
+        }
+
+        #[test]
+        fn render_markdown_round_trips_and_defaults_false() {
+            let mut node = Node::new("n1".to_string(), 0.0, 0.0, "**bold**".to_string());
+            assert!(!serde_json::to_string(&node)
+                .unwrap()
+                .contains("render_markdown"));
+            node.render_markdown = true;
+            let json = serde_json::to_string(&node).unwrap();
+            assert!(json.contains("\"render_markdown\":true"));
+            let back: Node = serde_json::from_str(&json).unwrap();
+            assert!(back.render_markdown);
+            assert_eq!(back.text, "**bold**");
+
+            let old = r#"{"id":"n2","x":0,"y":0,"text":"Old"}"#;
+            let node: Node = serde_json::from_str(old).unwrap();
+            assert!(!node.render_markdown);
+        }
+
+        #[test]
+        fn only_text_like_nodes_render_markdown_inline() {
+            let mut node = Node::new("n1".to_string(), 0.0, 0.0, "# Hi".to_string());
+            assert!(!node.renders_markdown_inline());
+            node.render_markdown = true;
+            for t in [NodeType::Text, NodeType::Idea, NodeType::Note] {
+                node.node_type = t;
+                assert!(node.renders_markdown_inline(), "{t:?}");
+            }
+            for t in [NodeType::Image, NodeType::Md, NodeType::Link] {
+                node.node_type = t;
+                assert!(!node.renders_markdown_inline(), "{t:?}");
+            }
+        }
+
+        #[test]
+        fn updated_at_round_trips_and_defaults_none() {
+            let mut node = Node::new("n1".to_string(), 0.0, 0.0, "Edited".to_string());
+            assert!(!serde_json::to_string(&node).unwrap().contains("updated_at"));
+
+            touch(&mut node, 1_700_000_000_123);
+            let json = serde_json::to_string(&node).unwrap();
+            assert!(json.contains("\"updated_at\":1700000000123"));
+            let back: Node = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.updated_at, Some(1_700_000_000_123));
+
+            // Boards written before the field existed load without a timestamp.
+            let old = r#"{"id":"n2","x":0,"y":0,"text":"Old"}"#;
+            let node: Node = serde_json::from_str(old).unwrap();
+            assert_eq!(node.updated_at, None);
         }
 
         #[test]
@@ -1251,14 +1992,20 @@ mod tests {
                     width: 200.0,
                     height: 100.0,
                     text: "Hello \"world\"".to_string(),
+                    title: None,
                     node_type: NodeType::Text,
                     color: None,
                     tags: vec![],
                     status: None,
                     group: None,
                     priority: None,
+                    collapsed: false,
+                    render_markdown: false,
+                    updated_at: None,
+                    extra: Default::default(),
                 }],
                 edges: vec![],
+                settings: Default::default(),
             };
 
             let json = serde_json::to_string_pretty(&board).unwrap();
@@ -1266,6 +2013,218 @@ mod tests {
             assert!(json.contains("\"x\": 100.0"));
             assert!(json.contains("Hello \\\"world\\\""));
         }
+        fn chain_board() -> Board {
+            let edge = |id: &str, from: &str, to: &str| Edge {
+                id: id.to_string(),
+                from_node: from.to_string(),
+                to_node: to.to_string(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            };
+            Board {
+                version: None,
+                nodes: ["a", "b", "c", "d"]
+                    .iter()
+                    .map(|id| Node::new(id.to_string(), 0.0, 0.0, id.to_string()))
+                    .collect(),
+                // a -> b -> c, c -> a (cycle), d is unconnected.
+                edges: vec![
+                    edge("e1", "a", "b"),
+                    edge("e2", "b", "c"),
+                    edge("e3", "c", "a"),
+                ],
+                settings: Default::default(),
+            }
+        }
+
+        #[test]
+        fn downstream_nodes_follows_edges_and_survives_cycles() {
+            let board = chain_board();
+            let down = board.downstream_nodes("a");
+            assert_eq!(down.len(), 2);
+            assert!(down.contains("b") && down.contains("c"));
+            assert!(board.downstream_nodes("d").is_empty());
+        }
+
+        #[test]
+        fn collapsed_hidden_skips_roots_already_hidden() {
+            let mut board = chain_board();
+            assert!(board.collapsed_hidden().is_empty());
+            // Both a and b collapsed on a cycle: a (first) hides b and c; b is
+            // already hidden so it can't hide a in turn.
+            board.nodes[0].collapsed = true;
+            board.nodes[1].collapsed = true;
+            let hidden = board.collapsed_hidden();
+            assert_eq!(hidden.len(), 2);
+            assert!(!hidden.contains("a"));
+        }
+    }
+
+    mod board_settings_tests {
+        use super::*;
+
+        #[test]
+        fn default_settings_are_omitted_from_json() {
+            let json = serde_json::to_string(&Board::default()).unwrap();
+            assert!(!json.contains("settings"), "{json}");
+            let board: Board = serde_json::from_str(r#"{"nodes":[],"edges":[]}"#).unwrap();
+            assert!(board.settings.is_default());
+        }
+
+        #[test]
+        fn default_node_color_round_trips() {
+            let mut board = Board::default();
+            board.settings.default_node_color = Some("#06b6d4".to_string());
+            let json = serde_json::to_string(&board).unwrap();
+            assert!(json.contains(r##""settings":{"default_node_color":"#06b6d4"}"##));
+            assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+        }
+
+        #[test]
+        fn new_node_inherits_default_color() {
+            let settings = BoardSettings {
+                default_node_color: Some("#22c55e".to_string()),
+                ..Default::default()
+            };
+            let mut node = Node::new("n".to_string(), 0.0, 0.0, String::new());
+            settings.apply_defaults(&mut node);
+            assert_eq!(node.color.as_deref(), Some("#22c55e"));
+        }
+
+        #[test]
+        fn no_default_keeps_type_color() {
+            let mut node = Node::new("n".to_string(), 0.0, 0.0, String::new());
+            BoardSettings::default().apply_defaults(&mut node);
+            assert_eq!(node.color, None);
+        }
+
+        #[test]
+        fn directed_defaults_true_and_is_omitted() {
+            assert!(Board::default().settings.directed);
+            let mut board = Board::default();
+            board.settings.directed = false;
+            let json = serde_json::to_string(&board).unwrap();
+            assert!(json.contains(r#""settings":{"directed":false}"#), "{json}");
+            assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+        }
+
+        #[test]
+        fn stacked_groups_round_trip() {
+            let mut board = Board::default();
+            board.settings.stacked_groups = vec!["todo".to_string()];
+            let json = serde_json::to_string(&board).unwrap();
+            assert!(
+                json.contains(r#""settings":{"stacked_groups":["todo"]}"#),
+                "{json}"
+            );
+            let back = serde_json::from_str::<Board>(&json).unwrap();
+            assert!(back.settings.is_stacked("todo"));
+            assert!(!back.settings.is_stacked("done"));
+        }
+
+        #[test]
+        fn explicit_color_wins() {
+            let settings = BoardSettings {
+                default_node_color: Some("#22c55e".to_string()),
+                ..Default::default()
+            };
+            let mut node = Node::new("n".to_string(), 0.0, 0.0, String::new());
+            node.color = Some("#ef4444".to_string());
+            settings.apply_defaults(&mut node);
+            assert_eq!(node.color.as_deref(), Some("#ef4444"));
+        }
+    }
+
+    mod node_title_tests {
+        use super::*;
+
+        #[test]
+        fn old_boards_load_without_title() {
+            let node: Node =
+                serde_json::from_str(r#"{"id":"a","x":0,"y":0,"text":"https://example.com"}"#)
+                    .unwrap();
+            assert_eq!(node.title, None);
+            assert_eq!(node.display_title(), None);
+        }
+
+        #[test]
+        fn title_round_trips_and_is_omitted_when_none() {
+            let mut node = Node::new("a".to_string(), 0.0, 0.0, "assets/x.png".to_string());
+            let json = serde_json::to_string(&node).unwrap();
+            assert!(!json.contains("title"), "{json}");
+
+            node.title = Some("Architecture sketch".to_string());
+            let json = serde_json::to_string(&node).unwrap();
+            assert!(json.contains(r#""title":"Architecture sketch""#), "{json}");
+            let back: Node = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, node);
+            assert_eq!(back.text, "assets/x.png");
+        }
+
+        #[test]
+        fn blank_title_is_no_title() {
+            let mut node = Node::new("a".to_string(), 0.0, 0.0, "A".to_string());
+            node.title = Some("   ".to_string());
+            assert_eq!(node.display_title(), None);
+            node.title = Some(" Roadmap ".to_string());
+            assert_eq!(node.display_title(), Some("Roadmap"));
+        }
+    }
+
+    mod round_coords_tests {
+        use super::*;
+
+        fn jittered() -> Board {
+            let mut n = Node::new(
+                "a".to_string(),
+                123.456_789_012_345,
+                -0.004,
+                "A".to_string(),
+            );
+            n.width = 200.005_1;
+            n.height = 99.999_999;
+            Board {
+                nodes: vec![n],
+                ..Board::default()
+            }
+        }
+
+        #[test]
+        fn rounds_coordinates_and_sizes() {
+            let mut board = jittered();
+            round_board_coords(&mut board, 2);
+            let n = &board.nodes[0];
+            assert_eq!((n.x, n.y, n.width, n.height), (123.46, -0.0, 200.01, 100.0));
+            assert_eq!(
+                serde_json::to_string(&n.x).unwrap(),
+                "123.46",
+                "rounded values serialize without trailing noise"
+            );
+        }
+
+        #[test]
+        fn rounding_is_idempotent() {
+            let mut once = jittered();
+            round_board_coords(&mut once, SAVE_COORD_DECIMALS);
+            let mut twice = once.clone();
+            round_board_coords(&mut twice, SAVE_COORD_DECIMALS);
+            assert_eq!(once, twice);
+
+            let json = serde_json::to_string(&once).unwrap();
+            let reloaded: Board = serde_json::from_str(&json).unwrap();
+            assert_eq!(reloaded, once);
+        }
+
+        #[test]
+        fn non_finite_values_pass_through() {
+            let mut board = jittered();
+            board.nodes[0].x = f64::NAN;
+            board.nodes[0].width = f64::INFINITY;
+            round_board_coords(&mut board, 2);
+            assert!(board.nodes[0].x.is_nan());
+            assert_eq!(board.nodes[0].width, f64::INFINITY);
+        }
     }
 
     mod validation_tests {
@@ -1281,6 +2240,8 @@ mod tests {
                 from_node: from.to_string(),
                 to_node: to.to_string(),
                 label: None,
+                from_port: None,
+                to_port: None,
             }
         }
 
@@ -1290,6 +2251,7 @@ mod tests {
                 version: None,
                 nodes: vec![node("a"), node("b")],
                 edges: vec![edge("e1", "a", "b")],
+                settings: Default::default(),
             };
             assert!(board.validate().is_empty(), "{:?}", board.validate());
         }
@@ -1305,6 +2267,7 @@ mod tests {
                 version: None,
                 nodes: vec![node("dup"), node("dup")],
                 edges: vec![],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert!(errs.contains(&ValidationError::DuplicateNodeId("dup".to_string())));
@@ -1316,6 +2279,7 @@ mod tests {
                 version: None,
                 nodes: vec![node("a"), node("b")],
                 edges: vec![edge("e", "a", "b"), edge("e", "b", "a")],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert!(errs.contains(&ValidationError::DuplicateEdgeId("e".to_string())));
@@ -1327,6 +2291,7 @@ mod tests {
                 version: None,
                 nodes: vec![node("a")],
                 edges: vec![edge("e1", "ghost", "a")],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert!(errs.contains(&ValidationError::DanglingEdge {
@@ -1341,6 +2306,7 @@ mod tests {
                 version: None,
                 nodes: vec![node("a")],
                 edges: vec![edge("e1", "a", "ghost")],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert!(errs.contains(&ValidationError::DanglingEdge {
@@ -1358,6 +2324,7 @@ mod tests {
                 version: None,
                 nodes: vec![n],
                 edges: vec![],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert!(errs.contains(&ValidationError::NonFiniteCoord {
@@ -1380,6 +2347,7 @@ mod tests {
                 version: None,
                 nodes: vec![low, high],
                 edges: vec![],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert!(errs.contains(&ValidationError::PriorityOutOfRange {
@@ -1401,6 +2369,7 @@ mod tests {
                     version: None,
                     nodes: vec![n],
                     edges: vec![],
+                    settings: Default::default(),
                 };
                 assert!(board.validate().is_empty(), "priority {p} should be valid");
             }
@@ -1412,6 +2381,7 @@ mod tests {
                 version: Some(CURRENT_BOARD_VERSION + 1),
                 nodes: vec![node("a"), node("b")],
                 edges: vec![edge("e1", "a", "b")],
+                settings: Default::default(),
             };
             let errs = board.validate();
             assert_eq!(
@@ -1426,11 +2396,24 @@ mod tests {
                 version: Some(CURRENT_BOARD_VERSION),
                 nodes: vec![],
                 edges: vec![],
+                settings: Default::default(),
             };
             assert!(with.validate().is_empty());
             assert!(Board::default().validate().is_empty());
         }
 
+        #[test]
+        fn newer_version_only_reports_future_versions() {
+            assert_eq!(Board::default().newer_version(), None);
+            let mut b = Board {
+                version: Some(CURRENT_BOARD_VERSION),
+                ..Board::default()
+            };
+            assert_eq!(b.newer_version(), None);
+            b.version = Some(CURRENT_BOARD_VERSION + 1);
+            assert_eq!(b.newer_version(), Some(CURRENT_BOARD_VERSION + 1));
+        }
+
         #[test]
         fn schema_version_defaults_to_current_when_absent() {
             assert_eq!(Board::default().schema_version(), CURRENT_BOARD_VERSION);
@@ -1438,6 +2421,7 @@ mod tests {
                 version: Some(7),
                 nodes: vec![],
                 edges: vec![],
+                settings: Default::default(),
             };
             assert_eq!(b.schema_version(), 7);
         }
@@ -1452,6 +2436,7 @@ mod tests {
                     edge("bad1", "a", "ghost"),
                     edge("bad2", "ghost", "b"),
                 ],
+                settings: Default::default(),
             };
             let dropped = board.drop_dangling_edges();
             assert_eq!(dropped, vec!["bad1".to_string(), "bad2".to_string()]);
@@ -1467,6 +2452,7 @@ mod tests {
                 version: None,
                 nodes: vec![node("a"), node("b")],
                 edges: vec![edge("e1", "a", "b")],
+                settings: Default::default(),
             };
             assert!(board.drop_dangling_edges().is_empty());
             assert_eq!(board.edges.len(), 1);
@@ -1487,6 +2473,7 @@ mod tests {
                 version: Some(1),
                 nodes: vec![],
                 edges: vec![],
+                settings: Default::default(),
             };
             let json = serde_json::to_string(&board).unwrap();
             assert!(json.contains("\"version\":1"));
@@ -1529,6 +2516,8 @@ mod tests {
                 from_node: "a".to_string(),
                 to_node: "b".to_string(),
                 label: None,
+                from_port: None,
+                to_port: None,
             };
 
             let json = serde_json::to_string(&edge).unwrap();
@@ -1544,6 +2533,8 @@ mod tests {
                 from_node: "a".to_string(),
                 to_node: "b".to_string(),
                 label: Some("depends on".to_string()),
+                from_port: None,
+                to_port: None,
             };
             let json = serde_json::to_string(&edge).unwrap();
             assert!(json.contains("\"label\":\"depends on\""));
@@ -1558,6 +2549,8 @@ mod tests {
                 from_node: "a".to_string(),
                 to_node: "b".to_string(),
                 label: None,
+                from_port: None,
+                to_port: None,
             };
             let json = serde_json::to_string(&edge).unwrap();
             assert!(!json.contains("label"));
@@ -1568,6 +2561,66 @@ mod tests {
             let json = r#"{"id":"e1","from_node":"a","to_node":"b"}"#;
             let edge: Edge = serde_json::from_str(json).unwrap();
             assert_eq!(edge.label, None);
+            assert_eq!(edge.ports(), (None, None));
+        }
+
+        #[test]
+        fn ports_round_trip_lowercase() {
+            let json =
+                r#"{"id":"e1","from_node":"a","to_node":"b","from_port":"right","to_port":"top"}"#;
+            let edge: Edge = serde_json::from_str(json).unwrap();
+            assert_eq!(edge.ports(), (Some(Port::Right), Some(Port::Top)));
+            assert_eq!(serde_json::to_string(&edge).unwrap(), json);
+        }
+    }
+
+    mod port_tests {
+        use super::*;
+
+        const RECT: (f64, f64, f64, f64) = (100.0, 50.0, 200.0, 100.0);
+
+        #[test]
+        fn top_anchor_is_the_top_midpoint() {
+            assert_eq!(port_anchor(RECT, Port::Top), (200.0, 50.0));
+        }
+
+        #[test]
+        fn bottom_anchor_is_the_bottom_midpoint() {
+            assert_eq!(port_anchor(RECT, Port::Bottom), (200.0, 150.0));
+        }
+
+        #[test]
+        fn left_anchor_is_the_left_midpoint() {
+            assert_eq!(port_anchor(RECT, Port::Left), (100.0, 100.0));
+        }
+
+        #[test]
+        fn right_anchor_is_the_right_midpoint() {
+            assert_eq!(port_anchor(RECT, Port::Right), (300.0, 100.0));
+        }
+
+        #[test]
+        fn normals_point_out_of_their_side() {
+            for port in [Port::Top, Port::Bottom, Port::Left, Port::Right] {
+                let (ax, ay) = port_anchor(RECT, port);
+                let (nx, ny) = port.normal();
+                let (cx, cy) = (200.0, 100.0);
+                assert!((ax + nx - cx).hypot(ay + ny - cy) > (ax - cx).hypot(ay - cy));
+            }
+        }
+
+        #[test]
+        fn nearest_port_picks_the_closest_side() {
+            assert_eq!(nearest_port(RECT, (200.0, 55.0)), Port::Top);
+            assert_eq!(nearest_port(RECT, (200.0, 160.0)), Port::Bottom);
+            assert_eq!(nearest_port(RECT, (110.0, 100.0)), Port::Left);
+            assert_eq!(nearest_port(RECT, (290.0, 90.0)), Port::Right);
+        }
+
+        #[test]
+        fn nearest_port_uses_side_distance_not_center_direction() {
+            // Near the right edge of a wide node, but above its center line.
+            assert_eq!(nearest_port(RECT, (295.0, 70.0)), Port::Right);
         }
     }
 
@@ -1599,6 +2652,16 @@ mod tests {
 
             assert_eq!(preview, deserialized);
         }
+
+        #[test]
+        fn preview_timeout_is_clamped_to_the_setting_range() {
+            assert_eq!(clamp_preview_timeout_secs(0), MIN_PREVIEW_TIMEOUT_SECS);
+            assert_eq!(clamp_preview_timeout_secs(30), 30);
+            assert_eq!(
+                clamp_preview_timeout_secs(u64::MAX),
+                MAX_PREVIEW_TIMEOUT_SECS
+            );
+        }
     }
 
     mod edge_cases {
@@ -1660,12 +2723,17 @@ mod tests {
                 width: 50.0,
                 height: 25.0,
                 text: "tiny".to_string(),
+                title: None,
                 node_type: NodeType::Text,
                 color: None,
                 tags: Vec::new(),
                 status: None,
                 group: None,
                 priority: None,
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
             assert!(node.contains_point(25.0, 12.0));
             assert!(node.contains_point(50.0, 25.0));
@@ -1712,12 +2780,17 @@ mod tests {
                 width: 200.0,
                 height: 100.0,
                 text: text.to_string(),
+                title: None,
                 node_type: NodeType::Text,
                 color: None,
                 tags: vec![],
                 status: None,
                 group: None,
                 priority: None,
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
 
             let json = serde_json::to_string(&node).unwrap();
@@ -1743,12 +2816,17 @@ mod tests {
                     width: 200.0,
                     height: 100.0,
                     text: "content".to_string(),
+                    title: None,
                     node_type,
                     color: None,
                     tags: vec![],
                     status: None,
                     group: None,
                     priority: None,
+                    collapsed: false,
+                    render_markdown: false,
+                    updated_at: None,
+                    extra: Default::default(),
                 };
 
                 let json = serde_json::to_string(&node).unwrap();
@@ -1767,12 +2845,17 @@ mod tests {
                 width: 200.123,
                 height: 100.789,
                 text: "precise".to_string(),
+                title: None,
                 node_type: NodeType::Text,
                 color: None,
                 tags: vec![],
                 status: None,
                 group: None,
                 priority: None,
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
 
             let json = serde_json::to_string(&node).unwrap();
@@ -1804,6 +2887,7 @@ mod tests {
                 version: None,
                 nodes,
                 edges: vec![],
+                settings: Default::default(),
             };
 
             assert_eq!(board.nodes.len(), 1000);
@@ -1833,6 +2917,8 @@ mod tests {
                     from_node: format!("n{}", i),
                     to_node: format!("n{}", i + 1),
                     label: None,
+                    from_port: None,
+                    to_port: None,
                 })
                 .collect();
 
@@ -1840,6 +2926,7 @@ mod tests {
                 version: None,
                 nodes,
                 edges,
+                settings: Default::default(),
             };
 
             assert_eq!(board.edges.len(), 99);
@@ -1872,6 +2959,8 @@ mod tests {
                         from_node: format!("n{}", i),
                         to_node: format!("n{}", j),
                         label: None,
+                        from_port: None,
+                        to_port: None,
                     });
                     edge_id += 1;
                 }
@@ -1881,6 +2970,7 @@ mod tests {
                 version: None,
                 nodes,
                 edges,
+                settings: Default::default(),
             };
 
             let expected_edges = n * (n - 1) / 2;
@@ -1901,18 +2991,24 @@ mod tests {
                 width: 200.0,
                 height: 100.0,
                 text: large_text.clone(),
+                title: None,
                 node_type: NodeType::Text,
                 color: None,
                 tags: vec![],
                 status: None,
                 group: None,
                 priority: None,
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
 
             let board = Board {
                 version: None,
                 nodes: vec![node],
                 edges: vec![],
+                settings: Default::default(),
             };
             let json = serde_json::to_string(&board).unwrap();
             let deserialized: Board = serde_json::from_str(&json).unwrap();
@@ -2077,4 +3173,308 @@ mod tests {
             assert_eq!(out.chars().count(), 20);
         }
     }
+
+    mod elide_middle_tests {
+        use super::*;
+
+        #[test]
+        fn short_string_unchanged() {
+            assert_eq!(elide_middle("https://a.io", 20), "https://a.io");
+            assert_eq!(elide_middle("exactly ten", 11), "exactly ten");
+        }
+
+        #[test]
+        fn long_string_elides_the_middle() {
+            let out = elide_middle("abcdefghijklmnopqrstuvwxyz", 11);
+            assert_eq!(out, "abcde…vwxyz");
+            assert_eq!(out.chars().count(), 11);
+        }
+
+        #[test]
+        fn url_keeps_scheme_and_host() {
+            let url = "https://example.com/some/deeply/nested/path/to/the/final-page";
+            let out = elide_middle(url, 40);
+            assert_eq!(out.chars().count(), 40);
+            assert!(out.starts_with("https://example.com/"), "{out}");
+            assert!(out.ends_with("/final-page"), "{out}");
+        }
+
+        #[test]
+        fn url_with_oversized_host_falls_back_to_halves() {
+            let url = "https://a-really-long-subdomain.example-company.com/page";
+            assert_eq!(elide_middle(url, 21), "https://a-…y.com/page");
+        }
+
+        #[test]
+        fn multibyte_text_is_char_boundary_safe() {
+            let out = elide_middle("世界世界世界世界世界世界😀😀😀😀", 7);
+            assert_eq!(out, "世界世…😀😀😀");
+            assert_eq!(elide_middle("😀😀😀", 0), "");
+            assert_eq!(elide_middle("😀😀😀", 1), "…");
+        }
+    }
+
+    mod graph_export_tests {
+        use super::*;
+
+        fn graph() -> Board {
+            Board {
+                version: None,
+                nodes: vec![
+                    Node::new("a".into(), 0.0, 0.0, "Say \"hi\"\nsecond line".into()),
+                    Node::new("b".into(), 0.0, 0.0, "Pricing, v2".into()),
+                ],
+                edges: vec![
+                    Edge {
+                        id: "e1".into(),
+                        from_node: "a".into(),
+                        to_node: "b".into(),
+                        label: Some("leads to".into()),
+                        from_port: None,
+                        to_port: None,
+                    },
+                    Edge {
+                        id: "e2".into(),
+                        from_node: "b".into(),
+                        to_node: "a".into(),
+                        label: None,
+                        from_port: None,
+                        to_port: None,
+                    },
+                ],
+                settings: Default::default(),
+            }
+        }
+
+        #[test]
+        fn dot_escapes_quotes_and_uses_first_line() {
+            let dot = board_to_dot(&graph());
+            assert!(dot.starts_with("digraph board {\n"));
+            assert!(dot.contains(r#""a" [label="Say \"hi\""];"#));
+            assert!(!dot.contains("second line"));
+        }
+
+        #[test]
+        fn dot_edges_with_and_without_labels() {
+            let dot = board_to_dot(&graph());
+            assert!(dot.contains(r#""a" -> "b" [label="leads to"];"#));
+            assert!(dot.contains("\"b\" -> \"a\";\n"));
+        }
+
+        #[test]
+        fn csv_quotes_fields_and_leaves_missing_label_empty() {
+            let csv = board_to_csv(&graph());
+            let lines: Vec<&str> = csv.lines().collect();
+            assert_eq!(lines[0], "from,to,label");
+            assert_eq!(lines[1], r#""Say ""hi""","Pricing, v2",leads to"#);
+            assert_eq!(lines[2], r#""Pricing, v2","Say ""hi""","#);
+        }
+
+        #[test]
+        fn blank_text_falls_back_to_id() {
+            let mut board = graph();
+            board.nodes[1].text = "  ".into();
+            assert!(board_to_dot(&board).contains(r#""b" [label="b"];"#));
+            assert!(board_to_csv(&board).contains("\nb,"));
+        }
+
+        #[test]
+        fn empty_board_is_valid_but_empty() {
+            let board = Board::default();
+            assert_eq!(board_to_dot(&board), "digraph board {\n}\n");
+            assert_eq!(board_to_csv(&board), "from,to,label\n");
+        }
+
+        #[test]
+        fn dot_undirected_board_is_a_graph() {
+            let mut board = graph();
+            board.settings.directed = false;
+            let dot = board_to_dot(&board);
+            assert!(dot.starts_with("graph board {\n"));
+            assert!(dot.contains(r#""a" -- "b" [label="leads to"];"#));
+            assert!(!dot.contains("->"));
+        }
+    }
+
+    mod subtree_markdown_tests {
+        use super::*;
+
+        fn edge(from: &str, to: &str) -> Edge {
+            Edge {
+                id: format!("{from}-{to}"),
+                from_node: from.into(),
+                to_node: to.into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            }
+        }
+
+        fn board(nodes: &[(&str, &str)], edges: &[(&str, &str)]) -> Board {
+            Board {
+                version: None,
+                nodes: nodes
+                    .iter()
+                    .map(|(id, text)| Node::new((*id).into(), 0.0, 0.0, (*text).into()))
+                    .collect(),
+                edges: edges.iter().map(|(f, t)| edge(f, t)).collect(),
+                settings: Default::default(),
+            }
+        }
+
+        #[test]
+        fn chain_nests_headings_by_depth() {
+            let b = board(
+                &[("a", "Goal"), ("b", "Step\nwith detail"), ("c", "Done")],
+                &[("a", "b"), ("b", "c")],
+            );
+            assert_eq!(
+                subtree_to_markdown(&b, "a"),
+                "# Goal\n\n## Step\n\nwith detail\n\n### Done\n"
+            );
+            // Starting mid-chain exports only the downstream part.
+            assert_eq!(
+                subtree_to_markdown(&b, "b"),
+                "# Step\n\nwith detail\n\n## Done\n"
+            );
+        }
+
+        #[test]
+        fn branches_follow_edge_order_depth_first() {
+            let b = board(
+                &[
+                    ("r", "Root"),
+                    ("x", "X"),
+                    ("x1", "X1"),
+                    ("y", "Y"),
+                    ("z", "Unrelated"),
+                ],
+                &[("r", "x"), ("r", "y"), ("x", "x1")],
+            );
+            assert_eq!(
+                subtree_to_markdown(&b, "r"),
+                "# Root\n\n## X\n\n### X1\n\n## Y\n"
+            );
+        }
+
+        #[test]
+        fn cycle_writes_each_node_once() {
+            let b = board(&[("a", "A"), ("b", "B")], &[("a", "b"), ("b", "a")]);
+            assert_eq!(subtree_to_markdown(&b, "a"), "# A\n\n## B\n");
+        }
+
+        #[test]
+        fn md_nodes_contribute_raw_content() {
+            let mut b = board(
+                &[("a", "Spec"), ("m", "## Details\n\n- one\n")],
+                &[("a", "m")],
+            );
+            b.nodes[1].node_type = NodeType::Md;
+            assert_eq!(
+                subtree_to_markdown(&b, "a"),
+                "# Spec\n\n## Details\n\n- one\n"
+            );
+        }
+
+        #[test]
+        fn unknown_root_is_empty() {
+            let b = board(&[("a", "A")], &[]);
+            assert_eq!(subtree_to_markdown(&b, "missing"), "");
+        }
+
+        #[test]
+        fn board_export_writes_every_root_in_order() {
+            let b = board(
+                &[("a", "A"), ("b", "B"), ("c", "C"), ("d", "Loose")],
+                &[("a", "b"), ("c", "b")],
+            );
+            // `b` has two parents but is written once, under the first.
+            assert_eq!(board_to_markdown(&b), "# A\n\n## B\n\n# C\n\n# Loose\n");
+        }
+
+        #[test]
+        fn board_export_includes_cycle_only_nodes() {
+            let b = board(&[("a", "A"), ("b", "B")], &[("a", "b"), ("b", "a")]);
+            assert_eq!(board_to_markdown(&b), "# A\n\n## B\n");
+        }
+
+        #[test]
+        fn board_export_of_empty_board_is_empty() {
+            assert_eq!(board_to_markdown(&Board::default()), "");
+        }
+    }
+
+    mod canvas_import_tests {
+        use super::*;
+
+        const CANVAS: &str = r##"{
+            "nodes": [
+                {"id": "g", "type": "group", "x": 0, "y": 0, "width": 1000, "height": 600, "label": "Plan"},
+                {"id": "t", "type": "text", "x": 10, "y": 20, "width": 250, "height": 60, "text": "Idea", "color": "4"},
+                {"id": "f", "type": "file", "x": 300, "y": 20, "width": 400, "height": 300, "file": "assets/Diagram.PNG"},
+                {"id": "n", "type": "file", "x": 1200, "y": 0, "width": 400, "height": 300, "file": "Notes/todo.md"},
+                {"id": "l", "type": "link", "x": 1200, "y": 400, "width": 300, "height": 80, "url": "https://example.com", "color": "#123456"}
+            ],
+            "edges": [
+                {"id": "e1", "fromNode": "t", "fromSide": "right", "toNode": "f", "label": "shows"},
+                {"id": "e2", "fromNode": "t", "toNode": "g"}
+            ]
+        }"##;
+
+        #[test]
+        fn maps_node_kinds() {
+            let b = board_from_canvas(CANVAS).unwrap();
+            let types: Vec<(&str, NodeType, &str)> = b
+                .nodes
+                .iter()
+                .map(|n| (n.id.as_str(), n.node_type, n.text.as_str()))
+                .collect();
+            assert_eq!(
+                types,
+                vec![
+                    ("t", NodeType::Text, "Idea"),
+                    ("f", NodeType::Image, "assets/Diagram.PNG"),
+                    ("n", NodeType::Link, "Notes/todo.md"),
+                    ("l", NodeType::Link, "https://example.com"),
+                ]
+            );
+            assert_eq!(b.nodes[1].width, 400.0);
+            assert_eq!(b.nodes[1].height, 300.0);
+        }
+
+        #[test]
+        fn groups_become_group_ids() {
+            let b = board_from_canvas(CANVAS).unwrap();
+            assert_eq!(b.nodes[0].group.as_deref(), Some("Plan"));
+            assert_eq!(b.nodes[1].group.as_deref(), Some("Plan"));
+            assert_eq!(b.nodes[2].group, None);
+        }
+
+        #[test]
+        fn colors_map_presets_and_keep_hex() {
+            let b = board_from_canvas(CANVAS).unwrap();
+            assert_eq!(b.nodes[0].color.as_deref(), Some("#22c55e"));
+            assert_eq!(b.nodes[1].color, None);
+            assert_eq!(b.nodes[3].color.as_deref(), Some("#123456"));
+        }
+
+        #[test]
+        fn edges_to_groups_are_dropped() {
+            let b = board_from_canvas(CANVAS).unwrap();
+            assert_eq!(b.edges.len(), 1);
+            assert_eq!(b.edges[0].label.as_deref(), Some("shows"));
+            assert!(b.validate().is_empty());
+        }
+
+        #[test]
+        fn edge_sides_become_ports() {
+            let b = board_from_canvas(CANVAS).unwrap();
+            assert_eq!(b.edges[0].ports(), (Some(Port::Right), None));
+        }
+
+        #[test]
+        fn rejects_non_canvas_text() {
+            assert!(board_from_canvas("not json").is_err());
+        }
+    }
 }
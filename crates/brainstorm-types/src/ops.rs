@@ -0,0 +1,1229 @@
+//! Pure board operations, independent of any frontend.
+//!
+//! Every function here takes a plain [`Board`] and nothing else — no signals, no
+//! DOM, no filesystem — so the canvas reducer, the headless CLI and any future
+//! integration share one implementation and one set of tests. Read-only exports
+//! ([`board_to_markdown`](crate::board_to_markdown),
+//! [`board_to_dot`](crate::board_to_dot), …) live at the crate root beside the
+//! types they render.
+
+use crate::{Board, Edge, Node, NodeType};
+use std::collections::{HashMap, HashSet};
+
+/// Append `node` to the board. Returns `false` (leaving the board unchanged)
+/// when a node with the same id already exists, so a replayed insert can't
+/// create a duplicate id.
+pub fn add_node(board: &mut Board, node: Node) -> bool {
+    if board.nodes.iter().any(|n| n.id == node.id) {
+        return false;
+    }
+    board.nodes.push(node);
+    true
+}
+
+/// Vertical gap between the lowest node on a board and a node appended under it.
+pub const APPEND_GAP: f64 = 40.0;
+
+/// Where a node added from outside the canvas goes: under the lowest node on
+/// the board, lined up with its left edge, or at the origin on an empty board.
+pub fn append_position(board: &Board) -> (f64, f64) {
+    board
+        .nodes
+        .iter()
+        .max_by(|a, b| (a.y + a.height).total_cmp(&(b.y + b.height)))
+        .map_or((0.0, 0.0), |n| (n.x, n.y + n.height + APPEND_GAP))
+}
+
+/// Remove the nodes whose ids are in `ids`, together with every edge touching
+/// one of them. Returns the removed nodes in board order so callers can clean
+/// up anything they reference (e.g. pasted image assets). Unknown ids are
+/// ignored.
+pub fn remove_nodes(board: &mut Board, ids: &[String]) -> Vec<Node> {
+    if ids.is_empty() {
+        return Vec::new();
+    }
+    let (removed, kept): (Vec<Node>, Vec<Node>) = std::mem::take(&mut board.nodes)
+        .into_iter()
+        .partition(|n| ids.contains(&n.id));
+    board.nodes = kept;
+    board
+        .edges
+        .retain(|e| !ids.contains(&e.from_node) && !ids.contains(&e.to_node));
+    removed
+}
+
+/// Remove the edge with id `id`, returning it if it existed.
+pub fn remove_edge(board: &mut Board, id: &str) -> Option<Edge> {
+    let index = board.edges.iter().position(|e| e.id == id)?;
+    Some(board.edges.remove(index))
+}
+
+/// Whether the board already has an edge `from -> to`. On an undirected board
+/// (`settings.directed == false`) an edge `to -> from` counts too.
+pub fn edge_exists(board: &Board, from: &str, to: &str) -> bool {
+    let directed = board.settings.directed;
+    board.edges.iter().any(|e| {
+        (e.from_node == from && e.to_node == to)
+            || (!directed && e.from_node == to && e.to_node == from)
+    })
+}
+
+/// The `(from, to)` node ids of the edge with id `edge_id`, if it exists.
+pub fn edge_endpoints(board: &Board, edge_id: &str) -> Option<(String, String)> {
+    board
+        .edges
+        .iter()
+        .find(|e| e.id == edge_id)
+        .map(|e| (e.from_node.clone(), e.to_node.clone()))
+}
+
+/// The id of the first edge (in board order) joining `a` and `b` in either
+/// direction — a pair of selected nodes has no order to honor.
+pub fn edge_between(board: &Board, a: &str, b: &str) -> Option<String> {
+    board
+        .edges
+        .iter()
+        .find(|e| (e.from_node == a && e.to_node == b) || (e.from_node == b && e.to_node == a))
+        .map(|e| e.id.clone())
+}
+
+/// Add an unlabeled edge `from -> to` with id `id`. Returns `false` without
+/// changing the board when either endpoint doesn't exist (so the board never
+/// gains a dangling edge this way) or the edge already exists per
+/// [`edge_exists`].
+pub fn connect(board: &mut Board, id: String, from: &str, to: &str) -> bool {
+    let exists = |id: &str| board.nodes.iter().any(|n| n.id == id);
+    if !exists(from) || !exists(to) || edge_exists(board, from, to) {
+        return false;
+    }
+    board.edges.push(Edge {
+        id,
+        from_node: from.to_string(),
+        to_node: to.to_string(),
+        label: None,
+        from_port: None,
+        to_port: None,
+    });
+    true
+}
+
+/// The part of the board made of the nodes in `ids` and the edges running
+/// between two of them, in board order. Edges leaving the set are dropped, so
+/// the result is self-contained (what copy/paste and subset exports need).
+pub fn extract_subgraph(board: &Board, ids: &HashSet<String>) -> Board {
+    Board {
+        version: board.version,
+        nodes: board
+            .nodes
+            .iter()
+            .filter(|n| ids.contains(&n.id))
+            .cloned()
+            .collect(),
+        edges: board
+            .edges
+            .iter()
+            .filter(|e| ids.contains(&e.from_node) && ids.contains(&e.to_node))
+            .cloned()
+            .collect(),
+        settings: Default::default(),
+    }
+}
+
+/// Replace the nodes in `ids` with one node holding `new_text`, centered on
+/// their bounding box and drawn at the first one's place in the stacking
+/// order. The new node takes the id of the first collapsed node in board
+/// order, which is returned; `None` (board unchanged) when no id matches.
+///
+/// Edges running between two collapsed nodes are dropped. Edges to the rest of
+/// the board are rewired to the new node, keeping their ids and labels, and an
+/// edge that would duplicate one already rewired (two members linked to the
+/// same outside node) is dropped too.
+pub fn collapse_to_node(board: &mut Board, ids: &[String], new_text: String) -> Option<String> {
+    let index = board.nodes.iter().position(|n| ids.contains(&n.id))?;
+    let id = board.nodes[index].id.clone();
+    let members: Vec<Node> = board
+        .nodes
+        .iter()
+        .filter(|n| ids.contains(&n.id))
+        .cloned()
+        .collect();
+    let (min_x, min_y, max_x, max_y) = crate::nodes_bounding_box(&members)?;
+
+    let mut node = Node::new(id.clone(), 0.0, 0.0, new_text);
+    node.x = (min_x + max_x - node.width) / 2.0;
+    node.y = (min_y + max_y - node.height) / 2.0;
+    board.nodes.retain(|n| !ids.contains(&n.id));
+    board.nodes.insert(index, node);
+
+    let old_edges = std::mem::take(&mut board.edges);
+    for mut edge in old_edges {
+        let from_in = ids.contains(&edge.from_node);
+        let to_in = ids.contains(&edge.to_node);
+        if from_in && to_in {
+            continue;
+        }
+        if from_in || to_in {
+            if from_in {
+                edge.from_node = id.clone();
+            } else {
+                edge.to_node = id.clone();
+            }
+            if edge_exists(board, &edge.from_node, &edge.to_node) {
+                continue;
+            }
+        }
+        board.edges.push(edge);
+    }
+    Some(id)
+}
+
+/// Summary counts for a board, as printed by `infinite-brainstorm stats`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BoardStats {
+    pub nodes: usize,
+    pub edges: usize,
+    /// Node count per type, in [`NodeType`] declaration order, omitting types
+    /// with no nodes.
+    pub by_type: Vec<(NodeType, usize)>,
+    /// Distinct tags across all nodes.
+    pub tags: usize,
+    /// Distinct group ids across all nodes.
+    pub groups: usize,
+    /// Nodes with no edge in or out.
+    pub orphans: usize,
+    /// Number of [`connected_components`] (strongly connected on a directed
+    /// board).
+    pub components: usize,
+    /// Whether the board [`has_cycle`].
+    pub has_cycle: bool,
+    /// Edges whose endpoint doesn't exist (see [`Board::drop_dangling_edges`]).
+    pub dangling_edges: usize,
+}
+
+impl std::fmt::Display for BoardStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "nodes: {}", self.nodes)?;
+        for (node_type, count) in &self.by_type {
+            writeln!(f, "  {}: {count}", node_type.as_str())?;
+        }
+        writeln!(f, "edges: {}", self.edges)?;
+        writeln!(f, "tags: {}", self.tags)?;
+        writeln!(f, "groups: {}", self.groups)?;
+        writeln!(f, "orphans: {}", self.orphans)?;
+        writeln!(f, "components: {}", self.components)?;
+        writeln!(f, "cycles: {}", if self.has_cycle { "yes" } else { "no" })?;
+        write!(f, "dangling edges: {}", self.dangling_edges)
+    }
+}
+
+/// Count the board's nodes, edges, types, tags, groups, orphans, components
+/// and dangling edges, and note whether it has a cycle. Components and cycles
+/// follow the board's `directed` setting.
+pub fn board_stats(board: &Board) -> BoardStats {
+    const TYPES: [NodeType; 7] = [
+        NodeType::Text,
+        NodeType::Idea,
+        NodeType::Note,
+        NodeType::Image,
+        NodeType::Md,
+        NodeType::Link,
+        NodeType::Unknown,
+    ];
+    let node_ids: HashSet<&str> = board.nodes.iter().map(|n| n.id.as_str()).collect();
+    BoardStats {
+        nodes: board.nodes.len(),
+        edges: board.edges.len(),
+        by_type: TYPES
+            .into_iter()
+            .map(|t| (t, board.nodes.iter().filter(|n| n.node_type == t).count()))
+            .filter(|&(_, count)| count > 0)
+            .collect(),
+        tags: board
+            .nodes
+            .iter()
+            .flat_map(|n| &n.tags)
+            .collect::<HashSet<_>>()
+            .len(),
+        groups: board
+            .nodes
+            .iter()
+            .filter_map(|n| n.group.as_deref())
+            .collect::<HashSet<_>>()
+            .len(),
+        orphans: orphan_nodes(board).len(),
+        components: connected_components(board).len(),
+        has_cycle: has_cycle(board),
+        dangling_edges: board
+            .edges
+            .iter()
+            .filter(|e| {
+                !node_ids.contains(e.from_node.as_str()) || !node_ids.contains(e.to_node.as_str())
+            })
+            .count(),
+    }
+}
+
+/// Ids of the nodes no edge touches (neither as `from_node` nor `to_node`), in
+/// board order. A self-loop counts as a connection.
+pub fn orphan_nodes(board: &Board) -> Vec<String> {
+    let connected: HashSet<&str> = board
+        .edges
+        .iter()
+        .flat_map(|e| [e.from_node.as_str(), e.to_node.as_str()])
+        .collect();
+    board
+        .nodes
+        .iter()
+        .filter(|n| !connected.contains(n.id.as_str()))
+        .map(|n| n.id.clone())
+        .collect()
+}
+
+/// Adjacency lists over node indices for the edges whose endpoints both exist:
+/// `(forward, reverse)`. On an undirected board both lists hold every edge in
+/// both directions.
+fn adjacency(board: &Board) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let index: HashMap<&str, usize> = board
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.id.as_str(), i))
+        .collect();
+    let mut forward = vec![Vec::new(); board.nodes.len()];
+    let mut reverse = vec![Vec::new(); board.nodes.len()];
+    for edge in &board.edges {
+        if let (Some(&a), Some(&b)) = (
+            index.get(edge.from_node.as_str()),
+            index.get(edge.to_node.as_str()),
+        ) {
+            forward[a].push(b);
+            reverse[b].push(a);
+            if !board.settings.directed {
+                forward[b].push(a);
+                reverse[a].push(b);
+            }
+        }
+    }
+    (forward, reverse)
+}
+
+/// The board's components as lists of node ids: strongly connected components
+/// (each node reaches every other along edge direction) on a directed board,
+/// plain connected components on an undirected one. Ids within a component and
+/// the components themselves follow board order (by first member); an
+/// unconnected node is a component of its own.
+pub fn connected_components(board: &Board) -> Vec<Vec<String>> {
+    let (forward, reverse) = adjacency(board);
+    let n = board.nodes.len();
+
+    // Kosaraju: finish order over the forward graph (iterative DFS), then sweep
+    // the reverse graph in reverse finish order. With symmetric adjacency this
+    // is ordinary connected components.
+    let mut order = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    for start in 0..n {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut stack = vec![(start, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            if let Some(&child) = forward[*node].get(*next) {
+                *next += 1;
+                if !visited[child] {
+                    visited[child] = true;
+                    stack.push((child, 0));
+                }
+            } else {
+                order.push(*node);
+                stack.pop();
+            }
+        }
+    }
+
+    let mut component = vec![usize::MAX; n];
+    let mut count = 0;
+    for &start in order.iter().rev() {
+        if component[start] != usize::MAX {
+            continue;
+        }
+        component[start] = count;
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in &reverse[node] {
+                if component[next] == usize::MAX {
+                    component[next] = count;
+                    stack.push(next);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    // Renumber by first member in board order.
+    let mut slot: HashMap<usize, usize> = HashMap::new();
+    let mut out: Vec<Vec<String>> = Vec::new();
+    for (i, node) in board.nodes.iter().enumerate() {
+        let at = *slot.entry(component[i]).or_insert_with(|| {
+            out.push(Vec::new());
+            out.len() - 1
+        });
+        out[at].push(node.id.clone());
+    }
+    out
+}
+
+/// Whether the board contains a cycle: a directed cycle (including a
+/// self-loop) on a directed board, or any closed path on an undirected one.
+/// Undirected, `a -> b` and `b -> a` are the same edge and don't form a cycle
+/// by themselves. Edges with a missing endpoint are ignored.
+pub fn has_cycle(board: &Board) -> bool {
+    let node_ids: HashSet<&str> = board.nodes.iter().map(|n| n.id.as_str()).collect();
+    let edges = board.edges.iter().filter(|e| {
+        node_ids.contains(e.from_node.as_str()) && node_ids.contains(e.to_node.as_str())
+    });
+    if board.settings.directed {
+        return edges.clone().any(|e| e.from_node == e.to_node)
+            || connected_components(board).iter().any(|c| c.len() > 1);
+    }
+
+    // Union-find: an edge joining two nodes already connected closes a cycle.
+    let mut parent: HashMap<&str, &str> = HashMap::new();
+    fn root<'a>(parent: &mut HashMap<&'a str, &'a str>, mut id: &'a str) -> &'a str {
+        while let Some(&up) = parent.get(id) {
+            id = up;
+        }
+        id
+    }
+    let mut seen: HashSet<(&str, &str)> = HashSet::new();
+    for edge in edges {
+        let (a, b) = (edge.from_node.as_str(), edge.to_node.as_str());
+        if !seen.insert((a.min(b), a.max(b))) {
+            continue;
+        }
+        let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+        if ra == rb {
+            return true;
+        }
+        parent.insert(ra, rb);
+    }
+    false
+}
+
+/// Whether adding an edge `from -> to` would close a directed cycle, i.e.
+/// `from` is already reachable from `to` along edge direction (a self-loop
+/// always would). Follows direction regardless of `settings.directed`; callers
+/// only enforce acyclicity on directed boards.
+pub fn would_create_cycle(board: &Board, from: &str, to: &str) -> bool {
+    from == to || board.downstream_nodes(to).contains(from)
+}
+
+/// Whether splicing `node` into edge `edge_id` (replacing `from -> to` with
+/// `from -> node -> to`, as the Alt-drop split does) would close a directed
+/// cycle. `false` for an unknown edge.
+pub fn split_would_create_cycle(board: &Board, edge_id: &str, node: &str) -> bool {
+    let Some(edge) = board.edges.iter().find(|e| e.id == edge_id) else {
+        return false;
+    };
+    let mut spliced = Board {
+        edges: board
+            .edges
+            .iter()
+            .filter(|e| e.id != edge_id)
+            .cloned()
+            .collect(),
+        ..Board::default()
+    };
+    if would_create_cycle(&spliced, &edge.from_node, node) {
+        return true;
+    }
+    spliced.edges.push(Edge {
+        id: String::new(),
+        from_node: edge.from_node.clone(),
+        to_node: node.to_string(),
+        label: None,
+        from_port: None,
+        to_port: None,
+    });
+    would_create_cycle(&spliced, node, &edge.to_node)
+}
+
+/// Ideal distance (world units) between the centers of two connected nodes in
+/// [`layout_positions`].
+pub const LAYOUT_SPACING: f64 = 300.0;
+
+/// Simulation steps [`layout_positions`] runs. Fixed (no convergence test) so
+/// the amount of work, and therefore the result, never depends on timing.
+const LAYOUT_ITERATIONS: usize = 200;
+
+/// SplitMix64: a tiny, well-mixed PRNG. Hand-rolled so layout results can't
+/// change with a dependency's algorithm and the crate stays serde-only.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)` from the top 53 bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Force-directed (Fruchterman–Reingold) layout: edges pull their endpoints
+/// toward [`LAYOUT_SPACING`] apart while every pair of nodes repels. Returns the
+/// new top-left `(id, x, y)` of every node, in board order; the laid-out graph
+/// is centered on the current centroid of the node centers, so the result stays
+/// where the user was looking.
+///
+/// Reproducibility guarantee: the output is a pure function of the node ids,
+/// sizes and order, the edges, and `seed`. Starting positions come from a
+/// seeded [`SplitMix64`] (never a global or time-based RNG), the simulation runs
+/// a fixed number of steps in board order, and nothing else is consulted — so
+/// running it twice on the same input with the same seed yields bit-identical
+/// coordinates, while a different seed gives a different arrangement.
+pub fn layout_positions(board: &Board, seed: u64) -> Vec<(String, f64, f64)> {
+    let n = board.nodes.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut rng = SplitMix64(seed);
+    let side = LAYOUT_SPACING * (n as f64).sqrt();
+    let mut pos: Vec<(f64, f64)> = (0..n)
+        .map(|_| (rng.next_f64() * side, rng.next_f64() * side))
+        .collect();
+
+    let index: HashMap<&str, usize> = board
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), i))
+        .collect();
+    let springs: Vec<(usize, usize)> = board
+        .edges
+        .iter()
+        .filter_map(|e| {
+            let a = *index.get(e.from_node.as_str())?;
+            let b = *index.get(e.to_node.as_str())?;
+            (a != b).then_some((a, b))
+        })
+        .collect();
+
+    let k = LAYOUT_SPACING;
+    let start_temperature = side / 10.0;
+    for step in 0..LAYOUT_ITERATIONS {
+        let mut disp = vec![(0.0_f64, 0.0_f64); n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let dist = dx.hypot(dy).max(0.01);
+                let force = k * k / dist;
+                let (fx, fy) = (dx / dist * force, dy / dist * force);
+                disp[i].0 += fx;
+                disp[i].1 += fy;
+                disp[j].0 -= fx;
+                disp[j].1 -= fy;
+            }
+        }
+        for &(a, b) in &springs {
+            let (dx, dy) = (pos[a].0 - pos[b].0, pos[a].1 - pos[b].1);
+            let dist = dx.hypot(dy).max(0.01);
+            let force = dist * dist / k;
+            let (fx, fy) = (dx / dist * force, dy / dist * force);
+            disp[a].0 -= fx;
+            disp[a].1 -= fy;
+            disp[b].0 += fx;
+            disp[b].1 += fy;
+        }
+        // Linear cooling: each node moves at most `temperature` per step.
+        let temperature = start_temperature * (1.0 - step as f64 / LAYOUT_ITERATIONS as f64);
+        for (p, (dx, dy)) in pos.iter_mut().zip(disp) {
+            let len = dx.hypot(dy);
+            if len > 0.0 {
+                let moved = len.min(temperature);
+                p.0 += dx / len * moved;
+                p.1 += dy / len * moved;
+            }
+        }
+    }
+
+    let centroid = |points: &mut dyn Iterator<Item = (f64, f64)>| {
+        let (sx, sy) = points.fold((0.0, 0.0), |acc, p| (acc.0 + p.0, acc.1 + p.1));
+        (sx / n as f64, sy / n as f64)
+    };
+    let (cx, cy) = centroid(&mut board.nodes.iter().map(Node::center));
+    let (lx, ly) = centroid(&mut pos.iter().copied());
+    board
+        .nodes
+        .iter()
+        .zip(pos)
+        .map(|(node, (x, y))| {
+            (
+                node.id.clone(),
+                x - lx + cx - node.width / 2.0,
+                y - ly + cy - node.height / 2.0,
+            )
+        })
+        .collect()
+}
+
+/// What changed between two versions of a board, by id. Ids are listed in the
+/// order of the board they come from (`b` for added, `a` for the rest).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BoardDiff {
+    /// Nodes only in `b`.
+    pub added_nodes: Vec<String>,
+    /// Nodes only in `a`.
+    pub removed_nodes: Vec<String>,
+    /// Nodes in both whose position or size differs.
+    pub moved_nodes: Vec<String>,
+    /// Nodes in both whose text or metadata differs (a node can be both moved
+    /// and changed).
+    pub changed_nodes: Vec<String>,
+    pub added_edges: Vec<String>,
+    pub removed_edges: Vec<String>,
+    /// Edges in both whose endpoints or label differ.
+    pub changed_edges: Vec<String>,
+}
+
+impl BoardDiff {
+    /// True when the boards hold the same nodes and edges.
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.moved_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+/// Compare an older board `a` (e.g. a backup) with a newer board `b`, matching
+/// nodes and edges by id. Geometry (`x`, `y`, `width`, `height`) counts as a
+/// move; every other node field counts as a change. Board settings aren't
+/// compared.
+pub fn diff_boards(a: &Board, b: &Board) -> BoardDiff {
+    let old_nodes: HashMap<&str, &Node> = a.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let new_nodes: HashMap<&str, &Node> = b.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let old_edges: HashMap<&str, &Edge> = a.edges.iter().map(|e| (e.id.as_str(), e)).collect();
+    let new_edges: HashMap<&str, &Edge> = b.edges.iter().map(|e| (e.id.as_str(), e)).collect();
+
+    let mut diff = BoardDiff {
+        added_nodes: b
+            .nodes
+            .iter()
+            .filter(|n| !old_nodes.contains_key(n.id.as_str()))
+            .map(|n| n.id.clone())
+            .collect(),
+        added_edges: b
+            .edges
+            .iter()
+            .filter(|e| !old_edges.contains_key(e.id.as_str()))
+            .map(|e| e.id.clone())
+            .collect(),
+        ..BoardDiff::default()
+    };
+    for old in &a.nodes {
+        let Some(new) = new_nodes.get(old.id.as_str()) else {
+            diff.removed_nodes.push(old.id.clone());
+            continue;
+        };
+        if (old.x, old.y, old.width, old.height) != (new.x, new.y, new.width, new.height) {
+            diff.moved_nodes.push(old.id.clone());
+        }
+        let same_geometry = Node {
+            x: old.x,
+            y: old.y,
+            width: old.width,
+            height: old.height,
+            ..(*new).clone()
+        };
+        if &same_geometry != old {
+            diff.changed_nodes.push(old.id.clone());
+        }
+    }
+    for old in &a.edges {
+        match new_edges.get(old.id.as_str()) {
+            None => diff.removed_edges.push(old.id.clone()),
+            Some(new) if *new != old => diff.changed_edges.push(old.id.clone()),
+            Some(_) => {}
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(ids: &[&str], edges: &[(&str, &str)]) -> Board {
+        Board {
+            version: None,
+            nodes: ids
+                .iter()
+                .map(|id| Node::new((*id).into(), 0.0, 0.0, (*id).into()))
+                .collect(),
+            edges: edges
+                .iter()
+                .map(|(f, t)| Edge {
+                    id: format!("{f}-{t}"),
+                    from_node: (*f).into(),
+                    to_node: (*t).into(),
+                    label: None,
+                    from_port: None,
+                    to_port: None,
+                })
+                .collect(),
+            settings: Default::default(),
+        }
+    }
+
+    fn node_ids(b: &Board) -> Vec<&str> {
+        b.nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    fn edge_ids(b: &Board) -> Vec<&str> {
+        b.edges.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    mod add_node_tests {
+        use super::*;
+
+        #[test]
+        fn appends_a_new_node() {
+            let mut b = board(&["a"], &[]);
+            assert!(add_node(
+                &mut b,
+                Node::new("b".into(), 1.0, 2.0, "B".into())
+            ));
+            assert_eq!(node_ids(&b), ["a", "b"]);
+        }
+
+        #[test]
+        fn rejects_a_duplicate_id() {
+            let mut b = board(&["a"], &[]);
+            assert!(!add_node(
+                &mut b,
+                Node::new("a".into(), 1.0, 2.0, "Again".into())
+            ));
+            assert_eq!(b.nodes.len(), 1);
+            assert_eq!(b.nodes[0].text, "a");
+        }
+    }
+
+    mod append_position_tests {
+        use super::*;
+
+        #[test]
+        fn goes_under_the_lowest_node() {
+            let mut b = board(&["a", "b"], &[]);
+            b.nodes[0].x = 10.0;
+            b.nodes[0].y = 300.0;
+            b.nodes[1].y = 50.0;
+            let bottom = 300.0 + b.nodes[0].height;
+            assert_eq!(append_position(&b), (10.0, bottom + APPEND_GAP));
+        }
+
+        #[test]
+        fn empty_board_starts_at_the_origin() {
+            assert_eq!(append_position(&board(&[], &[])), (0.0, 0.0));
+        }
+    }
+
+    mod remove_nodes_tests {
+        use super::*;
+
+        #[test]
+        fn removes_nodes_and_their_edges() {
+            let mut b = board(&["a", "b", "c"], &[("a", "b"), ("b", "c"), ("a", "c")]);
+            let removed = remove_nodes(&mut b, &["b".to_string()]);
+            assert_eq!(removed.len(), 1);
+            assert_eq!(removed[0].id, "b");
+            assert_eq!(node_ids(&b), ["a", "c"]);
+            assert_eq!(edge_ids(&b), ["a-c"]);
+        }
+
+        #[test]
+        fn unknown_ids_change_nothing() {
+            let mut b = board(&["a", "b"], &[("a", "b")]);
+            assert!(remove_nodes(&mut b, &["zzz".to_string()]).is_empty());
+            assert_eq!(b, board(&["a", "b"], &[("a", "b")]));
+        }
+    }
+
+    mod remove_edge_tests {
+        use super::*;
+
+        #[test]
+        fn removes_only_that_edge() {
+            let mut b = board(&["a", "b"], &[("a", "b"), ("b", "a")]);
+            assert_eq!(remove_edge(&mut b, "a-b").map(|e| e.id), Some("a-b".into()));
+            assert_eq!(edge_ids(&b), ["b-a"]);
+            assert_eq!(remove_edge(&mut b, "a-b"), None);
+        }
+    }
+
+    mod connect_tests {
+        use super::*;
+
+        #[test]
+        fn links_existing_nodes() {
+            let mut b = board(&["a", "b"], &[]);
+            assert!(connect(&mut b, "e1".into(), "a", "b"));
+            assert_eq!(b.edges.len(), 1);
+            assert_eq!(b.edges[0].id, "e1");
+            assert_eq!(b.edges[0].from_node, "a");
+            assert_eq!(b.edges[0].to_node, "b");
+            assert_eq!(b.edges[0].label, None);
+        }
+
+        #[test]
+        fn refuses_a_missing_endpoint() {
+            let mut b = board(&["a"], &[]);
+            assert!(!connect(&mut b, "e1".into(), "a", "ghost"));
+            assert!(!connect(&mut b, "e2".into(), "ghost", "a"));
+            assert!(b.edges.is_empty());
+        }
+    }
+
+    mod direction_tests {
+        use super::*;
+
+        fn undirected(mut b: Board) -> Board {
+            b.settings.directed = false;
+            b
+        }
+
+        #[test]
+        fn edge_exists_follows_direction_only_when_directed() {
+            let b = board(&["a", "b"], &[("a", "b")]);
+            assert!(edge_exists(&b, "a", "b"));
+            assert!(!edge_exists(&b, "b", "a"));
+            let b = undirected(b);
+            assert!(edge_exists(&b, "a", "b"));
+            assert!(edge_exists(&b, "b", "a"));
+            assert!(!edge_exists(&b, "a", "a"));
+        }
+
+        #[test]
+        fn connect_refuses_duplicates() {
+            let mut b = board(&["a", "b"], &[("a", "b")]);
+            assert!(!connect(&mut b, "dup".into(), "a", "b"));
+            assert!(connect(&mut b, "back".into(), "b", "a"));
+
+            let mut b = undirected(board(&["a", "b"], &[("a", "b")]));
+            assert!(!connect(&mut b, "back".into(), "b", "a"));
+            assert_eq!(edge_ids(&b), ["a-b"]);
+        }
+
+        #[test]
+        fn directed_components_are_strongly_connected() {
+            // a <-> b form a cycle; c hangs off b; d is alone.
+            let b = board(&["a", "b", "c", "d"], &[("a", "b"), ("b", "a"), ("b", "c")]);
+            assert_eq!(
+                connected_components(&b),
+                vec![vec!["a", "b"], vec!["c"], vec!["d"]]
+            );
+        }
+
+        #[test]
+        fn undirected_components_ignore_direction() {
+            let b = undirected(board(
+                &["a", "b", "c", "d", "e"],
+                &[("a", "b"), ("c", "b"), ("e", "d"), ("x", "a")],
+            ));
+            assert_eq!(
+                connected_components(&b),
+                vec![vec!["a", "b", "c"], vec!["d", "e"]]
+            );
+        }
+
+        #[test]
+        fn a_chain_is_one_component_only_when_undirected() {
+            let b = board(&["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+            assert_eq!(connected_components(&b).len(), 3);
+            assert_eq!(connected_components(&undirected(b)).len(), 1);
+        }
+
+        #[test]
+        fn directed_cycles() {
+            assert!(!has_cycle(&board(
+                &["a", "b", "c"],
+                &[("a", "b"), ("b", "c"), ("a", "c")]
+            )));
+            assert!(has_cycle(&board(
+                &["a", "b", "c"],
+                &[("a", "b"), ("b", "c"), ("c", "a")]
+            )));
+            assert!(has_cycle(&board(&["a"], &[("a", "a")])));
+        }
+
+        #[test]
+        fn would_create_cycle_allows_a_safe_edge() {
+            let b = board(&["a", "b", "c"], &[("a", "b"), ("b", "c")]);
+            assert!(!would_create_cycle(&b, "a", "c"));
+            assert!(!would_create_cycle(&b, "c", "d"));
+        }
+
+        #[test]
+        fn would_create_cycle_catches_a_two_cycle_and_self_loop() {
+            let b = board(&["a", "b"], &[("a", "b")]);
+            assert!(would_create_cycle(&b, "b", "a"));
+            assert!(would_create_cycle(&b, "a", "a"));
+        }
+
+        #[test]
+        fn would_create_cycle_follows_a_longer_path() {
+            let b = board(
+                &["a", "b", "c", "d", "e"],
+                &[("a", "b"), ("b", "c"), ("c", "d"), ("b", "e")],
+            );
+            assert!(would_create_cycle(&b, "d", "a"));
+            assert!(would_create_cycle(&b, "e", "b"));
+            assert!(!would_create_cycle(&b, "d", "e"));
+        }
+
+        #[test]
+        fn splitting_an_edge_checks_both_halves() {
+            // c already reaches a: putting c between a and b closes a -> c -> a.
+            let b = board(&["a", "b", "c"], &[("a", "b"), ("c", "a")]);
+            assert!(split_would_create_cycle(&b, "a-b", "c"));
+            // b reaches d: d between a and b closes d -> b -> d.
+            let b = board(&["a", "b", "d"], &[("a", "b"), ("b", "d")]);
+            assert!(split_would_create_cycle(&b, "a-b", "d"));
+            // A free node splits cleanly; an unknown edge is never refused.
+            let b = board(&["a", "b", "e"], &[("a", "b")]);
+            assert!(!split_would_create_cycle(&b, "a-b", "e"));
+            assert!(!split_would_create_cycle(&b, "nope", "e"));
+        }
+
+        #[test]
+        fn undirected_cycles() {
+            // The directed-acyclic triangle closes a loop once direction is gone.
+            let triangle = board(&["a", "b", "c"], &[("a", "b"), ("b", "c"), ("a", "c")]);
+            assert!(has_cycle(&undirected(triangle)));
+            assert!(!has_cycle(&undirected(board(
+                &["a", "b", "c"],
+                &[("a", "b"), ("b", "c")]
+            ))));
+            // A back-and-forth pair is one undirected edge, not a cycle.
+            assert!(!has_cycle(&undirected(board(
+                &["a", "b"],
+                &[("a", "b"), ("b", "a")]
+            ))));
+            assert!(has_cycle(&undirected(board(&["a"], &[("a", "a")]))));
+        }
+    }
+
+    mod edge_lookup_tests {
+        use super::*;
+
+        #[test]
+        fn edge_endpoints_reads_from_and_to() {
+            let b = board(&["a", "b"], &[("a", "b")]);
+            assert_eq!(
+                edge_endpoints(&b, "a-b"),
+                Some(("a".to_string(), "b".to_string()))
+            );
+            assert_eq!(edge_endpoints(&b, "missing"), None);
+        }
+
+        #[test]
+        fn edge_between_ignores_direction() {
+            let b = board(&["a", "b", "c"], &[("a", "b"), ("c", "b")]);
+            assert_eq!(edge_between(&b, "a", "b").as_deref(), Some("a-b"));
+            assert_eq!(edge_between(&b, "b", "a").as_deref(), Some("a-b"));
+            assert_eq!(edge_between(&b, "b", "c").as_deref(), Some("c-b"));
+            assert_eq!(edge_between(&b, "a", "c"), None);
+        }
+
+        #[test]
+        fn edge_between_prefers_the_first_edge() {
+            let b = board(&["a", "b"], &[("b", "a"), ("a", "b")]);
+            assert_eq!(edge_between(&b, "a", "b").as_deref(), Some("b-a"));
+        }
+    }
+
+    mod extract_subgraph_tests {
+        use super::*;
+
+        #[test]
+        fn keeps_selected_nodes_and_inner_edges() {
+            let b = board(&["a", "b", "c"], &[("a", "b"), ("b", "c"), ("c", "a")]);
+            let ids: HashSet<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+            let sub = extract_subgraph(&b, &ids);
+            assert_eq!(node_ids(&sub), ["a", "b"]);
+            assert_eq!(edge_ids(&sub), ["a-b"]);
+            assert!(sub.validate().is_empty());
+        }
+
+        #[test]
+        fn empty_selection_is_an_empty_board() {
+            let b = board(&["a"], &[]);
+            let sub = extract_subgraph(&b, &HashSet::new());
+            assert!(sub.nodes.is_empty() && sub.edges.is_empty());
+        }
+    }
+
+    mod collapse_to_node_tests {
+        use super::*;
+
+        #[test]
+        fn rewires_external_edges_to_the_new_node() {
+            let mut b = board(&["x", "a", "b", "y"], &[("x", "a"), ("b", "y")]);
+            let ids = vec!["a".to_string(), "b".to_string()];
+            let id = collapse_to_node(&mut b, &ids, "Summary".into()).unwrap();
+            assert_eq!(id, "a");
+            assert_eq!(node_ids(&b), ["x", "a", "y"]);
+            assert_eq!(b.nodes[1].text, "Summary");
+            let ends: Vec<(&str, &str, &str)> = b
+                .edges
+                .iter()
+                .map(|e| (e.id.as_str(), e.from_node.as_str(), e.to_node.as_str()))
+                .collect();
+            assert_eq!(ends, [("x-a", "x", "a"), ("b-y", "a", "y")]);
+        }
+
+        #[test]
+        fn drops_internal_edges() {
+            let mut b = board(&["a", "b", "c"], &[("a", "b"), ("b", "c"), ("c", "a")]);
+            let ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+            collapse_to_node(&mut b, &ids, "All".into());
+            assert_eq!(node_ids(&b), ["a"]);
+            assert!(b.edges.is_empty());
+        }
+
+        #[test]
+        fn merges_edges_that_would_duplicate() {
+            let mut b = board(&["a", "b", "x"], &[("a", "x"), ("b", "x")]);
+            let ids = vec!["a".to_string(), "b".to_string()];
+            collapse_to_node(&mut b, &ids, "Both".into());
+            assert_eq!(edge_ids(&b), ["a-x"]);
+        }
+
+        #[test]
+        fn centers_on_the_members() {
+            let mut b = board(&["a", "b"], &[]);
+            b.nodes[1].x = 400.0;
+            b.nodes[1].y = 200.0;
+            let ids = vec!["a".to_string(), "b".to_string()];
+            collapse_to_node(&mut b, &ids, "Mid".into());
+            let n = &b.nodes[0];
+            let (w, h) = (400.0 + n.width, 200.0 + n.height);
+            assert_eq!(n.x + n.width / 2.0, w / 2.0);
+            assert_eq!(n.y + n.height / 2.0, h / 2.0);
+        }
+
+        #[test]
+        fn unknown_ids_leave_the_board_alone() {
+            let mut b = board(&["a"], &[]);
+            assert_eq!(
+                collapse_to_node(&mut b, &["zz".to_string()], "X".into()),
+                None
+            );
+            assert_eq!(b.nodes[0].text, "a");
+        }
+    }
+
+    mod board_stats_tests {
+        use super::*;
+
+        #[test]
+        fn counts_types_tags_groups_and_orphans() {
+            let mut b = board(&["a", "b", "c", "d"], &[("a", "b"), ("b", "ghost")]);
+            b.nodes[1].node_type = NodeType::Idea;
+            b.nodes[2].node_type = NodeType::Idea;
+            b.nodes[0].tags = vec!["x".into(), "y".into()];
+            b.nodes[1].tags = vec!["x".into()];
+            b.nodes[2].group = Some("g1".into());
+            b.nodes[3].group = Some("g1".into());
+            let stats = board_stats(&b);
+            assert_eq!(stats.nodes, 4);
+            assert_eq!(stats.edges, 2);
+            assert_eq!(
+                stats.by_type,
+                vec![(NodeType::Text, 2), (NodeType::Idea, 2)]
+            );
+            assert_eq!(stats.tags, 2);
+            assert_eq!(stats.groups, 1);
+            assert_eq!(stats.orphans, 2);
+            assert_eq!(stats.components, 4);
+            assert!(!stats.has_cycle);
+            assert_eq!(stats.dangling_edges, 1);
+        }
+
+        #[test]
+        fn components_and_cycles_follow_directed() {
+            let mut b = board(&["a", "b", "c"], &[("a", "b"), ("b", "a")]);
+            let stats = board_stats(&b);
+            assert_eq!(stats.components, 2);
+            assert!(stats.has_cycle);
+
+            b.settings.directed = false;
+            let stats = board_stats(&b);
+            assert_eq!(stats.components, 2);
+            assert!(!stats.has_cycle);
+
+            b.edges.truncate(1);
+            b.settings.directed = true;
+            assert_eq!(board_stats(&b).components, 3);
+        }
+
+        #[test]
+        fn empty_board_is_all_zero() {
+            assert_eq!(board_stats(&Board::default()), BoardStats::default());
+        }
+
+        #[test]
+        fn display_lists_each_count() {
+            let text = board_stats(&board(&["a"], &[])).to_string();
+            assert_eq!(
+                text,
+                "nodes: 1\n  text: 1\nedges: 0\ntags: 0\ngroups: 0\norphans: 1\ncomponents: 1\ncycles: no\ndangling edges: 0"
+            );
+        }
+    }
+
+    mod orphan_nodes_tests {
+        use super::*;
+
+        #[test]
+        fn lists_unconnected_nodes_in_board_order() {
+            let b = board(&["a", "b", "c", "d"], &[("b", "c")]);
+            assert_eq!(orphan_nodes(&b), ["a", "d"]);
+        }
+
+        #[test]
+        fn edge_target_alone_is_connected() {
+            let b = board(&["a", "b"], &[("ghost", "b")]);
+            assert_eq!(orphan_nodes(&b), ["a"]);
+        }
+
+        #[test]
+        fn self_loop_is_connected() {
+            let b = board(&["a", "b"], &[("a", "a")]);
+            assert_eq!(orphan_nodes(&b), ["b"]);
+        }
+
+        #[test]
+        fn empty_board_has_none() {
+            assert!(orphan_nodes(&Board::default()).is_empty());
+        }
+    }
+
+    mod layout_positions_tests {
+        use super::*;
+
+        fn sample() -> Board {
+            board(
+                &["a", "b", "c", "d", "e"],
+                &[("a", "b"), ("b", "c"), ("c", "a"), ("d", "e")],
+            )
+        }
+
+        #[test]
+        fn same_seed_is_bit_identical() {
+            let b = sample();
+            assert_eq!(layout_positions(&b, 7), layout_positions(&b, 7));
+        }
+
+        #[test]
+        fn different_seeds_differ() {
+            let b = sample();
+            assert_ne!(layout_positions(&b, 7), layout_positions(&b, 8));
+        }
+
+        #[test]
+        fn returns_every_node_in_board_order() {
+            let ids: Vec<String> = layout_positions(&sample(), 1)
+                .into_iter()
+                .map(|(id, _, _)| id)
+                .collect();
+            assert_eq!(ids, ["a", "b", "c", "d", "e"]);
+            assert!(layout_positions(&Board::default(), 1).is_empty());
+        }
+
+        #[test]
+        fn keeps_the_centroid_and_spreads_nodes() {
+            let b = sample();
+            let laid = layout_positions(&b, 42);
+            let center = |x: f64, y: f64| (x + b.nodes[0].width / 2.0, y + b.nodes[0].height / 2.0);
+            let (sx, sy) = laid.iter().fold((0.0, 0.0), |acc, (_, x, y)| {
+                let (cx, cy) = center(*x, *y);
+                (acc.0 + cx, acc.1 + cy)
+            });
+            let (ox, oy) = b.nodes[0].center();
+            assert!((sx / 5.0 - ox).abs() < 1e-6 && (sy / 5.0 - oy).abs() < 1e-6);
+            // Nodes no longer all sit at the origin: every pair is pushed apart.
+            for (i, (_, xi, yi)) in laid.iter().enumerate() {
+                for (_, xj, yj) in &laid[i + 1..] {
+                    assert!((xi - xj).hypot(yi - yj) > LAYOUT_SPACING / 4.0);
+                }
+            }
+        }
+    }
+
+    mod diff_boards_tests {
+        use super::*;
+
+        #[test]
+        fn identical_boards_have_no_diff() {
+            let b = board(&["a", "b"], &[("a", "b")]);
+            assert!(diff_boards(&b, &b.clone()).is_empty());
+        }
+
+        #[test]
+        fn added_and_removed_nodes_and_edges() {
+            let old = board(&["a", "b"], &[("a", "b")]);
+            let new = board(&["b", "c"], &[("b", "c")]);
+            let diff = diff_boards(&old, &new);
+            assert_eq!(diff.added_nodes, ["c"]);
+            assert_eq!(diff.removed_nodes, ["a"]);
+            assert_eq!(diff.added_edges, ["b-c"]);
+            assert_eq!(diff.removed_edges, ["a-b"]);
+            assert!(diff.moved_nodes.is_empty() && diff.changed_nodes.is_empty());
+        }
+
+        #[test]
+        fn moved_node_is_not_changed() {
+            let old = board(&["a"], &[]);
+            let mut new = old.clone();
+            new.nodes[0].x = 40.0;
+            new.nodes[0].height = 120.0;
+            let diff = diff_boards(&old, &new);
+            assert_eq!(diff.moved_nodes, ["a"]);
+            assert!(diff.changed_nodes.is_empty());
+        }
+
+        #[test]
+        fn text_and_metadata_changes() {
+            let old = board(&["a", "b"], &[("a", "b")]);
+            let mut new = old.clone();
+            new.nodes[0].text = "edited".into();
+            new.nodes[1].tags = vec!["urgent".into()];
+            new.edges[0].label = Some("depends on".into());
+            let diff = diff_boards(&old, &new);
+            assert_eq!(diff.changed_nodes, ["a", "b"]);
+            assert!(diff.moved_nodes.is_empty());
+            assert_eq!(diff.changed_edges, ["a-b"]);
+        }
+
+        #[test]
+        fn moved_and_edited_node_is_both() {
+            let old = board(&["a"], &[]);
+            let mut new = old.clone();
+            new.nodes[0].y = -10.0;
+            new.nodes[0].text = "edited".into();
+            let diff = diff_boards(&old, &new);
+            assert_eq!(diff.moved_nodes, ["a"]);
+            assert_eq!(diff.changed_nodes, ["a"]);
+        }
+    }
+}
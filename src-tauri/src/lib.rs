@@ -5,11 +5,11 @@ use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
 use std::sync::Mutex;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
 /// Hash of the exact bytes the app last wrote to `board.json` via
@@ -34,7 +34,16 @@ fn content_hash(content: &str) -> u64 {
 }
 
 pub use brainstorm_types::{
-    Board, Edge, LinkPreview, Node, NodeType, ValidationError, CURRENT_BOARD_VERSION,
+    board_from_canvas, board_to_csv, board_to_dot, board_to_markdown, ops, Board, Edge,
+    LinkPreview, Node, NodeType, ValidationError, CURRENT_BOARD_VERSION,
+};
+pub use pdf::{render_board_pdf, PdfMode, PDF_FILE_NAME};
+
+mod pdf;
+
+use brainstorm_types::{
+    clamp_preview_timeout_secs, round_board_coords, DEFAULT_PREVIEW_TIMEOUT_SECS,
+    MAX_PREVIEW_TIMEOUT_SECS, PDF_EXISTS_ERROR, SAVE_COORD_DECIMALS,
 };
 
 /// Outcome of validating a board file's raw text: the structural errors from
@@ -68,12 +77,12 @@ impl ValidationReport {
 }
 
 /// Return the top-level object keys that are not part of the known board schema
-/// (`version`, `nodes`, `edges`). Serde silently ignores extra keys at
+/// (`version`, `nodes`, `edges`, `settings`). Serde silently ignores extra keys at
 /// deserialize time; this lets the validator *warn* about them so a typo'd or
 /// future key is visible rather than swallowed. Non-object JSON yields an empty
 /// list (the structural parse error is surfaced elsewhere). Pure + testable.
 pub fn unknown_top_level_keys(raw: &str) -> Vec<String> {
-    const KNOWN: [&str; 3] = ["version", "nodes", "edges"];
+    const KNOWN: [&str; 4] = ["version", "nodes", "edges", "settings"];
     match serde_json::from_str::<serde_json::Value>(raw) {
         Ok(serde_json::Value::Object(map)) => map
             .keys()
@@ -278,7 +287,7 @@ type:<node_type> | tag:<tag> | status:<status> | group:<group> | priority:<n>"
 // output deterministic + SSRF-safe.
 // ----------------------------------------------------------------------------
 
-use brainstorm_types::{fit_camera, nodes_bounding_box, palette, Camera};
+use brainstorm_types::{fit_camera, nodes_bounding_box, palette, port_anchor, Camera, Port};
 
 /// How the export frames the board within the output image.
 #[derive(Debug, Clone, PartialEq)]
@@ -518,7 +527,14 @@ pub fn render_board_svg(
     ));
 
     render_groups_svg(&mut svg, &nodes, &camera);
-    render_edges_svg(&mut svg, &nodes, &edges, &camera, zoom);
+    render_edges_svg(
+        &mut svg,
+        &nodes,
+        &edges,
+        &camera,
+        zoom,
+        board.settings.directed,
+    );
     for node in &nodes {
         render_node_svg(&mut svg, node, &camera, zoom);
     }
@@ -593,8 +609,16 @@ fn render_groups_svg(svg: &mut String, nodes: &[Node], camera: &Camera) {
 }
 
 /// Emit edges (clipped line + arrowhead + optional label pill). Ported from
-/// canvas `draw_edge` / `draw_arrowhead`.
-fn render_edges_svg(svg: &mut String, nodes: &[Node], edges: &[Edge], camera: &Camera, zoom: f64) {
+/// canvas `draw_edge` / `draw_arrowhead`, including ends pinned to a port; an
+/// undirected board gets no arrowheads.
+fn render_edges_svg(
+    svg: &mut String,
+    nodes: &[Node],
+    edges: &[Edge],
+    camera: &Camera,
+    zoom: f64,
+    directed: bool,
+) {
     let node_map: std::collections::HashMap<&str, &Node> =
         nodes.iter().map(|n| (n.id.as_str(), n)).collect();
 
@@ -607,27 +631,23 @@ fn render_edges_svg(svg: &mut String, nodes: &[Node], edges: &[Edge], camera: &C
             _ => continue,
         };
 
-        let from_cx = from.x + from.width / 2.0;
-        let from_cy = from.y + from.height / 2.0;
-        let to_cx = to.x + to.width / 2.0;
-        let to_cy = to.y + to.height / 2.0;
-
-        let (from_bx, from_by) = clip_line_to_rect(
-            to_cx,
-            to_cy,
-            from_cx,
-            from_cy,
-            from.width / 2.0,
-            from.height / 2.0,
-        );
-        let (to_bx, to_by) = clip_line_to_rect(
-            from_cx,
-            from_cy,
-            to_cx,
-            to_cy,
-            to.width / 2.0,
-            to.height / 2.0,
-        );
+        // A pinned end sits on its port; a free one aims at the other end and
+        // is clipped back to its node's border.
+        let anchor = |node: &Node, port: Option<Port>| {
+            port.map_or(node.center(), |port| {
+                port_anchor((node.x, node.y, node.width, node.height), port)
+            })
+        };
+        let (from_a, to_a) = (anchor(from, edge.from_port), anchor(to, edge.to_port));
+        let clip = |node: &Node, port: Option<Port>, own: (f64, f64), other: (f64, f64)| {
+            if port.is_some() {
+                return own;
+            }
+            let (half_w, half_h) = (node.width / 2.0, node.height / 2.0);
+            clip_line_to_rect(other.0, other.1, own.0, own.1, half_w, half_h)
+        };
+        let (from_bx, from_by) = clip(from, edge.from_port, from_a, to_a);
+        let (to_bx, to_by) = clip(to, edge.to_port, to_a, from_a);
 
         let (from_sx, from_sy) = camera.world_to_screen(from_bx, from_by);
         let (to_sx, to_sy) = camera.world_to_screen(to_bx, to_by);
@@ -645,21 +665,23 @@ fn render_edges_svg(svg: &mut String, nodes: &[Node], edges: &[Edge], camera: &C
         ));
 
         // Arrowhead triangle (canvas `draw_arrowhead`, spread 0.4 rad).
-        let spread = 0.4;
-        let x1 = to_sx - arrow_size * (angle - spread).cos();
-        let y1 = to_sy - arrow_size * (angle - spread).sin();
-        let x2 = to_sx - arrow_size * (angle + spread).cos();
-        let y2 = to_sy - arrow_size * (angle + spread).sin();
-        svg.push_str(&format!(
-            "<polygon points=\"{},{} {},{} {},{}\" fill=\"{}\"/>\n",
-            fmt_coord(to_sx),
-            fmt_coord(to_sy),
-            fmt_coord(x1),
-            fmt_coord(y1),
-            fmt_coord(x2),
-            fmt_coord(y2),
-            palette::EDGE_COLOR
-        ));
+        if directed {
+            let spread = 0.4;
+            let x1 = to_sx - arrow_size * (angle - spread).cos();
+            let y1 = to_sy - arrow_size * (angle - spread).sin();
+            let x2 = to_sx - arrow_size * (angle + spread).cos();
+            let y2 = to_sy - arrow_size * (angle + spread).sin();
+            svg.push_str(&format!(
+                "<polygon points=\"{},{} {},{} {},{}\" fill=\"{}\"/>\n",
+                fmt_coord(to_sx),
+                fmt_coord(to_sy),
+                fmt_coord(x1),
+                fmt_coord(y1),
+                fmt_coord(x2),
+                fmt_coord(y2),
+                palette::EDGE_COLOR
+            ));
+        }
 
         if let Some(ref label) = edge.label {
             let mid_x = (from_sx + to_sx) / 2.0;
@@ -827,17 +849,19 @@ const SVG_FONT_MONO: &str = "ui-monospace, &apos;SF Mono&apos;, Menlo, Consolas,
 /// was unused. Callers that need it for a Tauri command propagate the `Err` to
 /// the frontend; setup-time callers log and degrade.
 fn get_board_path() -> Result<PathBuf, String> {
-    // Use parent of src-tauri (project root) during dev, or current dir in production
     let cwd =
         std::env::current_dir().map_err(|e| format!("Cannot determine current directory: {e}"))?;
+    Ok(board_path_for_cwd(&cwd))
+}
 
-    // If we're in src-tauri, go up one level to project root
-    let path = if cwd.ends_with("src-tauri") {
-        cwd.parent().unwrap_or(&cwd).join("board.json")
+/// `board.json` for a given working directory: the parent of `src-tauri`
+/// (project root) during dev, or the cwd itself in production.
+fn board_path_for_cwd(cwd: &Path) -> PathBuf {
+    if cwd.ends_with("src-tauri") {
+        cwd.parent().unwrap_or(cwd).join("board.json")
     } else {
         cwd.join("board.json")
-    };
-    Ok(path)
+    }
 }
 
 /// Public resolver for the active `board.json` path, used by the CLI
@@ -955,7 +979,10 @@ pub fn write_board_atomic(path: &std::path::Path, board: &Board) -> Result<(), S
 }
 
 #[tauri::command]
-fn save_board(board: Board) -> Result<(), String> {
+fn save_board(mut board: Board) -> Result<(), String> {
+    // The frontend already rounds; repeat it so every writer gets bounded
+    // precision (rounding is idempotent).
+    round_board_coords(&mut board, SAVE_COORD_DECIMALS);
     let path = get_board_path()?;
     write_board_atomic(&path, &board)
 }
@@ -966,6 +993,13 @@ fn get_board_path_cmd() -> Result<String, String> {
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Show the active `board.json` in the OS file manager (the HUD path label).
+#[tauri::command]
+fn reveal_board_file() -> Result<(), String> {
+    let path = get_board_path()?;
+    tauri_plugin_opener::reveal_item_in_dir(&path).map_err(|e| e.to_string())
+}
+
 /// Maximum number of redirect hops we will follow before giving up. Each hop is
 /// independently re-resolved and re-checked against the IP policy, so this is a
 /// hard bound on the redirect chain a malicious server can drive us through.
@@ -1061,31 +1095,98 @@ fn validate_url_host(parsed: &reqwest::Url) -> Result<(), String> {
     check_host_allowed(host, port)
 }
 
-#[tauri::command]
-async fn fetch_link_preview(url: String) -> Result<LinkPreview, String> {
-    // Skip non-HTTP URLs (file://, etc.)
-    if !url.starts_with("http://") && !url.starts_with("https://") {
-        return Ok(LinkPreview {
-            url: url.clone(),
-            title: Some(url),
-            description: None,
-            image: None,
-            site_name: Some("Local File".to_string()),
-        });
+/// `User-Agent` sent with link-preview fetches unless the user sets their own.
+const DEFAULT_PREVIEW_USER_AGENT: &str =
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36";
+
+/// How the link-preview HTTP client is built: the frontend's "Preview timeout"
+/// and "Preview User-Agent" settings, pushed via [`set_preview_client`].
+#[derive(Debug, Clone, PartialEq)]
+struct PreviewClientConfig {
+    timeout: Duration,
+    user_agent: String,
+}
+
+impl Default for PreviewClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(DEFAULT_PREVIEW_TIMEOUT_SECS.into()),
+            user_agent: DEFAULT_PREVIEW_USER_AGENT.to_string(),
+        }
     }
+}
+
+impl PreviewClientConfig {
+    /// Config from the raw settings: the timeout is clamped by
+    /// [`clamp_preview_timeout_secs`], and a missing or blank user agent falls
+    /// back to [`DEFAULT_PREVIEW_USER_AGENT`].
+    fn from_settings(timeout_secs: u64, user_agent: Option<&str>) -> Self {
+        Self {
+            timeout: Duration::from_secs(clamp_preview_timeout_secs(timeout_secs).into()),
+            user_agent: user_agent
+                .map(str::trim)
+                .filter(|ua| !ua.is_empty())
+                .unwrap_or(DEFAULT_PREVIEW_USER_AGENT)
+                .to_string(),
+        }
+    }
+
+    /// The client [`fetch_capped`] sends requests with. Fails on a user agent
+    /// that isn't a valid header value (e.g. one containing a newline).
+    fn build_client(&self) -> Result<reqwest::Client, String> {
+        reqwest::Client::builder()
+            .timeout(self.timeout)
+            // Disable reqwest's automatic redirect handling: we follow redirects
+            // manually so we can re-resolve and re-validate every hop's host
+            // against the IP policy (DNS-rebinding-safe). The hop count is capped
+            // by MAX_REDIRECTS.
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(self.user_agent.as_str())
+            .build()
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// The user's link-preview client settings; `None` until the frontend pushes
+/// them, which means [`PreviewClientConfig::default`].
+static PREVIEW_CLIENT_CONFIG: Mutex<Option<PreviewClientConfig>> = Mutex::new(None);
+
+/// Set the link-preview timeout and user agent (`None` or blank restores the
+/// default agent). Called by the frontend at startup and whenever either
+/// setting changes.
+#[tauri::command]
+fn set_preview_client(timeout_secs: u64, user_agent: Option<String>) -> Result<(), String> {
+    let config = PreviewClientConfig::from_settings(timeout_secs, user_agent.as_deref());
+    // Reject a bad user agent now rather than on every later fetch.
+    config.build_client()?;
+    *PREVIEW_CLIENT_CONFIG
+        .lock()
+        .map_err(|_| "Preview client setting is poisoned".to_string())? = Some(config);
+    Ok(())
+}
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        // Disable reqwest's automatic redirect handling: we follow redirects
-        // manually so we can re-resolve and re-validate every hop's host
-        // against the IP policy (DNS-rebinding-safe). The hop count is capped
-        // by MAX_REDIRECTS.
-        .redirect(reqwest::redirect::Policy::none())
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
-        .build()
-        .map_err(|e| e.to_string())?;
+/// A response body read by [`fetch_capped`].
+struct CappedBody {
+    /// Up to the requested cap; anything past it is cut off.
+    bytes: Vec<u8>,
+    content_type: Option<String>,
+    /// Where the redirects ended, for resolving relative URLs in the body.
+    final_url: reqwest::Url,
+}
 
-    let mut current_url = reqwest::Url::parse(&url).map_err(|e| e.to_string())?;
+/// GET an http(s) `url` with the link-preview client policy: every redirect hop
+/// is followed by hand and re-validated against the IP policy (at most
+/// [`MAX_REDIRECTS`]), and the body is streamed with a running cap of
+/// `max_bytes` so a huge response can't OOM the process.
+async fn fetch_capped(url: &str, max_bytes: usize) -> Result<CappedBody, String> {
+    let config = PREVIEW_CLIENT_CONFIG
+        .lock()
+        .map_err(|_| "Preview client setting is poisoned".to_string())?
+        .clone()
+        .unwrap_or_default();
+    let client = config.build_client()?;
+
+    let mut current_url = reqwest::Url::parse(url).map_err(|e| e.to_string())?;
     let mut response;
     let mut hops = 0usize;
 
@@ -1126,18 +1227,74 @@ async fn fetch_link_preview(url: String) -> Result<LinkPreview, String> {
         break;
     }
 
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
     // Stream the body with a running byte cap so a huge response cannot OOM the
     // process before we parse it.
     let mut buf: Vec<u8> = Vec::new();
     while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
-        if buf.len() + chunk.len() > MAX_PREVIEW_BODY_BYTES {
-            let remaining = MAX_PREVIEW_BODY_BYTES - buf.len();
+        if buf.len() + chunk.len() > max_bytes {
+            let remaining = max_bytes - buf.len();
             buf.extend_from_slice(&chunk[..remaining]);
             break;
         }
         buf.extend_from_slice(&chunk);
     }
-    let html = String::from_utf8_lossy(&buf).into_owned();
+    Ok(CappedBody {
+        bytes: buf,
+        content_type,
+        final_url: current_url,
+    })
+}
+
+/// Largest remote markdown file `fetch_markdown_url` will return.
+const MAX_MARKDOWN_BODY_BYTES: usize = 1024 * 1024; // 1 MB
+
+/// Fetch the body of a remote `.md`/`.markdown` URL for a link node, under the
+/// same SSRF and redirect policy as link previews. Refuses non-http(s) URLs,
+/// HTML responses (e.g. a repo's rendered file page rather than the raw file)
+/// and files over [`MAX_MARKDOWN_BODY_BYTES`].
+#[tauri::command]
+async fn fetch_markdown_url(url: String) -> Result<String, String> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("Only http(s) markdown URLs can be fetched".to_string());
+    }
+    // One byte over the cap tells "exactly the limit" apart from "truncated".
+    let body = fetch_capped(&url, MAX_MARKDOWN_BODY_BYTES + 1).await?;
+    if body.bytes.len() > MAX_MARKDOWN_BODY_BYTES {
+        return Err(format!(
+            "Markdown file too large (max {} bytes)",
+            MAX_MARKDOWN_BODY_BYTES
+        ));
+    }
+    let is_html = body
+        .content_type
+        .is_some_and(|t| t.to_ascii_lowercase().starts_with("text/html"));
+    if is_html {
+        return Err("URL returned an HTML page, not a markdown file".to_string());
+    }
+    Ok(String::from_utf8_lossy(&body.bytes).into_owned())
+}
+
+#[tauri::command]
+async fn fetch_link_preview(url: String) -> Result<LinkPreview, String> {
+    // Skip non-HTTP URLs (file://, etc.)
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Ok(LinkPreview {
+            url: url.clone(),
+            title: Some(url),
+            description: None,
+            image: None,
+            site_name: Some("Local File".to_string()),
+        });
+    }
+
+    let body = fetch_capped(&url, MAX_PREVIEW_BODY_BYTES).await?;
+    let current_url = body.final_url;
+    let html = String::from_utf8_lossy(&body.bytes).into_owned();
     let document = Html::parse_document(&html);
 
     // Selectors for Open Graph and fallback meta tags
@@ -1186,10 +1343,47 @@ async fn fetch_link_preview(url: String) -> Result<LinkPreview, String> {
     })
 }
 
+/// User-configured assets directory (the frontend's "Assets folder" setting,
+/// pushed via [`set_assets_dir`]). `None` means the default `assets/` folder
+/// next to `board.json`.
+static ASSETS_DIR_OVERRIDE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Where pasted images live: `configured` when set (`~` and `file://` expanded;
+/// a relative path is taken relative to the board directory), otherwise
+/// `assets/` next to `board.json`. Pure so the resolution rules are testable.
+fn resolve_assets_dir(board_dir: &Path, configured: Option<&str>) -> PathBuf {
+    match configured.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(configured) => {
+            let path = expand_path(configured);
+            if path.is_absolute() {
+                path
+            } else {
+                board_dir.join(path)
+            }
+        }
+        None => board_dir.join("assets"),
+    }
+}
+
+/// The active assets directory. Every command that writes, reads, or deletes
+/// assets goes through this, so they can never disagree about where images live.
 fn get_assets_dir() -> Result<PathBuf, String> {
-    let board_path = get_board_path()?;
-    let parent = board_path.parent().unwrap_or(&board_path).to_path_buf();
-    Ok(parent.join("assets"))
+    let configured = ASSETS_DIR_OVERRIDE
+        .lock()
+        .map_err(|_| "Assets dir setting is poisoned".to_string())?
+        .clone();
+    Ok(resolve_assets_dir(&board_dir()?, configured.as_deref()))
+}
+
+/// Point asset storage at `path` (`None` or blank restores the default).
+/// Called by the frontend at startup and whenever the setting changes.
+#[tauri::command]
+fn set_assets_dir(path: Option<String>) -> Result<(), String> {
+    let mut slot = ASSETS_DIR_OVERRIDE
+        .lock()
+        .map_err(|_| "Assets dir setting is poisoned".to_string())?;
+    *slot = path.filter(|p| !p.trim().is_empty());
+    Ok(())
 }
 
 /// Maximum byte size for an image we will base64-encode and hand to the
@@ -1330,7 +1524,8 @@ fn read_image_base64_scoped(path: &str, allowed_roots: &[PathBuf]) -> Result<Str
 
 #[tauri::command]
 fn read_image_base64(path: String) -> Result<String, String> {
-    read_image_base64_scoped(&path, &[board_dir()?])
+    // The assets dir may be configured outside the board directory.
+    read_image_base64_scoped(&path, &[board_dir()?, get_assets_dir()?])
 }
 
 /// Validate and read a local Markdown file. Pure (no AppHandle) so it can be
@@ -1363,17 +1558,10 @@ fn read_markdown_file(path: String) -> Result<String, String> {
     read_markdown_file_scoped(&path, &roots)
 }
 
-#[tauri::command]
-fn delete_asset(path: String) -> Result<(), String> {
-    let file_path = PathBuf::from(&path);
-
-    // Only allow deleting files in the assets folder (safety check). Derive the
-    // assets dir from the SAME source paste_image writes to (get_assets_dir,
-    // anchored on the board.json directory) rather than re-deriving from cwd.join
-    // ("assets"): the two could diverge (e.g. running from src-tauri resolves the
-    // board to the project root) and a cwd-anchored check would wrongly reject —
-    // or, worse, wrongly accept — a delete.
-    let assets_dir = get_assets_dir()?;
+/// Delete `path` if it resolves inside `assets_dir`. Pure (explicit assets dir)
+/// so the containment check is unit-testable.
+fn delete_asset_scoped(path: &str, assets_dir: &Path) -> Result<(), String> {
+    let file_path = PathBuf::from(path);
 
     // Canonicalize paths to prevent path traversal attacks
     let canonical_file = file_path
@@ -1392,6 +1580,176 @@ fn delete_asset(path: String) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+fn delete_asset(path: String) -> Result<(), String> {
+    // Only allow deleting files in the assets folder (safety check). Derive the
+    // assets dir from the SAME source paste_image writes to (get_assets_dir,
+    // which honors the configured folder) rather than re-deriving from
+    // cwd.join("assets"): the two could diverge (e.g. running from src-tauri
+    // resolves the board to the project root) and a cwd-anchored check would
+    // wrongly reject — or, worse, wrongly accept — a delete.
+    delete_asset_scoped(&path, &get_assets_dir()?)
+}
+
+/// File name of the opt-in board preview image, written next to `board.json` so
+/// file browsers and launchers can show what a board looks like.
+pub const THUMBNAIL_FILE_NAME: &str = "board-thumbnail.png";
+
+/// Cap on thumbnail payloads. A downscaled preview is tens of KB; anything near
+/// this is a bug, not a thumbnail.
+const MAX_THUMBNAIL_BYTES: usize = 2 * 1024 * 1024; // 2 MB
+
+/// Validate and write thumbnail PNG bytes to `dest`. Pure (explicit board dir)
+/// so the scope check can be unit-tested. The bytes must sniff as PNG, and
+/// `dest` must resolve inside `board_dir` — its parent is canonicalized, so a
+/// `..` climb or an absolute path elsewhere is rejected before anything is
+/// written. Writes via a sibling temp file + rename so a reader never sees a
+/// half-written image.
+fn save_thumbnail_scoped(
+    dest: &Path,
+    board_dir: &Path,
+    png_bytes: &[u8],
+) -> Result<PathBuf, String> {
+    if png_bytes.len() > MAX_THUMBNAIL_BYTES {
+        return Err(format!(
+            "Thumbnail too large: {} bytes (max {} bytes)",
+            png_bytes.len(),
+            MAX_THUMBNAIL_BYTES
+        ));
+    }
+    if sniff_image_mime(png_bytes) != Some("image/png") {
+        return Err("Thumbnail must be PNG data".to_string());
+    }
+
+    let file_name = dest
+        .file_name()
+        .ok_or_else(|| "Thumbnail path has no file name".to_string())?;
+    let parent = dest
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let canonical_parent = parent
+        .canonicalize()
+        .map_err(|e| format!("Thumbnail directory not found: {}", e))?;
+    let canonical_root = board_dir
+        .canonicalize()
+        .map_err(|e| format!("Board directory not found: {}", e))?;
+    if !canonical_parent.starts_with(&canonical_root) {
+        return Err("Access denied: thumbnail path is outside the board directory".to_string());
+    }
+
+    let target = canonical_parent.join(file_name);
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = target.with_file_name(tmp_name);
+    fs::write(&tmp_path, png_bytes).map_err(|e| format!("Failed to write thumbnail: {}", e))?;
+    fs::rename(&tmp_path, &target).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write thumbnail: {}", e)
+    })?;
+
+    Ok(target)
+}
+
+/// Write the frontend-rendered board preview to `board-thumbnail.png` in the
+/// board directory. The watcher filters to `board.json`, so this never
+/// triggers a reload.
+#[tauri::command]
+fn save_thumbnail(png_bytes: Vec<u8>) -> Result<(), String> {
+    let dir = board_dir()?;
+    save_thumbnail_scoped(&dir.join(THUMBNAIL_FILE_NAME), &dir, &png_bytes).map(|_| ())
+}
+
+/// Export the board the frontend is showing (`board_json`, so unsaved edits
+/// are included) to `board.pdf` next to `board.json`, laid out in `mode`
+/// (`"cards"` or `"board"`). Writes via a temp file + rename like the
+/// thumbnail, and returns the written path.
+#[tauri::command]
+fn export_pdf(board_json: String, mode: String, overwrite: bool) -> Result<String, String> {
+    let mode = PdfMode::parse(&mode)?;
+    let board: Board =
+        serde_json::from_str(&board_json).map_err(|e| format!("Invalid board: {}", e))?;
+    let dir = board_dir()?;
+    let target = dir.join(PDF_FILE_NAME);
+    // An earlier export is only replaced once the user has confirmed it.
+    if target.exists() && !overwrite {
+        return Err(PDF_EXISTS_ERROR.to_string());
+    }
+    let bytes = render_board_pdf(&board, mode, &[dir.clone(), get_assets_dir()?]);
+
+    let tmp_path = dir.join(format!("{PDF_FILE_NAME}.tmp"));
+    fs::write(&tmp_path, bytes).map_err(|e| format!("Failed to write PDF: {}", e))?;
+    fs::rename(&tmp_path, &target).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write PDF: {}", e)
+    })?;
+    Ok(target.to_string_lossy().into_owned())
+}
+
+/// Longest text, in characters, a node added by [`add_text_node`] may hold;
+/// anything past it is cut off.
+const MAX_ADDED_NODE_CHARS: usize = 10_000;
+
+/// `text` trimmed and capped at [`MAX_ADDED_NODE_CHARS`]; blank text is refused.
+fn added_node_text(text: &str) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Node text is empty".to_string());
+    }
+    Ok(text.chars().take(MAX_ADDED_NODE_CHARS).collect())
+}
+
+/// Append a text node holding `text` (see [`added_node_text`]) to the board at
+/// `path`, creating the file when it doesn't exist yet. The node goes where
+/// [`ops::append_position`] puts it and gets the board's default color. Pure
+/// (explicit path) so it can be tested against a temp board.
+///
+/// Only used while no window is open: a running frontend adds the node itself
+/// (see [`add_text_node`]). The write is left unmarked as our own, so the file
+/// watcher still reports it.
+pub fn add_text_node_at(path: &Path, text: &str) -> Result<Node, String> {
+    let text = added_node_text(text)?;
+    let mut board = load_board_at(path)?;
+    let (x, y) = ops::append_position(&board);
+    let mut node = Node::new(uuid::Uuid::new_v4().to_string(), x, y, text);
+    board.settings.apply_defaults(&mut node);
+    board.nodes.push(node.clone());
+    write_board_atomic(path, &board)?;
+
+    *LAST_SELF_WRITE_HASH
+        .lock()
+        .unwrap_or_else(|p| p.into_inner()) = None;
+    Ok(node)
+}
+
+/// Payload of the `node-added` event: a node for the frontend to create.
+#[derive(Debug, Clone, Serialize)]
+struct AddedNode {
+    id: String,
+    text: String,
+}
+
+/// Append a text node to the active board from outside the canvas (e.g. a
+/// global hotkey capturing the clipboard). Returns the new node's id.
+///
+/// With a window open, the frontend creates the node from a `node-added` event
+/// so it lands in its undo history and its next save. Writing `board.json` here
+/// instead could be lost: a debounced save of the webview's board, already
+/// queued, would overwrite the file before the deferred watcher reload read it.
+#[tauri::command]
+fn add_text_node(app: AppHandle, text: String) -> Result<String, String> {
+    if app.webview_windows().is_empty() {
+        return add_text_node_at(&get_board_path()?, &text).map(|node| node.id);
+    }
+    let added = AddedNode {
+        id: uuid::Uuid::new_v4().to_string(),
+        text: added_node_text(&text)?,
+    };
+    app.emit("node-added", &added)
+        .map_err(|e| format!("Failed to add node: {}", e))?;
+    Ok(added.id)
+}
+
 #[tauri::command]
 fn paste_image(app: AppHandle) -> Result<PasteImageResult, String> {
     let clipboard = app.clipboard();
@@ -1678,11 +2036,18 @@ pub fn run() {
             load_board,
             save_board,
             get_board_path_cmd,
+            reveal_board_file,
             fetch_link_preview,
+            fetch_markdown_url,
             paste_image,
             read_image_base64,
             read_markdown_file,
-            delete_asset
+            delete_asset,
+            set_assets_dir,
+            set_preview_client,
+            save_thumbnail,
+            export_pdf,
+            add_text_node
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1714,7 +2079,10 @@ mod tests {
                     from_node: "a".to_string(),
                     to_node: "b".to_string(),
                     label: Some("relates".to_string()),
+                    from_port: None,
+                    to_port: None,
                 }],
+                settings: Default::default(),
             }
         }
 
@@ -2134,6 +2502,250 @@ mod tests {
         }
     }
 
+    mod assets_dir_tests {
+        use super::*;
+
+        #[test]
+        fn defaults_to_assets_next_to_board() {
+            let board = Path::new("/boards/plan");
+            assert_eq!(resolve_assets_dir(board, None), board.join("assets"));
+            assert_eq!(resolve_assets_dir(board, Some("  ")), board.join("assets"));
+        }
+
+        #[test]
+        fn configured_dir_is_absolute_or_board_relative() {
+            let board = Path::new("/boards/plan");
+            assert_eq!(
+                resolve_assets_dir(board, Some("/media/shared")),
+                PathBuf::from("/media/shared")
+            );
+            assert_eq!(
+                resolve_assets_dir(board, Some("../media")),
+                board.join("../media")
+            );
+        }
+
+        #[test]
+        fn default_dir_follows_board_not_cwd() {
+            // Running from src-tauri in dev: the board lives in the project root,
+            // and so do its assets — not `src-tauri/assets`.
+            let cwd = Path::new("/proj/src-tauri");
+            let board = board_path_for_cwd(cwd);
+            assert_eq!(board, PathBuf::from("/proj/board.json"));
+            let assets = resolve_assets_dir(board.parent().unwrap(), None);
+            assert_eq!(assets, PathBuf::from("/proj/assets"));
+            assert_ne!(assets, cwd.join("assets"));
+        }
+
+        #[test]
+        fn deletes_asset_in_default_dir_and_rejects_board_dir_files() {
+            let dir = tempfile::tempdir().unwrap();
+            let assets_dir = resolve_assets_dir(dir.path(), None);
+            std::fs::create_dir_all(&assets_dir).unwrap();
+            let asset = assets_dir.join("pasted.png");
+            std::fs::write(&asset, b"png").unwrap();
+            let board_file = dir.path().join("board.json");
+            std::fs::write(&board_file, b"{}").unwrap();
+
+            delete_asset_scoped(asset.to_str().unwrap(), &assets_dir).unwrap();
+            assert!(!asset.exists());
+
+            assert!(delete_asset_scoped(board_file.to_str().unwrap(), &assets_dir).is_err());
+            assert!(board_file.exists());
+        }
+
+        #[test]
+        fn deletes_asset_inside_configured_dir() {
+            let dir = tempfile::tempdir().unwrap();
+            let media = dir.path().join("media");
+            std::fs::create_dir_all(&media).unwrap();
+            let asset = media.join("a.png");
+            std::fs::write(&asset, b"png").unwrap();
+
+            let assets_dir = resolve_assets_dir(dir.path(), media.to_str());
+            delete_asset_scoped(asset.to_str().unwrap(), &assets_dir).unwrap();
+            assert!(!asset.exists());
+        }
+
+        #[test]
+        fn rejects_delete_outside_configured_dir() {
+            let dir = tempfile::tempdir().unwrap();
+            let media = dir.path().join("media");
+            std::fs::create_dir_all(&media).unwrap();
+            let assets_dir = resolve_assets_dir(dir.path(), media.to_str());
+
+            // The default `assets/` folder is no longer in scope once configured.
+            let default_assets = dir.path().join("assets");
+            std::fs::create_dir_all(&default_assets).unwrap();
+            let stale = default_assets.join("old.png");
+            std::fs::write(&stale, b"png").unwrap();
+            assert!(delete_asset_scoped(stale.to_str().unwrap(), &assets_dir).is_err());
+            assert!(stale.exists());
+
+            // A `..` climb out of the configured dir.
+            let board_file = dir.path().join("board.json");
+            std::fs::write(&board_file, b"{}").unwrap();
+            let traversal = media.join("..").join("board.json");
+            assert!(delete_asset_scoped(traversal.to_str().unwrap(), &assets_dir).is_err());
+            assert!(board_file.exists());
+
+            // A sibling directory that merely shares the prefix.
+            let sibling = dir.path().join("media-other");
+            std::fs::create_dir_all(&sibling).unwrap();
+            let other = sibling.join("x.png");
+            std::fs::write(&other, b"png").unwrap();
+            assert!(delete_asset_scoped(other.to_str().unwrap(), &assets_dir).is_err());
+            assert!(other.exists());
+        }
+    }
+
+    mod save_thumbnail_tests {
+        use super::*;
+
+        const PNG_BYTES: &[u8] = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 1, 2, 3];
+
+        #[test]
+        fn writes_bytes_to_thumbnail_path_in_board_dir() {
+            let dir = tempfile::tempdir().unwrap();
+            let dest = dir.path().join(THUMBNAIL_FILE_NAME);
+
+            let written = save_thumbnail_scoped(&dest, dir.path(), PNG_BYTES).unwrap();
+
+            assert_eq!(
+                written,
+                dir.path().canonicalize().unwrap().join(THUMBNAIL_FILE_NAME)
+            );
+            assert_eq!(std::fs::read(&dest).unwrap(), PNG_BYTES);
+            // The temp file is renamed away, not left behind.
+            assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        }
+
+        #[test]
+        fn overwrites_previous_thumbnail() {
+            let dir = tempfile::tempdir().unwrap();
+            let dest = dir.path().join(THUMBNAIL_FILE_NAME);
+            std::fs::write(&dest, b"old").unwrap();
+
+            save_thumbnail_scoped(&dest, dir.path(), PNG_BYTES).unwrap();
+
+            assert_eq!(std::fs::read(&dest).unwrap(), PNG_BYTES);
+        }
+
+        #[test]
+        fn rejects_paths_outside_board_dir() {
+            let dir = tempfile::tempdir().unwrap();
+            let board = dir.path().join("board");
+            std::fs::create_dir_all(&board).unwrap();
+
+            // `..` climb out of the board dir.
+            let traversal = board.join("..").join(THUMBNAIL_FILE_NAME);
+            assert!(save_thumbnail_scoped(&traversal, &board, PNG_BYTES).is_err());
+
+            // A sibling directory that merely shares the prefix.
+            let sibling = dir.path().join("board-other");
+            std::fs::create_dir_all(&sibling).unwrap();
+            let dest = sibling.join(THUMBNAIL_FILE_NAME);
+            assert!(save_thumbnail_scoped(&dest, &board, PNG_BYTES).is_err());
+
+            assert!(!dir.path().join(THUMBNAIL_FILE_NAME).exists());
+            assert!(!dest.exists());
+        }
+
+        #[test]
+        fn rejects_non_png_bytes() {
+            let dir = tempfile::tempdir().unwrap();
+            let dest = dir.path().join(THUMBNAIL_FILE_NAME);
+
+            assert!(save_thumbnail_scoped(&dest, dir.path(), b"not a png").is_err());
+            assert!(save_thumbnail_scoped(&dest, dir.path(), &[0xFF, 0xD8, 0xFF, 0]).is_err());
+            assert!(!dest.exists());
+        }
+    }
+
+    mod add_text_node_tests {
+        use super::*;
+
+        #[test]
+        fn appends_one_node_to_an_existing_board() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("board.json");
+            let mut existing = Board::default();
+            existing
+                .nodes
+                .push(Node::new("a".to_string(), 10.0, 20.0, "A".to_string()));
+            existing.settings.default_node_color = Some("#4a9eff".to_string());
+            write_board_atomic(&path, &existing).unwrap();
+
+            let node = add_text_node_at(&path, "  Captured idea \n").unwrap();
+
+            let board = load_board_at(&path).unwrap();
+            assert_eq!(board.nodes.len(), 2);
+            assert_eq!(board.nodes[1].id, node.id);
+            assert_eq!(board.nodes[1].text, "Captured idea");
+            assert_eq!(board.nodes[1].color.as_deref(), Some("#4a9eff"));
+            // Placed under the existing node, left edges aligned.
+            assert_eq!(board.nodes[1].x, 10.0);
+            assert!(board.nodes[1].y >= 20.0 + board.nodes[0].height);
+        }
+
+        #[test]
+        fn creates_a_missing_board() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("new").join("board.json");
+
+            add_text_node_at(&path, "First").unwrap();
+
+            let board = load_board_at(&path).unwrap();
+            assert_eq!(board.nodes.len(), 1);
+            assert_eq!(board.nodes[0].text, "First");
+            assert_eq!((board.nodes[0].x, board.nodes[0].y), (0.0, 0.0));
+        }
+
+        #[test]
+        fn rejects_blank_text_and_caps_length() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("board.json");
+
+            assert!(add_text_node_at(&path, " \n\t").is_err());
+            assert!(!path.exists());
+
+            let long = "x".repeat(MAX_ADDED_NODE_CHARS + 50);
+            let node = add_text_node_at(&path, &long).unwrap();
+            assert_eq!(node.text.chars().count(), MAX_ADDED_NODE_CHARS);
+        }
+    }
+
+    mod preview_client_tests {
+        use super::*;
+
+        #[test]
+        fn custom_timeout_and_user_agent_are_applied() {
+            let config = PreviewClientConfig::from_settings(30, Some("curl/8.5.0"));
+            assert_eq!(config.timeout, Duration::from_secs(30));
+            assert_eq!(config.user_agent, "curl/8.5.0");
+            assert!(config.build_client().is_ok());
+        }
+
+        #[test]
+        fn blank_user_agent_and_out_of_range_timeout_fall_back() {
+            let config = PreviewClientConfig::from_settings(0, Some("  "));
+            assert_eq!(config.timeout, Duration::from_secs(1));
+            assert_eq!(config.user_agent, DEFAULT_PREVIEW_USER_AGENT);
+            let config = PreviewClientConfig::from_settings(9_999, None);
+            assert_eq!(
+                config.timeout,
+                Duration::from_secs(MAX_PREVIEW_TIMEOUT_SECS.into())
+            );
+        }
+
+        #[test]
+        fn invalid_user_agent_is_rejected() {
+            let config = PreviewClientConfig::from_settings(10, Some("bad\nagent"));
+            assert!(config.build_client().is_err());
+            assert!(set_preview_client(10, Some("bad\nagent".into())).is_err());
+        }
+    }
+
     mod ssrf_tests {
         use super::*;
         use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
@@ -2261,12 +2873,17 @@ mod tests {
                 width: 200.0,
                 height: 120.0,
                 text: "Pricing idea".to_string(),
+                title: None,
                 node_type: NodeType::Idea,
                 color: Some("#ff6600".to_string()),
                 tags: vec!["urgent".to_string(), "pricing".to_string()],
                 status: Some("in-progress".to_string()),
                 group: Some("cluster-a".to_string()),
                 priority: Some(2),
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
             let plain = Node {
                 id: "text-2".to_string(),
@@ -2275,12 +2892,17 @@ mod tests {
                 width: 200.0,
                 height: 100.0,
                 text: "Plain node".to_string(),
+                title: None,
                 node_type: NodeType::Text,
                 color: None,
                 tags: vec![],
                 status: None,
                 group: None,
                 priority: None,
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             };
             Board {
                 version: None,
@@ -2290,7 +2912,10 @@ mod tests {
                     from_node: "idea-1".to_string(),
                     to_node: "text-2".to_string(),
                     label: Some("depends on".to_string()),
+                    from_port: None,
+                    to_port: None,
                 }],
+                settings: Default::default(),
             }
         }
 
@@ -2341,6 +2966,20 @@ mod tests {
             assert_eq!(a, b);
         }
 
+        #[test]
+        fn pinned_port_moves_the_edge_start() {
+            let edge_line = |board: &Board| {
+                let out = render_board_svg(board, &NodeFilter::All, &fit_opts()).unwrap();
+                out.lines()
+                    .find(|l| l.starts_with("<line"))
+                    .map(str::to_string)
+            };
+            let mut pinned = fixture();
+            pinned.edges[0].from_port = Some(Port::Bottom);
+            assert!(edge_line(&fixture()).is_some());
+            assert_ne!(edge_line(&fixture()), edge_line(&pinned));
+        }
+
         #[test]
         fn group_filter_excludes_other_nodes_and_cross_group_edge() {
             let out = render_board_svg(
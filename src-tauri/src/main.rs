@@ -6,16 +6,17 @@ use std::process::ExitCode;
 
 use clap::{Parser, Subcommand};
 use infinite_brainstorm_lib::{
-    default_board_path, load_board_at, query_board, render_board_svg, validate_board_text,
+    board_from_canvas, board_to_csv, board_to_dot, board_to_markdown, default_board_path,
+    load_board_at, ops, query_board, render_board_svg, validate_board_text, write_board_atomic,
     ExportOptions, ExportView, NodeFilter,
 };
 
 /// Infinite Brainstorm — agent-native infinite canvas.
 ///
 /// With no subcommand, launches the desktop app (reads/writes `./board.json` in
-/// the current working directory). The `validate`, `query`, and `export`
-/// subcommands are headless helpers for agents to inspect or render a board
-/// without opening the UI.
+/// the current working directory). The `validate`, `query`, `stats`, `export`,
+/// `export-md`, and `import-canvas` subcommands are headless helpers for agents
+/// and scripts to inspect, render or convert a board without opening the UI.
 #[derive(Parser)]
 #[command(name = "infinite-brainstorm", version, about, long_about = None)]
 struct Cli {
@@ -40,14 +41,42 @@ enum Command {
         #[arg(long)]
         path: Option<PathBuf>,
     },
+    /// Print summary counts for a board: nodes (per type), edges, tags, groups,
+    /// orphan nodes, and dangling edges.
+    Stats {
+        /// Path to the board file (defaults to ./board.json in the cwd).
+        path: Option<PathBuf>,
+    },
+    /// Write the whole board as a Markdown outline (one section per root node,
+    /// children nested by edge). Prints to stdout unless `--out` is given.
+    ExportMd {
+        /// Path to the board file (defaults to ./board.json in the cwd).
+        board: Option<PathBuf>,
+        /// Write the Markdown to this file instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Convert a JSON Canvas (`.canvas`, e.g. from Obsidian) file into a board.
+    /// Refuses to overwrite an existing board unless `--force` is given.
+    ImportCanvas {
+        /// The `.canvas` file to read.
+        canvas: PathBuf,
+        /// The board file to write.
+        board: PathBuf,
+        /// Overwrite `board` if it already exists.
+        #[arg(long)]
+        force: bool,
+    },
     /// Render a board to an image WITHOUT opening the GUI (read-only). Writes an
     /// SVG to `--out`; positions the camera with `--fit` (default), `--region`,
-    /// or `--camera`, optionally restricting to `--nodes`/`--group`.
+    /// or `--camera`, optionally restricting to `--nodes`/`--group`. A `.dot`/`.gv`
+    /// or `.csv` `--out` writes the whole graph as Graphviz DOT or an edge list.
     Export {
         /// Path to the board file (defaults to ./board.json in the cwd).
         board: Option<PathBuf>,
-        /// Output image path. Extension selects the format: `.svg` is supported;
-        /// `.png` is a documented follow-up (headless PNG not yet implemented).
+        /// Output path. Extension selects the format: `.svg` is supported, as are
+        /// `.dot`/`.gv` (Graphviz) and `.csv` (`from,to,label` edge list); `.png`
+        /// is a documented follow-up (headless PNG not yet implemented).
         #[arg(long)]
         out: PathBuf,
         /// Fit all (filtered) nodes with padding. This is the default when no
@@ -170,6 +199,92 @@ fn run_query(expr: String, path: Option<PathBuf>) -> ExitCode {
     }
 }
 
+/// Load the board at `path` (or the default board), printing the error and
+/// returning `None` when it can't be resolved or parsed.
+fn load_for_command(path: Option<PathBuf>) -> Option<infinite_brainstorm_lib::Board> {
+    let path = match resolve_path(path) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return None;
+        }
+    };
+    match load_board_at(&path) {
+        Ok(b) => Some(b),
+        Err(e) => {
+            eprintln!("error: {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Run the `stats` subcommand: print [`ops::board_stats`] for the board.
+fn run_stats(path: Option<PathBuf>) -> ExitCode {
+    let Some(board) = load_for_command(path) else {
+        return ExitCode::FAILURE;
+    };
+    println!("{}", ops::board_stats(&board));
+    ExitCode::SUCCESS
+}
+
+/// Run the `export-md` subcommand: render [`board_to_markdown`] to stdout or
+/// `--out`. Read-only on the board.
+fn run_export_md(board: Option<PathBuf>, out: Option<PathBuf>) -> ExitCode {
+    let Some(board) = load_for_command(board) else {
+        return ExitCode::FAILURE;
+    };
+    let markdown = board_to_markdown(&board);
+    match out {
+        Some(out) => {
+            if let Err(e) = std::fs::write(&out, markdown) {
+                eprintln!("error: cannot write {}: {e}", out.display());
+                return ExitCode::FAILURE;
+            }
+            println!("{}", out.display());
+        }
+        None => print!("{markdown}"),
+    }
+    ExitCode::SUCCESS
+}
+
+/// Run the `import-canvas` subcommand: convert a JSON Canvas file with
+/// [`board_from_canvas`] and write it atomically to `board`. An existing board
+/// is only replaced with `--force`.
+fn run_import_canvas(canvas: PathBuf, board: PathBuf, force: bool) -> ExitCode {
+    if board.exists() && !force {
+        eprintln!(
+            "error: {} already exists; pass --force to overwrite it",
+            board.display()
+        );
+        return ExitCode::FAILURE;
+    }
+    let raw = match std::fs::read_to_string(&canvas) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {e}", canvas.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let imported = match board_from_canvas(&raw) {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("error: {}: {e}", canvas.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = write_board_atomic(&board, &imported) {
+        eprintln!("error: cannot write {}: {e}", board.display());
+        return ExitCode::FAILURE;
+    }
+    println!(
+        "{}: imported {} node(s), {} edge(s)",
+        board.display(),
+        imported.nodes.len(),
+        imported.edges.len()
+    );
+    ExitCode::SUCCESS
+}
+
 /// Parse a comma-separated list of `n` finite floats, erroring on the wrong
 /// count or any non-numeric component. Used for `--region`/`--camera`.
 fn parse_floats(label: &str, raw: &str, n: usize) -> Result<Vec<f64>, String> {
@@ -225,8 +340,10 @@ fn resolve_view(
 }
 
 /// Run the `export` subcommand. Loads the board read-only, resolves the view +
-/// node filter, and writes an SVG to `--out`. PNG (and any other extension) is a
-/// non-zero error with a documented message. Never writes the board file.
+/// node filter, and writes an SVG to `--out`. `.dot`/`.gv`/`.csv` outputs are
+/// graph dumps of the whole board (view and subset flags don't apply). PNG (and
+/// any other extension) is a non-zero error with a documented message. Never
+/// writes the board file.
 #[allow(clippy::too_many_arguments)]
 fn run_export(
     board: Option<PathBuf>,
@@ -300,6 +417,19 @@ fn run_export(
             println!("{}", out.display());
             ExitCode::SUCCESS
         }
+        Some(fmt @ ("dot" | "gv" | "csv")) => {
+            let text = if fmt == "csv" {
+                board_to_csv(&board)
+            } else {
+                board_to_dot(&board)
+            };
+            if let Err(e) = std::fs::write(&out, text) {
+                eprintln!("error: cannot write {}: {e}", out.display());
+                return ExitCode::FAILURE;
+            }
+            println!("{}", out.display());
+            ExitCode::SUCCESS
+        }
         Some("png") => {
             eprintln!(
                 "error: headless PNG export is not yet supported; export to .svg and \
@@ -309,7 +439,7 @@ rasterize externally (e.g. `rsvg-convert`/`resvg`). Tracking: PNG follow-up."
         }
         _ => {
             eprintln!(
-                "error: unsupported output extension for {}; use .svg",
+                "error: unsupported output extension for {}; use .svg, .dot, or .csv",
                 out.display()
             );
             ExitCode::FAILURE
@@ -323,6 +453,13 @@ fn main() -> ExitCode {
     match cli.command {
         Some(Command::Validate { path }) => run_validate(path),
         Some(Command::Query { expr, path }) => run_query(expr, path),
+        Some(Command::Stats { path }) => run_stats(path),
+        Some(Command::ExportMd { board, out }) => run_export_md(board, out),
+        Some(Command::ImportCanvas {
+            canvas,
+            board,
+            force,
+        }) => run_import_canvas(canvas, board, force),
         Some(Command::Export {
             board,
             out,
@@ -0,0 +1,862 @@
+//! PDF export (`export_pdf`).
+//!
+//! Prints a board to a PDF. `cards` mode puts each node on its own A4 page,
+//! scaled to fill it (index-card printing); `board` mode lays the whole board
+//! out on one page sized to its bounding box. Layout ([`page_layouts`]) is pure
+//! and unit-tested; [`render_board_pdf`] then draws each page with
+//! `pdf-writer`, which owns the object, stream and cross-reference syntax.
+//!
+//! Text is set in the base-14 Courier font: there is nothing to embed, and its
+//! fixed 0.6em advance is exactly the `approx_text_width` heuristic, so the
+//! shared `wrap_text` breaks lines where they print. Courier only covers
+//! WinAnsi (Latin-1 plus the typographic punctuation in 0x80-0x9F); other
+//! scripts print as `?`. Markdown nodes print as plain text, and local image
+//! nodes are re-encoded as JPEG under the same path scoping as
+//! `read_image_base64`.
+
+use crate::{
+    approx_text_width, scope_path, sniff_image_mime, wrap_text, Board, Node, NodeType,
+    MAX_IMAGE_BYTES,
+};
+use brainstorm_types::nodes_bounding_box;
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// File name of the exported PDF, written next to `board.json`.
+pub const PDF_FILE_NAME: &str = "board.pdf";
+
+/// A4 portrait, in PDF points (1/72 inch) — the page size for `cards` mode.
+const CARD_PAGE: (f64, f64) = (595.0, 842.0);
+
+/// Blank margin around a card on its page, or around the whole board on the
+/// single `board` page, in points.
+const PAGE_MARGIN: f64 = 36.0;
+
+/// Largest page side viewers reliably accept (Acrobat's 200-inch limit). A
+/// board bigger than this is scaled down to fit rather than clipped.
+const MAX_PAGE_SIDE: f64 = 14_400.0;
+
+/// Node body font, padding and `[TYPE]` label band in world units — the same
+/// values `render_node_svg` uses at zoom 1, scaled with the card.
+const BODY_FONT: f64 = 12.0;
+const BODY_PADDING: f64 = 8.0;
+const LABEL_BAND: f64 = 16.0;
+const LINE_HEIGHT: f64 = 1.4;
+const SMALL_FONT: f64 = 9.0;
+
+/// Longest side, in pixels, an embedded image is stored at. Print needs no
+/// more, and it keeps a board of phone photos from producing a huge file.
+const MAX_IMAGE_PX: u32 = 2000;
+
+/// Border gray for nodes without a color of their own.
+const BORDER_GRAY: f32 = 0.6;
+/// Gray of the `[TYPE]` label, priority, status and tags.
+const META_GRAY: f32 = 0.45;
+/// Gray of the body text.
+const TEXT_GRAY: f32 = 0.1;
+
+/// Resource name of the Courier font on every page.
+const FONT: Name = Name(b"F1");
+
+/// How [`render_board_pdf`] lays the board out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfMode {
+    /// One node per A4 page, scaled to fill it.
+    Cards,
+    /// The whole board on a single page.
+    Board,
+}
+
+impl PdfMode {
+    /// Parse the frontend's mode string (`"cards"` or `"board"`).
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "cards" => Ok(PdfMode::Cards),
+            "board" => Ok(PdfMode::Board),
+            other => Err(format!(
+                "unsupported PDF mode '{other}'. Supported: cards | board"
+            )),
+        }
+    }
+}
+
+/// Where a node's card lands on its page, in points. `(x, y)` is the card's
+/// bottom-left corner (PDF's origin is the bottom-left of the page) and `scale`
+/// converts world units to points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CardLayout {
+    scale: f64,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl CardLayout {
+    /// Page position of a world-unit offset `(dx, dy)` from the card's top-left
+    /// corner (y grows downward in the world, upward on the page).
+    fn at(&self, dx: f64, dy: f64) -> (f32, f32) {
+        (
+            (self.x + dx * self.scale) as f32,
+            (self.y + self.height - dy * self.scale) as f32,
+        )
+    }
+
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+}
+
+/// Scale `node` to fill a `page` (points) inside the margin, keeping its
+/// aspect ratio, and center it. Zero-sized nodes are treated as one unit so the
+/// scale stays finite.
+fn card_layout(node: &Node, page: (f64, f64)) -> CardLayout {
+    let (page_w, page_h) = page;
+    let avail_w = (page_w - 2.0 * PAGE_MARGIN).max(1.0);
+    let avail_h = (page_h - 2.0 * PAGE_MARGIN).max(1.0);
+    let node_w = node.width.max(1.0);
+    let node_h = node.height.max(1.0);
+    let scale = (avail_w / node_w).min(avail_h / node_h);
+    let width = node_w * scale;
+    let height = node_h * scale;
+    CardLayout {
+        scale,
+        x: (page_w - width) / 2.0,
+        y: (page_h - height) / 2.0,
+        width,
+        height,
+    }
+}
+
+/// The single page of `board` mode: its size in points plus the mapping from
+/// world coordinates onto it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BoardPage {
+    size: (f64, f64),
+    scale: f64,
+    min_x: f64,
+    min_y: f64,
+}
+
+impl BoardPage {
+    /// Size the page to the nodes' bounding box (one world unit per point) plus
+    /// the margin, scaling down when a side would pass [`MAX_PAGE_SIDE`].
+    /// `None` for an empty board.
+    fn fit(nodes: &[Node]) -> Option<Self> {
+        let (min_x, min_y, max_x, max_y) = nodes_bounding_box(nodes)?;
+        let world_w = (max_x - min_x).max(1.0);
+        let world_h = (max_y - min_y).max(1.0);
+        let avail = MAX_PAGE_SIDE - 2.0 * PAGE_MARGIN;
+        let scale = (avail / world_w).min(avail / world_h).min(1.0);
+        Some(BoardPage {
+            size: (
+                world_w * scale + 2.0 * PAGE_MARGIN,
+                world_h * scale + 2.0 * PAGE_MARGIN,
+            ),
+            scale,
+            min_x,
+            min_y,
+        })
+    }
+
+    /// Where `node` sits on the page.
+    fn place(&self, node: &Node) -> CardLayout {
+        let width = node.width * self.scale;
+        let height = node.height * self.scale;
+        CardLayout {
+            scale: self.scale,
+            x: PAGE_MARGIN + (node.x - self.min_x) * self.scale,
+            y: self.size.1 - PAGE_MARGIN - (node.y - self.min_y) * self.scale - height,
+            width,
+            height,
+        }
+    }
+}
+
+/// One laid-out page: its size in points, the edges drawn under the cards as
+/// center-to-center lines, and the cards in paint order.
+#[derive(Debug, Clone)]
+struct PageLayout {
+    size: (f64, f64),
+    edges: Vec<((f64, f64), (f64, f64))>,
+    cards: Vec<(Node, CardLayout)>,
+}
+
+/// Lay `board` out as pages in `mode`, with nodes auto-sized the way the canvas
+/// shows them. `cards` mode prints in reading order (top to bottom, then left
+/// to right) so the stack comes out of the printer the way the board reads;
+/// `board` mode keeps board order and draws edges. An empty board still
+/// yields one blank A4 page in `board` mode.
+fn page_layouts(board: &Board, mode: PdfMode) -> Vec<PageLayout> {
+    let mut working = board.clone();
+    working.apply_auto_size();
+
+    match mode {
+        PdfMode::Cards => {
+            let mut nodes = working.nodes;
+            nodes.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+            nodes
+                .into_iter()
+                .map(|node| {
+                    let layout = card_layout(&node, CARD_PAGE);
+                    PageLayout {
+                        size: CARD_PAGE,
+                        edges: Vec::new(),
+                        cards: vec![(node, layout)],
+                    }
+                })
+                .collect()
+        }
+        PdfMode::Board => {
+            let Some(page) = BoardPage::fit(&working.nodes) else {
+                return vec![PageLayout {
+                    size: CARD_PAGE,
+                    edges: Vec::new(),
+                    cards: Vec::new(),
+                }];
+            };
+            let centers: HashMap<&str, (f64, f64)> = working
+                .nodes
+                .iter()
+                .map(|n| (n.id.as_str(), page.place(n).center()))
+                .collect();
+            let edges = working
+                .edges
+                .iter()
+                .filter_map(|edge| {
+                    Some((
+                        *centers.get(edge.from_node.as_str())?,
+                        *centers.get(edge.to_node.as_str())?,
+                    ))
+                })
+                .collect();
+            let cards = working
+                .nodes
+                .iter()
+                .map(|node| (node.clone(), page.place(node)))
+                .collect();
+            vec![PageLayout {
+                size: page.size,
+                edges,
+                cards,
+            }]
+        }
+    }
+}
+
+/// The body lines printed on a node's card, in world units: its title (or
+/// text) wrapped to the card width, cut to the lines that fit below the label
+/// band. When text is cut, the last kept line ends in an ellipsis.
+fn card_text_lines(node: &Node) -> Vec<String> {
+    let plain;
+    let text = match node.display_title() {
+        Some(title) => title,
+        None if node.node_type == NodeType::Md || node.renders_markdown_inline() => {
+            plain = markdown_plain_text(&node.text);
+            &plain
+        }
+        None => &node.text,
+    };
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+    let max_width = node.width - 2.0 * BODY_PADDING;
+    let max_height = node.height - LABEL_BAND - BODY_PADDING;
+    let line_height = BODY_FONT * LINE_HEIGHT;
+    let visible = ((max_height / line_height).floor() as usize).max(1);
+
+    let mut lines = wrap_text(text, max_width, BODY_FONT);
+    if lines.len() > visible {
+        lines.truncate(visible);
+        let last = &mut lines[visible - 1];
+        while !last.is_empty()
+            && approx_text_width(&format!("{last}\u{2026}"), BODY_FONT) > max_width
+        {
+            last.pop();
+        }
+        last.push('\u{2026}');
+    }
+    lines
+}
+
+/// Markdown source as the plain text a printed card shows: one line per
+/// block, list items prefixed with `•`, link and image text kept, and markup
+/// (emphasis, headings, rules, raw HTML) dropped.
+fn markdown_plain_text(md: &str) -> String {
+    let mut out = String::new();
+    let break_line = |out: &mut String| {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+    };
+    for event in Parser::new(md) {
+        match event {
+            Event::Text(text) | Event::Code(text) => out.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => out.push('\n'),
+            Event::Start(Tag::Item) => {
+                break_line(&mut out);
+                out.push_str("\u{2022} ");
+            }
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock | TagEnd::Item,
+            ) => break_line(&mut out),
+            _ => {}
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Fit an `img_w` x `img_h` image inside `area` (`x, y, w, h`, bottom-left
+/// origin) without distorting it, centered. Returns the drawn rectangle.
+fn fit_image_rect(img_w: u32, img_h: u32, area: (f64, f64, f64, f64)) -> (f64, f64, f64, f64) {
+    let (x, y, w, h) = area;
+    if img_w == 0 || img_h == 0 || w <= 0.0 || h <= 0.0 {
+        return (x, y, 0.0, 0.0);
+    }
+    let scale = (w / img_w as f64).min(h / img_h as f64);
+    let draw_w = img_w as f64 * scale;
+    let draw_h = img_h as f64 * scale;
+    (
+        x + (w - draw_w) / 2.0,
+        y + (h - draw_h) / 2.0,
+        draw_w,
+        draw_h,
+    )
+}
+
+/// The file an image node embeds: its path, unless the node isn't an image or
+/// points at a remote URL (those print their text instead).
+fn local_image_path(node: &Node) -> Option<&str> {
+    (node.node_type == NodeType::Image
+        && !node.text.starts_with("http://")
+        && !node.text.starts_with("https://"))
+    .then_some(node.text.as_str())
+}
+
+/// `s` in Courier's WinAnsi encoding: Latin-1 maps to itself, the 0x80–0x9F
+/// block holds typographic punctuation (curly quotes, dashes, bullet,
+/// ellipsis, ...) and a few letters, tabs become spaces, and anything else
+/// prints as `?`.
+fn win_ansi(s: &str) -> Vec<u8> {
+    s.chars()
+        .map(|c| match c {
+            '\t' => b' ',
+            ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+            '\u{20ac}' => 0x80,
+            '\u{201a}' => 0x82,
+            '\u{0192}' => 0x83,
+            '\u{201e}' => 0x84,
+            '\u{2026}' => 0x85,
+            '\u{2020}' => 0x86,
+            '\u{2021}' => 0x87,
+            '\u{02c6}' => 0x88,
+            '\u{2030}' => 0x89,
+            '\u{0160}' => 0x8a,
+            '\u{2039}' => 0x8b,
+            '\u{0152}' => 0x8c,
+            '\u{017d}' => 0x8e,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{02dc}' => 0x98,
+            '\u{2122}' => 0x99,
+            '\u{0161}' => 0x9a,
+            '\u{203a}' => 0x9b,
+            '\u{0153}' => 0x9c,
+            '\u{017e}' => 0x9e,
+            '\u{0178}' => 0x9f,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// A `#rrggbb` color as PDF RGB components in `0..=1`.
+fn pdf_rgb(hex: &str) -> Option<(f32, f32, f32)> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(hex.get(i..i + 2)?, 16)
+            .ok()
+            .map(|v| v as f32 / 255.0)
+    };
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// A JPEG ready to embed as a DCT image XObject.
+struct PdfImage {
+    width: u32,
+    height: u32,
+    jpeg: Vec<u8>,
+}
+
+/// Draw a Courier text run with its baseline at `(x, y)`.
+fn draw_text(content: &mut Content, (x, y): (f32, f32), font_pt: f64, gray: f32, text: &str) {
+    content
+        .begin_text()
+        .set_font(FONT, font_pt as f32)
+        .set_fill_gray(gray)
+        .next_line(x, y)
+        .show(Str(&win_ansi(text)))
+        .end_text();
+}
+
+/// Draw one node's card — white surface, border in the node's color, the
+/// `[TYPE]` label with priority and status, tags along the bottom, and either
+/// the embedded image or the wrapped body text — at `layout`. An embedded
+/// image is drawn as the XObject named `image`.
+fn draw_card(
+    content: &mut Content,
+    node: &Node,
+    layout: &CardLayout,
+    image: Option<(Name, &PdfImage)>,
+) {
+    let (r, g, b) =
+        node.color
+            .as_deref()
+            .and_then(pdf_rgb)
+            .unwrap_or((BORDER_GRAY, BORDER_GRAY, BORDER_GRAY));
+    content
+        .save_state()
+        .set_fill_gray(1.0)
+        .set_stroke_rgb(r, g, b)
+        .set_line_width(1.0)
+        .rect(
+            layout.x as f32,
+            layout.y as f32,
+            layout.width as f32,
+            layout.height as f32,
+        )
+        .fill_nonzero_and_stroke()
+        .restore_state();
+
+    let s = layout.scale;
+    let pad = 4.0;
+    let type_label = node.node_type.label();
+    draw_text(
+        content,
+        layout.at(pad, pad + SMALL_FONT),
+        SMALL_FONT * s,
+        META_GRAY,
+        type_label,
+    );
+    if let Some(priority) = node.priority {
+        let dx = pad + approx_text_width(type_label, SMALL_FONT) + pad;
+        let p_text = format!("P{}", priority.clamp(1, 5));
+        draw_text(
+            content,
+            layout.at(dx, pad + SMALL_FONT),
+            SMALL_FONT * s,
+            META_GRAY,
+            &p_text,
+        );
+    }
+    if let Some(status) = node.status.as_deref() {
+        let dx = node.width - pad - approx_text_width(status, SMALL_FONT);
+        draw_text(
+            content,
+            layout.at(dx, pad + SMALL_FONT),
+            SMALL_FONT * s,
+            META_GRAY,
+            status,
+        );
+    }
+    if !node.tags.is_empty() {
+        draw_text(
+            content,
+            layout.at(pad, node.height - pad),
+            8.0 * s,
+            META_GRAY,
+            &node.tags.join(", "),
+        );
+    }
+
+    if let Some((name, image)) = image {
+        let (area_x, area_y) = layout.at(BODY_PADDING, node.height - BODY_PADDING);
+        let area = (
+            area_x as f64,
+            area_y as f64,
+            (node.width - 2.0 * BODY_PADDING) * s,
+            (node.height - LABEL_BAND - 2.0 * BODY_PADDING) * s,
+        );
+        let (x, y, w, h) = fit_image_rect(image.width, image.height, area);
+        content
+            .save_state()
+            .transform([w as f32, 0.0, 0.0, h as f32, x as f32, y as f32])
+            .x_object(name)
+            .restore_state();
+        return;
+    }
+
+    let lines = card_text_lines(node);
+    let line_height = BODY_FONT * LINE_HEIGHT;
+    let center_y = LABEL_BAND + (node.height - LABEL_BAND) / 2.0;
+    let start_y = center_y - lines.len() as f64 * line_height / 2.0 + line_height / 2.0;
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let dx = (node.width - approx_text_width(line, BODY_FONT)) / 2.0;
+        // Drop the baseline by ~0.3em so the line is centered on `dy`.
+        let dy = start_y + i as f64 * line_height + BODY_FONT * 0.3;
+        draw_text(content, layout.at(dx, dy), BODY_FONT * s, TEXT_GRAY, line);
+    }
+}
+
+/// Load a local image node's file for embedding: scoped to `allowed_roots`
+/// and capped at [`MAX_IMAGE_BYTES`] like `read_image_base64`, flattened onto
+/// white (JPEG has no alpha), downscaled to [`MAX_IMAGE_PX`], and re-encoded
+/// as JPEG.
+fn load_pdf_image(path: &str, allowed_roots: &[PathBuf]) -> Result<PdfImage, String> {
+    let canonical = scope_path(path, allowed_roots)?;
+    let meta = fs::metadata(&canonical).map_err(|e| format!("Failed to stat file: {}", e))?;
+    if meta.len() > MAX_IMAGE_BYTES {
+        return Err(format!(
+            "Image too large: {} bytes (max {} bytes)",
+            meta.len(),
+            MAX_IMAGE_BYTES
+        ));
+    }
+    let data = fs::read(&canonical).map_err(|e| format!("Failed to read file: {}", e))?;
+    if sniff_image_mime(&data).is_none() {
+        return Err("Unsupported or non-image file content".to_string());
+    }
+
+    let img =
+        image::load_from_memory(&data).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let img = if img.width() > MAX_IMAGE_PX || img.height() > MAX_IMAGE_PX {
+        img.thumbnail(MAX_IMAGE_PX, MAX_IMAGE_PX)
+    } else {
+        img
+    };
+    let rgba = img.to_rgba8();
+    let rgb = image::RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let over_white = |c: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+        image::Rgb([over_white(r), over_white(g), over_white(b)])
+    });
+
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 85)
+        .encode_image(&rgb)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    Ok(PdfImage {
+        width: rgb.width(),
+        height: rgb.height(),
+        jpeg,
+    })
+}
+
+/// Render `board` to PDF bytes in `mode`, embedding local image files found
+/// inside `allowed_roots`; images that don't load print their path as text.
+/// Pure apart from those image reads.
+pub fn render_board_pdf(board: &Board, mode: PdfMode, allowed_roots: &[PathBuf]) -> Vec<u8> {
+    let mut pdf = Pdf::new();
+    let mut next_ref = Ref::new(1);
+    let catalog_id = next_ref.bump();
+    let tree_id = next_ref.bump();
+    let font_id = next_ref.bump();
+    pdf.catalog(catalog_id).pages(tree_id);
+    pdf.type1_font(font_id)
+        .base_font(Name(b"Courier"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    let mut page_ids = Vec::new();
+    for page in page_layouts(board, mode) {
+        let page_id = next_ref.bump();
+        let content_id = next_ref.bump();
+        page_ids.push(page_id);
+
+        let mut content = Content::new();
+        if !page.edges.is_empty() {
+            content
+                .save_state()
+                .set_stroke_gray(BORDER_GRAY)
+                .set_line_width(1.0);
+            for ((x1, y1), (x2, y2)) in &page.edges {
+                content
+                    .move_to(*x1 as f32, *y1 as f32)
+                    .line_to(*x2 as f32, *y2 as f32)
+                    .stroke();
+            }
+            content.restore_state();
+        }
+        let mut images: Vec<(String, Ref, PdfImage)> = Vec::new();
+        for (node, layout) in &page.cards {
+            let image = local_image_path(node).and_then(|p| load_pdf_image(p, allowed_roots).ok());
+            match image {
+                Some(image) => {
+                    let name = format!("Im{}", images.len());
+                    draw_card(
+                        &mut content,
+                        node,
+                        layout,
+                        Some((Name(name.as_bytes()), &image)),
+                    );
+                    images.push((name, next_ref.bump(), image));
+                }
+                None => draw_card(&mut content, node, layout, None),
+            }
+        }
+
+        let mut page_writer = pdf.page(page_id);
+        page_writer
+            .media_box(Rect::new(0.0, 0.0, page.size.0 as f32, page.size.1 as f32))
+            .parent(tree_id)
+            .contents(content_id);
+        let mut resources = page_writer.resources();
+        resources.fonts().pair(FONT, font_id);
+        let mut x_objects = resources.x_objects();
+        for (name, id, _) in &images {
+            x_objects.pair(Name(name.as_bytes()), *id);
+        }
+        x_objects.finish();
+        resources.finish();
+        page_writer.finish();
+
+        pdf.stream(content_id, &content.finish());
+        for (_, id, image) in &images {
+            let mut x_object = pdf.image_xobject(*id, &image.jpeg);
+            x_object.filter(Filter::DctDecode);
+            x_object.width(image.width as i32);
+            x_object.height(image.height as i32);
+            x_object.color_space().device_rgb();
+            x_object.bits_per_component(8);
+        }
+    }
+
+    let count = page_ids.len() as i32;
+    pdf.pages(tree_id).kids(page_ids).count(count);
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Edge;
+
+    fn sized(id: &str, x: f64, y: f64, w: f64, h: f64, text: &str) -> Node {
+        let mut n = Node::new(id.to_string(), x, y, text.to_string());
+        n.width = w;
+        n.height = h;
+        n
+    }
+
+    fn board(nodes: Vec<Node>) -> Board {
+        Board {
+            nodes,
+            ..Default::default()
+        }
+    }
+
+    fn card_ids(page: &PageLayout) -> Vec<&str> {
+        page.cards.iter().map(|(n, _)| n.id.as_str()).collect()
+    }
+
+    mod layout_tests {
+        use super::*;
+
+        #[test]
+        fn mode_parses_both_layouts() {
+            assert_eq!(PdfMode::parse("cards"), Ok(PdfMode::Cards));
+            assert_eq!(PdfMode::parse("board"), Ok(PdfMode::Board));
+            assert!(PdfMode::parse("poster").is_err());
+        }
+
+        #[test]
+        fn wide_card_fills_page_width_and_is_centered() {
+            let layout = card_layout(&sized("a", 0.0, 0.0, 200.0, 100.0, "x"), CARD_PAGE);
+            let avail_w = CARD_PAGE.0 - 2.0 * PAGE_MARGIN;
+            assert!((layout.width - avail_w).abs() < 1e-9);
+            assert!((layout.height - avail_w / 2.0).abs() < 1e-9);
+            assert!((layout.x - PAGE_MARGIN).abs() < 1e-9);
+            assert!((layout.y * 2.0 + layout.height - CARD_PAGE.1).abs() < 1e-9);
+        }
+
+        #[test]
+        fn tall_card_fills_page_height() {
+            let layout = card_layout(&sized("a", 0.0, 0.0, 100.0, 1000.0, "x"), CARD_PAGE);
+            let avail_h = CARD_PAGE.1 - 2.0 * PAGE_MARGIN;
+            assert!((layout.height - avail_h).abs() < 1e-9);
+            assert!(layout.width < CARD_PAGE.0 - 2.0 * PAGE_MARGIN);
+        }
+
+        #[test]
+        fn zero_sized_card_keeps_a_finite_scale() {
+            let layout = card_layout(&sized("a", 0.0, 0.0, 0.0, 0.0, ""), CARD_PAGE);
+            assert!(layout.scale.is_finite());
+        }
+
+        #[test]
+        fn board_page_maps_world_one_to_one_with_flipped_y() {
+            let nodes = vec![
+                sized("a", 100.0, 50.0, 200.0, 100.0, "a"),
+                sized("b", 500.0, 350.0, 200.0, 100.0, "b"),
+            ];
+            let page = BoardPage::fit(&nodes).unwrap();
+            assert_eq!(page.scale, 1.0);
+            assert_eq!(
+                page.size,
+                (600.0 + 2.0 * PAGE_MARGIN, 400.0 + 2.0 * PAGE_MARGIN)
+            );
+            let top_left = page.place(&nodes[0]);
+            assert_eq!(top_left.x, PAGE_MARGIN);
+            assert_eq!(top_left.y + top_left.height, page.size.1 - PAGE_MARGIN);
+            let bottom_right = page.place(&nodes[1]);
+            assert_eq!(bottom_right.y, PAGE_MARGIN);
+        }
+
+        #[test]
+        fn huge_board_page_is_scaled_within_the_limit() {
+            let nodes = vec![
+                sized("a", 0.0, 0.0, 200.0, 100.0, "a"),
+                sized("b", 50_000.0, 0.0, 200.0, 100.0, "b"),
+            ];
+            let page = BoardPage::fit(&nodes).unwrap();
+            assert!(page.scale < 1.0);
+            assert!(page.size.0 <= MAX_PAGE_SIDE + 1e-6);
+            assert!(BoardPage::fit(&[]).is_none());
+        }
+
+        #[test]
+        fn image_is_fitted_without_distortion() {
+            let (x, y, w, h) = fit_image_rect(400, 200, (10.0, 20.0, 100.0, 100.0));
+            assert_eq!((x, w), (10.0, 100.0));
+            assert_eq!((y, h), (45.0, 50.0));
+            assert_eq!(fit_image_rect(0, 10, (1.0, 2.0, 3.0, 4.0)).2, 0.0);
+        }
+    }
+
+    mod pagination_tests {
+        use super::*;
+
+        #[test]
+        fn cards_mode_makes_one_a4_page_per_node_in_reading_order() {
+            let b = board(vec![
+                sized("below", 0.0, 300.0, 200.0, 100.0, "c"),
+                sized("right", 400.0, 0.0, 200.0, 100.0, "b"),
+                sized("left", 0.0, 0.0, 200.0, 100.0, "a"),
+            ]);
+            let pages = page_layouts(&b, PdfMode::Cards);
+            let ids: Vec<Vec<&str>> = pages.iter().map(card_ids).collect();
+            assert_eq!(ids, [["left"], ["right"], ["below"]]);
+            assert!(pages
+                .iter()
+                .all(|p| p.size == CARD_PAGE && p.edges.is_empty()));
+            let (node, layout) = &pages[0].cards[0];
+            assert_eq!(*layout, card_layout(node, CARD_PAGE));
+        }
+
+        #[test]
+        fn board_mode_puts_every_node_and_edge_on_one_page() {
+            let mut b = board(vec![
+                sized("a", 0.0, 0.0, 200.0, 100.0, "a"),
+                sized("b", 400.0, 300.0, 200.0, 100.0, "b"),
+            ]);
+            b.edges.push(Edge {
+                id: "e".to_string(),
+                from_node: "a".to_string(),
+                to_node: "b".to_string(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            });
+            b.edges.push(Edge {
+                id: "dangling".to_string(),
+                from_node: "a".to_string(),
+                to_node: "gone".to_string(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            });
+
+            let pages = page_layouts(&b, PdfMode::Board);
+            assert_eq!(pages.len(), 1);
+            assert_eq!(card_ids(&pages[0]), ["a", "b"]);
+            let centers: Vec<(f64, f64)> = pages[0].cards.iter().map(|(_, l)| l.center()).collect();
+            assert_eq!(pages[0].edges, [(centers[0], centers[1])]);
+        }
+
+        #[test]
+        fn empty_board_prints_one_blank_page_in_board_mode() {
+            assert!(page_layouts(&Board::default(), PdfMode::Cards).is_empty());
+            let pages = page_layouts(&Board::default(), PdfMode::Board);
+            assert_eq!(pages.len(), 1);
+            assert_eq!(pages[0].size, CARD_PAGE);
+            assert!(pages[0].cards.is_empty());
+        }
+
+        #[test]
+        fn only_local_image_nodes_embed_a_file() {
+            let mut local = sized("a", 0.0, 0.0, 200.0, 100.0, "assets/pic.png");
+            local.node_type = NodeType::Image;
+            let mut remote = sized("b", 0.0, 0.0, 200.0, 100.0, "https://x/y.png");
+            remote.node_type = NodeType::Image;
+            let text = sized("c", 0.0, 0.0, 200.0, 100.0, "assets/pic.png");
+            assert_eq!(local_image_path(&local), Some("assets/pic.png"));
+            assert_eq!(local_image_path(&remote), None);
+            assert_eq!(local_image_path(&text), None);
+        }
+    }
+
+    mod text_tests {
+        use super::*;
+
+        #[test]
+        fn long_text_is_cut_with_an_ellipsis() {
+            let words = vec!["word"; 200].join(" ");
+            let lines = card_text_lines(&sized("a", 0.0, 0.0, 200.0, 100.0, &words));
+            let line_height = BODY_FONT * LINE_HEIGHT;
+            let fits = ((100.0 - LABEL_BAND - BODY_PADDING) / line_height).floor() as usize;
+            assert_eq!(lines.len(), fits);
+            let last = lines.last().unwrap();
+            assert!(last.ends_with('\u{2026}'));
+            assert!(approx_text_width(last, BODY_FONT) <= 200.0 - 2.0 * BODY_PADDING);
+        }
+
+        #[test]
+        fn short_text_is_kept_and_title_wins() {
+            let mut n = sized("a", 0.0, 0.0, 200.0, 100.0, "https://example.com");
+            assert_eq!(card_text_lines(&n), vec!["https://example.com"]);
+            n.title = Some("Example".to_string());
+            assert_eq!(card_text_lines(&n), vec!["Example"]);
+            n.title = None;
+            n.text = "  ".to_string();
+            assert!(card_text_lines(&n).is_empty());
+        }
+
+        #[test]
+        fn markdown_cards_print_plain_text() {
+            assert_eq!(
+                markdown_plain_text(
+                    "# Plan\n> **Bold** and `code`\n- see [docs](https://x.y)\n---\n```\nlet x;\n```"
+                ),
+                "Plan\nBold and code\n\u{2022} see docs\nlet x;"
+            );
+            let mut n = sized("a", 0.0, 0.0, 400.0, 200.0, "## Heading\n* item");
+            n.node_type = NodeType::Md;
+            assert_eq!(card_text_lines(&n), vec!["Heading", "\u{2022} item"]);
+        }
+
+        #[test]
+        fn text_is_encoded_as_winansi() {
+            assert_eq!(win_ansi("a(b)\tc"), b"a(b) c");
+            assert_eq!(win_ansi("caf\u{e9}\u{2026}"), b"caf\xe9\x85");
+            assert_eq!(
+                win_ansi("\u{201c}\u{2019}\u{201d}\u{2013}\u{2014}\u{2022}\u{20ac}\u{2122}"),
+                b"\x93\x92\x94\x96\x97\x95\x80\x99"
+            );
+            // Holes in the 0x80-0x9F block and other scripts fall back.
+            assert_eq!(win_ansi("\u{3b1}\u{81}\u{1f600}"), b"???");
+        }
+    }
+}
@@ -8,12 +8,17 @@ fn sample_node(id: &str, text: &str) -> Node {
         width: 200.0,
         height: 100.0,
         text: text.to_string(),
+        title: None,
         node_type: NodeType::Text,
         color: None,
         tags: vec![],
         status: None,
         group: None,
         priority: None,
+        collapsed: false,
+        render_markdown: false,
+        updated_at: None,
+        extra: Default::default(),
     }
 }
 
@@ -26,7 +31,10 @@ fn sample_board() -> Board {
             from_node: "n1".to_string(),
             to_node: "n2".to_string(),
             label: Some("connects".to_string()),
+            from_port: None,
+            to_port: None,
         }],
+        settings: Default::default(),
     }
 }
 
@@ -12,12 +12,17 @@ fn sample_node(id: &str, text: &str) -> Node {
         width: 200.0,
         height: 100.0,
         text: text.to_string(),
+        title: None,
         node_type: NodeType::Text,
         color: None,
         tags: vec![],
         status: None,
         group: None,
         priority: None,
+        collapsed: false,
+        render_markdown: false,
+        updated_at: None,
+        extra: Default::default(),
     }
 }
 
@@ -33,12 +38,17 @@ fn decorated_board() -> Board {
                 width: 240.0,
                 height: 120.0,
                 text: "Decorated".to_string(),
+                title: None,
                 node_type: NodeType::Idea,
                 color: Some("#ff6600".to_string()),
                 tags: vec!["urgent".to_string(), "pricing".to_string()],
                 status: Some("in-progress".to_string()),
                 group: Some("cluster-a".to_string()),
                 priority: Some(2),
+                collapsed: false,
+                render_markdown: false,
+                updated_at: None,
+                extra: Default::default(),
             },
         ],
         edges: vec![Edge {
@@ -46,7 +56,10 @@ fn decorated_board() -> Board {
             from_node: "n1".to_string(),
             to_node: "n2".to_string(),
             label: Some("depends on".to_string()),
+            from_port: None,
+            to_port: None,
         }],
+        settings: Default::default(),
     }
 }
 
@@ -0,0 +1,155 @@
+//! End-to-end tests for the headless subcommands: each runs the real binary
+//! against boards in a temp dir and checks its output, exit code and any file
+//! it writes.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use infinite_brainstorm_lib::{load_board_at, write_board_atomic, Board, Edge, Node, NodeType};
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_infinite-brainstorm"))
+        .args(args)
+        .output()
+        .expect("run infinite-brainstorm")
+}
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().expect("utf-8 temp path")
+}
+
+fn sample_board() -> Board {
+    let mut idea = Node::new(
+        "b".to_string(),
+        300.0,
+        0.0,
+        "Child\nwith detail".to_string(),
+    );
+    idea.node_type = NodeType::Idea;
+    idea.tags = vec!["urgent".to_string()];
+    Board {
+        version: None,
+        nodes: vec![
+            Node::new("a".to_string(), 0.0, 0.0, "Root".to_string()),
+            idea,
+            Node::new("c".to_string(), 0.0, 300.0, "Loose".to_string()),
+        ],
+        edges: vec![Edge {
+            id: "e1".to_string(),
+            from_node: "a".to_string(),
+            to_node: "b".to_string(),
+            label: None,
+            from_port: None,
+            to_port: None,
+        }],
+        settings: Default::default(),
+    }
+}
+
+#[test]
+fn stats_reports_counts() {
+    let dir = tempfile::tempdir().unwrap();
+    let board = dir.path().join("board.json");
+    write_board_atomic(&board, &sample_board()).unwrap();
+
+    let out = cli(&["stats", path_str(&board)]);
+    assert!(out.status.success());
+    let text = stdout(&out);
+    assert!(text.contains("nodes: 3"), "{text}");
+    assert!(text.contains("  idea: 1"), "{text}");
+    assert!(text.contains("edges: 1"), "{text}");
+    assert!(text.contains("tags: 1"), "{text}");
+    assert!(text.contains("orphans: 1"), "{text}");
+    assert!(text.contains("cycles: no"), "{text}");
+}
+
+#[test]
+fn export_md_prints_the_outline() {
+    let dir = tempfile::tempdir().unwrap();
+    let board = dir.path().join("board.json");
+    write_board_atomic(&board, &sample_board()).unwrap();
+
+    let out = cli(&["export-md", path_str(&board)]);
+    assert!(out.status.success());
+    assert_eq!(
+        stdout(&out),
+        "# Root\n\n## Child\n\nwith detail\n\n# Loose\n"
+    );
+}
+
+#[test]
+fn export_md_writes_out_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let board = dir.path().join("board.json");
+    let md = dir.path().join("board.md");
+    write_board_atomic(&board, &sample_board()).unwrap();
+
+    let out = cli(&["export-md", path_str(&board), "--out", path_str(&md)]);
+    assert!(out.status.success());
+    assert!(std::fs::read_to_string(&md)
+        .unwrap()
+        .starts_with("# Root\n"));
+}
+
+#[test]
+fn import_canvas_writes_a_board() {
+    let dir = tempfile::tempdir().unwrap();
+    let canvas = dir.path().join("map.canvas");
+    let board = dir.path().join("board.json");
+    std::fs::write(
+        &canvas,
+        r#"{"nodes":[
+            {"id":"t","type":"text","x":0,"y":0,"width":250,"height":60,"text":"Hello"},
+            {"id":"l","type":"link","x":400,"y":0,"width":250,"height":60,"url":"https://example.com"}
+        ],"edges":[{"id":"e","fromNode":"t","toNode":"l"}]}"#,
+    )
+    .unwrap();
+
+    let out = cli(&["import-canvas", path_str(&canvas), path_str(&board)]);
+    assert!(out.status.success());
+    let imported = load_board_at(&board).unwrap();
+    assert_eq!(imported.nodes.len(), 2);
+    assert_eq!(imported.nodes[1].node_type, NodeType::Link);
+    assert_eq!(imported.edges.len(), 1);
+}
+
+#[test]
+fn import_canvas_needs_force_to_overwrite() {
+    let dir = tempfile::tempdir().unwrap();
+    let canvas = dir.path().join("map.canvas");
+    let board = dir.path().join("board.json");
+    std::fs::write(&canvas, r#"{"nodes":[],"edges":[]}"#).unwrap();
+    write_board_atomic(&board, &sample_board()).unwrap();
+
+    let out = cli(&["import-canvas", path_str(&canvas), path_str(&board)]);
+    assert!(!out.status.success());
+    assert_eq!(load_board_at(&board).unwrap().nodes.len(), 3);
+
+    let out = cli(&[
+        "import-canvas",
+        path_str(&canvas),
+        path_str(&board),
+        "--force",
+    ]);
+    assert!(out.status.success());
+    assert!(load_board_at(&board).unwrap().nodes.is_empty());
+}
+
+#[test]
+fn validate_fails_on_dangling_edge() {
+    let dir = tempfile::tempdir().unwrap();
+    let board = dir.path().join("board.json");
+    let mut broken = sample_board();
+    broken.edges[0].to_node = "ghost".to_string();
+    write_board_atomic(&board, &broken).unwrap();
+
+    let out = cli(&["validate", path_str(&board)]);
+    assert!(!out.status.success());
+
+    write_board_atomic(&board, &sample_board()).unwrap();
+    assert!(cli(&["validate", path_str(&board)]).status.success());
+}
@@ -1,19 +1,34 @@
 use crate::canvas::{
-    get_canvas_context, render_board, ImageCache, LinkPreviewCache, LoadState, RenderState,
-    IMAGE_CACHE_CAP,
+    body_text_measure, cubic_point, draws_wrapped_text, edge_anchors, edge_bezier,
+    edge_control_point, get_canvas_context, height_for_text, parallel_edge_bends, quad_point,
+    recent_edit_alpha, render_board, selection_bounds, show_individual_handles, shown_nodes,
+    width_for_text, CollapseView, DiffOverlay, FrameStats, ImageCache, LinkPreviewCache, LoadState,
+    ProgressiveCursor, RenderState, FRAME_STATS_WINDOW, IMAGE_CACHE_CAP, PROGRESSIVE_BATCH,
+    SELECTION_BOUNDS_PAD,
 };
 use crate::components::{
-    ErrorBanner, ImageModal, MarkdownModal, MarkdownOverlays, Minimap, NodeEditor, SearchOverlay,
+    show_toast, ContextMenu, EdgeLabelEditor, ErrorBanner, GifOverlays, ImageModal, MarkdownModal,
+    MarkdownOverlays, Minimap, NodeEditor, NodeTooltip, SaveIndicator, SearchOverlay,
+    SelectionToolbar, SettingsPanel, TagFilter, Toast, TypeFilter, VoidOverview,
 };
 use crate::history::{EditKind, History};
-use crate::interaction::{reduce, BoardAction, SideEffect};
+use crate::interaction::{
+    connected_node_at, group_frame, next_group_id, nodes_at_point, pack_without_overlap,
+    paste_offset, rect_handle_at, reduce, remap_copy, resize_rect, resize_rect_min, restack_group,
+    scale_rects, select_group, split_edge_with, touch_changed, Alignment, BoardAction, NodeRect,
+    SideEffect, StickyConnect, DUPLICATE_OFFSET,
+};
+use crate::settings::{
+    load_settings, save_settings, EdgeStyle, EdgeTrigger, Settings, MAX_AUTOSAVE_MS,
+};
 use crate::state::{
-    Board, Camera, Edge, LinkPreview, Node, NodeType, ResizeHandle, MIN_NODE_HEIGHT,
-    MIN_NODE_WIDTH, RESIZE_HANDLE_SIZE,
+    board_to_csv, board_to_dot, elide_middle, nearest_port, ops, round_board_coords, Board, Camera,
+    Edge, LinkPreview, Node, NodeType, ResizeHandle, DEFAULT_NODE_HEIGHT, DEFAULT_NODE_WIDTH,
+    PDF_EXISTS_ERROR, RESIZE_HANDLE_SIZE, SAVE_COORD_DECIMALS,
 };
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use pulldown_cmark::{html, Event, Parser};
+use pulldown_cmark::{html, CowStr, Event, Parser, Tag, TagEnd};
 use serde::{Deserialize, Serialize};
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -27,6 +42,16 @@ extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"])]
     async fn invoke(cmd: &str, args: JsValue) -> JsValue;
 
+    /// `invoke` for commands whose failure matters: a rejected promise (the
+    /// command returned `Err`) comes back as `Err` instead of throwing.
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], js_name = invoke, catch)]
+    async fn try_invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+
+    /// `invoke` called synchronously: the IPC request is sent before this
+    /// returns, and the promise settles with the command's result.
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], js_name = invoke)]
+    fn invoke_now(cmd: &str, args: JsValue) -> js_sys::Promise;
+
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
     async fn listen(event: &str, handler: &Closure<dyn Fn(JsValue)>) -> JsValue;
 }
@@ -101,8 +126,14 @@ async fn load_board_storage() -> LoadOutcome {
 /// so a malformed file can't be overwritten by the next save.
 ///
 /// Shared by both the initial-load effect and the file-watcher reload path
-/// (immediate and deferred) so the three sites stay in lockstep.
-async fn reload_board_into(set_board: WriteSignal<Board>, load_error: RwSignal<Option<String>>) {
+/// (immediate and deferred) so the three sites stay in lockstep. A board from a
+/// newer schema version still loads; its version goes into `newer_version` for
+/// the warning banner.
+async fn reload_board_into(
+    set_board: WriteSignal<Board>,
+    load_error: RwSignal<Option<String>>,
+    newer_version: RwSignal<Option<u32>>,
+) {
     match load_board_storage().await {
         LoadOutcome::Loaded(mut loaded_board) => {
             loaded_board.apply_auto_size();
@@ -124,6 +155,7 @@ async fn reload_board_into(set_board: WriteSignal<Board>, load_error: RwSignal<O
                 );
             }
             load_error.set(None);
+            newer_version.set(loaded_board.newer_version());
             set_board.set(loaded_board);
         }
         LoadOutcome::Absent => {
@@ -139,20 +171,104 @@ async fn reload_board_into(set_board: WriteSignal<Board>, load_error: RwSignal<O
     }
 }
 
-pub(crate) async fn save_board_storage(board: &Board) {
-    if is_tauri() {
-        let args = serde_wasm_bindgen::to_value(&SaveBoardArgs {
-            board: board.clone(),
-        })
-        .unwrap();
-        let _ = invoke("save_board", args).await;
-    } else if let Ok(json) = serde_json::to_string(board) {
-        if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
-            let _ = storage.set_item(LOCALSTORAGE_KEY, &json);
+/// Typical per-origin localStorage quota. Browsers allow roughly 5M UTF-16
+/// code units (what JS strings are made of), so sizes here count those rather
+/// than UTF-8 bytes.
+const LOCALSTORAGE_QUOTA_UNITS: usize = 5 * 1024 * 1024;
+
+/// Fraction of [`LOCALSTORAGE_QUOTA_UNITS`] past which a browser save warns
+/// that storage is running out.
+const QUOTA_WARN_RATIO: f64 = 0.8;
+
+/// Approximate size of `board` once persisted, in UTF-16 code units: the
+/// length of its JSON serialization as the JS string the browser build writes
+/// to localStorage.
+pub fn estimate_board_bytes(board: &Board) -> usize {
+    serde_json::to_string(board).map_or(0, |json| json.encode_utf16().count())
+}
+
+/// How close a board of `units` is to the localStorage quota.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuotaCheck {
+    Ok,
+    /// Past [`QUOTA_WARN_RATIO`]; carries the percentage of the quota in use.
+    NearLimit(u32),
+    OverLimit,
+}
+
+/// Classify a persisted size against [`LOCALSTORAGE_QUOTA_UNITS`].
+pub fn check_quota(units: usize) -> QuotaCheck {
+    let ratio = units as f64 / LOCALSTORAGE_QUOTA_UNITS as f64;
+    if ratio >= 1.0 {
+        QuotaCheck::OverLimit
+    } else if ratio >= QUOTA_WARN_RATIO {
+        QuotaCheck::NearLimit((ratio * 100.0) as u32)
+    } else {
+        QuotaCheck::Ok
+    }
+}
+
+/// Persist the board: through the Tauri `save_board` command on desktop, or to
+/// localStorage in the browser. Returns the browser quota check for the write;
+/// `Err` when localStorage refused it (typically the quota), so the caller can
+/// tell the user the change wasn't saved rather than losing it silently.
+///
+/// The write is issued before this returns; the future only waits for its
+/// outcome. That keeps a flush from `beforeunload` synchronous, since nothing
+/// spawned there is guaranteed to run before the page goes away.
+pub(crate) fn save_board_storage(
+    board: &Board,
+) -> impl std::future::Future<Output = Result<QuotaCheck, String>> {
+    let mut board = board.clone();
+    round_board_coords(&mut board, SAVE_COORD_DECIMALS);
+    let (tauri_write, browser_write) = if is_tauri() {
+        // JSON-compatible output: the default serializer turns maps (a node's
+        // flattened unknown keys) into JS `Map`s, which don't survive the IPC.
+        let args = SaveBoardArgs { board }
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap();
+        let write = wasm_bindgen_futures::JsFuture::from(invoke_now("save_board", args));
+        (Some(write), Ok(QuotaCheck::Ok))
+    } else {
+        (None, save_local_storage(&board))
+    };
+    async move {
+        match tauri_write {
+            Some(write) => write.await.map(|_| QuotaCheck::Ok).map_err(|e| {
+                e.as_string()
+                    .unwrap_or_else(|| "board.json could not be written".to_string())
+            }),
+            None => browser_write,
         }
     }
 }
 
+/// The browser half of [`save_board_storage`]: check the board's estimated
+/// size against the quota, then write its JSON to localStorage. A board past
+/// the quota is refused up front with the same message a failed write gets.
+fn save_local_storage(board: &Board) -> Result<QuotaCheck, String> {
+    let units = estimate_board_bytes(board);
+    let full = || {
+        format!(
+            "Browser storage is full (board is ~{} KB) — the latest change was NOT saved. \
+             Download board.json to keep your work, or remove large images.",
+            units * 2 / 1024
+        )
+    };
+    let check = check_quota(units);
+    if check == QuotaCheck::OverLimit {
+        return Err(full());
+    }
+    let json = serde_json::to_string(board).map_err(|e| e.to_string())?;
+    let Some(storage) = local_storage() else {
+        return Ok(QuotaCheck::Ok);
+    };
+    storage
+        .set_item(LOCALSTORAGE_KEY, &json)
+        .map_err(|_| full())?;
+    Ok(check)
+}
+
 /// Read the `window.localStorage` handle, if available. localStorage is present
 /// in both the Tauri webview and a plain browser, so camera persistence works in
 /// either mode without an IPC round-trip.
@@ -181,37 +297,43 @@ fn load_camera_storage(key: &str) -> Option<Camera> {
         .map(CameraPersist::to_camera)
 }
 
+/// The active `board.json` path from the desktop backend; `None` in browser
+/// mode (or if the backend can't resolve it).
+async fn active_board_path() -> Option<String> {
+    if !is_tauri() {
+        return None;
+    }
+    invoke("get_board_path_cmd", JsValue::NULL)
+        .await
+        .as_string()
+}
+
 /// Resolve the per-board camera key. In Tauri mode the board path is appended so
 /// each `board.json` directory keeps its own viewport; in browser mode (single
 /// board) the bare prefix is used.
-async fn camera_storage_key() -> String {
-    if is_tauri() {
-        let result = invoke("get_board_path_cmd", JsValue::NULL).await;
-        if let Some(path) = result.as_string() {
-            return format!("{}:{}", CAMERA_KEY_PREFIX, path);
-        }
+fn camera_storage_key(board_path: Option<&str>) -> String {
+    match board_path {
+        Some(path) => format!("{}:{}", CAMERA_KEY_PREFIX, path),
+        None => CAMERA_KEY_PREFIX.to_string(),
     }
-    CAMERA_KEY_PREFIX.to_string()
 }
 
-/// Trailing-edge debounce window for the persistence sink, in milliseconds.
-///
-/// Bursts of mutations (e.g. a drag emitting many intermediate states, or rapid
-/// keystrokes) are coalesced into a single disk write that fires this long after
-/// the last [`RequestSave::call`].
-const SAVE_DEBOUNCE_MS: u32 = 220;
+/// Longest board path, in characters, the HUD shows before eliding the middle.
+const BOARD_PATH_DISPLAY_CHARS: usize = 40;
 
 /// A `Copy` handle to the centralized, debounced persistence sink.
 ///
 /// All mutation sites call [`RequestSave::call`] instead of invoking
 /// `save_board_storage` directly. Calls mark the board dirty and (re)arm a single
-/// trailing-edge timer; the actual write reads the latest board state at flush
-/// time, so coalesced bursts persist exactly the final state once.
+/// trailing-edge timer (the user's `autosave_ms` setting); the actual write reads
+/// the latest board state at flush time, so coalesced bursts persist exactly the
+/// final state once.
 #[derive(Clone, Copy)]
 pub struct RequestSave {
     // `Rc<dyn Fn()>` is `!Send`/`!Sync`, so it lives in thread-local arena storage
     // (`LocalStorage`). This is sound in the single-threaded CSR/WASM runtime.
     inner: StoredValue<Rc<dyn Fn()>, LocalStorage>,
+    flush: StoredValue<Rc<dyn Fn()>, LocalStorage>,
 }
 
 impl RequestSave {
@@ -220,39 +342,208 @@ impl RequestSave {
         let f = self.inner.get_value();
         f();
     }
+
+    /// Write a pending save now instead of waiting out the timer. A no-op when
+    /// nothing is pending. Used when the window loses focus or unloads; the
+    /// write is issued before this returns (see [`save_board_storage`]).
+    pub fn flush(&self) {
+        let f = self.flush.get_value();
+        f();
+    }
+}
+
+/// A `Copy` handle for jumping the camera to a new view (fit, focus, search,
+/// minimap). [`FlyCamera::to`] animates there with [`ease_camera`], or jumps
+/// straight there when the reduce-motion setting is on, and persists the view
+/// once it lands.
+#[derive(Clone, Copy)]
+pub struct FlyCamera {
+    inner: StoredValue<Rc<dyn Fn(Camera)>, LocalStorage>,
+}
+
+impl FlyCamera {
+    /// Move the camera to `target`, cancelling any glide or flight in progress.
+    pub fn to(&self, target: Camera) {
+        let f = self.inner.get_value();
+        f(target);
+    }
+}
+
+/// The debounce bookkeeping behind [`RequestSave`], kept free of timers so it's
+/// testable natively: whether a write is pending and how long to wait for it.
+#[derive(Debug, Default)]
+pub struct SaveScheduler {
+    pending: bool,
+}
+
+impl SaveScheduler {
+    /// An edit landed: a write is now pending. Returns the delay to (re)arm the
+    /// trailing timer with — the configured `interval_ms`, capped at
+    /// [`MAX_AUTOSAVE_MS`].
+    pub fn request(&mut self, interval_ms: u32) -> u32 {
+        self.pending = true;
+        interval_ms.min(MAX_AUTOSAVE_MS)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Take the pending write, reading the state to save through `latest` at
+    /// this moment, so the newest board is what gets written. Whichever of the
+    /// timer and an early flush runs second finds nothing left and writes
+    /// nothing.
+    pub fn flush<T>(&mut self, latest: impl FnOnce() -> T) -> Option<T> {
+        std::mem::take(&mut self.pending).then(latest)
+    }
+}
+
+/// Where the board stands relative to what's on disk (or in localStorage), for
+/// the HUD's save indicator. Driven by [`make_request_save`]: every edit marks it
+/// `Dirty`, the debounce timer firing moves it to `Saving`, and the write's
+/// outcome settles it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SaveState {
+    /// Everything is persisted.
+    #[default]
+    Saved,
+    /// There are edits a queued save hasn't written yet.
+    Dirty,
+    /// A write is in flight.
+    Saving,
+    /// The last write failed; the message says why.
+    Failed(String),
+}
+
+impl SaveState {
+    /// An edit landed. Always dirty — even mid-save or after a failure, since the
+    /// edit queues another write.
+    pub fn edited(&self) -> SaveState {
+        SaveState::Dirty
+    }
+
+    /// The debounced write started.
+    pub fn save_started(&self) -> SaveState {
+        SaveState::Saving
+    }
+
+    /// The write finished. Success only settles to `Saved` if nothing was edited
+    /// while it was in flight; otherwise the newer edits keep it `Dirty` until
+    /// their own write lands. A failure always shows.
+    pub fn save_finished(&self, result: Result<(), String>) -> SaveState {
+        match (self, result) {
+            (_, Err(msg)) => SaveState::Failed(msg),
+            (SaveState::Saving, Ok(())) => SaveState::Saved,
+            (other, Ok(())) => other.clone(),
+        }
+    }
+
+    /// Short HUD label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SaveState::Saved => "saved",
+            SaveState::Dirty => "unsaved",
+            SaveState::Saving => "saving\u{2026}",
+            SaveState::Failed(_) => "save failed",
+        }
+    }
 }
 
 /// Build the debounced persistence sink.
 ///
 /// Returns a [`RequestSave`] whose every call cancels any pending timer and arms
-/// a fresh trailing-edge [`gloo_timers::callback::Timeout`]. When the timer fires
-/// it reads `board` untracked, persists it, and clears `local_edit_pending`.
+/// a fresh trailing-edge [`gloo_timers::callback::Timeout`] for the `autosave_ms`
+/// read from `settings` at call time. When the timer fires — or
+/// [`RequestSave::flush`] runs first — it reads `board` untracked, persists it,
+/// and clears `local_edit_pending`.
 /// `local_edit_pending` is raised on every call so the file watcher (P1.4) can
 /// distinguish our own in-flight edits from genuine external changes.
-fn make_request_save(board: ReadSignal<Board>, local_edit_pending: RwSignal<bool>) -> RequestSave {
+///
+/// A failed write is reported through `save_error` (cleared by the next
+/// successful save); crossing into the near-quota band shows a `toast` once.
+/// `save_state` follows each call through the [`SaveState`] transitions.
+fn make_request_save(
+    board: ReadSignal<Board>,
+    settings: ReadSignal<Settings>,
+    local_edit_pending: RwSignal<bool>,
+    save_state: RwSignal<SaveState>,
+    save_error: RwSignal<Option<String>>,
+    toast: RwSignal<Option<String>>,
+) -> RequestSave {
     // Holds the live timer so a subsequent call drops (cancels) it before arming
     // a new one — this is what coalesces a burst into one write.
     let pending: Rc<RefCell<Option<gloo_timers::callback::Timeout>>> = Rc::new(RefCell::new(None));
+    // Whether the last save was already near the quota, so the warning fires
+    // when the board grows into that band rather than on every save.
+    let near_limit = Rc::new(Cell::new(false));
 
-    let sink: Rc<dyn Fn()> = Rc::new(move || {
-        local_edit_pending.set(true);
-        let pending_for_timer = pending.clone();
-        let timeout = gloo_timers::callback::Timeout::new(SAVE_DEBOUNCE_MS, move || {
-            // Clear our own handle first so the closure can't keep the Timeout
-            // alive after it fires.
-            pending_for_timer.borrow_mut().take();
-            let current_board = board.get_untracked();
-            spawn_local(async move {
-                save_board_storage(&current_board).await;
-                local_edit_pending.set(false);
-            });
+    let scheduler = Rc::new(RefCell::new(SaveScheduler::default()));
+
+    let write: Rc<dyn Fn(Board)> = Rc::new(move |current_board: Board| {
+        let near_limit = near_limit.clone();
+        save_state.update(|s| *s = s.save_started());
+        let save = save_board_storage(&current_board);
+        spawn_local(async move {
+            let result = save.await;
+            let outcome = result.as_ref().map(|_| ()).map_err(Clone::clone);
+            save_state.update(|s| *s = s.save_finished(outcome));
+            match result {
+                Ok(check) => {
+                    save_error.set(None);
+                    let near = matches!(check, QuotaCheck::NearLimit(_));
+                    if let QuotaCheck::NearLimit(percent) = check {
+                        if !near_limit.get() {
+                            show_toast(
+                                toast,
+                                format!("Browser storage {percent}% full — consider removing large images"),
+                            );
+                        }
+                    }
+                    near_limit.set(near);
+                }
+                Err(msg) => {
+                    web_sys::console::error_1(&format!("Failed to save board: {msg}").into());
+                    save_error.set(Some(msg));
+                }
+            }
+            local_edit_pending.set(false);
         });
-        // Dropping the previous Timeout (if any) cancels it.
-        *pending.borrow_mut() = Some(timeout);
     });
 
+    let flush: Rc<dyn Fn()> = {
+        let pending = pending.clone();
+        let scheduler = scheduler.clone();
+        let write = write.clone();
+        Rc::new(move || {
+            // Drop (cancel) the timer first; it would find nothing to write anyway.
+            pending.borrow_mut().take();
+            let latest = scheduler.borrow_mut().flush(|| board.get_untracked());
+            if let Some(current_board) = latest {
+                write(current_board);
+            }
+        })
+    };
+
+    let sink: Rc<dyn Fn()> = {
+        let flush = flush.clone();
+        Rc::new(move || {
+            local_edit_pending.set(true);
+            save_state.update(|s| *s = s.edited());
+            let delay = scheduler
+                .borrow_mut()
+                .request(settings.with_untracked(|s| s.autosave_ms));
+            let flush = flush.clone();
+            // The timeout owns a clone of `flush`, which holds `pending`; the
+            // flush takes the handle out first, so the cycle ends once it fires.
+            let timeout = gloo_timers::callback::Timeout::new(delay, move || flush());
+            // Dropping the previous Timeout (if any) cancels it.
+            *pending.borrow_mut() = Some(timeout);
+        })
+    };
+
     RequestSave {
         inner: StoredValue::new_local(sink),
+        flush: StoredValue::new_local(flush),
     }
 }
 
@@ -265,6 +556,24 @@ pub type Snapshot = (Board, HashSet<String>);
 /// lives behind `Rc<RefCell<..>>` rather than a signal.
 type BoardHistory = Rc<RefCell<History<Snapshot>>>;
 
+/// The pure core of [`Dispatcher::apply`]: push `snap` (the board and node
+/// selection before the edit) onto `history` as a `kind` edit at `now_ms`, then
+/// reduce `action` on that board and stamp the nodes it changed (see
+/// [`touch_changed`]).
+pub fn apply_action(
+    history: &mut History<Snapshot>,
+    snap: Snapshot,
+    action: BoardAction,
+    kind: EditKind,
+    now_ms: f64,
+) -> (Board, Vec<SideEffect>) {
+    let previous = snap.0.clone();
+    history.push_kind_at(snap, kind, now_ms);
+    let (mut next_board, effects) = reduce(previous.clone(), action);
+    touch_changed(&previous, &mut next_board, now_ms as i64);
+    (next_board, effects)
+}
+
 /// Holds the requestAnimationFrame render callback so it isn't dropped while the
 /// browser owns it. Stored behind `Rc<RefCell<..>>` so the closure can be set
 /// once and kept alive for the component's lifetime.
@@ -281,15 +590,21 @@ type RenderClosure = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
 /// The history `Rc` is `!Send`, so — like [`RequestSave`] — it is parked in
 /// thread-local `LocalStorage` arena storage, which keeps this struct `Copy` and
 /// cheap to stash in [`EditingCtx`] for the editor components to dispatch through.
+/// That is how text edits become undoable: `NodeEditor` commits and the markdown
+/// modal's Save go through [`apply`](Self::apply) like moves and deletes, so no
+/// component needs the history itself in a context.
 #[derive(Clone, Copy)]
 pub struct Dispatcher {
     board: ReadSignal<Board>,
     set_board: WriteSignal<Board>,
     selected_nodes: ReadSignal<HashSet<String>>,
     set_selected_nodes: WriteSignal<HashSet<String>>,
+    selected_edge: ReadSignal<Option<String>>,
     set_selected_edge: WriteSignal<Option<String>>,
     history: StoredValue<BoardHistory, LocalStorage>,
     request_save: RequestSave,
+    /// User preferences; deletes read the assets folder from here.
+    settings: ReadSignal<Settings>,
 }
 
 impl Dispatcher {
@@ -297,21 +612,31 @@ impl Dispatcher {
     ///
     /// Exposed for the deferred-snapshot path (F114): drag/resize call this once on
     /// the first actual movement (not on mouse-down) so a plain click never creates
-    /// a junk undo entry. [`apply`](Self::apply) calls it internally for one-shot
-    /// actions.
+    /// a junk undo entry. One-shot actions snapshot inside [`apply`](Self::apply)
+    /// (through [`apply_action`]).
     pub fn snapshot(&self) {
         self.snapshot_kind(None);
     }
 
     /// Like [`snapshot`](Self::snapshot) but tags the entry with an [`EditKind`]
     /// so successive same-kind edits (e.g. repeated type-cycling) coalesce into a
-    /// single undo step inside [`History`].
+    /// single undo step inside [`History`], as long as each lands within
+    /// [`COALESCE_WINDOW_MS`](crate::history::COALESCE_WINDOW_MS) of the last.
     pub fn snapshot_kind(&self, kind: EditKind) {
         let snap = (
             self.board.get_untracked(),
             self.selected_nodes.get_untracked(),
         );
-        self.history.get_value().borrow_mut().push_kind(snap, kind);
+        self.history
+            .get_value()
+            .borrow_mut()
+            .push_kind_at(snap, kind, js_sys::Date::now());
+    }
+
+    /// Drop the snapshot a cancelled gesture took, so aborting a drag or resize
+    /// with Escape leaves no empty undo step behind.
+    pub fn discard_snapshot(&self) {
+        self.history.get_value().borrow_mut().discard_last();
     }
 
     /// Run the side effects a [`reduce`] call produced.
@@ -353,27 +678,13 @@ impl Dispatcher {
         }
     }
 
-    /// Commit the reduced board + optional new selection and run side effects,
-    /// WITHOUT taking a new history snapshot. Used by continuous gestures
-    /// (drag/resize) where [`snapshot`](Self::snapshot) was already taken on the
-    /// first movement.
-    fn commit(&self, action: BoardAction, new_selection: Option<HashSet<String>>) {
-        let (next_board, effects) = reduce(self.board.get_untracked(), action);
-        self.set_board.set(next_board);
-        if let Some(selection) = new_selection {
-            self.set_selected_nodes.set(selection);
-        }
-        self.run_effects(effects);
-    }
-
     /// The single mutation entry point: snapshot once, reduce, commit, dispatch.
     ///
     /// `new_selection` replaces the node selection when `Some` (e.g. select the
     /// freshly created/pasted node, or clear selection after a delete); pass `None`
     /// to leave selection untouched.
     pub fn apply(&self, action: BoardAction, new_selection: Option<HashSet<String>>) {
-        self.snapshot();
-        self.commit(action, new_selection);
+        self.apply_coalesced(action, new_selection, None);
     }
 
     /// Like [`apply`](Self::apply) but coalesces with the immediately preceding
@@ -385,40 +696,92 @@ impl Dispatcher {
         new_selection: Option<HashSet<String>>,
         kind: EditKind,
     ) {
-        self.snapshot_kind(kind);
-        self.commit(action, new_selection);
+        let snap = (
+            self.board.get_untracked(),
+            self.selected_nodes.get_untracked(),
+        );
+        let (next_board, effects) = apply_action(
+            &mut self.history.get_value().borrow_mut(),
+            snap,
+            action,
+            kind,
+            js_sys::Date::now(),
+        );
+        self.set_board.set(next_board);
+        if let Some(selection) = new_selection {
+            self.set_selected_nodes.set(selection);
+        }
+        self.run_effects(effects);
+    }
+
+    /// Put `ids` into a fresh group (Cmd+G, selection toolbar "Group").
+    pub fn group_nodes(&self, ids: Vec<String>) {
+        let group = Some(next_group_id(&self.board.get_untracked()));
+        self.apply(
+            BoardAction::AssignGroup {
+                ids,
+                group,
+                color: None,
+            },
+            None,
+        );
+    }
+
+    /// Delete `ids` and every edge touching them, then clear the selection
+    /// (Delete key, selection toolbar). Asset cleanup rides along as a side effect.
+    pub fn delete_nodes(&self, ids: Vec<String>) {
+        self.apply(
+            BoardAction::DeleteSelected {
+                node_ids: ids,
+                edge_id: None,
+                assets_dir: self.settings.with_untracked(|s| s.assets_dir.clone()),
+            },
+            Some(HashSet::new()),
+        );
     }
 
-    /// Undo the last mutation, restoring both the board and the selection that was
-    /// live when the snapshot was taken (F115). Returns `true` if anything changed.
+    /// Undo the last mutation. The current selection survives where its ids still
+    /// exist; otherwise the selection live at snapshot time comes back (F115).
+    /// Returns `true` if anything changed.
     pub fn undo(&self) -> bool {
         let current = (
             self.board.get_untracked(),
             self.selected_nodes.get_untracked(),
         );
+        let current_selection = current.1.clone();
         if let Some((board, selection)) = self.history.get_value().borrow_mut().undo(current) {
-            self.set_board.set(board);
-            self.set_selected_nodes.set(selection);
-            self.set_selected_edge.set(None);
-            self.request_save.call();
+            self.restore(board, &current_selection, selection);
             true
         } else {
             false
         }
     }
 
-    /// Redo the last undone mutation, restoring board + selection. Returns `true`
-    /// if anything changed.
+    /// Commit a board popped off the history, keeping whatever is selected now
+    /// if it survives the jump (see [`restored_selection`]).
+    fn restore(&self, board: Board, current: &HashSet<String>, snapshot: HashSet<String>) {
+        let (nodes, edge) = restored_selection(
+            &board,
+            current,
+            self.selected_edge.get_untracked(),
+            snapshot,
+        );
+        self.set_board.set(board);
+        self.set_selected_nodes.set(nodes);
+        self.set_selected_edge.set(edge);
+        self.request_save.call();
+    }
+
+    /// Redo the last undone mutation, with the same selection rule as
+    /// [`undo`](Self::undo). Returns `true` if anything changed.
     pub fn redo(&self) -> bool {
         let current = (
             self.board.get_untracked(),
             self.selected_nodes.get_untracked(),
         );
+        let current_selection = current.1.clone();
         if let Some((board, selection)) = self.history.get_value().borrow_mut().redo(current) {
-            self.set_board.set(board);
-            self.set_selected_nodes.set(selection);
-            self.set_selected_edge.set(None);
-            self.request_save.call();
+            self.restore(board, &current_selection, selection);
             true
         } else {
             false
@@ -426,6 +789,31 @@ impl Dispatcher {
     }
 }
 
+/// Selection to show after undo/redo lands on `board`. Currently selected nodes
+/// and edge stay selected as long as they still exist there; only when none of
+/// the nodes survive does the snapshot's own selection come back (so undoing a
+/// delete reselects what was deleted, and undoing a create reselects what was
+/// selected before it).
+fn restored_selection(
+    board: &Board,
+    current_nodes: &HashSet<String>,
+    current_edge: Option<String>,
+    snapshot_nodes: HashSet<String>,
+) -> (HashSet<String>, Option<String>) {
+    let kept: HashSet<String> = current_nodes
+        .iter()
+        .filter(|id| board.nodes.iter().any(|n| &n.id == *id))
+        .cloned()
+        .collect();
+    let nodes = if kept.is_empty() {
+        snapshot_nodes
+    } else {
+        kept
+    };
+    let edge = current_edge.filter(|id| board.edges.iter().any(|e| &e.id == id));
+    (nodes, edge)
+}
+
 #[derive(Serialize, Deserialize)]
 struct SaveBoardArgs {
     board: Board,
@@ -448,6 +836,29 @@ struct ReadMarkdownFileArgs {
     path: String,
 }
 
+#[derive(Serialize, Deserialize)]
+struct FetchMarkdownUrlArgs {
+    url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveThumbnailArgs {
+    png_bytes: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SetAssetsDirArgs {
+    path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetPreviewClientArgs {
+    timeout_secs: u32,
+    user_agent: Option<String>,
+}
+
 #[derive(Clone, Default)]
 struct DragState {
     is_dragging: bool,
@@ -459,6 +870,12 @@ struct DragState {
     /// first actual movement (not mouse-down) so a plain click never creates a junk
     /// undo entry (F114).
     snapshotted: bool,
+    /// Whether the last drag move was alignment-snapped on the `(x, y)` axes. A
+    /// snapped axis skips snap-to-grid on release so the alignment survives.
+    aligned: (bool, bool),
+    /// URL of a link node pressed without modifiers. Copied to the clipboard on
+    /// release only if the press never turned into a drag.
+    link_click: Option<String>,
 }
 
 #[derive(Clone)]
@@ -468,6 +885,10 @@ struct PanState {
     start_y: f64,
     camera_start_x: f64,
     camera_start_y: f64,
+    /// Last pointer sample `(x, y, timestamp_ms)`, for release velocity.
+    last_sample: (f64, f64, f64),
+    /// Smoothed pointer velocity in screen px/ms, handed to the inertia glide.
+    velocity: (f64, f64),
 }
 
 impl Default for PanState {
@@ -478,14 +899,29 @@ impl Default for PanState {
             start_y: 0.0,
             camera_start_x: 0.0,
             camera_start_y: 0.0,
+            last_sample: (0.0, 0.0, 0.0),
+            velocity: (0.0, 0.0),
         }
     }
 }
 
+/// One frame's numbers for the dev render-stats overlay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct RenderStats {
+    last_ms: f64,
+    avg_ms: f64,
+    max_ms: f64,
+    nodes: usize,
+    edges: usize,
+}
+
 #[derive(Clone, Default)]
 struct EdgeCreationState {
     is_creating: bool,
     from_node_id: Option<String>,
+    /// Canvas position the drag started at, for the empty-space release guard.
+    start_x: f64,
+    start_y: f64,
     current_x: f64,
     current_y: f64,
 }
@@ -505,6 +941,62 @@ struct ResizeState {
     /// first actual movement so a click on a handle without dragging creates no junk
     /// undo entry (F114).
     snapshotted: bool,
+    /// Group resize from the multi-selection bounds: each selected node's
+    /// original `(x, y, width, height)`. Empty for a single-node resize; the
+    /// `original_*` fields then hold the (unpadded) selection bounds.
+    group: Vec<NodeRect>,
+}
+
+/// Put every dragged node back at the position it had when the drag began.
+/// Used when Escape cancels a drag mid-gesture.
+fn revert_drag(board: &mut Board, start_positions: &HashMap<String, (f64, f64)>) {
+    for node in &mut board.nodes {
+        if let Some(&(x, y)) = start_positions.get(&node.id) {
+            node.x = x;
+            node.y = y;
+        }
+    }
+}
+
+/// Which corner handle of the multi-selection bounds world point `(x, y)` is
+/// on, with the selection's unpadded `(x, y, width, height)`. The handles sit
+/// on the dashed outline, `SELECTION_BOUNDS_PAD` screen pixels out.
+fn group_handle_at(
+    board: &Board,
+    selected: &HashSet<String>,
+    camera: &Camera,
+    x: f64,
+    y: f64,
+) -> Option<((f64, f64, f64, f64), ResizeHandle)> {
+    let ((min_x, min_y, max_x, max_y), _) = selection_bounds(board, selected)?;
+    let pad = SELECTION_BOUNDS_PAD / camera.zoom;
+    let padded = (
+        min_x - pad,
+        min_y - pad,
+        max_x - min_x + pad * 2.0,
+        max_y - min_y + pad * 2.0,
+    );
+    let handle = rect_handle_at(padded, x, y, RESIZE_HANDLE_SIZE / camera.zoom)?;
+    Some(((min_x, min_y, max_x - min_x, max_y - min_y), handle))
+}
+
+/// Restore the resized node's (or group's) original geometry. Used when Escape
+/// cancels a resize mid-gesture.
+fn revert_resize(board: &mut Board, resize: &ResizeState) {
+    for (id, (x, y, width, height)) in &resize.group {
+        if let Some(node) = board.nodes.iter_mut().find(|n| &n.id == id) {
+            (node.x, node.y, node.width, node.height) = (*x, *y, *width, *height);
+        }
+    }
+    let Some(id) = &resize.node_id else {
+        return;
+    };
+    if let Some(node) = board.nodes.iter_mut().find(|n| &n.id == id) {
+        node.x = resize.original_x;
+        node.y = resize.original_y;
+        node.width = resize.original_width;
+        node.height = resize.original_height;
+    }
 }
 
 pub(crate) fn parse_markdown(md: &str) -> String {
@@ -512,8 +1004,33 @@ pub(crate) fn parse_markdown(md: &str) -> String {
     // markup (e.g. `<img onerror=...>`) is rendered as literal text rather than
     // reaching the inner_html sink as active HTML. push_html HTML-escapes Text
     // events, so the angle brackets show and no attributes/handlers execute.
+    // Link and image destinations go through unescaped too, so any scheme
+    // other than http(s) (`javascript:`, `data:`, ...) is blanked: remote `.md`
+    // files render here and a script URL could otherwise reach the Tauri IPC.
     let parser = Parser::new(md).map(|event| match event {
         Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_markdown_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_markdown_url(dest_url),
+            title,
+            id,
+        }),
         other => other,
     });
     let mut html_output = String::new();
@@ -521,97 +1038,797 @@ pub(crate) fn parse_markdown(md: &str) -> String {
     html_output
 }
 
-/// Record a freshly decoded image in the cache and enforce the LRU bound.
-///
-/// `lru` is the insertion-order key log; the newly loaded `url` is appended (and
-/// any earlier occurrence removed so it isn't double-counted). If the number of
-/// `Loaded` entries exceeds [`IMAGE_CACHE_CAP`], the least-recently inserted keys
-/// that are **not** referenced by `live_urls` (currently on the board) are
-/// dropped. On-board images are always kept regardless of age so visible nodes
-/// never lose their picture.
-fn insert_loaded_image(
-    cache: &ImageCache,
-    lru: &Rc<RefCell<VecDeque<String>>>,
-    url: String,
-    img: HtmlImageElement,
-    live_urls: &HashSet<String>,
-) {
-    {
-        let mut order = lru.borrow_mut();
-        order.retain(|k| k != &url);
-        order.push_back(url.clone());
+/// `url` when it's relative or http(s), else an empty destination. Browsers
+/// ignore whitespace and control characters around and inside a scheme, so
+/// they're dropped before looking for one.
+fn safe_markdown_url(url: CowStr<'_>) -> CowStr<'_> {
+    let compact: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    let scheme = compact
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+    match scheme {
+        None => url,
+        Some(scheme) if ["http", "https"].contains(&scheme.to_ascii_lowercase().as_str()) => url,
+        Some(_) => CowStr::Borrowed(""),
     }
-    cache.borrow_mut().insert(url, LoadState::Loaded(img));
+}
 
-    // Decide which keys to evict using the pure helper, then apply.
-    let loaded_keys: HashSet<String> = cache
-        .borrow()
-        .iter()
-        .filter(|(_, v)| matches!(v, LoadState::Loaded(_)))
-        .map(|(k, _)| k.clone())
-        .collect();
+/// Rendered markdown keyed by a hash of its source, so re-renders (every pan or
+/// zoom re-runs the overlay closure) reuse the HTML of unchanged text and only
+/// edited content goes back through [`parse_markdown`]. Each entry keeps its
+/// source and a hit compares it, so a hash collision re-parses instead of
+/// showing another node's HTML. Nodes with identical text, like the
+/// "Loading..." placeholder of several linked files, share one entry. Clones
+/// share the same map.
+#[derive(Clone, Default)]
+pub struct MarkdownCache {
+    entries: Rc<RefCell<HashMap<u64, MarkdownEntry>>>,
+}
 
-    let victims = {
-        let order = lru.borrow();
-        plan_lru_eviction(&order, &loaded_keys, live_urls, IMAGE_CACHE_CAP)
-    };
+struct MarkdownEntry {
+    source: String,
+    html: String,
+    /// Looked up since the last [`MarkdownCache::sweep`].
+    used: bool,
+}
 
-    if victims.is_empty() {
-        return;
+impl MarkdownCache {
+    /// Sanitized HTML for `text`, parsed only on a cache miss.
+    pub fn get_or_parse(&self, text: &str) -> String {
+        self.get_or_insert_with(markdown_key(text), text, parse_markdown)
     }
-    {
-        let mut cache_mut = cache.borrow_mut();
-        for v in &victims {
-            cache_mut.remove(v);
-        }
+
+    fn get_or_insert_with(
+        &self,
+        key: u64,
+        text: &str,
+        parse: impl FnOnce(&str) -> String,
+    ) -> String {
+        let mut entries = self.entries.borrow_mut();
+        let entry = match entries.get_mut(&key) {
+            Some(entry) if entry.source == text => entry,
+            _ => {
+                let fresh = MarkdownEntry {
+                    source: text.to_string(),
+                    html: parse(text),
+                    used: false,
+                };
+                entries.insert(key, fresh);
+                entries.get_mut(&key).unwrap()
+            }
+        };
+        entry.used = true;
+        entry.html.clone()
     }
-    lru.borrow_mut().retain(|k| !victims.contains(k));
-}
 
-/// Pure LRU eviction planner: given the insertion-order `order` log, the set of
-/// `loaded_keys` (entries actually holding a decoded image), the `live_urls`
-/// currently on the board (never evicted), and the soft `cap`, return the keys to
-/// drop — the oldest loaded, non-live entries — so the loaded count returns to
-/// `cap`. Side-effect-free and host-testable (no `HtmlImageElement` needed).
-fn plan_lru_eviction(
-    order: &VecDeque<String>,
-    loaded_keys: &HashSet<String>,
-    live_urls: &HashSet<String>,
-    cap: usize,
-) -> HashSet<String> {
-    let loaded_count = loaded_keys.len();
-    let mut victims = HashSet::new();
-    if loaded_count <= cap {
-        return victims;
+    /// Drop every entry not looked up since the previous sweep, so HTML for
+    /// deleted or edited nodes doesn't pile up over a session. Called once per
+    /// render, after the overlays have fetched their HTML.
+    pub fn sweep(&self) {
+        self.entries
+            .borrow_mut()
+            .retain(|_, entry| std::mem::take(&mut entry.used));
     }
-    let mut to_evict = loaded_count - cap;
-    for key in order {
-        if to_evict == 0 {
-            break;
+}
+
+fn markdown_key(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Flatten markdown to readable plain text for the hover tooltip: markup and
+/// raw HTML dropped, link text kept without its URL, list items bulleted, and
+/// one line per block.
+pub fn markdown_to_plain(md: &str) -> String {
+    let mut out = String::new();
+    for event in Parser::new(md) {
+        match event {
+            Event::Text(text) | Event::Code(text) => out.push_str(&text),
+            Event::SoftBreak => out.push(' '),
+            Event::HardBreak | Event::Rule => out.push('\n'),
+            Event::Start(Tag::Item) => out.push_str("• "),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::TableRow
+                | TagEnd::TableHead,
+            ) => out.push('\n'),
+            Event::End(TagEnd::TableCell) => out.push(' '),
+            _ => {}
         }
-        if !live_urls.contains(key) && loaded_keys.contains(key) {
-            victims.insert(key.clone());
-            to_evict -= 1;
+    }
+    let mut lines: Vec<&str> = Vec::new();
+    for line in out.lines().map(str::trim_end) {
+        if !(line.is_empty() && lines.last().is_none_or(|l| l.is_empty())) {
+            lines.push(line);
         }
     }
-    victims
+    lines.join("\n").trim().to_string()
 }
 
-/// Check if a path points to a local .md file (not HTTP URL)
-pub fn is_local_md_file(path: &str) -> bool {
-    let path_lower = path.to_lowercase();
-    if !path_lower.ends_with(".md") {
-        return false;
+/// Horizontal step (world units) per heading level in [`markdown_to_board`].
+const MD_IMPORT_INDENT: f64 = 300.0;
+
+/// Vertical gap between consecutive imported sections.
+const MD_IMPORT_ROW_GAP: f64 = 40.0;
+
+/// Turn a markdown document into a board outline: one node per heading, whose
+/// text is the heading title followed by the markdown under it (rendered as
+/// markdown when there is any), and an edge from each heading to the
+/// subsections nested under it (a skipped level attaches to the nearest
+/// shallower heading). Text before the first heading becomes its own
+/// unconnected node, so a document with no headings imports as a single node.
+///
+/// Nodes are laid out as an indented outline from `origin` (top-left), one row
+/// per section in document order. Ids are `md-<n>` / `md-e<n>`; callers
+/// merging into an existing board remap them.
+pub fn markdown_to_board(md: &str, origin: (f64, f64)) -> Board {
+    // (depth, title, body range) per section, in document order.
+    let mut sections: Vec<(usize, String, std::ops::Range<usize>)> = Vec::new();
+    let mut preamble_end = md.len();
+    let mut heading: Option<(usize, String)> = None;
+    for (event, range) in Parser::new(md).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                match sections.last_mut() {
+                    Some((_, _, body)) => body.end = range.start,
+                    None => preamble_end = range.start,
+                }
+                heading = Some((level as usize, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, title)) = heading.as_mut() {
+                    title.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, title)) = heading.take() {
+                    sections.push((level, title, range.end..md.len()));
+                }
+            }
+            _ => {}
+        }
     }
-    path.starts_with('/') || path.starts_with("file://") || path.starts_with('~')
-}
 
-/// Extract the lowercased host portion of an `http(s)://` URL, or `None` if the
-/// URL is not http(s) or has no host. Pure string parsing — no allocation of a
-/// full URL parser, kept small so it is easy to unit-test.
-fn http_host(url: &str) -> Option<String> {
-    let rest = url
-        .strip_prefix("http://")
+    let mut board = Board::default();
+    let (mut y, mut count) = (origin.1, 0);
+    let mut push_node = |board: &mut Board, text: String, markdown: bool, depth: usize| {
+        count += 1;
+        let id = format!("md-{count}");
+        let (width, height) = Node::auto_size(&text);
+        let mut node = Node::new(
+            id.clone(),
+            origin.0 + depth as f64 * MD_IMPORT_INDENT,
+            y,
+            text,
+        );
+        node.width = width;
+        node.height = height;
+        node.render_markdown = markdown;
+        y += height + MD_IMPORT_ROW_GAP;
+        board.nodes.push(node);
+        id
+    };
+
+    let preamble = md[..preamble_end].trim();
+    if !preamble.is_empty() || sections.is_empty() {
+        push_node(&mut board, preamble.to_string(), false, 0);
+    }
+
+    // Open ancestors as (heading level, node id); popped once a heading at the
+    // same or a shallower level closes them.
+    let mut open: Vec<(usize, String)> = Vec::new();
+    for (level, title, body) in sections {
+        while open.last().is_some_and(|(l, _)| *l >= level) {
+            open.pop();
+        }
+        let body = md[body].trim();
+        let text = if body.is_empty() {
+            title.trim().to_string()
+        } else {
+            format!("{}\n\n{body}", title.trim())
+        };
+        let id = push_node(&mut board, text, !body.is_empty(), open.len());
+        if let Some((_, parent)) = open.last() {
+            let edge_id = format!("md-e{}", board.edges.len() + 1);
+            ops::connect(&mut board, edge_id, parent, &id);
+        }
+        open.push((level, id));
+    }
+    board
+}
+
+/// Tags whose content is never text: scripts, styles and document metadata.
+const HTML_SKIPPED_TAGS: &[&str] = &["script", "style", "head", "title", "template"];
+
+/// Convert pasted HTML into markdown for an `md` node. Covers headings,
+/// paragraphs and line breaks, bold/italic, inline and block code, links and
+/// nested ordered/unordered lists; any other tag is dropped but keeps its text,
+/// while scripts, styles and the document head are dropped whole. Whitespace
+/// collapses the way a browser lays it out, except inside `<pre>`.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    // Open lists, innermost last: `None` for `<ul>`, the next number for `<ol>`.
+    let mut lists: Vec<Option<u32>> = Vec::new();
+    // Targets of the open `<a>` tags; `None` for one without a usable href.
+    let mut links: Vec<Option<String>> = Vec::new();
+    let mut skipping: Option<String> = None;
+    let mut pre_depth = 0usize;
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let tag_end = rest.strip_prefix('<').and_then(|_| rest.find('>'));
+        let Some(tag_end) = tag_end else {
+            let text_end = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '<')
+                .map_or(rest.len(), |(i, _)| i);
+            if skipping.is_none() {
+                let text = decode_html_entities(&rest[..text_end]);
+                if pre_depth > 0 {
+                    out.push_str(&text);
+                } else {
+                    push_collapsed(&mut out, &text);
+                }
+            }
+            rest = &rest[text_end..];
+            continue;
+        };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if let Some(skipped) = &skipping {
+            if closing && name == *skipped {
+                skipping = None;
+            }
+            continue;
+        }
+        if !closing && !tag.ends_with('/') && HTML_SKIPPED_TAGS.contains(&name.as_str()) {
+            skipping = Some(name);
+            continue;
+        }
+        let heading = name
+            .strip_prefix('h')
+            .and_then(|level| level.parse::<usize>().ok())
+            .filter(|level| (1..=6).contains(level));
+        match (name.as_str(), closing) {
+            _ if heading.is_some() => {
+                end_line(&mut out, 2);
+                if !closing {
+                    out.push_str(&"#".repeat(heading.unwrap_or(1)));
+                    out.push(' ');
+                }
+            }
+            ("p" | "div", _) if lists.is_empty() => end_line(&mut out, 2),
+            ("br", _) => {
+                out.truncate(out.trim_end_matches(' ').len());
+                out.push('\n');
+            }
+            ("hr", _) => {
+                end_line(&mut out, 2);
+                out.push_str("---");
+                end_line(&mut out, 2);
+            }
+            ("strong" | "b", _) => push_marker(&mut out, "**", closing),
+            ("em" | "i", _) => push_marker(&mut out, "*", closing),
+            ("code", _) if pre_depth == 0 => push_marker(&mut out, "`", closing),
+            ("pre", false) => {
+                end_line(&mut out, 2);
+                out.push_str("```\n");
+                pre_depth += 1;
+            }
+            ("pre", true) => {
+                pre_depth = pre_depth.saturating_sub(1);
+                end_line(&mut out, 1);
+                out.push_str("```");
+                end_line(&mut out, 2);
+            }
+            ("a", false) => {
+                let href = html_attr(tag, "href").filter(|h| {
+                    !h.is_empty() && !h.to_ascii_lowercase().starts_with("javascript:")
+                });
+                if href.is_some() {
+                    out.push('[');
+                }
+                links.push(href);
+            }
+            ("a", true) => {
+                if let Some(Some(href)) = links.pop() {
+                    out.push_str(&format!("]({href})"));
+                }
+            }
+            ("ul", false) => {
+                end_line(&mut out, 1);
+                lists.push(None);
+            }
+            ("ol", false) => {
+                end_line(&mut out, 1);
+                let start = html_attr(tag, "start").and_then(|s| s.parse().ok());
+                lists.push(Some(start.unwrap_or(1)));
+            }
+            ("ul" | "ol", true) => {
+                lists.pop();
+                end_line(&mut out, if lists.is_empty() { 2 } else { 1 });
+            }
+            ("li", false) => {
+                end_line(&mut out, 1);
+                out.push_str(&"    ".repeat(lists.len().saturating_sub(1)));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        out.push_str(&format!("{number}. "));
+                        *number += 1;
+                    }
+                    _ => out.push_str("- "),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let trimmed: Vec<&str> = out.lines().map(str::trim_end).collect();
+    let mut markdown = trimmed.join("\n");
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+    markdown.trim().to_string()
+}
+
+/// End the current markdown line, leaving at least `newlines` line breaks
+/// (two for a paragraph gap). Nothing is added at the very start.
+fn end_line(out: &mut String, newlines: usize) {
+    out.truncate(out.trim_end_matches(' ').len());
+    if out.is_empty() {
+        return;
+    }
+    let have = out.len() - out.trim_end_matches('\n').len();
+    for _ in have..newlines {
+        out.push('\n');
+    }
+}
+
+/// Open or close an inline markdown span. A closing marker goes before any
+/// trailing space, since `**bold **` would not render as bold.
+fn push_marker(out: &mut String, marker: &str, closing: bool) {
+    if closing && out.ends_with(' ') {
+        out.pop();
+        out.push_str(marker);
+        out.push(' ');
+    } else {
+        out.push_str(marker);
+    }
+}
+
+/// Append HTML text with each whitespace run collapsed to one space, dropping
+/// it entirely at the start of a line.
+fn push_collapsed(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+    }
+}
+
+/// Value of attribute `name` in the inside of an HTML tag (`a href="…"`),
+/// entity-decoded.
+fn html_attr(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find(name) {
+        let start = from + found;
+        let after = lower[start + name.len()..].trim_start();
+        if lower[..start].ends_with(char::is_whitespace) && after.starts_with('=') {
+            // `lower` is byte-for-byte the same length as `tag`.
+            let value = tag[tag.len() - after.len() + 1..].trim_start();
+            let raw = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or(""),
+                _ => value.split(char::is_whitespace).next().unwrap_or(""),
+            };
+            return Some(decode_html_entities(raw));
+        }
+        from = start + name.len();
+    }
+    None
+}
+
+/// Decode the named entities common in copied HTML plus numeric ones; anything
+/// unrecognized is left as written.
+fn decode_html_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let c = match &rest[1..semi] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let number = entity.strip_prefix('#')?;
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => number.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, semi + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Toast text after loading a backup to compare against: counts of what the
+/// live board added, removed and changed relative to it.
+fn diff_summary(diff: &ops::BoardDiff) -> String {
+    if diff.is_empty() {
+        return "No changes since backup".to_string();
+    }
+    let changed_nodes = diff.moved_nodes.len() + diff.changed_nodes.len();
+    format!(
+        "vs backup: +{} \u{2212}{} ~{} nodes, +{} \u{2212}{} ~{} edges",
+        diff.added_nodes.len(),
+        diff.removed_nodes.len(),
+        changed_nodes,
+        diff.added_edges.len(),
+        diff.removed_edges.len(),
+        diff.changed_edges.len(),
+    )
+}
+
+/// How long the cursor rests on a node before its full-text tooltip shows.
+const TOOLTIP_DELAY_MS: u32 = 600;
+
+/// Tooltip body for `node`: the full text, flattened to plain text where it
+/// renders as markdown (a local `.md` link uses its loaded file), under the
+/// node's title when it has one. `None` for untitled images, untitled web links
+/// (the canvas `title` attribute already shows their URL) and empty text.
+fn tooltip_text(node: &Node, md_cache: &HashMap<String, LoadState<String>>) -> Option<String> {
+    let title = node.display_title();
+    let text = match node.node_type {
+        NodeType::Md => markdown_to_plain(&node.text),
+        NodeType::Link if is_md_link(&node.text) => match md_cache.get(&node.text) {
+            Some(LoadState::Loaded(content)) => markdown_to_plain(content),
+            _ => String::new(),
+        },
+        // A titled image/link shows its path or URL under the title.
+        NodeType::Image | NodeType::Link if title.is_some() => node.text.trim().to_string(),
+        NodeType::Image | NodeType::Link => return None,
+        _ if node.renders_markdown_inline() => markdown_to_plain(&node.text),
+        _ => node.text.trim().to_string(),
+    };
+    let text = match title {
+        Some(title) if text.is_empty() => title.to_string(),
+        Some(title) => format!("{title}\n\n{text}"),
+        None => text,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Most link-preview fetches in flight at once.
+const LINK_FETCH_CONCURRENCY: usize = 4;
+
+/// Slot accounting for a concurrency-limited job queue. Pure bookkeeping — the
+/// caller starts whatever [`enqueue`](Self::enqueue) or
+/// [`finish`](Self::finish) hands back — so it's testable without WASM.
+#[derive(Debug)]
+pub struct FetchQueue<T> {
+    limit: usize,
+    in_flight: usize,
+    pending: VecDeque<T>,
+}
+
+impl<T> FetchQueue<T> {
+    /// A queue running at most `limit` jobs at once (at least one).
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            in_flight: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Offer a job. Returns it back if a slot is free (the caller starts it now
+    /// and the slot counts as taken); otherwise it waits in FIFO order.
+    pub fn enqueue(&mut self, job: T) -> Option<T> {
+        if self.in_flight < self.limit {
+            self.in_flight += 1;
+            Some(job)
+        } else {
+            self.pending.push_back(job);
+            None
+        }
+    }
+
+    /// Mark one running job done. Returns the next waiting job, which inherits
+    /// the freed slot, if there is one.
+    pub fn finish(&mut self) -> Option<T> {
+        self.in_flight = self.in_flight.saturating_sub(1);
+        let next = self.pending.pop_front()?;
+        self.in_flight += 1;
+        Some(next)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// Everything a link-preview fetch needs to publish its result, plus the shared
+/// queue that gates how many run at once.
+#[derive(Clone)]
+struct LinkPreviewFetcher {
+    link_cache: LinkPreviewCache,
+    image_cache: ImageCache,
+    image_lru: Rc<RefCell<VecDeque<String>>>,
+    board: ReadSignal<Board>,
+    link_trigger: WriteSignal<u32>,
+    image_trigger: WriteSignal<u32>,
+    queue: Rc<RefCell<FetchQueue<String>>>,
+}
+
+impl LinkPreviewFetcher {
+    /// Fetch `url` in the background (its queue slot is already taken), then
+    /// hand the slot to the next queued URL.
+    fn spawn(self, url: String) {
+        spawn_local(async move {
+            self.fetch(url).await;
+            let next = self.queue.borrow_mut().finish();
+            if let Some(next) = next {
+                self.spawn(next);
+            }
+        });
+    }
+
+    /// Fetch one preview into the cache, kicking off its OG image load.
+    async fn fetch(&self, url: String) {
+        let trigger = self.link_trigger;
+        let img_trigger = self.image_trigger;
+        let board = self.board;
+        let args =
+            serde_wasm_bindgen::to_value(&FetchLinkPreviewArgs { url: url.clone() }).unwrap();
+        let result = invoke("fetch_link_preview", args).await;
+
+        if let Ok(preview) = serde_wasm_bindgen::from_value::<LinkPreview>(result) {
+            // If preview has an image, start loading it
+            if let Some(ref image_url) = preview.image {
+                let img_url = image_url.clone();
+                let needs_img_load = {
+                    let cache = self.image_cache.borrow();
+                    !cache.contains_key(&img_url)
+                };
+
+                if needs_img_load {
+                    self.image_cache
+                        .borrow_mut()
+                        .insert(img_url.clone(), LoadState::Loading);
+
+                    let img = HtmlImageElement::new().unwrap();
+                    let cache_for_onload = self.image_cache.clone();
+                    let lru_for_onload = self.image_lru.clone();
+                    let url_for_closure = img_url.clone();
+
+                    let onload = Closure::wrap(Box::new({
+                        let img = img.clone();
+                        let cache = cache_for_onload.clone();
+                        let lru = lru_for_onload.clone();
+                        let url = url_for_closure.clone();
+                        move || {
+                            // OG preview images aren't node.text, so none
+                            // are "live" for LRU purposes; the cap applies.
+                            let live_urls: HashSet<String> = board
+                                .get_untracked()
+                                .nodes
+                                .iter()
+                                .filter(|n| n.node_type == NodeType::Image)
+                                .map(|n| n.text.clone())
+                                .collect();
+                            insert_loaded_image(&cache, &lru, url.clone(), img.clone(), &live_urls);
+                            img_trigger.update(|n| *n = n.wrapping_add(1));
+                        }
+                    }) as Box<dyn Fn()>);
+
+                    let onerror = Closure::wrap(Box::new({
+                        let cache = cache_for_onload.clone();
+                        let url = url_for_closure.clone();
+                        move || {
+                            cache.borrow_mut().insert(url.clone(), LoadState::Failed);
+                            img_trigger.update(|n| *n = n.wrapping_add(1));
+                        }
+                    }) as Box<dyn Fn()>);
+
+                    img.set_onload(Some(onload.as_ref().unchecked_ref()));
+                    onload.forget();
+                    img.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+                    onerror.forget();
+                    img.set_src(&img_url);
+                }
+            }
+
+            self.link_cache
+                .borrow_mut()
+                .insert(url, LoadState::Loaded(preview));
+            trigger.update(|n| *n = n.wrapping_add(1));
+        } else {
+            // Preview fetch failed (backend error / SSRF block / bad data):
+            // mark Failed so the node falls back to the raw URL instead of
+            // spinning, and won't auto-refetch until evicted.
+            self.link_cache.borrow_mut().insert(url, LoadState::Failed);
+            trigger.update(|n| *n = n.wrapping_add(1));
+        }
+    }
+}
+
+/// Record a freshly decoded image in the cache and enforce the LRU bound.
+///
+/// `lru` is the insertion-order key log; the newly loaded `url` is appended (and
+/// any earlier occurrence removed so it isn't double-counted). If the number of
+/// `Loaded` entries exceeds [`IMAGE_CACHE_CAP`], the least-recently inserted keys
+/// that are **not** referenced by `live_urls` (currently on the board) are
+/// dropped. On-board images are always kept regardless of age so visible nodes
+/// never lose their picture.
+fn insert_loaded_image(
+    cache: &ImageCache,
+    lru: &Rc<RefCell<VecDeque<String>>>,
+    url: String,
+    img: HtmlImageElement,
+    live_urls: &HashSet<String>,
+) {
+    {
+        let mut order = lru.borrow_mut();
+        order.retain(|k| k != &url);
+        order.push_back(url.clone());
+    }
+    cache.borrow_mut().insert(url, LoadState::Loaded(img));
+
+    // Decide which keys to evict using the pure helper, then apply.
+    let loaded_keys: HashSet<String> = cache
+        .borrow()
+        .iter()
+        .filter(|(_, v)| matches!(v, LoadState::Loaded(_)))
+        .map(|(k, _)| k.clone())
+        .collect();
+
+    let victims = {
+        let order = lru.borrow();
+        plan_lru_eviction(&order, &loaded_keys, live_urls, IMAGE_CACHE_CAP)
+    };
+
+    if victims.is_empty() {
+        return;
+    }
+    {
+        let mut cache_mut = cache.borrow_mut();
+        for v in &victims {
+            cache_mut.remove(v);
+        }
+    }
+    lru.borrow_mut().retain(|k| !victims.contains(k));
+}
+
+/// Pure LRU eviction planner: given the insertion-order `order` log, the set of
+/// `loaded_keys` (entries actually holding a decoded image), the `live_urls`
+/// currently on the board (never evicted), and the soft `cap`, return the keys to
+/// drop — the oldest loaded, non-live entries — so the loaded count returns to
+/// `cap`. Side-effect-free and host-testable (no `HtmlImageElement` needed).
+fn plan_lru_eviction(
+    order: &VecDeque<String>,
+    loaded_keys: &HashSet<String>,
+    live_urls: &HashSet<String>,
+    cap: usize,
+) -> HashSet<String> {
+    let loaded_count = loaded_keys.len();
+    let mut victims = HashSet::new();
+    if loaded_count <= cap {
+        return victims;
+    }
+    let mut to_evict = loaded_count - cap;
+    for key in order {
+        if to_evict == 0 {
+            break;
+        }
+        if !live_urls.contains(key) && loaded_keys.contains(key) {
+            victims.insert(key.clone());
+            to_evict -= 1;
+        }
+    }
+    victims
+}
+
+/// Check if a path points to a local .md file (not HTTP URL)
+pub fn is_local_md_file(path: &str) -> bool {
+    let path_lower = path.to_lowercase();
+    if !path_lower.ends_with(".md") {
+        return false;
+    }
+    path.starts_with('/') || path.starts_with("file://") || path.starts_with('~')
+}
+
+/// Check if a URL points at a remote markdown file: `http(s)://` with a path
+/// ending in `.md` or `.markdown` (case-insensitive), ignoring any query string
+/// or fragment. Such link nodes are fetched through `fetch_markdown_url` and
+/// rendered like local `.md` links instead of getting a web preview.
+pub fn is_markdown_url(url: &str) -> bool {
+    let lower = url.trim().to_lowercase();
+    let Some(rest) = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    let path = rest.split(['?', '#']).next().unwrap_or_default();
+    // A bare host (`https://notes.md`) has no file to read.
+    path.contains('/') && (path.ends_with(".md") || path.ends_with(".markdown"))
+}
+
+/// Whether a link node's target renders as markdown: a local `.md` file or a
+/// remote markdown URL.
+pub fn is_md_link(target: &str) -> bool {
+    is_local_md_file(target) || is_markdown_url(target)
+}
+
+/// Check if an image source is a GIF, so it can be shown through an animated
+/// `<img>` overlay instead of the canvas (which only ever paints the first
+/// frame). Recognizes a `.gif` path/URL (ignoring any query or fragment), a
+/// `data:image/gif` URL, and a base64 payload starting with the GIF magic bytes
+/// (`GIF8` encodes as `R0lGOD`) in case the data URL's mime type is generic.
+pub fn is_gif(src: &str) -> bool {
+    if let Some(data) = src.strip_prefix("data:") {
+        if data.to_ascii_lowercase().starts_with("image/gif") {
+            return true;
+        }
+        return data
+            .split_once("base64,")
+            .is_some_and(|(_, payload)| payload.starts_with("R0lGOD"));
+    }
+    let path = src.split(['?', '#']).next().unwrap_or(src);
+    path.to_ascii_lowercase().ends_with(".gif")
+}
+
+/// Extract the lowercased host portion of an `http(s)://` URL, or `None` if the
+/// URL is not http(s) or has no host. Pure string parsing — no allocation of a
+/// full URL parser, kept small so it is easy to unit-test.
+fn http_host(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("http://")
         .or_else(|| url.strip_prefix("https://"))?;
     // Host ends at the first '/', '?', '#', or end of string. Strip userinfo
     // ("user:pass@host") and the port (":443") if present.
@@ -634,6 +1851,35 @@ fn http_host(url: &str) -> Option<String> {
     }
 }
 
+/// Whether `s` (trimmed) is a single bare `http(s)://` URL with a plausible
+/// host: dotted labels of letters, digits, and inner hyphens, or `localhost` /
+/// an IPv6 literal. Sentences, file paths, and scheme-less domains don't
+/// qualify — this decides whether a committed text node auto-converts to a
+/// `link` node, so it errs toward leaving text alone.
+pub fn looks_like_url(s: &str) -> bool {
+    let s = s.trim();
+    if s.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let Some(host) = http_host(s) else {
+        return false;
+    };
+    if host.starts_with('[') {
+        return host.len() > 2 && host.ends_with(']');
+    }
+    if host == "localhost" {
+        return true;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|l| {
+            !l.is_empty()
+                && !l.starts_with('-')
+                && !l.ends_with('-')
+                && l.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
 /// Decide whether `url` points at a *clearly public* host that is safe to
 /// auto-fetch a link preview for on board load. This is a conservative
 /// allowlist policy: we only auto-fetch hostnames that look like registrable
@@ -710,29 +1956,89 @@ fn point_near_line(px: f64, py: f64, x1: f64, y1: f64, x2: f64, y2: f64, thresho
     dist < threshold
 }
 
-/// Case-insensitive substring match of `query` against a node's searchable text:
-/// its body text, any of its tags, and its status. An empty/whitespace-only query
-/// matches nothing (so a blank search box doesn't select every node).
-///
-/// Pure and allocation-light so the search overlay can filter a 100+ node board on
-/// every keystroke without touching the DOM or signals.
-pub fn node_matches_query(node: &Node, query: &str) -> bool {
-    let q = query.trim().to_lowercase();
-    if q.is_empty() {
-        return false;
-    }
-    if node.text.to_lowercase().contains(&q) {
-        return true;
-    }
-    if node.tags.iter().any(|t| t.to_lowercase().contains(&q)) {
-        return true;
-    }
-    if let Some(status) = &node.status {
-        if status.to_lowercase().contains(&q) {
-            return true;
+/// Straight segments a bent edge is split into for hit-testing.
+const EDGE_CURVE_SAMPLES: usize = 16;
+
+/// Whether `(px, py)` lies within `threshold` of the curve traced by `point_at`
+/// over `t` in `0..=1`, approximated by [`EDGE_CURVE_SAMPLES`] straight
+/// segments.
+fn point_near_sampled(
+    (px, py): (f64, f64),
+    point_at: impl Fn(f64) -> (f64, f64),
+    threshold: f64,
+) -> bool {
+    let points: Vec<(f64, f64)> = (0..=EDGE_CURVE_SAMPLES)
+        .map(|i| point_at(i as f64 / EDGE_CURVE_SAMPLES as f64))
+        .collect();
+    points
+        .windows(2)
+        .any(|w| point_near_line(px, py, w[0].0, w[0].1, w[1].0, w[1].1, threshold))
+}
+
+/// Whether `(px, py)` lies within `threshold` of the quadratic curve `from` →
+/// `to` with control point `ctrl`.
+fn point_near_curve(
+    point: (f64, f64),
+    from: (f64, f64),
+    ctrl: (f64, f64),
+    to: (f64, f64),
+    threshold: f64,
+) -> bool {
+    point_near_sampled(point, |t| quad_point(from, ctrl, to, t), threshold)
+}
+
+/// Whether `(px, py)` lies within `threshold` of the cubic bezier `curve`
+/// (`[start, ctrl1, ctrl2, end]`, as from [`edge_bezier`]).
+fn point_near_bezier(point: (f64, f64), curve: &[(f64, f64); 4], threshold: f64) -> bool {
+    point_near_sampled(point, |t| cubic_point(curve, t), threshold)
+}
+
+/// First edge whose center-to-center line (or curve, for a bent parallel edge
+/// or in [`EdgeStyle::Curved`]) passes within `threshold` (world units) of
+/// `(x, y)`, skipping edges that touch `skip_node`. Edges with a missing
+/// endpoint never match.
+#[allow(clippy::too_many_arguments)]
+fn edge_near_point<'a>(
+    board: &'a Board,
+    x: f64,
+    y: f64,
+    threshold: f64,
+    skip_node: Option<&str>,
+    hidden_types: &HashSet<String>,
+    collapsed: &HashSet<String>,
+    style: EdgeStyle,
+) -> Option<&'a Edge> {
+    // Edges to a filtered-out or collapsed-away node are hidden, so they can't
+    // be hit either.
+    let node_map: HashMap<&str, &Node> = shown_nodes(board, hidden_types, collapsed)
+        .into_iter()
+        .map(|n| (n.id.as_str(), n))
+        .collect();
+    let bends = parallel_edge_bends(board);
+    board.edges.iter().find(|edge| {
+        if skip_node.is_some_and(|id| edge.from_node == id || edge.to_node == id) {
+            return false;
         }
-    }
-    false
+        let from = node_map.get(edge.from_node.as_str());
+        let to = node_map.get(edge.to_node.as_str());
+        if let (Some(from), Some(to)) = (from, to) {
+            if style == EdgeStyle::Curved {
+                let bend = bends.get(edge.id.as_str()).copied().unwrap_or(0.0);
+                let curve = edge_bezier(from, to, edge.ports(), bend);
+                return point_near_bezier((x, y), &curve, threshold);
+            }
+            let ((from_x, from_y), (to_x, to_y)) = edge_anchors(from, to, edge.ports());
+            match bends.get(edge.id.as_str()) {
+                Some(&bend) if bend != 0.0 => {
+                    let ctrl = edge_control_point((from_x, from_y), (to_x, to_y), bend);
+                    point_near_curve((x, y), (from_x, from_y), ctrl, (to_x, to_y), threshold)
+                }
+                _ => point_near_line(x, y, from_x, from_y, to_x, to_y, threshold),
+            }
+        } else {
+            false
+        }
+    })
 }
 
 // `nodes_bounding_box` and `fit_camera` were relocated to `brainstorm-types` so
@@ -741,29 +2047,205 @@ pub fn node_matches_query(node: &Node, query: &str) -> bool {
 // keeps resolving for `src/components/minimap.rs` and the fit-to-view call site.
 pub use brainstorm_types::{fit_camera, nodes_bounding_box};
 
-/// Documented canvas grid spacing in world units. Node positions snap to this on
-/// drag release so layouts stay aligned (matches the 50px grid in CLAUDE.md).
+/// Padding left around a node focused with Shift+F, as a fraction of the
+/// node's own size on each side (fit-to-view uses 0.1).
+const FOCUS_NODE_MARGIN: f64 = 0.04;
+
+/// Camera for Shift+F "read mode": `node` zoomed to fill most of a
+/// `canvas_w` x `canvas_h` viewport and centered. Zoom stays within
+/// [`fit_camera`]'s `0.1..=5.0`, so a tiny node stops at the maximum zoom.
+pub fn focus_node_camera(node: &Node, canvas_w: f64, canvas_h: f64) -> Camera {
+    let bbox = (node.x, node.y, node.x + node.width, node.y + node.height);
+    fit_camera(bbox, canvas_w, canvas_h, FOCUS_NODE_MARGIN)
+}
+
+/// Documented canvas grid spacing in world units: the spacing `draw_grid` draws,
+/// and what drags and resizes snap to (matches the 50px grid in CLAUDE.md).
 pub const GRID_SIZE: f64 = 50.0;
 
-/// Read `window.devicePixelRatio`, clamped to a sane `0.5..=4.0` range. Falls back
-/// to `1.0` when the window or property is unavailable (e.g. the test harness).
-fn device_pixel_ratio() -> f64 {
-    web_sys::window()
-        .map(|w| w.device_pixel_ratio())
-        .filter(|r| r.is_finite() && *r > 0.0)
-        .unwrap_or(1.0)
-        .clamp(0.5, 4.0)
+/// Read `window.devicePixelRatio`, clamped to a sane `0.5..=4.0` range. Falls back
+/// to `1.0` when the window or property is unavailable (e.g. the test harness).
+fn device_pixel_ratio() -> f64 {
+    web_sys::window()
+        .map(|w| w.device_pixel_ratio())
+        .filter(|r| r.is_finite() && *r > 0.0)
+        .unwrap_or(1.0)
+        .clamp(0.5, 4.0)
+}
+
+/// Round a world coordinate to the nearest multiple of `grid`. A non-positive or
+/// non-finite grid is a no-op so a bad constant can't NaN the layout. Pure so the
+/// snap behavior is unit-testable without a canvas.
+#[must_use]
+pub fn snap_to_grid(v: f64, grid: f64) -> f64 {
+    if !grid.is_finite() || grid <= 0.0 || !v.is_finite() {
+        return v;
+    }
+    (v / grid).round() * grid
+}
+
+/// Adjust a resize pointer delta so the corner `handle` drags lands on the
+/// `grid`: the opposite corner stays put, so both the moved edges end up on
+/// grid lines. `rect` is the `(x, y, width, height)` at the start of the resize.
+#[must_use]
+pub fn snap_resize_delta(
+    (x, y, width, height): (f64, f64, f64, f64),
+    handle: ResizeHandle,
+    (dx, dy): (f64, f64),
+    grid: f64,
+) -> (f64, f64) {
+    let corner_x = match handle {
+        ResizeHandle::TopLeft | ResizeHandle::BottomLeft => x,
+        ResizeHandle::TopRight | ResizeHandle::BottomRight => x + width,
+    };
+    let corner_y = match handle {
+        ResizeHandle::TopLeft | ResizeHandle::TopRight => y,
+        ResizeHandle::BottomLeft | ResizeHandle::BottomRight => y + height,
+    };
+    (
+        snap_to_grid(corner_x + dx, grid) - corner_x,
+        snap_to_grid(corner_y + dy, grid) - corner_y,
+    )
+}
+
+/// Screen-space distance (CSS px) within which a dragged node's edge or center
+/// snaps to a neighbor's matching line. Divided by zoom before use so the feel is
+/// constant at every zoom level.
+pub const ALIGN_SNAP_PX: f64 = 6.0;
+
+/// World-unit distance within which a double-click lands "next to" the last
+/// created node and gets stacked under it (see [`aligned_new_position`]).
+const NEW_NODE_COLUMN_SNAP: f64 = 120.0;
+
+/// World-unit gap between a column-stacked new node and the one above it.
+const NEW_NODE_ROW_GAP: f64 = 20.0;
+
+/// Top-left for a node created by double-clicking at world point `cursor`.
+/// Normally the default-sized node is centered on the cursor. When the cursor
+/// is within `threshold` (on both axes) of where the next row under `last` (the
+/// previously created node) would be centered, the node instead takes `last`'s
+/// x and sits one row below it, so quick double-clicks build a tidy column.
+pub fn aligned_new_position(last: Option<&Node>, cursor: (f64, f64), threshold: f64) -> (f64, f64) {
+    let (width, height) = (DEFAULT_NODE_WIDTH, DEFAULT_NODE_HEIGHT);
+    if let Some(last) = last {
+        let below = last.y + last.height + NEW_NODE_ROW_GAP;
+        let near_x = (cursor.0 - (last.x + last.width / 2.0)).abs() <= threshold;
+        let near_y = (cursor.1 - (below + height / 2.0)).abs() <= threshold;
+        if near_x && near_y {
+            return (last.x, below);
+        }
+    }
+    (cursor.0 - width / 2.0, cursor.1 - height / 2.0)
+}
+
+/// Screen-space distance (CSS px) from an edge's line within which an Alt-dropped
+/// node's center splices it into that edge. Looser than the 10px edge-click
+/// tolerance since the node body hides the line under the pointer.
+const EDGE_DROP_PX: f64 = 16.0;
+
+/// Minimum screen-space distance (CSS px) an edge drag must travel before being
+/// released over empty canvas creates a connected node there. A shorter flick is
+/// treated as a slip and discarded.
+const EDGE_NEW_NODE_MIN_PX: f64 = 24.0;
+
+/// Seed Shift+L hands to [`ops::layout_positions`]. A constant rather than a
+/// fresh random value, so the same board always gets the same arrangement.
+const AUTO_LAYOUT_SEED: u64 = 0;
+
+/// World-unit size change per Ctrl+Alt+Arrow press; Shift uses the large step.
+const KEY_RESIZE_STEP: f64 = 10.0;
+const KEY_RESIZE_STEP_LARGE: f64 = 50.0;
+
+/// Whether a canvas keydown edits the board (and so is ignored while the board
+/// is locked read-only). `cmd` is Cmd/Ctrl held; `alt` is Alt held (Alt+Arrow
+/// aligns, Ctrl+Alt+Arrow resizes). Mirrors the editing arms of the canvas
+/// keydown handler; copy, select-all, search, fit and zoom stay available.
+pub fn is_mutating_shortcut(key: &str, cmd: bool, alt: bool) -> bool {
+    match key {
+        "Backspace" | "Delete" | "t" | "T" => true,
+        "z" | "v" | "d" | "g" | "G" => cmd,
+        "s" | "S" | "p" | "P" | "m" | "M" | "c" | "C" | "L" | "]" | "[" => !cmd,
+        "ArrowLeft" | "ArrowRight" | "ArrowUp" | "ArrowDown" => alt,
+        _ => false,
+    }
+}
+
+/// The alignment an Alt+Arrow press applies to the selection: the arrow's side
+/// of the selection bounds, or with Shift the center line along that axis.
+pub fn arrow_alignment(key: &str, shift: bool) -> Option<Alignment> {
+    Some(match (key, shift) {
+        ("ArrowLeft", false) => Alignment::Left,
+        ("ArrowRight", false) => Alignment::Right,
+        ("ArrowUp", false) => Alignment::Top,
+        ("ArrowDown", false) => Alignment::Bottom,
+        ("ArrowLeft" | "ArrowRight", true) => Alignment::CenterX,
+        ("ArrowUp" | "ArrowDown", true) => Alignment::CenterY,
+        _ => return None,
+    })
+}
+
+/// An alignment guide produced by [`alignment_snap`], in world coordinates: a
+/// vertical line at `x` or a horizontal line at `y`. `render_board` draws these
+/// across the whole viewport while a drag is snapped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Guide {
+    Vertical(f64),
+    Horizontal(f64),
+}
+
+/// Smallest correction that moves one of `moving`'s three lines onto one of the
+/// `targets` lines, if any pair is within `threshold`. Returns `(delta, target)`.
+fn nearest_alignment(moving: [f64; 3], targets: &[f64], threshold: f64) -> Option<(f64, f64)> {
+    let mut best: Option<(f64, f64)> = None;
+    for m in moving {
+        for &t in targets {
+            let delta = t - m;
+            if delta.abs() <= threshold && best.is_none_or(|(d, _)| delta.abs() < d.abs()) {
+                best = Some((delta, t));
+            }
+        }
+    }
+    best
 }
 
-/// Round a world coordinate to the nearest multiple of `grid`. A non-positive or
-/// non-finite grid is a no-op so a bad constant can't NaN the layout. Pure so the
-/// snap behavior is unit-testable without a canvas.
+/// Design-tool style alignment snapping. `moving` and every entry of `others` are
+/// world-space `(min_x, min_y, max_x, max_y)` rects. On each axis, the moving
+/// rect's edges and center are compared to every other rect's edges and center;
+/// the closest pair within `threshold` (world units) wins and yields a correction
+/// plus a [`Guide`] at the aligned line. Returns `(dx, dy, guides)` — `0.0` and no
+/// guide on an axis with nothing in range. Pure so the snap math is unit-tested.
 #[must_use]
-pub fn snap_to_grid(v: f64, grid: f64) -> f64 {
-    if !grid.is_finite() || grid <= 0.0 || !v.is_finite() {
-        return v;
-    }
-    (v / grid).round() * grid
+pub fn alignment_snap(
+    moving: (f64, f64, f64, f64),
+    others: &[(f64, f64, f64, f64)],
+    threshold: f64,
+) -> (f64, f64, Vec<Guide>) {
+    let (min_x, min_y, max_x, max_y) = moving;
+    let xs: Vec<f64> = others
+        .iter()
+        .flat_map(|r| [r.0, (r.0 + r.2) / 2.0, r.2])
+        .collect();
+    let ys: Vec<f64> = others
+        .iter()
+        .flat_map(|r| [r.1, (r.1 + r.3) / 2.0, r.3])
+        .collect();
+
+    let mut guides = Vec::new();
+    let dx = match nearest_alignment([min_x, (min_x + max_x) / 2.0, max_x], &xs, threshold) {
+        Some((delta, x)) => {
+            guides.push(Guide::Vertical(x));
+            delta
+        }
+        None => 0.0,
+    };
+    let dy = match nearest_alignment([min_y, (min_y + max_y) / 2.0, max_y], &ys, threshold) {
+        Some((delta, y)) => {
+            guides.push(Guide::Horizontal(y));
+            delta
+        }
+        None => 0.0,
+    };
+    (dx, dy, guides)
 }
 
 /// Compute a uniform fit transform mapping a world-space `bbox` into a `mw` x `mh`
@@ -789,6 +2271,131 @@ pub fn minimap_transform(
     (scale, off_x, off_y)
 }
 
+/// The nodes at least partly inside a `view_w` x `view_h` (CSS px) viewport seen
+/// through `camera`, in board order.
+pub fn nodes_in_viewport<'a>(
+    board: &'a Board,
+    camera: &Camera,
+    view_w: f64,
+    view_h: f64,
+) -> Vec<&'a Node> {
+    let (min_x, min_y) = camera.screen_to_world(0.0, 0.0);
+    let (max_x, max_y) = camera.screen_to_world(view_w, view_h);
+    board
+        .nodes
+        .iter()
+        .filter(|n| intersects_box(n, min_x, min_y, max_x, max_y))
+        .collect()
+}
+
+/// Whether the view has drifted off a non-empty board so that no node is on
+/// screen — the condition that brings up the centered overview. An unmeasured
+/// (zero-sized) viewport never counts.
+pub fn lost_in_void(board: &Board, camera: &Camera, view_w: f64, view_h: f64) -> bool {
+    view_w > 0.0
+        && view_h > 0.0
+        && !board.nodes.is_empty()
+        && nodes_in_viewport(board, camera, view_w, view_h).is_empty()
+}
+
+/// Pan-inertia friction, per millisecond: velocity decays by `e^(-friction * dt)`,
+/// so 0.004 halves the glide speed roughly every 170ms.
+const PAN_FRICTION: f64 = 0.004;
+/// Glide speed (screen px/ms) below which inertia stops outright.
+const INERTIA_MIN_SPEED: f64 = 0.02;
+/// A release more than this long after the last pointer move means the user had
+/// already stopped, so no glide starts.
+const INERTIA_RELEASE_WINDOW_MS: f64 = 80.0;
+
+/// One step of pan inertia: decay `velocity` (screen px/ms) exponentially over
+/// `dt` ms with the given `friction`. Direction is preserved (both axes scale by
+/// the same factor); once the speed drops under [`INERTIA_MIN_SPEED`] the result
+/// snaps to exactly zero so the glide loop has a clean stop condition.
+#[must_use]
+pub fn step_inertia(velocity: (f64, f64), dt: f64, friction: f64) -> (f64, f64) {
+    let factor = (-friction * dt.max(0.0)).exp();
+    let (vx, vy) = (velocity.0 * factor, velocity.1 * factor);
+    if !(vx.is_finite() && vy.is_finite()) || vx.hypot(vy) < INERTIA_MIN_SPEED {
+        (0.0, 0.0)
+    } else {
+        (vx, vy)
+    }
+}
+
+/// Duration of an animated camera jump (fit, focus, search, minimap).
+const CAMERA_FLIGHT_MS: f64 = 250.0;
+
+/// Camera `t` of the way (`0..=1`, clamped) through a jump from `from` to `to`,
+/// eased in and out (cubic) so the move starts and lands gently. Position is
+/// interpolated linearly in the eased time and zoom geometrically, so zooming
+/// from 0.5 to 2 passes 1 halfway; zoom stays within the app's `0.1..=5.0`.
+pub fn ease_camera(from: &Camera, to: &Camera, t: f64) -> Camera {
+    let t = if t.is_nan() { 1.0 } else { t.clamp(0.0, 1.0) };
+    let eased = if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    };
+    let lerp = |a: f64, b: f64| a + (b - a) * eased;
+    let (zoom_from, zoom_to) = (from.zoom.clamp(0.1, 5.0), to.zoom.clamp(0.1, 5.0));
+    // Exact at both ends, so a finished flight lands on the target precisely.
+    let zoom = if eased >= 1.0 {
+        zoom_to
+    } else if eased <= 0.0 {
+        zoom_from
+    } else {
+        (zoom_from.ln() + (zoom_to.ln() - zoom_from.ln()) * eased)
+            .exp()
+            .clamp(0.1, 5.0)
+    };
+    Camera {
+        x: lerp(from.x, to.x),
+        y: lerp(from.y, to.y),
+        zoom,
+    }
+}
+
+/// How long after a drag, resize, pan or box select ends that a double-click on
+/// empty space doesn't create a node, so the release plus a quick click can't
+/// leave a stray one.
+const DOUBLECLICK_SUPPRESS_MS: f64 = 300.0;
+/// Pointer travel (screen px) below which a press-and-release is still a click
+/// rather than a drag, resize, pan or box select, for [`DOUBLECLICK_SUPPRESS_MS`].
+const GESTURE_SLOP_PX: f64 = 4.0;
+
+/// Whether a double-click at `now_ms` comes too soon after the last pointer
+/// gesture that moved something (ended at `last_gesture_end_ms`) to be meant.
+/// A gesture ending exactly `window_ms` ago no longer suppresses it.
+pub fn should_suppress_doubleclick(last_gesture_end_ms: f64, now_ms: f64, window_ms: f64) -> bool {
+    let elapsed = now_ms - last_gesture_end_ms;
+    (0.0..window_ms).contains(&elapsed)
+}
+
+/// Wheel delta (pixels) that zooms by one [`WHEEL_ZOOM_STEP`] at sensitivity 1.
+/// A typical mouse notch reports about this much.
+const WHEEL_NOTCH_PX: f64 = 100.0;
+/// Zoom multiplier for one notch at sensitivity 1.
+const WHEEL_ZOOM_STEP: f64 = 1.1;
+/// Largest zoom change a single wheel event may make (either direction), so a
+/// big trackpad or high-resolution wheel delta can't jump the zoom.
+const MAX_WHEEL_ZOOM_STEP: f64 = 1.5;
+/// Pixels per line for wheel events reported in lines (`DOM_DELTA_LINE`).
+const WHEEL_LINE_PX: f64 = 33.0;
+
+/// Zoom multiplier for a wheel event with vertical delta `delta_y` (pixels;
+/// negative = scroll up = zoom in). Proportional to the delta and scaled by
+/// `sensitivity`, with a notch at sensitivity 1 giving the classic ×1.1 / ÷1.1,
+/// and clamped per event to [`MAX_WHEEL_ZOOM_STEP`].
+#[must_use]
+pub fn zoom_factor(delta_y: f64, sensitivity: f64) -> f64 {
+    let max = MAX_WHEEL_ZOOM_STEP.ln();
+    let step = -delta_y / WHEEL_NOTCH_PX * sensitivity.max(0.0) * WHEEL_ZOOM_STEP.ln();
+    if !step.is_finite() {
+        return 1.0;
+    }
+    step.clamp(-max, max).exp()
+}
+
 /// Serializable camera snapshot persisted to localStorage so a reopened board
 /// restores its last pan/zoom (F105). Kept separate from [`Camera`] (which is not
 /// `Serialize`) to avoid widening the shared type's derives.
@@ -823,6 +2430,36 @@ impl CameraPersist {
     }
 }
 
+/// Per-node scroll offsets for the markdown modal, so reopening a long note in
+/// the same session lands where the reader left off. Session-only: nothing is
+/// persisted, so an app restart starts every note at the top.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScrollMemory {
+    offsets: HashMap<String, f64>,
+}
+
+impl ScrollMemory {
+    /// Remember `top` for `node_id`. Scrolling back to the top (or a bogus,
+    /// non-finite offset) forgets the entry instead of storing a zero.
+    pub fn set(&mut self, node_id: &str, top: f64) {
+        if top.is_finite() && top > 0.0 {
+            self.offsets.insert(node_id.to_string(), top);
+        } else {
+            self.offsets.remove(node_id);
+        }
+    }
+
+    /// The remembered offset for `node_id`, if any.
+    pub fn get(&self, node_id: &str) -> Option<f64> {
+        self.offsets.get(node_id).copied()
+    }
+
+    /// Forget the offsets of nodes no longer on the board.
+    pub fn retain(&mut self, live_ids: &HashSet<&str>) {
+        self.offsets.retain(|id, _| live_ids.contains(id.as_str()));
+    }
+}
+
 /// Board data + viewport + persistence. The shared "document" surface that every
 /// component reading or mutating the canvas needs. Split out of the former
 /// monolithic `BoardCtx` (P3.2 / F32) so a component pulls in only the slice it
@@ -839,10 +2476,23 @@ pub struct BoardDataCtx {
     /// True while a debounced local write is queued or in flight. The file watcher
     /// can check this to avoid reloading over the user's own pending edits.
     pub local_edit_pending: RwSignal<bool>,
+    /// Saved / dirty / saving / failed, for the HUD's save indicator.
+    pub save_state: RwSignal<SaveState>,
     /// Main canvas display size in CSS pixels `(width, height)`, updated each
     /// rendered frame. The minimap reads this to draw the viewport rectangle and
     /// to recenter the camera on click. `(0, 0)` until the first frame lays out.
     pub viewport_size: ReadSignal<(f64, f64)>,
+    /// Animated camera jumps; use instead of `set_camera` when moving the view
+    /// somewhere on the user's behalf.
+    pub fly_camera: FlyCamera,
+    /// Node types filtered out of the canvas (drawing and hit-testing); see
+    /// [`crate::canvas::visible_nodes_by_type`].
+    pub hidden_types: RwSignal<HashSet<String>>,
+    /// Tags toggled on in the tag filter bar; nodes with none of them draw
+    /// faded (see [`crate::canvas::tag_dimmed`]). Empty means no filter.
+    pub active_tags: RwSignal<HashSet<String>>,
+    /// What collapsed nodes hide, recomputed only when the board changes.
+    pub collapse: Memo<CollapseView>,
 }
 
 /// Selection state: which nodes/edges are selected, plus the search overlay
@@ -865,6 +2515,9 @@ pub struct SelectionCtx {
 pub struct EditingCtx {
     pub editing_node: ReadSignal<Option<String>>,
     pub set_editing_node: WriteSignal<Option<String>>,
+    /// Edge whose label the inline label editor is open on (double-click an
+    /// edge).
+    pub editing_edge: RwSignal<Option<String>>,
     pub modal_image: ReadSignal<Option<String>>,
     pub set_modal_image: WriteSignal<Option<String>>,
     pub modal_md: ReadSignal<Option<(String, bool)>>,
@@ -872,14 +2525,41 @@ pub struct EditingCtx {
     pub md_edit_text: ReadSignal<String>,
     pub set_md_edit_text: WriteSignal<String>,
     pub md_file_cache: ReadSignal<HashMap<String, LoadState<String>>>,
+    /// Where each note was scrolled to when the markdown modal last showed it.
+    pub md_scroll: StoredValue<ScrollMemory>,
+    /// Loaded GIF image nodes, keyed by node URL, mapped to the resolved `<img>`
+    /// source. Read by the GIF overlay so those nodes animate.
+    pub gif_sources: ReadSignal<HashMap<String, String>>,
     /// Most recent board.json parse error (if any). Set on a failed load so the
     /// error banner can surface it; cleared on the next successful load.
     pub load_error: RwSignal<Option<String>>,
+    /// Schema version of the loaded board when it's newer than this build
+    /// supports; the error banner shows a dismissible warning while set.
+    pub newer_version: RwSignal<Option<u32>>,
+    /// Why the last save failed (a full browser localStorage, or the desktop
+    /// backend refusing the write), if it did; the error banner shows it until a
+    /// save succeeds.
+    pub save_error: RwSignal<Option<String>>,
+    /// Transient confirmation shown by the toast; set via [`show_toast`].
+    pub toast: RwSignal<Option<String>>,
+    /// Open node context menu: screen position and the node it was opened on.
+    pub context_menu: RwSignal<Option<(f64, f64, String)>>,
+    /// Hover tooltip: screen position (by the cursor) and the node's full text.
+    pub tooltip: RwSignal<Option<(f64, f64, String)>>,
     /// Single mutation entry point. Editor components dispatch text edits through
     /// this so each commit snapshots undo history (fixes undo dropping typed text).
     pub dispatch: Dispatcher,
 }
 
+/// User preferences (see [`crate::settings`]) and the HUD panel that edits them.
+#[derive(Clone, Copy)]
+pub struct SettingsCtx {
+    pub settings: ReadSignal<Settings>,
+    pub set_settings: WriteSignal<Settings>,
+    pub settings_open: ReadSignal<bool>,
+    pub set_settings_open: WriteSignal<bool>,
+}
+
 /// Resolve the canvas-relative screen position of a pointer event, or `None` if
 /// the canvas isn't mounted yet. `let-else` keeps the handlers branch-free and
 /// removes the `canvas_ref.get().unwrap()` panic sites (P3.2 / F8). Accepts any
@@ -909,6 +2589,165 @@ fn event_world_pos(
     Some(camera.screen_to_world(canvas_x, canvas_y))
 }
 
+/// Glide the camera after a pan release. Each animation frame moves the view by
+/// `velocity * dt` (screen px, converted through the live zoom) and decays the
+/// velocity with [`step_inertia`]. The glide ends when it comes to rest — calling
+/// `on_rest` so the final viewport gets persisted — or silently as soon as
+/// `generation` moves past `run`, i.e. the user started another interaction.
+fn run_pan_inertia(
+    velocity: (f64, f64),
+    camera: ReadSignal<Camera>,
+    set_camera: WriteSignal<Camera>,
+    generation: StoredValue<u32>,
+    run: u32,
+    on_rest: impl Fn() + 'static,
+) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let frame = Rc::new(RefCell::new(None::<Closure<dyn FnMut(f64)>>));
+    let frame_for_loop = frame.clone();
+    let window_for_loop = window.clone();
+    let mut velocity = velocity;
+    let mut last_ts: Option<f64> = None;
+    *frame.borrow_mut() = Some(Closure::wrap(Box::new(move |ts: f64| {
+        if generation.get_value() != run {
+            let _ = frame_for_loop.borrow_mut().take();
+            return;
+        }
+        let dt = last_ts.map_or(16.0, |prev| (ts - prev).clamp(0.0, 64.0));
+        last_ts = Some(ts);
+        let zoom = camera.get_untracked().zoom;
+        set_camera.update(|c| {
+            c.x -= velocity.0 * dt / zoom;
+            c.y -= velocity.1 * dt / zoom;
+        });
+        velocity = step_inertia(velocity, dt, PAN_FRICTION);
+        if velocity == (0.0, 0.0) {
+            on_rest();
+            let _ = frame_for_loop.borrow_mut().take();
+            return;
+        }
+        if let Some(cb) = frame_for_loop.borrow().as_ref() {
+            let _ = window_for_loop.request_animation_frame(cb.as_ref().unchecked_ref());
+        }
+    }) as Box<dyn FnMut(f64)>));
+    let first = frame.borrow();
+    if let Some(cb) = first.as_ref() {
+        let _ = window.request_animation_frame(cb.as_ref().unchecked_ref());
+    }
+}
+
+/// Animate the camera from `from` to `to` over [`CAMERA_FLIGHT_MS`] with
+/// [`ease_camera`], one step per animation frame, calling `on_done` once it
+/// lands. Like [`run_pan_inertia`] it stops silently as soon as `generation`
+/// moves past `run`, so grabbing the canvas mid-flight takes over at once.
+fn run_camera_flight(
+    from: Camera,
+    to: Camera,
+    set_camera: WriteSignal<Camera>,
+    generation: StoredValue<u32>,
+    run: u32,
+    on_done: impl Fn() + 'static,
+) {
+    let Some(window) = web_sys::window() else {
+        set_camera.set(to);
+        on_done();
+        return;
+    };
+    let frame = Rc::new(RefCell::new(None::<Closure<dyn FnMut(f64)>>));
+    let frame_for_loop = frame.clone();
+    let window_for_loop = window.clone();
+    let mut start_ts: Option<f64> = None;
+    *frame.borrow_mut() = Some(Closure::wrap(Box::new(move |ts: f64| {
+        if generation.get_value() != run {
+            let _ = frame_for_loop.borrow_mut().take();
+            return;
+        }
+        let t = (ts - *start_ts.get_or_insert(ts)) / CAMERA_FLIGHT_MS;
+        set_camera.set(ease_camera(&from, &to, t));
+        if t >= 1.0 {
+            on_done();
+            let _ = frame_for_loop.borrow_mut().take();
+            return;
+        }
+        if let Some(cb) = frame_for_loop.borrow().as_ref() {
+            let _ = window_for_loop.request_animation_frame(cb.as_ref().unchecked_ref());
+        }
+    }) as Box<dyn FnMut(f64)>));
+    let first = frame.borrow();
+    if let Some(cb) = first.as_ref() {
+        let _ = window.request_animation_frame(cb.as_ref().unchecked_ref());
+    }
+}
+
+/// Offer `contents` as a browser download named `filename` via a Blob URL and a
+/// synthetic anchor click. Silently does nothing outside a DOM (no window).
+pub(crate) fn download_text(filename: &str, mime: &str, contents: &str) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+
+    let array = js_sys::Array::new();
+    array.push(&JsValue::from_str(contents));
+    let opts = web_sys::BlobPropertyBag::new();
+    opts.set_type(mime);
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&array, &opts) else {
+        return;
+    };
+
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+        return;
+    };
+    if let Ok(el) = document.create_element("a") {
+        let a: web_sys::HtmlAnchorElement = el.unchecked_into();
+        a.set_href(&url);
+        a.set_download(filename);
+        a.click();
+    }
+    let _ = web_sys::Url::revoke_object_url(&url);
+}
+
+/// How often (ms) the canvas repaints to fade recently-edited markers.
+const RECENT_EDIT_REPAINT_MS: u32 = 5000;
+
+/// Longest edge, in pixels, of the `board-thumbnail.png` OS preview.
+const THUMBNAIL_MAX_PX: f64 = 320.0;
+/// Minimum gap between thumbnail writes. Board changes inside the window ride
+/// along with the already-scheduled write instead of arming another.
+const THUMBNAIL_INTERVAL_MS: u32 = 30_000;
+
+/// Scale a `width` x `height` canvas down so its longest edge is at most
+/// `max_px`, preserving aspect ratio. Never upscales; never returns a zero edge.
+#[must_use]
+pub fn thumbnail_dimensions(width: u32, height: u32, max_px: f64) -> (u32, u32) {
+    let longest = width.max(height).max(1) as f64;
+    let scale = (max_px / longest).min(1.0);
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
+/// Downscale the rendered `canvas` into an offscreen canvas and return it as
+/// PNG bytes — the same `to_data_url` pipeline as "Export PNG", just smaller.
+fn canvas_thumbnail_png(canvas: &HtmlCanvasElement) -> Option<Vec<u8>> {
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let (w, h) = thumbnail_dimensions(canvas.width(), canvas.height(), THUMBNAIL_MAX_PX);
+    let thumb: HtmlCanvasElement = document.create_element("canvas").ok()?.unchecked_into();
+    thumb.set_width(w);
+    thumb.set_height(h);
+    let ctx = get_canvas_context(&thumb).ok()?;
+    ctx.draw_image_with_html_canvas_element_and_dw_and_dh(canvas, 0.0, 0.0, w as f64, h as f64)
+        .ok()?;
+    let data_url = thumb.to_data_url_with_type("image/png").ok()?;
+    let b64 = data_url.split_once(',')?.1;
+    // `atob` yields a "binary string": one char per byte, all <= U+00FF.
+    let binary = window.atob(b64).ok()?;
+    Some(binary.chars().map(|c| c as u8).collect())
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     let (board, set_board) = signal(Board::default());
@@ -917,18 +2756,73 @@ pub fn App() -> impl IntoView {
     let (selected_edge, set_selected_edge) = signal::<Option<String>>(None);
     let (drag_state, set_drag_state) = signal(DragState::default());
     let (pan_state, set_pan_state) = signal(PanState::default());
+    // Bumped on every new pointer/wheel/key interaction; a running pan-inertia
+    // glide or camera flight stops as soon as it sees a generation other than
+    // its own.
+    let inertia_generation = StoredValue::new(0u32);
+    // Position in the orphan list for the `O` key; taken modulo the current
+    // orphan count so connecting or deleting nodes just shortens the cycle.
+    let orphan_cursor = StoredValue::new(0usize);
+    // When the last drag/resize/pan/box select that actually moved something
+    // ended; a double-click right after it is ignored.
+    let last_gesture_end = StoredValue::new(f64::NEG_INFINITY);
+    // Node most recently created by double-click, which the next one may stack
+    // under (`stack_new_nodes`).
+    let last_created_node = StoredValue::new(None::<String>);
+    let (settings, set_settings) = signal(load_settings());
+    let (settings_open, set_settings_open) = signal(false);
+    // Persist preference changes (skipping the initial run, which just loaded them).
+    Effect::new(move |prev: Option<()>| {
+        let current = settings.get();
+        if prev.is_some() {
+            save_settings(&current);
+        }
+    });
     let (editing_node, set_editing_node) = signal::<Option<String>>(None);
+    let editing_edge = RwSignal::new(None::<String>);
+    let md_scroll = StoredValue::new(ScrollMemory::default());
     let (edge_creation, set_edge_creation) = signal(EdgeCreationState::default());
     let (resize_state, set_resize_state) = signal(ResizeState::default());
     let (cursor_style, set_cursor_style) = signal("crosshair".to_string());
+    // Connect mode (toggled with `E` when the edge trigger is `ConnectMode`):
+    // a plain drag from a node draws an edge. Per-session, never persisted.
+    let connect_mode = RwSignal::new(false);
+    // Sticky multi-connect (`H`): the armed source survives each connection.
+    let sticky_connect = RwSignal::new(StickyConnect::Off);
+    // Board picked with "Compare…" (e.g. `board.json.bak`); while set, the canvas
+    // outlines what differs from it. View-only: the live board is never touched.
+    let compare_backup = RwSignal::new(None::<Board>);
+    // Its outlines, re-diffed only when the board or the backup changes.
+    let backup_diff = Memo::new(move |_| {
+        compare_backup.with(|backup| {
+            backup
+                .as_ref()
+                .map(|backup| board.with(|b| DiffOverlay::new(b, backup)))
+        })
+    });
+    // Node types toggled off in the type filter bar (`NodeType::as_str` names):
+    // not drawn and not hit-tested. Per-session, never persisted.
+    let hidden_types = RwSignal::new(HashSet::<String>::new());
+    // Tags toggled on in the tag filter bar: nodes without any of them are
+    // drawn faded. Display-only and per-session, like the type filter.
+    let active_tags = RwSignal::new(HashSet::<String>::new());
+    // Collapsed subtrees, derived once per board change for rendering and
+    // hit-testing alike.
+    let collapse = Memo::new(move |_| board.with(CollapseView::new));
     let (last_mouse_world_pos, set_last_mouse_world_pos) = signal((0.0f64, 0.0f64));
+    // Topmost node under the idle cursor; its edges are emphasized on the canvas.
+    let (hovered_node, set_hovered_node) = signal::<Option<String>>(None);
     // Main canvas display size in CSS px, refreshed each frame for the minimap.
     let (viewport_size, set_viewport_size) = signal((0.0f64, 0.0f64));
     let (selection_box, set_selection_box) = signal::<Option<(f64, f64, f64, f64)>>(None);
+    // Smart-guide lines shown while a node drag is aligned to a neighbor.
+    let (alignment_guides, set_alignment_guides) = signal::<Vec<Guide>>(Vec::new());
     let (modal_image, set_modal_image) = signal::<Option<String>>(None);
     let (modal_md, set_modal_md) = signal::<Option<(String, bool)>>(None); // (node_id, is_editing)
     let (md_edit_text, set_md_edit_text) = signal::<String>(String::new()); // Separate signal to avoid re-render on typing
     let (node_clipboard, set_node_clipboard) = signal::<Option<(Vec<Node>, Vec<Edge>)>>(None);
+    // Pastes of the current copy so far, so offset-mode pastes cascade.
+    let paste_count = StoredValue::new(0u32);
     // Search overlay (P2.4 / F99): `Some(query)` while the Cmd/Ctrl+F overlay is
     // open; `None` when closed. Matches are reflected into `selected_nodes` so they
     // render with the existing selection highlight.
@@ -939,10 +2833,22 @@ pub fn App() -> impl IntoView {
 
     // Undo/redo history - using Rc<RefCell> since mutations don't need reactivity.
     // Snapshots are (Board, node selection) so undo/redo restore the selection too.
-    let history: BoardHistory = Rc::new(RefCell::new(History::new(100)));
+    let history: BoardHistory = Rc::new(RefCell::new(History::new(
+        settings.get_untracked().history_depth(),
+    )));
+    // Follow the "Undo steps" setting; lowering it drops the oldest steps now.
+    {
+        let history = history.clone();
+        Effect::new(move || {
+            let depth = settings.with(Settings::history_depth);
+            history.borrow_mut().set_max_size(depth);
+        });
+    }
 
     let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
     let file_input_ref = NodeRef::<leptos::html::Input>::new();
+    let md_input_ref = NodeRef::<leptos::html::Input>::new();
+    let backup_input_ref = NodeRef::<leptos::html::Input>::new();
     let image_cache: ImageCache = Rc::new(RefCell::new(HashMap::new()));
     let image_cache_for_render = image_cache.clone();
     let image_cache_for_load = image_cache.clone();
@@ -962,16 +2868,60 @@ pub fn App() -> impl IntoView {
     // Markdown file cache stored as a signal (for local .md files in link nodes)
     let (md_file_cache, set_md_file_cache) =
         signal::<HashMap<String, LoadState<String>>>(HashMap::new());
+    // Resolved sources for GIF image nodes, rendered as `<img>` overlays.
+    let (gif_sources, set_gif_sources) = signal::<HashMap<String, String>>(HashMap::new());
     let (image_load_trigger, set_image_load_trigger) = signal(0u32);
     let (link_preview_trigger, set_link_preview_trigger) = signal(0u32);
+    // Bumped periodically while any node is still recently edited, so its canvas
+    // marker keeps fading with no other input.
+    let (recent_edit_tick, set_recent_edit_tick) = signal(0u32);
+    // Published by the render loop while the render-stats setting is on.
+    let (render_stats, set_render_stats) = signal::<Option<RenderStats>>(None);
     let load_error = RwSignal::<Option<String>>::new(None);
+    let newer_version = RwSignal::<Option<u32>>::new(None);
+    // Where the desktop app reads and writes the board; shown in the HUD.
+    let board_path = RwSignal::<Option<String>>::new(None);
+    let save_error = RwSignal::<Option<String>>::new(None);
+    let toast = RwSignal::<Option<String>>::new(None);
+    let context_menu = RwSignal::<Option<(f64, f64, String)>>::new(None);
+    let tooltip = RwSignal::<Option<(f64, f64, String)>>::new(None);
+    // Pending show-tooltip timer; replacing or clearing it cancels the show.
+    let tooltip_timer = StoredValue::new_local(None::<gloo_timers::callback::Timeout>);
+    let hide_tooltip = move || {
+        tooltip_timer.set_value(None);
+        if tooltip.get_untracked().is_some() {
+            tooltip.set(None);
+        }
+    };
     let local_edit_pending = RwSignal::<bool>::new(false);
     // Set when an external board-changed event arrives while a local interaction
     // (drag/resize/edge-creation/text-edit) or a queued save is in flight. The
     // reload is deferred and flushed by an effect once the interaction settles,
     // so the watcher can never clobber an edit mid-gesture (P1.4 / F50).
     let pending_external_reload = RwSignal::<bool>::new(false);
-    let request_save = make_request_save(board, local_edit_pending);
+    let save_state = RwSignal::new(SaveState::Saved);
+    let request_save = make_request_save(
+        board,
+        settings,
+        local_edit_pending,
+        save_state,
+        save_error,
+        toast,
+    );
+
+    // Never lose a queued save: write it as soon as the window loses focus or
+    // is about to unload instead of waiting out the autosave delay. The flush
+    // issues the write inside the handler, before the page can go away.
+    {
+        let flush_cb = Closure::<dyn FnMut()>::new(move || request_save.flush());
+        if let Some(window) = web_sys::window() {
+            for event in ["blur", "beforeunload"] {
+                let _ = window
+                    .add_event_listener_with_callback(event, flush_cb.as_ref().unchecked_ref());
+            }
+        }
+        flush_cb.forget();
+    }
 
     // Debounced camera persistence (F105). Pan/zoom end-points call this; a burst
     // of wheel ticks coalesces into one localStorage write 200ms after the last
@@ -994,6 +2944,101 @@ pub fn App() -> impl IntoView {
     let persist_camera_now = move || {
         (persist_camera.get_value())();
     };
+    let fly_camera = FlyCamera {
+        inner: StoredValue::new_local(Rc::new(move |target: Camera| {
+            inertia_generation.update_value(|g| *g = g.wrapping_add(1));
+            if settings.get_untracked().reduce_motion {
+                set_camera.set(target);
+                persist_camera_now();
+                return;
+            }
+            run_camera_flight(
+                camera.get_untracked(),
+                target,
+                set_camera,
+                inertia_generation,
+                inertia_generation.get_value(),
+                persist_camera_now,
+            );
+        }) as Rc<dyn Fn(Camera)>),
+    };
+
+    // Opt-in OS preview (desktop only): board changes arm a single timer that,
+    // when it fires, downscales the live canvas and hands the PNG to
+    // `save_thumbnail`. Unlike the save debounce, a pending timer is NOT re-armed
+    // — that's what throttles writes to one per THUMBNAIL_INTERVAL_MS.
+    let schedule_thumbnail: StoredValue<Rc<dyn Fn()>, LocalStorage> = {
+        let pending: Rc<RefCell<Option<gloo_timers::callback::Timeout>>> =
+            Rc::new(RefCell::new(None));
+        let sink: Rc<dyn Fn()> = Rc::new(move || {
+            if pending.borrow().is_some() {
+                return;
+            }
+            let pending_for_timer = pending.clone();
+            let timeout = gloo_timers::callback::Timeout::new(THUMBNAIL_INTERVAL_MS, move || {
+                pending_for_timer.borrow_mut().take();
+                if !settings.get_untracked().board_thumbnail {
+                    return;
+                }
+                let Some(canvas) = canvas_ref.get_untracked() else {
+                    return;
+                };
+                let Some(png_bytes) = canvas_thumbnail_png(&canvas) else {
+                    return;
+                };
+                spawn_local(async move {
+                    if let Ok(args) = serde_wasm_bindgen::to_value(&SaveThumbnailArgs { png_bytes })
+                    {
+                        let _ = invoke("save_thumbnail", args).await;
+                    }
+                });
+            });
+            *pending.borrow_mut() = Some(timeout);
+        });
+        StoredValue::new_local(sink)
+    };
+    // Tell the backend where assets live; it resolves, writes, reads, and
+    // deletes images against this folder. Re-sent whenever the setting changes.
+    Effect::new(move || {
+        let dir = settings.with(|s| s.assets_dir.trim().to_string());
+        if !is_tauri() {
+            return;
+        }
+        spawn_local(async move {
+            let path = (!dir.is_empty()).then_some(dir);
+            if let Ok(args) = serde_wasm_bindgen::to_value(&SetAssetsDirArgs { path }) {
+                let _ = invoke("set_assets_dir", args).await;
+            }
+        });
+    });
+    // Same for the link-preview client's timeout and User-Agent.
+    Effect::new(move || {
+        let (timeout_secs, user_agent) = settings.with(|s| {
+            (
+                s.preview_timeout_secs,
+                s.preview_user_agent.trim().to_string(),
+            )
+        });
+        if !is_tauri() {
+            return;
+        }
+        spawn_local(async move {
+            let user_agent = (!user_agent.is_empty()).then_some(user_agent);
+            let args = SetPreviewClientArgs {
+                timeout_secs,
+                user_agent,
+            };
+            if let Ok(args) = serde_wasm_bindgen::to_value(&args) {
+                let _ = invoke("set_preview_client", args).await;
+            }
+        });
+    });
+    Effect::new(move || {
+        board.track();
+        if settings.with(|s| s.board_thumbnail) && is_tauri() {
+            (schedule_thumbnail.get_value())();
+        }
+    });
 
     // Single mutation entry point shared by handlers and editor components.
     let dispatch = Dispatcher {
@@ -1001,9 +3046,11 @@ pub fn App() -> impl IntoView {
         set_board,
         selected_nodes,
         set_selected_nodes,
+        selected_edge,
         set_selected_edge,
         history: StoredValue::new_local(history),
         request_save,
+        settings,
     };
 
     provide_context(BoardDataCtx {
@@ -1013,7 +3060,12 @@ pub fn App() -> impl IntoView {
         set_camera,
         request_save,
         local_edit_pending,
+        save_state,
         viewport_size,
+        fly_camera,
+        hidden_types,
+        active_tags,
+        collapse,
     });
     provide_context(SelectionCtx {
         selected_nodes,
@@ -1023,9 +3075,16 @@ pub fn App() -> impl IntoView {
         search_query,
         set_search_query,
     });
+    provide_context(SettingsCtx {
+        settings,
+        set_settings,
+        settings_open,
+        set_settings_open,
+    });
     provide_context(EditingCtx {
         editing_node,
         set_editing_node,
+        editing_edge,
         modal_image,
         set_modal_image,
         modal_md,
@@ -1033,7 +3092,14 @@ pub fn App() -> impl IntoView {
         md_edit_text,
         set_md_edit_text,
         md_file_cache,
+        md_scroll,
+        gif_sources,
         load_error,
+        newer_version,
+        save_error,
+        toast,
+        context_menu,
+        tooltip,
         dispatch,
     });
 
@@ -1047,12 +3113,14 @@ pub fn App() -> impl IntoView {
             gloo_timers::future::TimeoutFuture::new(50).await;
             // Resolve the per-board camera key before restoring so subsequent
             // pan/zoom writes land under the right (board-specific) key.
-            let key = camera_storage_key().await;
+            let path = active_board_path().await;
+            let key = camera_storage_key(path.as_deref());
+            board_path.set(path);
             camera_key.set_value(key.clone());
             if let Some(restored) = load_camera_storage(&key) {
                 set_camera.set(restored);
             }
-            reload_board_into(set_board, load_error).await;
+            reload_board_into(set_board, load_error, newer_version).await;
         });
     });
 
@@ -1065,6 +3133,7 @@ pub fn App() -> impl IntoView {
             || resize_state.get_untracked().is_resizing
             || edge_creation.get_untracked().is_creating
             || editing_node.get_untracked().is_some()
+            || editing_edge.get_untracked().is_some()
             || local_edit_pending.get_untracked()
     };
 
@@ -1092,7 +3161,7 @@ pub fn App() -> impl IntoView {
 
             web_sys::console::log_1(&"External board change detected, reloading...".into());
             spawn_local(async move {
-                reload_board_into(set_board, load_error).await;
+                reload_board_into(set_board, load_error, newer_version).await;
             });
         });
 
@@ -1102,6 +3171,38 @@ pub fn App() -> impl IntoView {
         });
     });
 
+    // Nodes added from outside the canvas (`add_text_node`, Tauri only). The
+    // backend leaves board.json alone while a window is open and hands the node
+    // over instead, so it goes through the dispatcher (undo, default color,
+    // debounced save) rather than racing a queued save of this board.
+    Effect::new(move || {
+        if !is_tauri() {
+            return;
+        }
+
+        #[derive(Deserialize)]
+        struct AddedNode {
+            id: String,
+            text: String,
+        }
+
+        let handler = Closure::new(move |event: JsValue| {
+            let added = js_sys::Reflect::get(&event, &"payload".into())
+                .ok()
+                .and_then(|payload| serde_wasm_bindgen::from_value::<AddedNode>(payload).ok());
+            let Some(AddedNode { id, text }) = added else {
+                return;
+            };
+            let (x, y) = board.with_untracked(ops::append_position);
+            dispatch.apply(BoardAction::CreateNode(Node::new(id, x, y, text)), None);
+        });
+
+        spawn_local(async move {
+            let _ = listen("node-added", &handler).await;
+            handler.forget();
+        });
+    });
+
     // Deferred-reload flush: when an external change was deferred during an
     // interaction, re-run the reload once the interaction settles. This effect
     // subscribes (tracked) to every interaction signal plus the pending flag, so
@@ -1116,12 +3217,13 @@ pub fn App() -> impl IntoView {
             || resize_state.get().is_resizing
             || edge_creation.get().is_creating
             || editing_node.get().is_some()
+            || editing_edge.get().is_some()
             || local_edit_pending.get();
 
         if pending && !busy {
             pending_external_reload.set(false);
             spawn_local(async move {
-                reload_board_into(set_board, load_error).await;
+                reload_board_into(set_board, load_error, newer_version).await;
             });
         }
     });
@@ -1200,6 +3302,7 @@ pub fn App() -> impl IntoView {
                                 let cache = cache_for_onload.clone();
                                 let lru = lru_for_onload.clone();
                                 let url = url_for_closure.clone();
+                                let src = image_src.clone();
                                 move || {
                                     web_sys::console::log_1(
                                         &format!("Image loaded successfully: {}", url).into(),
@@ -1219,6 +3322,11 @@ pub fn App() -> impl IntoView {
                                         img.clone(),
                                         &live_urls,
                                     );
+                                    if is_gif(&url) || is_gif(&src) {
+                                        set_gif_sources.update(|g| {
+                                            g.insert(url.clone(), src.clone());
+                                        });
+                                    }
                                     trigger.update(|n| *n = n.wrapping_add(1));
                                 }
                             })
@@ -1255,16 +3363,27 @@ pub fn App() -> impl IntoView {
         }
     });
 
-    // Link preview fetching effect
+    // Link preview fetching effect. Fetches go through a shared queue so a board
+    // full of links runs at most `LINK_FETCH_CONCURRENCY` at a time.
     Effect::new({
         let link_cache = link_preview_cache_for_fetch.clone();
-        let image_cache = image_cache_for_link_preview.clone();
-        let image_lru = image_lru_for_link_preview.clone();
+        let fetcher = LinkPreviewFetcher {
+            link_cache: link_preview_cache_for_fetch.clone(),
+            image_cache: image_cache_for_link_preview.clone(),
+            image_lru: image_lru_for_link_preview.clone(),
+            board,
+            link_trigger: set_link_preview_trigger,
+            image_trigger: set_image_load_trigger,
+            queue: Rc::new(RefCell::new(FetchQueue::new(LINK_FETCH_CONCURRENCY))),
+        };
         move || {
             let current_board = board.get();
 
             for node in &current_board.nodes {
-                if node.node_type == NodeType::Link && !node.text.is_empty() {
+                if node.node_type == NodeType::Link
+                    && !node.text.is_empty()
+                    && !is_markdown_url(&node.text)
+                {
                     let url = node.text.clone();
 
                     // SSRF gate: only auto-fetch previews for clearly-public
@@ -1283,131 +3402,62 @@ pub fn App() -> impl IntoView {
                     };
 
                     if needs_fetch {
-                        // Mark as loading
+                        // Mark as loading; it stays that way while queued.
                         link_cache
                             .borrow_mut()
                             .insert(url.clone(), LoadState::Loading);
-
-                        let cache_for_result = link_cache.clone();
-                        let image_cache_for_result = image_cache.clone();
-                        let image_lru_for_result = image_lru.clone();
-                        let trigger = set_link_preview_trigger;
-                        let img_trigger = set_image_load_trigger;
-
-                        spawn_local(async move {
-                            let args = serde_wasm_bindgen::to_value(&FetchLinkPreviewArgs {
-                                url: url.clone(),
-                            })
-                            .unwrap();
-                            let result = invoke("fetch_link_preview", args).await;
-
-                            if let Ok(preview) =
-                                serde_wasm_bindgen::from_value::<LinkPreview>(result)
-                            {
-                                // If preview has an image, start loading it
-                                if let Some(ref image_url) = preview.image {
-                                    let img_url = image_url.clone();
-                                    let needs_img_load = {
-                                        let cache = image_cache_for_result.borrow();
-                                        !cache.contains_key(&img_url)
-                                    };
-
-                                    if needs_img_load {
-                                        image_cache_for_result
-                                            .borrow_mut()
-                                            .insert(img_url.clone(), LoadState::Loading);
-
-                                        let img = HtmlImageElement::new().unwrap();
-                                        let cache_for_onload = image_cache_for_result.clone();
-                                        let lru_for_onload = image_lru_for_result.clone();
-                                        let url_for_closure = img_url.clone();
-
-                                        let onload = Closure::wrap(Box::new({
-                                            let img = img.clone();
-                                            let cache = cache_for_onload.clone();
-                                            let lru = lru_for_onload.clone();
-                                            let url = url_for_closure.clone();
-                                            move || {
-                                                // OG preview images aren't node.text, so none
-                                                // are "live" for LRU purposes; the cap applies.
-                                                let live_urls: HashSet<String> = board
-                                                    .get_untracked()
-                                                    .nodes
-                                                    .iter()
-                                                    .filter(|n| n.node_type == NodeType::Image)
-                                                    .map(|n| n.text.clone())
-                                                    .collect();
-                                                insert_loaded_image(
-                                                    &cache,
-                                                    &lru,
-                                                    url.clone(),
-                                                    img.clone(),
-                                                    &live_urls,
-                                                );
-                                                img_trigger.update(|n| *n = n.wrapping_add(1));
-                                            }
-                                        })
-                                            as Box<dyn Fn()>);
-
-                                        let onerror = Closure::wrap(Box::new({
-                                            let cache = cache_for_onload.clone();
-                                            let url = url_for_closure.clone();
-                                            move || {
-                                                cache
-                                                    .borrow_mut()
-                                                    .insert(url.clone(), LoadState::Failed);
-                                                img_trigger.update(|n| *n = n.wrapping_add(1));
-                                            }
-                                        })
-                                            as Box<dyn Fn()>);
-
-                                        img.set_onload(Some(onload.as_ref().unchecked_ref()));
-                                        onload.forget();
-                                        img.set_onerror(Some(onerror.as_ref().unchecked_ref()));
-                                        onerror.forget();
-                                        img.set_src(&img_url);
-                                    }
-                                }
-
-                                cache_for_result
-                                    .borrow_mut()
-                                    .insert(url, LoadState::Loaded(preview));
-                                trigger.update(|n| *n = n.wrapping_add(1));
-                            } else {
-                                // Preview fetch failed (backend error / SSRF block / bad data):
-                                // mark Failed so the node falls back to the raw URL instead of
-                                // spinning, and won't auto-refetch until evicted.
-                                cache_for_result.borrow_mut().insert(url, LoadState::Failed);
-                                trigger.update(|n| *n = n.wrapping_add(1));
-                            }
-                        });
+                        let start_now = fetcher.queue.borrow_mut().enqueue(url);
+                        if let Some(url) = start_now {
+                            fetcher.clone().spawn(url);
+                        }
                     }
                 }
             }
         }
     });
 
-    // Markdown file fetching effect (for local .md files in link nodes)
+    // Markdown file fetching effect (for local .md files and remote markdown
+    // URLs in link nodes)
     Effect::new(move || {
         let current_board = board.get();
         let current_cache = md_file_cache.get();
 
         for node in &current_board.nodes {
-            if node.node_type == NodeType::Link && is_local_md_file(&node.text) {
+            if node.node_type == NodeType::Link && is_md_link(&node.text) {
                 let path = node.text.clone();
 
                 if !current_cache.contains_key(&path) {
+                    // Same gates as link previews: reads go through the Tauri
+                    // backend, and a board.json link mustn't auto-fetch an
+                    // internal host. Blocked reads show as failed, not loading.
+                    if !is_tauri() || (is_markdown_url(&path) && !is_public_http_host(&path)) {
+                        set_md_file_cache.update(|c| {
+                            c.insert(path, LoadState::Failed);
+                        });
+                        continue;
+                    }
+
                     // Mark as loading
                     set_md_file_cache.update(|c| {
                         c.insert(path.clone(), LoadState::Loading);
                     });
 
                     spawn_local(async move {
-                        let args = serde_wasm_bindgen::to_value(&ReadMarkdownFileArgs {
-                            path: path.clone(),
-                        })
-                        .unwrap();
-                        let result = invoke("read_markdown_file", args).await;
+                        let result = if is_markdown_url(&path) {
+                            let args = serde_wasm_bindgen::to_value(&FetchMarkdownUrlArgs {
+                                url: path.clone(),
+                            })
+                            .unwrap();
+                            try_invoke("fetch_markdown_url", args)
+                                .await
+                                .unwrap_or(JsValue::NULL)
+                        } else {
+                            let args = serde_wasm_bindgen::to_value(&ReadMarkdownFileArgs {
+                                path: path.clone(),
+                            })
+                            .unwrap();
+                            invoke("read_markdown_file", args).await
+                        };
 
                         // A non-string result means the backend read failed; record
                         // Failed (distinct from Loading) so the overlay shows an error
@@ -1445,7 +3495,7 @@ pub fn App() -> impl IntoView {
                     NodeType::Image => {
                         live_image_urls.insert(node.text.clone());
                     }
-                    NodeType::Link if is_local_md_file(&node.text) => {
+                    NodeType::Link if is_md_link(&node.text) => {
                         live_md_paths.insert(node.text.clone());
                     }
                     NodeType::Link => {
@@ -1485,9 +3535,29 @@ pub fn App() -> impl IntoView {
             set_md_file_cache.update(|c| {
                 c.retain(|path, _| live_md_paths.contains(path));
             });
+
+            // Forget markdown-modal scroll offsets of deleted nodes.
+            let live_ids: HashSet<&str> =
+                current_board.nodes.iter().map(|n| n.id.as_str()).collect();
+            md_scroll.update_value(|m| m.retain(&live_ids));
         }
     });
 
+    // Fade the recently-edited markers: repaint every few seconds, but only
+    // while some node is still inside the fade window.
+    gloo_timers::callback::Interval::new(RECENT_EDIT_REPAINT_MS, move || {
+        let now = js_sys::Date::now();
+        let any_recent = board.with_untracked(|b| {
+            b.nodes
+                .iter()
+                .any(|n| recent_edit_alpha(n.updated_at, now) > 0.0)
+        });
+        if any_recent {
+            set_recent_edit_tick.update(|t| *t = t.wrapping_add(1));
+        }
+    })
+    .forget();
+
     // Render coalescer (P2.1): instead of drawing synchronously on every signal
     // change (once per mousemove during a drag), each change marks the canvas
     // dirty and schedules a SINGLE requestAnimationFrame. The rAF callback reads
@@ -1496,10 +3566,21 @@ pub fn App() -> impl IntoView {
     let render_scheduled: Rc<Cell<bool>> = Rc::new(Cell::new(false));
     // Holds the rAF callback so it isn't dropped while the browser owns it.
     let render_closure: RenderClosure = Rc::new(RefCell::new(None));
+    // Progressive rendering (see [`ProgressiveCursor`]): armed when a much larger
+    // board lands, so its first render is spread over several frames.
+    let progress: Rc<Cell<ProgressiveCursor>> = Rc::new(Cell::new(ProgressiveCursor::default()));
+    // Render timings for the dev stats overlay; only sampled while it's enabled.
+    let frame_stats = Rc::new(RefCell::new(FrameStats::new(FRAME_STATS_WINDOW)));
+    let last_node_count: Rc<Cell<usize>> = Rc::new(Cell::new(0));
 
     {
         let render_scheduled = render_scheduled.clone();
         let render_closure_store = render_closure.clone();
+        // The closure re-queues itself while a progressive pass is catching up.
+        // This Rc cycle is intentional: the render loop lives as long as the app.
+        let render_closure_self = render_closure.clone();
+        let progress = progress.clone();
+        let frame_stats = frame_stats.clone();
         let image_cache_for_render = image_cache_for_render.clone();
         let link_preview_cache_for_render = link_preview_cache_for_render.clone();
 
@@ -1514,6 +3595,28 @@ pub fn App() -> impl IntoView {
             let current_editing = editing_node.get_untracked();
             let current_edge_creation = edge_creation.get_untracked();
             let current_selection_box = selection_box.get_untracked();
+            let current_guides = alignment_guides.get_untracked();
+            let current_hovered = hovered_node.get_untracked();
+            let current_sticky = sticky_connect.get_untracked();
+            let current_hidden_types = hidden_types.get_untracked();
+            let current_active_tags = active_tags.get_untracked();
+            let current_collapse = collapse.get_untracked();
+            let current_diff = backup_diff.get_untracked();
+            let corner_radius = settings.get_untracked().node_corner_radius;
+            let rotate_edge_labels = settings.get_untracked().rotate_edge_labels;
+            let edge_opacity = settings.get_untracked().edge_opacity;
+            let selection_style = settings.get_untracked().selection_style;
+            let edge_style = settings.get_untracked().edge_style;
+            // Ghosts only once the drag has actually moved something.
+            let current_drag = drag_state.get_untracked();
+            let show_ghosts = settings.get_untracked().drag_ghost
+                && current_drag.is_dragging
+                && current_drag.snapshotted;
+            let performance = settings
+                .get_untracked()
+                .render_stats
+                .then(|| web_sys::window().and_then(|w| w.performance()))
+                .flatten();
 
             if let Some(canvas) = canvas_ref.get_untracked() {
                 let canvas_el: &HtmlCanvasElement = &canvas;
@@ -1544,6 +3647,8 @@ pub fn App() -> impl IntoView {
                     // Reset to the identity transform first (set_transform replaces,
                     // it doesn't compose) so repeated frames don't accumulate scale.
                     let _ = ctx.set_transform(dpr, 0.0, 0.0, dpr, 0.0, 0.0);
+                    let mut cursor = progress.get();
+                    let started = performance.as_ref().map_or(0.0, |p| p.now());
                     render_board(RenderState {
                         ctx: &ctx,
                         canvas: canvas_el,
@@ -1552,16 +3657,57 @@ pub fn App() -> impl IntoView {
                         selected_nodes: &current_selected,
                         selected_edge: current_selected_edge.as_ref(),
                         editing_node: current_editing.as_ref(),
+                        hovered_node: current_hovered.as_ref(),
+                        armed_node: current_sticky.armed(),
                         edge_preview: current_edge_creation.is_creating.then_some((
                             current_edge_creation.from_node_id.as_ref(),
                             current_edge_creation.current_x,
                             current_edge_creation.current_y,
                         )),
                         selection_box: current_selection_box,
+                        corner_radius,
+                        rotate_edge_labels,
+                        edge_opacity,
+                        selection_style,
+                        hidden_types: &current_hidden_types,
+                        collapse: &current_collapse,
+                        active_tags: &current_active_tags,
+                        edge_style,
+                        guides: &current_guides,
+                        drag_ghosts: show_ghosts.then_some(&current_drag.node_start_positions),
+                        diff: current_diff.as_ref(),
                         image_cache: &image_cache_for_render,
                         link_preview_cache: &link_preview_cache_for_render,
                         dpr,
+                        progress: &mut cursor,
+                        now_ms: js_sys::Date::now(),
                     });
+                    progress.set(cursor);
+                    if let Some(performance) = &performance {
+                        let mut stats = frame_stats.borrow_mut();
+                        stats.record(performance.now() - started);
+                        set_render_stats.set(Some(RenderStats {
+                            last_ms: stats.last(),
+                            avg_ms: stats.avg(),
+                            max_ms: stats.max(),
+                            nodes: current_board.nodes.len(),
+                            edges: current_board.edges.len(),
+                        }));
+                    }
+
+                    // Mid-pass: draw the next batch next frame, unless a signal
+                    // change already queued one (which restarted the pass).
+                    if !cursor.is_caught_up() && !render_scheduled.replace(true) {
+                        let queued = render_closure_self.borrow().as_ref().is_some_and(|cb| {
+                            web_sys::window().is_some_and(|win| {
+                                win.request_animation_frame(cb.as_ref().unchecked_ref())
+                                    .is_ok()
+                            })
+                        });
+                        if !queued {
+                            render_scheduled.set(false);
+                        }
+                    }
                 }
             }
         }) as Box<dyn FnMut()>);
@@ -1579,8 +3725,29 @@ pub fn App() -> impl IntoView {
         editing_node.track();
         edge_creation.track();
         selection_box.track();
+        alignment_guides.track();
+        drag_state.track(); // drag ghosts
+        hovered_node.track();
+        sticky_connect.track(); // armed-source highlight
+        backup_diff.track(); // backup diff overlay
+        hidden_types.track(); // type filter
+        active_tags.track(); // tag filter dimming
+        settings.track(); // corner radius, edge label rotation
         image_load_trigger.track(); // image loads
         link_preview_trigger.track(); // link preview loads
+        recent_edit_tick.track(); // recently-edited marker fade
+
+        // A much larger board (initial load, external reload) starts a fresh
+        // progressive pass; any other change restarts an in-flight one against
+        // the current viewport, so pan/zoom never waits on stale batches.
+        let node_count = board.with(|b| b.nodes.len());
+        let mut cursor = progress.get();
+        if node_count.saturating_sub(last_node_count.replace(node_count)) > PROGRESSIVE_BATCH {
+            cursor.arm();
+        } else {
+            cursor.restart();
+        }
+        progress.set(cursor);
 
         if render_scheduled.replace(true) {
             // A frame is already queued; the rAF callback will pick up the latest
@@ -1605,8 +3772,32 @@ pub fn App() -> impl IntoView {
         }
     });
 
+    // With "prevent cycles" on, a directed board refuses an edge that would
+    // close a loop; the toast says why nothing was drawn.
+    let refuse_cycle = move |b: &Board, from: &str, to: &str| {
+        let refused = settings.get_untracked().prevent_cycles
+            && b.settings.directed
+            && ops::would_create_cycle(b, from, to);
+        if refused {
+            show_toast(toast, "Edge not added: it would create a cycle");
+        }
+        refused
+    };
+    // Same guard for splicing a node into an edge with an Alt-drop.
+    let refuse_split = move |b: &Board, edge_id: &str, node_id: &str| {
+        let refused = settings.get_untracked().prevent_cycles
+            && b.settings.directed
+            && ops::split_would_create_cycle(b, edge_id, node_id);
+        if refused {
+            show_toast(toast, "Node not inserted: it would create a cycle");
+        }
+        refused
+    };
+
     let on_mouse_down = move |ev: web_sys::MouseEvent| {
-        if editing_node.get_untracked().is_some() {
+        hide_tooltip();
+        inertia_generation.update_value(|g| *g = g.wrapping_add(1));
+        if editing_node.get_untracked().is_some() || editing_edge.get_untracked().is_some() {
             return;
         }
 
@@ -1624,13 +3815,20 @@ pub fn App() -> impl IntoView {
         let current_board = board.get_untracked();
         let current_selected = selected_nodes.get_untracked();
         let handle_size = RESIZE_HANDLE_SIZE / cam.zoom;
+        // A locked board still selects, pans and box-selects, but never starts
+        // a resize, an edge or a node drag.
+        let locked = settings.get_untracked().read_only;
 
         // First check if clicking on a resize handle of any selected node
         // (handles extend outside node bounds, so check before contains_point)
         let resize_hit = current_board
             .nodes
             .iter()
-            .filter(|n| current_selected.contains(&n.id))
+            .filter(|n| {
+                !locked
+                    && show_individual_handles(current_selected.len())
+                    && current_selected.contains(&n.id)
+            })
             .find_map(|n| {
                 n.resize_handle_at(world_x, world_y, handle_size)
                     .map(|h| (n, h))
@@ -1651,27 +3849,88 @@ pub fn App() -> impl IntoView {
                 original_width: node.width,
                 original_height: node.height,
                 snapshotted: false,
+                group: Vec::new(),
             });
             return;
         }
 
-        let clicked_node = current_board
-            .nodes
-            .iter()
-            .rev()
-            .find(|n| n.contains_point(world_x, world_y));
+        // A multi-selection resizes as a group from its bounding-box handles.
+        let group_hit = (!locked)
+            .then(|| group_handle_at(&current_board, &current_selected, &cam, world_x, world_y))
+            .flatten();
+        if let Some(((x, y, width, height), handle)) = group_hit {
+            set_resize_state.set(ResizeState {
+                is_resizing: true,
+                node_id: None,
+                handle: Some(handle),
+                start_mouse_x: world_x,
+                start_mouse_y: world_y,
+                original_x: x,
+                original_y: y,
+                original_width: width,
+                original_height: height,
+                snapshotted: false,
+                group: current_board
+                    .nodes
+                    .iter()
+                    .filter(|n| current_selected.contains(&n.id))
+                    .map(|n| (n.id.clone(), (n.x, n.y, n.width, n.height)))
+                    .collect(),
+            });
+            return;
+        }
+
+        let clicked_node = shown_nodes(
+            &current_board,
+            &hidden_types.get_untracked(),
+            &collapse.read_untracked().hidden,
+        )
+        .into_iter()
+        .rev()
+        .find(|n| n.contains_point(world_x, world_y));
 
         if let Some(node) = clicked_node {
             set_selected_edge.set(None);
-            if ev.shift_key() {
+            let trigger = settings.get_untracked().edge_trigger;
+            let starts_edge =
+                trigger.starts_edge(ev.shift_key(), ev.alt_key(), connect_mode.get_untracked());
+            // With a sticky source armed, a plain click connects it to this node.
+            let sticky_edge = sticky_connect.with_untracked(|s| s.click_edge(&node.id));
+            if let Some((from, to)) = sticky_edge.filter(|_| !starts_edge && !locked) {
+                if current_board.nodes.iter().any(|n| n.id == from) {
+                    if !ops::edge_exists(&current_board, &from, &to)
+                        && !refuse_cycle(&current_board, &from, &to)
+                    {
+                        dispatch.apply(
+                            BoardAction::CreateEdge {
+                                id: uuid::Uuid::new_v4().to_string(),
+                                from_node: from,
+                                to_node: to,
+                                to_port: None,
+                            },
+                            None,
+                        );
+                    }
+                    return;
+                }
+                // The armed source has been deleted since; wait for a new one.
+                sticky_connect.set(StickyConnect::Waiting);
+            }
+            if starts_edge && !locked {
                 set_edge_creation.set(EdgeCreationState {
                     is_creating: true,
                     from_node_id: Some(node.id.clone()),
+                    start_x: canvas_x,
+                    start_y: canvas_y,
                     current_x: canvas_x,
                     current_y: canvas_y,
                 });
             } else {
-                if ev.meta_key() || ev.ctrl_key() {
+                let plain_click = !(ev.alt_key() || ev.meta_key() || ev.ctrl_key());
+                if let Some(group) = node.group.as_deref().filter(|_| ev.alt_key()) {
+                    // Alt+click a grouped node selects its whole group.
+                    set_selected_nodes.set(select_group(&current_board, group));
+                } else if ev.meta_key() || ev.ctrl_key() {
                     set_selected_nodes.update(|s| {
                         if !s.remove(&node.id) {
                             s.insert(node.id.clone());
@@ -1681,18 +3940,13 @@ pub fn App() -> impl IntoView {
                     set_selected_nodes.set([node.id.clone()].into_iter().collect());
                 }
 
-                // Copy link URL to clipboard when clicking a link node
-                if node.node_type == NodeType::Link && !node.text.is_empty() {
-                    let url = node.text.clone();
-                    spawn_local(async move {
-                        if let Some(window) = web_sys::window() {
-                            let clipboard = window.navigator().clipboard();
-                            let _ =
-                                wasm_bindgen_futures::JsFuture::from(clipboard.write_text(&url))
-                                    .await;
-                        }
-                    });
-                }
+                // A plain click on a link node copies its URL (when enabled) —
+                // but only on release, so starting a drag never touches the clipboard.
+                let link_click = (plain_click
+                    && node.node_type == NodeType::Link
+                    && !node.text.is_empty()
+                    && settings.get_untracked().copy_link_on_click)
+                    .then(|| node.text.clone());
 
                 let selected = selected_nodes.get_untracked();
                 let mut start_positions = HashMap::new();
@@ -1710,41 +3964,27 @@ pub fn App() -> impl IntoView {
                 // drag movement in on_mouse_move (F114), so a plain click (mouse down
                 // + up without moving) leaves no junk undo entry.
                 set_drag_state.set(DragState {
-                    is_dragging: true,
+                    is_dragging: !locked,
                     is_box_selecting: false,
                     start_x: canvas_x,
                     start_y: canvas_y,
                     node_start_positions: start_positions,
                     snapshotted: false,
+                    aligned: (false, false),
+                    link_click,
                 });
             }
         } else {
-            let node_map: HashMap<&str, &Node> = current_board
-                .nodes
-                .iter()
-                .map(|n| (n.id.as_str(), n))
-                .collect();
-            let clicked_edge = current_board.edges.iter().find(|edge| {
-                let from = node_map.get(edge.from_node.as_str());
-                let to = node_map.get(edge.to_node.as_str());
-                if let (Some(from), Some(to)) = (from, to) {
-                    let from_cx = from.x + from.width / 2.0;
-                    let from_cy = from.y + from.height / 2.0;
-                    let to_cx = to.x + to.width / 2.0;
-                    let to_cy = to.y + to.height / 2.0;
-                    point_near_line(
-                        world_x,
-                        world_y,
-                        from_cx,
-                        from_cy,
-                        to_cx,
-                        to_cy,
-                        10.0 / cam.zoom,
-                    )
-                } else {
-                    false
-                }
-            });
+            let clicked_edge = edge_near_point(
+                &current_board,
+                world_x,
+                world_y,
+                10.0 / cam.zoom,
+                None,
+                &hidden_types.get_untracked(),
+                &collapse.read_untracked().hidden,
+                settings.get_untracked().edge_style,
+            );
 
             if let Some(edge) = clicked_edge {
                 set_selected_nodes.set(HashSet::new());
@@ -1762,6 +4002,8 @@ pub fn App() -> impl IntoView {
                         start_y: canvas_y,
                         node_start_positions: HashMap::new(),
                         snapshotted: false,
+                        aligned: (false, false),
+                        link_click: None,
                     });
                 } else {
                     set_pan_state.set(PanState {
@@ -1770,6 +4012,8 @@ pub fn App() -> impl IntoView {
                         start_y: canvas_y,
                         camera_start_x: cam.x,
                         camera_start_y: cam.y,
+                        last_sample: (canvas_x, canvas_y, js_sys::Date::now()),
+                        velocity: (0.0, 0.0),
                     });
                 }
             }
@@ -1789,8 +4033,21 @@ pub fn App() -> impl IntoView {
         if current_resize.is_resizing {
             let cam = camera.get_untracked();
             let (world_x, world_y) = cam.screen_to_world(canvas_x, canvas_y);
-            let dx = world_x - current_resize.start_mouse_x;
-            let dy = world_y - current_resize.start_mouse_y;
+            let mut dx = world_x - current_resize.start_mouse_x;
+            let mut dy = world_y - current_resize.start_mouse_y;
+            if let (true, Some(handle)) =
+                (settings.get_untracked().resize_snap, current_resize.handle)
+            {
+                // Land the dragged corner on the grid (for a group, the corner
+                // of the selection box) so resized edges line up with it.
+                let original = (
+                    current_resize.original_x,
+                    current_resize.original_y,
+                    current_resize.original_width,
+                    current_resize.original_height,
+                );
+                (dx, dy) = snap_resize_delta(original, handle, (dx, dy), GRID_SIZE);
+            }
 
             // Deferred undo snapshot: take it once, on the first actual resize move,
             // capturing the board+selection BEFORE any geometry change (F114).
@@ -1799,53 +4056,60 @@ pub fn App() -> impl IntoView {
                 set_resize_state.update(|s| s.snapshotted = true);
             }
 
+            let fit_text_on_resize = settings.get_untracked().fit_text_on_resize;
             set_board.update(|b| {
-                if let Some(node_id) = &current_resize.node_id {
-                    if let Some(node) = b.nodes.iter_mut().find(|n| &n.id == node_id) {
-                        match current_resize.handle {
-                            Some(ResizeHandle::TopLeft) => {
-                                let new_width =
-                                    (current_resize.original_width - dx).max(MIN_NODE_WIDTH);
-                                let new_height =
-                                    (current_resize.original_height - dy).max(MIN_NODE_HEIGHT);
-                                let actual_dx = current_resize.original_width - new_width;
-                                let actual_dy = current_resize.original_height - new_height;
-                                node.x = current_resize.original_x + actual_dx;
-                                node.y = current_resize.original_y + actual_dy;
-                                node.width = new_width;
-                                node.height = new_height;
-                            }
-                            Some(ResizeHandle::TopRight) => {
-                                let new_width =
-                                    (current_resize.original_width + dx).max(MIN_NODE_WIDTH);
-                                let new_height =
-                                    (current_resize.original_height - dy).max(MIN_NODE_HEIGHT);
-                                let actual_dy = current_resize.original_height - new_height;
-                                node.y = current_resize.original_y + actual_dy;
-                                node.width = new_width;
-                                node.height = new_height;
-                            }
-                            Some(ResizeHandle::BottomLeft) => {
-                                let new_width =
-                                    (current_resize.original_width - dx).max(MIN_NODE_WIDTH);
-                                let new_height =
-                                    (current_resize.original_height + dy).max(MIN_NODE_HEIGHT);
-                                let actual_dx = current_resize.original_width - new_width;
-                                node.x = current_resize.original_x + actual_dx;
-                                node.width = new_width;
-                                node.height = new_height;
-                            }
-                            Some(ResizeHandle::BottomRight) => {
-                                let new_width =
-                                    (current_resize.original_width + dx).max(MIN_NODE_WIDTH);
-                                let new_height =
-                                    (current_resize.original_height + dy).max(MIN_NODE_HEIGHT);
-                                node.width = new_width;
-                                node.height = new_height;
-                            }
-                            None => {}
+                if let (false, Some(handle)) =
+                    (current_resize.group.is_empty(), current_resize.handle)
+                {
+                    let from = (
+                        current_resize.original_x,
+                        current_resize.original_y,
+                        current_resize.original_width,
+                        current_resize.original_height,
+                    );
+                    let to = resize_rect(from, handle, dx, dy);
+                    for (id, rect) in scale_rects(&current_resize.group, from, to) {
+                        if let Some(node) = b.nodes.iter_mut().find(|n| n.id == id) {
+                            (node.x, node.y, node.width, node.height) = rect;
                         }
                     }
+                    return;
+                }
+                let (Some(node_id), Some(handle)) =
+                    (&current_resize.node_id, current_resize.handle)
+                else {
+                    return;
+                };
+                if let Some(node) = b.nodes.iter_mut().find(|n| &n.id == node_id) {
+                    let original = (
+                        current_resize.original_x,
+                        current_resize.original_y,
+                        current_resize.original_width,
+                        current_resize.original_height,
+                    );
+                    // With the setting on, a text node stops shrinking where its
+                    // wrapped text would start to be clipped.
+                    let fit_ctx = (fit_text_on_resize
+                        && draws_wrapped_text(node)
+                        && !node.text.trim().is_empty())
+                    .then(|| canvas_ref.get_untracked())
+                    .flatten()
+                    .and_then(|canvas| get_canvas_context(&canvas).ok());
+                    (node.x, node.y, node.width, node.height) = match &fit_ctx {
+                        Some(ctx) => {
+                            let measure = body_text_measure(ctx);
+                            let text = node.text.as_str();
+                            resize_rect_min(
+                                original,
+                                handle,
+                                dx,
+                                dy,
+                                width_for_text(text, &measure),
+                                |width| height_for_text(text, width, &measure),
+                            )
+                        }
+                        None => resize_rect(original, handle, dx, dy),
+                    };
                 }
             });
         } else if edge_state.is_creating {
@@ -1865,11 +4129,52 @@ pub fn App() -> impl IntoView {
                 set_drag_state.update(|s| s.snapshotted = true);
             }
 
+            // Smart guides: snap the dragged selection's bounding box to the
+            // edges/centers of the stationary nodes and remember which axes
+            // aligned so release doesn't grid-snap them away.
+            let current_board = board.get_untracked();
+            let moving_rects: Vec<(f64, f64, f64, f64)> = current_board
+                .nodes
+                .iter()
+                .filter_map(|n| {
+                    let (sx, sy) = current_drag.node_start_positions.get(&n.id)?;
+                    Some((sx + dx, sy + dy, sx + dx + n.width, sy + dy + n.height))
+                })
+                .collect();
+            let (snap_dx, snap_dy, guides) = match moving_rects.split_first() {
+                Some((first, rest)) => {
+                    let moving = rest.iter().fold(*first, |acc, r| {
+                        (
+                            acc.0.min(r.0),
+                            acc.1.min(r.1),
+                            acc.2.max(r.2),
+                            acc.3.max(r.3),
+                        )
+                    });
+                    let others: Vec<(f64, f64, f64, f64)> = current_board
+                        .nodes
+                        .iter()
+                        .filter(|n| !current_drag.node_start_positions.contains_key(&n.id))
+                        .map(|n| (n.x, n.y, n.x + n.width, n.y + n.height))
+                        .collect();
+                    alignment_snap(moving, &others, ALIGN_SNAP_PX / cam.zoom)
+                }
+                None => (0.0, 0.0, Vec::new()),
+            };
+            let aligned = (
+                guides.iter().any(|g| matches!(g, Guide::Vertical(_))),
+                guides.iter().any(|g| matches!(g, Guide::Horizontal(_))),
+            );
+            if current_drag.aligned != aligned {
+                set_drag_state.update(|s| s.aligned = aligned);
+            }
+            set_alignment_guides.set(guides);
+
             set_board.update(|b| {
                 for (id, (start_x, start_y)) in &current_drag.node_start_positions {
                     if let Some(node) = b.nodes.iter_mut().find(|n| &n.id == id) {
-                        node.x = start_x + dx;
-                        node.y = start_y + dy;
+                        node.x = start_x + dx + snap_dx;
+                        node.y = start_y + dy + snap_dy;
                     }
                 }
             });
@@ -1893,6 +4198,19 @@ pub fn App() -> impl IntoView {
                 c.x = current_pan.camera_start_x - dx;
                 c.y = current_pan.camera_start_y - dy;
             });
+
+            // Track release velocity for inertia, lightly smoothed so one jittery
+            // sample doesn't decide the glide.
+            let now = js_sys::Date::now();
+            let (last_x, last_y, last_t) = current_pan.last_sample;
+            let dt = now - last_t;
+            if dt > 0.0 {
+                let (vx, vy) = ((canvas_x - last_x) / dt, (canvas_y - last_y) / dt);
+                set_pan_state.update(|p| {
+                    p.velocity = (0.7 * vx + 0.3 * p.velocity.0, 0.7 * vy + 0.3 * p.velocity.1);
+                    p.last_sample = (canvas_x, canvas_y, now);
+                });
+            }
         } else {
             // Update cursor based on what we're hovering over
             let cam = camera.get_untracked();
@@ -1904,21 +4222,74 @@ pub fn App() -> impl IntoView {
             // Track mouse position for paste operations
             set_last_mouse_world_pos.set((world_x, world_y));
 
+            // Only write on change so idle mouse moves don't schedule frames.
+            let hovered = shown_nodes(
+                &current_board,
+                &hidden_types.get_untracked(),
+                &collapse.read_untracked().hidden,
+            )
+            .into_iter()
+            .rev()
+            .find(|n| n.contains_point(world_x, world_y))
+            .map(|n| n.id.clone());
+            let hover_changed = hovered_node.get_untracked() != hovered;
+            // A showing tooltip stays put while the cursor stays on its node;
+            // otherwise each move restarts the rest timer.
+            if hover_changed || tooltip.get_untracked().is_none() {
+                hide_tooltip();
+                let text = hovered.as_ref().and_then(|id| {
+                    let node = current_board.nodes.iter().find(|n| &n.id == id)?;
+                    md_file_cache.with_untracked(|cache| tooltip_text(node, cache))
+                });
+                if let Some(text) = text {
+                    let (x, y) = (ev.client_x() as f64, ev.client_y() as f64);
+                    tooltip_timer.set_value(Some(gloo_timers::callback::Timeout::new(
+                        TOOLTIP_DELAY_MS,
+                        move || tooltip.set(Some((x, y, text))),
+                    )));
+                }
+            }
+            if hover_changed {
+                set_hovered_node.set(hovered);
+            }
+
             let mut new_cursor = "crosshair";
 
+            let resize_cursor = |handle| match handle {
+                ResizeHandle::TopLeft | ResizeHandle::BottomRight => "nwse-resize",
+                ResizeHandle::TopRight | ResizeHandle::BottomLeft => "nesw-resize",
+            };
+            let individual_handles = show_individual_handles(current_selected.len());
+            let group_handle =
+                group_handle_at(&current_board, &current_selected, &cam, world_x, world_y);
+            if let Some((_, handle)) = group_handle {
+                new_cursor = resize_cursor(handle);
+            }
+
             // Check if over a resize handle on a selected node
-            for node in current_board.nodes.iter().rev() {
-                if current_selected.contains(&node.id) {
+            for node in shown_nodes(
+                &current_board,
+                &hidden_types.get_untracked(),
+                &collapse.read_untracked().hidden,
+            )
+            .into_iter()
+            .rev()
+            {
+                if group_handle.is_some() {
+                    break;
+                }
+                if individual_handles && current_selected.contains(&node.id) {
                     if let Some(handle) = node.resize_handle_at(world_x, world_y, handle_size) {
-                        new_cursor = match handle {
-                            ResizeHandle::TopLeft | ResizeHandle::BottomRight => "nwse-resize",
-                            ResizeHandle::TopRight | ResizeHandle::BottomLeft => "nesw-resize",
-                        };
+                        new_cursor = resize_cursor(handle);
                         break;
                     }
                 }
                 if node.contains_point(world_x, world_y) {
-                    new_cursor = "move";
+                    let connecting = (settings.get_untracked().edge_trigger
+                        == EdgeTrigger::ConnectMode
+                        && connect_mode.get_untracked())
+                        || sticky_connect.with_untracked(|s| s.click_edge(&node.id).is_some());
+                    new_cursor = if connecting { "alias" } else { "move" };
                     break;
                 }
             }
@@ -1928,7 +4299,8 @@ pub fn App() -> impl IntoView {
     };
 
     let on_mouse_up = move |ev: web_sys::MouseEvent| {
-        let was_panning = pan_state.get_untracked().is_panning;
+        let released_pan = pan_state.get_untracked();
+        let was_panning = released_pan.is_panning;
         let was_dragging = drag_state.get_untracked().is_dragging;
         let was_resizing = resize_state.get_untracked().is_resizing;
         let resize_snapshotted = resize_state.get_untracked().snapshotted;
@@ -1936,6 +4308,26 @@ pub fn App() -> impl IntoView {
         let current_drag = drag_state.get_untracked();
         let edge_state = edge_creation.get_untracked();
 
+        // A gesture that carried the pointer past the click slop counts as a
+        // manipulation, so a double-click completed right after it is ignored.
+        let gesture_start = if was_resizing {
+            // Resize tracks its start in world units; the camera holds still.
+            let (x, y) = resize_state.with_untracked(|r| (r.start_mouse_x, r.start_mouse_y));
+            Some(camera.with_untracked(|c| c.world_to_screen(x, y)))
+        } else if was_dragging || current_drag.is_box_selecting {
+            Some((current_drag.start_x, current_drag.start_y))
+        } else if was_panning {
+            Some((released_pan.start_x, released_pan.start_y))
+        } else {
+            None
+        };
+        let travelled = gesture_start
+            .zip(event_canvas_pos(canvas_ref, &ev))
+            .is_some_and(|((sx, sy), (x, y))| (x - sx).hypot(y - sy) > GESTURE_SLOP_PX);
+        if travelled {
+            last_gesture_end.set_value(js_sys::Date::now());
+        }
+
         if was_resizing {
             set_resize_state.set(ResizeState::default());
 
@@ -1952,22 +4344,58 @@ pub fn App() -> impl IntoView {
                 let cam = camera.get_untracked();
                 if let Some((world_x, world_y)) = event_world_pos(canvas_ref, &cam, &ev) {
                     let current_board = board.get_untracked();
-                    if let Some(target) = current_board
-                        .nodes
-                        .iter()
-                        .rev()
-                        .find(|n| n.contains_point(world_x, world_y))
+                    if let Some(target) = shown_nodes(
+                        &current_board,
+                        &hidden_types.get_untracked(),
+                        &collapse.read_untracked().hidden,
+                    )
+                    .into_iter()
+                    .rev()
+                    .find(|n| n.contains_point(world_x, world_y))
                     {
-                        if &target.id != from_id {
+                        if &target.id != from_id
+                            && !ops::edge_exists(&current_board, from_id, &target.id)
+                            && !refuse_cycle(&current_board, from_id, &target.id)
+                        {
+                            // The side nearest the drop pins where it enters.
+                            let rect = (target.x, target.y, target.width, target.height);
                             dispatch.apply(
                                 BoardAction::CreateEdge {
                                     id: uuid::Uuid::new_v4().to_string(),
                                     from_node: from_id.clone(),
                                     to_node: target.id.clone(),
+                                    to_port: Some(nearest_port(rect, (world_x, world_y))),
                                 },
                                 None,
                             );
+                            sticky_connect.update(|s| *s = s.connected(from_id));
                         }
+                    } else if (edge_state.current_x - edge_state.start_x)
+                        .hypot(edge_state.current_y - edge_state.start_y)
+                        >= EDGE_NEW_NODE_MIN_PX
+                    {
+                        // Released over empty canvas: create a node there and
+                        // connect it, then drop straight into editing it.
+                        let (mut node, edge) = connected_node_at(
+                            from_id,
+                            world_x,
+                            world_y,
+                            (
+                                uuid::Uuid::new_v4().to_string(),
+                                uuid::Uuid::new_v4().to_string(),
+                            ),
+                        );
+                        board.with_untracked(|b| b.settings.apply_defaults(&mut node));
+                        let new_id = node.id.clone();
+                        dispatch.apply(
+                            BoardAction::PasteNodes {
+                                nodes: vec![node],
+                                edges: vec![edge],
+                            },
+                            Some([new_id.clone()].into_iter().collect()),
+                        );
+                        set_editing_node.set(Some(new_id));
+                        sticky_connect.update(|s| *s = s.connected(from_id));
                     }
                 }
             }
@@ -1978,12 +4406,15 @@ pub fn App() -> impl IntoView {
         if current_drag.is_box_selecting {
             if let Some((min_x, min_y, max_x, max_y)) = selection_box.get_untracked() {
                 let current_board = board.get_untracked();
-                let nodes_in_box: HashSet<String> = current_board
-                    .nodes
-                    .iter()
-                    .filter(|n| intersects_box(n, min_x, min_y, max_x, max_y))
-                    .map(|n| n.id.clone())
-                    .collect();
+                let nodes_in_box: HashSet<String> = shown_nodes(
+                    &current_board,
+                    &hidden_types.get_untracked(),
+                    &collapse.read_untracked().hidden,
+                )
+                .into_iter()
+                .filter(|n| intersects_box(n, min_x, min_y, max_x, max_y))
+                .map(|n| n.id.clone())
+                .collect();
 
                 if ev.shift_key() {
                     set_selected_nodes.update(|s| s.extend(nodes_in_box));
@@ -1994,6 +4425,7 @@ pub fn App() -> impl IntoView {
             set_selection_box.set(None);
         }
 
+        set_alignment_guides.set(Vec::new());
         set_drag_state.set(DragState::default());
         set_pan_state.set(PanState::default());
 
@@ -2003,21 +4435,121 @@ pub fn App() -> impl IntoView {
             // Snap-to-grid on release (F110): align each moved node's top-left to
             // the documented 50px grid so layouts stay tidy. The undo snapshot was
             // already taken at drag start, so the snapped position is what persists.
+            // Axes held by a smart-guide alignment keep their aligned position.
             let moved_ids: HashSet<&String> = current_drag.node_start_positions.keys().collect();
+            let (aligned_x, aligned_y) = current_drag.aligned;
+            // Alt-dropping a single node onto an edge splices it into that edge.
+            // Tested at the release position (before grid snap), against the
+            // node's center; rides on the drag's undo snapshot.
+            let split_target = if ev.alt_key() && moved_ids.len() == 1 {
+                let cam = camera.get_untracked();
+                board.with_untracked(|b| {
+                    let dragged = b.nodes.iter().find(|n| moved_ids.contains(&n.id))?;
+                    let (cx, cy) = dragged.center();
+                    edge_near_point(
+                        b,
+                        cx,
+                        cy,
+                        EDGE_DROP_PX / cam.zoom,
+                        Some(&dragged.id),
+                        &hidden_types.get_untracked(),
+                        &collapse.read_untracked().hidden,
+                        settings.get_untracked().edge_style,
+                    )
+                    .map(|e| (e.id.clone(), dragged.id.clone()))
+                    .filter(|(edge_id, node_id)| !refuse_split(b, edge_id, node_id))
+                })
+            } else {
+                None
+            };
+            let grid_snap = settings.get_untracked().grid_snap;
             set_board.update(|b| {
                 for node in b.nodes.iter_mut() {
                     if moved_ids.contains(&node.id) {
-                        node.x = snap_to_grid(node.x, GRID_SIZE);
-                        node.y = snap_to_grid(node.y, GRID_SIZE);
+                        if grid_snap && !aligned_x {
+                            node.x = snap_to_grid(node.x, GRID_SIZE);
+                        }
+                        if grid_snap && !aligned_y {
+                            node.y = snap_to_grid(node.y, GRID_SIZE);
+                        }
+                    }
+                }
+                if let Some((edge_id, node_id)) = &split_target {
+                    split_edge_with(
+                        b,
+                        edge_id,
+                        node_id,
+                        (
+                            uuid::Uuid::new_v4().to_string(),
+                            uuid::Uuid::new_v4().to_string(),
+                        ),
+                    );
+                }
+                // Dropping a member of an auto-stacked group reorders its
+                // column, which stays anchored where it was before the drag
+                // (unless the whole column was dragged, which moves it).
+                let stacked: HashSet<String> = b
+                    .nodes
+                    .iter()
+                    .filter(|n| moved_ids.contains(&n.id))
+                    .filter_map(|n| n.group.clone())
+                    .filter(|g| b.settings.is_stacked(g))
+                    .collect();
+                let no_anchor = HashMap::new();
+                for group in stacked {
+                    let whole_column = b
+                        .nodes
+                        .iter()
+                        .filter(|n| n.group.as_deref() == Some(group.as_str()))
+                        .all(|n| moved_ids.contains(&n.id));
+                    let before = if whole_column {
+                        &no_anchor
+                    } else {
+                        &current_drag.node_start_positions
+                    };
+                    if let Some(frame) = group_frame(b, &group, before) {
+                        restack_group(b, &group, frame);
                     }
                 }
             });
             request_save.call();
         }
 
-        // Pan-end: persist the new viewport (F105).
+        // A link node clicked without dragging: copy its URL and say so.
+        if let Some(url) = current_drag.link_click.filter(|_| !drag_snapshotted) {
+            spawn_local(async move {
+                let Some(window) = web_sys::window() else {
+                    return;
+                };
+                let clipboard = window.navigator().clipboard();
+                if wasm_bindgen_futures::JsFuture::from(clipboard.write_text(&url))
+                    .await
+                    .is_ok()
+                {
+                    show_toast(toast, "Link copied to clipboard");
+                }
+            });
+        }
+
+        // Pan-end: persist the new viewport (F105). With inertia on and the
+        // pointer still moving at release, glide first and persist at rest.
         if was_panning {
-            persist_camera_now();
+            let released_recently =
+                js_sys::Date::now() - released_pan.last_sample.2 <= INERTIA_RELEASE_WINDOW_MS;
+            let velocity = step_inertia(released_pan.velocity, 0.0, PAN_FRICTION);
+            if settings.get_untracked().pan_inertia && released_recently && velocity != (0.0, 0.0) {
+                let run = inertia_generation.get_value();
+                run_pan_inertia(
+                    velocity,
+                    camera,
+                    set_camera,
+                    inertia_generation,
+                    run,
+                    persist_camera_now,
+                );
+            } else {
+                persist_camera_now();
+            }
         }
     };
 
@@ -2037,8 +4569,10 @@ pub fn App() -> impl IntoView {
     // gesture, F20). It only resets the transient hover cursor; the gesture itself
     // continues via the document-level listeners registered below.
     let on_mouse_leave = move |_ev: web_sys::MouseEvent| {
+        hide_tooltip();
         if !gesture_active() {
             set_cursor_style.set("crosshair".to_string());
+            set_hovered_node.set(None);
         }
     };
 
@@ -2132,13 +4666,23 @@ pub fn App() -> impl IntoView {
     }
 
     let on_wheel = move |ev: web_sys::WheelEvent| {
+        hide_tooltip();
         ev.prevent_default();
+        inertia_generation.update_value(|g| *g = g.wrapping_add(1));
 
         let Some((canvas_x, canvas_y)) = event_canvas_pos(canvas_ref, &ev) else {
             return;
         };
 
-        let zoom_factor = if ev.delta_y() < 0.0 { 1.1 } else { 0.9 };
+        let prefs = settings.get_untracked();
+        let mut delta_y = ev.delta_y();
+        if ev.delta_mode() == web_sys::WheelEvent::DOM_DELTA_LINE {
+            delta_y *= WHEEL_LINE_PX;
+        }
+        if prefs.natural_scroll {
+            delta_y = -delta_y;
+        }
+        let zoom_factor = zoom_factor(delta_y, prefs.zoom_sensitivity);
 
         set_camera.update(|c| {
             let (world_x, world_y) = c.screen_to_world(canvas_x, canvas_y);
@@ -2162,11 +4706,14 @@ pub fn App() -> impl IntoView {
             };
 
             let current_board = board.get_untracked();
-            let clicked_node = current_board
-                .nodes
-                .iter()
-                .rev()
-                .find(|n| n.contains_point(world_x, world_y));
+            let clicked_node = shown_nodes(
+                &current_board,
+                &hidden_types.get_untracked(),
+                &collapse.read_untracked().hidden,
+            )
+            .into_iter()
+            .rev()
+            .find(|n| n.contains_point(world_x, world_y));
 
             if let Some(node) = clicked_node {
                 if node.node_type == NodeType::Image {
@@ -2178,26 +4725,57 @@ pub fn App() -> impl IntoView {
                 } else if node.node_type == NodeType::Md {
                     // Open MD in modal (view mode)
                     set_modal_md.set(Some((node.id.clone(), false)));
-                } else if node.node_type == NodeType::Link && is_local_md_file(&node.text) {
-                    // Open local .md file in modal (view mode)
+                } else if node.node_type == NodeType::Link && is_md_link(&node.text) {
+                    // Open a linked .md file (local or remote) in modal (view mode)
                     set_modal_md.set(Some((node.id.clone(), false)));
                 } else if node.node_type == NodeType::Link {
                     // Open regular link in browser
                     if let Some(window) = web_sys::window() {
                         let _ = window.open_with_url_and_target(&node.text, "_blank");
                     }
-                } else {
+                } else if !settings.get_untracked().read_only {
                     // Edit mode for text, idea, note nodes
                     set_editing_node.set(Some(node.id.clone()));
                 }
-            } else {
+            } else if !settings.get_untracked().read_only {
+                // On an edge: edit its label instead of creating a node.
+                if let Some(edge) = edge_near_point(
+                    &current_board,
+                    world_x,
+                    world_y,
+                    10.0 / cam.zoom,
+                    None,
+                    &hidden_types.get_untracked(),
+                    &collapse.read_untracked().hidden,
+                    settings.get_untracked().edge_style,
+                ) {
+                    set_selected_edge.set(Some(edge.id.clone()));
+                    editing_edge.set(Some(edge.id.clone()));
+                    return;
+                }
+                // A double-click straight after a drag, resize, pan or box
+                // select is a misfire, not a request for a new node. Editing
+                // and opening nodes above stay immediate.
+                if should_suppress_doubleclick(
+                    last_gesture_end.get_value(),
+                    js_sys::Date::now(),
+                    DOUBLECLICK_SUPPRESS_MS,
+                ) {
+                    return;
+                }
+                let last = last_created_node
+                    .get_value()
+                    .filter(|_| settings.get_untracked().stack_new_nodes)
+                    .and_then(|id| current_board.nodes.iter().find(|n| n.id == id));
+                let (x, y) = aligned_new_position(last, (world_x, world_y), NEW_NODE_COLUMN_SNAP);
                 let new_node = Node::new(
                     uuid::Uuid::new_v4().to_string(),
-                    world_x - 100.0,
-                    world_y - 50.0,
+                    x,
+                    y,
                     "New Node".to_string(),
                 );
                 let new_id = new_node.id.clone();
+                last_created_node.set_value(Some(new_id.clone()));
 
                 dispatch.apply(
                     BoardAction::CreateNode(new_node),
@@ -2208,8 +4786,41 @@ pub fn App() -> impl IntoView {
         }
     };
 
+    // Abort the gesture in progress, if any: a drag or resize snaps its nodes
+    // back to where they started and drops the snapshot it took; an edge being
+    // drawn or a selection box is simply discarded. Returns whether anything
+    // was cancelled, so Escape only clears the selection when idle.
+    let cancel_gesture = move || -> bool {
+        let current_drag = drag_state.get_untracked();
+        let current_resize = resize_state.get_untracked();
+        if current_resize.is_resizing {
+            if current_resize.snapshotted {
+                set_board.update(|b| revert_resize(b, &current_resize));
+                dispatch.discard_snapshot();
+            }
+            set_resize_state.set(ResizeState::default());
+            return true;
+        }
+        if edge_creation.get_untracked().is_creating {
+            set_edge_creation.set(EdgeCreationState::default());
+            return true;
+        }
+        if current_drag.is_dragging || current_drag.is_box_selecting {
+            if current_drag.is_dragging && current_drag.snapshotted {
+                set_board.update(|b| revert_drag(b, &current_drag.node_start_positions));
+                dispatch.discard_snapshot();
+            }
+            set_alignment_guides.set(Vec::new());
+            set_selection_box.set(None);
+            set_drag_state.set(DragState::default());
+            return true;
+        }
+        false
+    };
+
     let on_keydown = move |ev: web_sys::KeyboardEvent| {
-        if editing_node.get_untracked().is_some() {
+        inertia_generation.update_value(|g| *g = g.wrapping_add(1));
+        if editing_node.get_untracked().is_some() || editing_edge.get_untracked().is_some() {
             return;
         }
         // While a modal is open, swallow canvas shortcuts (F113). The document-level
@@ -2220,6 +4831,11 @@ pub fn App() -> impl IntoView {
         }
 
         let key = ev.key();
+        if settings.get_untracked().read_only
+            && is_mutating_shortcut(&key, ev.meta_key() || ev.ctrl_key(), ev.alt_key())
+        {
+            return;
+        }
         let selected = selected_nodes.get_untracked();
         let edge_sel = selected_edge.get_untracked();
 
@@ -2240,74 +4856,36 @@ pub fn App() -> impl IntoView {
                         BoardAction::DeleteSelected {
                             node_ids: vec![],
                             edge_id: Some(edge_id),
+                            assets_dir: String::new(),
                         },
                         None,
                     );
                     set_selected_edge.set(None);
                 } else if !selected.is_empty() {
-                    // Asset cleanup is modeled as a SideEffect by the reducer.
-                    dispatch.apply(
-                        BoardAction::DeleteSelected {
-                            node_ids: selected.into_iter().collect(),
-                            edge_id: None,
-                        },
-                        Some(HashSet::new()),
-                    );
+                    dispatch.delete_nodes(selected.into_iter().collect());
                 }
             }
             "c" if (ev.meta_key() || ev.ctrl_key()) && !selected.is_empty() => {
-                let current_board = board.get_untracked();
-                let copied_nodes: Vec<Node> = current_board
-                    .nodes
-                    .iter()
-                    .filter(|n| selected.contains(&n.id))
-                    .cloned()
-                    .collect();
-                let copied_edges: Vec<Edge> = current_board
-                    .edges
-                    .iter()
-                    .filter(|e| selected.contains(&e.from_node) && selected.contains(&e.to_node))
-                    .cloned()
-                    .collect();
-                set_node_clipboard.set(Some((copied_nodes, copied_edges)));
+                let copied = board.with_untracked(|b| ops::extract_subgraph(b, &selected));
+                set_node_clipboard.set(Some((copied.nodes, copied.edges)));
+                paste_count.set_value(0);
             }
             "v" if ev.meta_key() || ev.ctrl_key() => {
                 if let Some((ref nodes, ref edges)) = node_clipboard.get_untracked() {
                     if !nodes.is_empty() {
                         ev.prevent_default();
 
-                        // Calculate center of copied nodes
-                        let cx = nodes.iter().map(|n| n.x + n.width / 2.0).sum::<f64>()
-                            / nodes.len() as f64;
-                        let cy = nodes.iter().map(|n| n.y + n.height / 2.0).sum::<f64>()
-                            / nodes.len() as f64;
-                        let (mouse_x, mouse_y) = last_mouse_world_pos.get_untracked();
-
-                        // Build old_id -> new_id mapping
-                        let id_map: HashMap<String, String> = nodes
-                            .iter()
-                            .map(|n| (n.id.clone(), uuid::Uuid::new_v4().to_string()))
-                            .collect();
-
-                        let new_nodes: Vec<Node> = nodes
-                            .iter()
-                            .map(|n| Node {
-                                id: id_map[&n.id].clone(),
-                                x: n.x - cx + mouse_x,
-                                y: n.y - cy + mouse_y,
-                                ..n.clone()
-                            })
-                            .collect();
+                        let repeat = paste_count.get_value();
+                        paste_count.set_value(repeat + 1);
+                        let (dx, dy) = paste_offset(
+                            nodes,
+                            settings.get_untracked().paste_mode,
+                            last_mouse_world_pos.get_untracked(),
+                            repeat,
+                        );
 
-                        let new_edges: Vec<Edge> = edges
-                            .iter()
-                            .map(|e| Edge {
-                                id: uuid::Uuid::new_v4().to_string(),
-                                from_node: id_map[&e.from_node].clone(),
-                                to_node: id_map[&e.to_node].clone(),
-                                label: e.label.clone(),
-                            })
-                            .collect();
+                        let (new_nodes, new_edges) =
+                            remap_copy(nodes, edges, (dx, dy), || uuid::Uuid::new_v4().to_string());
 
                         let new_ids: HashSet<String> =
                             new_nodes.iter().map(|n| n.id.clone()).collect();
@@ -2323,6 +4901,20 @@ pub fn App() -> impl IntoView {
                 }
                 // If no internal clipboard, let ClipboardEvent fire for image paste
             }
+            "d" if (ev.meta_key() || ev.ctrl_key()) && !selected.is_empty() => {
+                // Duplicate in place: copies of the selection (and the edges
+                // inside it) just down-right of the originals, then selected.
+                ev.prevent_default();
+                let copied = board.with_untracked(|b| ops::extract_subgraph(b, &selected));
+                let (nodes, edges) = remap_copy(
+                    &copied.nodes,
+                    &copied.edges,
+                    (DUPLICATE_OFFSET, DUPLICATE_OFFSET),
+                    || uuid::Uuid::new_v4().to_string(),
+                );
+                let new_ids: HashSet<String> = nodes.iter().map(|n| n.id.clone()).collect();
+                dispatch.apply(BoardAction::PasteNodes { nodes, edges }, Some(new_ids));
+            }
             "t" | "T" if !selected.is_empty() => {
                 // Tapping `T` repeatedly to land on a type coalesces into one
                 // undo step rather than one-per-press.
@@ -2332,33 +4924,278 @@ pub fn App() -> impl IntoView {
                     Some("cycle-type"),
                 );
             }
-            "a" | "A" if ev.meta_key() || ev.ctrl_key() => {
-                // Select all nodes (F103). Edge selection is mutually exclusive
-                // with a node multi-selection, so clear it.
+            "s" | "S" if !(ev.meta_key() || ev.ctrl_key()) && !selected.is_empty() => {
+                // Step status todo → in-progress → done → none; a quick run of
+                // presses is one undo step, like `T`.
+                dispatch.apply_coalesced(
+                    BoardAction::CycleStatus(selected.into_iter().collect()),
+                    None,
+                    Some("cycle-status"),
+                );
+            }
+            "p" | "P" if !(ev.meta_key() || ev.ctrl_key()) && !selected.is_empty() => {
+                // Step priority none → 1 … 5 → none, coalesced the same way.
+                dispatch.apply_coalesced(
+                    BoardAction::CyclePriority(selected.into_iter().collect()),
+                    None,
+                    Some("cycle-priority"),
+                );
+            }
+            "ArrowLeft" | "ArrowRight" | "ArrowUp" | "ArrowDown"
+                if ev.ctrl_key() && ev.alt_key() && !selected.is_empty() =>
+            {
+                // Keyboard resize: grow/shrink the selection from its bottom-right
+                // corner. A run of presses coalesces into one undo step.
                 ev.prevent_default();
-                let all_ids: HashSet<String> = board
-                    .get_untracked()
+                let step = if ev.shift_key() {
+                    KEY_RESIZE_STEP_LARGE
+                } else {
+                    KEY_RESIZE_STEP
+                };
+                let (dx, dy) = match key.as_str() {
+                    "ArrowLeft" => (-step, 0.0),
+                    "ArrowRight" => (step, 0.0),
+                    "ArrowUp" => (0.0, -step),
+                    _ => (0.0, step),
+                };
+                let current_board = board.get_untracked();
+                for node in current_board
                     .nodes
                     .iter()
+                    .filter(|n| selected.contains(&n.id))
+                {
+                    let (x, y, width, height) = resize_rect(
+                        (node.x, node.y, node.width, node.height),
+                        ResizeHandle::BottomRight,
+                        dx,
+                        dy,
+                    );
+                    dispatch.apply_coalesced(
+                        BoardAction::ResizeNode {
+                            id: node.id.clone(),
+                            x,
+                            y,
+                            width,
+                            height,
+                        },
+                        None,
+                        Some("keyboard-resize"),
+                    );
+                }
+            }
+            "ArrowLeft" | "ArrowRight" | "ArrowUp" | "ArrowDown"
+                if ev.alt_key() && !(ev.meta_key() || ev.ctrl_key()) && selected.len() >= 2 =>
+            {
+                // Alt+Arrow lines the selection up on that side of its bounds;
+                // Alt+Shift+Arrow centers it along that axis.
+                ev.prevent_default();
+                if let Some(to) = arrow_alignment(&key, ev.shift_key()) {
+                    dispatch.apply(
+                        BoardAction::Align {
+                            ids: selected.into_iter().collect(),
+                            to,
+                        },
+                        None,
+                    );
+                }
+            }
+            "m" | "M" if !(ev.meta_key() || ev.ctrl_key()) && !selected.is_empty() => {
+                // Show text/idea/note content as rendered markdown (or back).
+                dispatch.apply(
+                    BoardAction::ToggleMarkdown(selected.into_iter().collect()),
+                    None,
+                );
+            }
+            "c" | "C" if !(ev.meta_key() || ev.ctrl_key()) && selected.len() >= 2 => {
+                // Merge the selection into one summary node that keeps the
+                // cluster's outside edges. It starts with the first node's text
+                // and opens in the editor so it can be retitled straight away.
+                let first = board.with_untracked(|b| {
+                    b.nodes
+                        .iter()
+                        .find(|n| selected.contains(&n.id))
+                        .map(|n| (n.id.clone(), n.text.clone()))
+                });
+                if let Some((id, text)) = first {
+                    let count = selected.len();
+                    dispatch.apply(
+                        BoardAction::CollapseNodes {
+                            ids: selected.into_iter().collect(),
+                            text,
+                        },
+                        Some([id.clone()].into_iter().collect()),
+                    );
+                    set_editing_node.set(Some(id));
+                    show_toast(toast, format!("Collapsed {count} nodes into one"));
+                }
+            }
+            "L" if !(ev.meta_key() || ev.ctrl_key()) => {
+                // Shift+L: force-directed layout of the whole board. The seed is
+                // fixed, so the same board always settles the same way.
+                if board.with_untracked(|b| !b.nodes.is_empty()) {
+                    dispatch.apply(
+                        BoardAction::AutoLayout {
+                            seed: AUTO_LAYOUT_SEED,
+                        },
+                        None,
+                    );
+                    show_toast(
+                        toast,
+                        "Auto-layout applied (Cmd/Ctrl+Z to undo)".to_string(),
+                    );
+                }
+            }
+            "x" | "X" if !(ev.meta_key() || ev.ctrl_key()) => {
+                // Swap selection focus: an edge becomes its two nodes, and two
+                // nodes become the edge joining them.
+                if let Some(edge_id) = &edge_sel {
+                    if let Some((from, to)) =
+                        board.with_untracked(|b| ops::edge_endpoints(b, edge_id))
+                    {
+                        set_selected_nodes.set([from, to].into_iter().collect());
+                        set_selected_edge.set(None);
+                    }
+                } else if let [first, second] = selected.iter().collect::<Vec<_>>()[..] {
+                    if let Some(edge) =
+                        board.with_untracked(|b| ops::edge_between(b, first, second))
+                    {
+                        set_selected_nodes.set(HashSet::new());
+                        set_selected_edge.set(Some(edge));
+                    }
+                }
+            }
+            "o" | "O" if !(ev.meta_key() || ev.ctrl_key()) => {
+                // Step through the nodes no edge touches: select the next one and
+                // center it, wrapping at the end of the list.
+                let next = board.with_untracked(|b| {
+                    let orphans = ops::orphan_nodes(b);
+                    if orphans.is_empty() {
+                        return None;
+                    }
+                    let index = orphan_cursor.get_value() % orphans.len();
+                    orphan_cursor.set_value(index + 1);
+                    b.nodes
+                        .iter()
+                        .find(|n| n.id == orphans[index])
+                        .map(|n| (n.id.clone(), n.x + n.width / 2.0, n.y + n.height / 2.0))
+                });
+                if let Some((id, wx, wy)) = next {
+                    set_selected_nodes.set([id].into_iter().collect());
+                    set_selected_edge.set(None);
+                    if let Some(canvas) = canvas_ref.get_untracked() {
+                        let rect = canvas.get_bounding_client_rect();
+                        let (cw, ch) = (rect.width(), rect.height());
+                        let mut target = camera.get_untracked();
+                        target.x = wx - cw / target.zoom / 2.0;
+                        target.y = wy - ch / target.zoom / 2.0;
+                        fly_camera.to(target);
+                    }
+                }
+            }
+            "e" | "E" if !(ev.meta_key() || ev.ctrl_key()) => {
+                let trigger = settings.get_untracked().edge_trigger;
+                if trigger == EdgeTrigger::ConnectMode {
+                    let on = trigger.toggle_connect_mode(connect_mode.get_untracked());
+                    connect_mode.set(on);
+                    show_toast(
+                        toast,
+                        if on {
+                            "Connect mode on"
+                        } else {
+                            "Connect mode off"
+                        },
+                    );
+                }
+            }
+            "h" | "H" if !(ev.meta_key() || ev.ctrl_key()) => {
+                let next = sticky_connect.get_untracked().toggle();
+                show_toast(
+                    toast,
+                    if next == StickyConnect::Off {
+                        "Sticky connect off"
+                    } else {
+                        "Sticky connect on \u{2014} draw an edge to arm its source"
+                    },
+                );
+                sticky_connect.set(next);
+            }
+            "]" | "[" if !(ev.meta_key() || ev.ctrl_key()) => {
+                // Cycle which of the nodes stacked under the pointer is on top.
+                let (x, y) = last_mouse_world_pos.get_untracked();
+                if board.with_untracked(|b| nodes_at_point(b, x, y).len() >= 2) {
+                    dispatch.apply(
+                        BoardAction::CycleStack {
+                            x,
+                            y,
+                            reverse: key == "[",
+                        },
+                        None,
+                    );
+                }
+            }
+            "g" | "G" if (ev.meta_key() || ev.ctrl_key()) && !selected.is_empty() => {
+                // Group the selection under a fresh id (Cmd+G), or clear the
+                // group on every selected node (Cmd+Shift+G).
+                ev.prevent_default();
+                let ids = selected.into_iter().collect();
+                if ev.shift_key() {
+                    dispatch.apply(
+                        BoardAction::AssignGroup {
+                            ids,
+                            group: None,
+                            color: None,
+                        },
+                        None,
+                    );
+                } else {
+                    dispatch.group_nodes(ids);
+                }
+            }
+            "a" | "A" if ev.meta_key() || ev.ctrl_key() => {
+                // Select all shown nodes (F103): nothing hidden by the type
+                // filter or a collapsed ancestor, so a Delete right after can't
+                // remove what the user can't see. Edge selection is mutually
+                // exclusive with a node multi-selection, so clear it.
+                ev.prevent_default();
+                let all_ids: HashSet<String> = board.with_untracked(|b| {
+                    shown_nodes(
+                        b,
+                        &hidden_types.get_untracked(),
+                        &collapse.read_untracked().hidden,
+                    )
+                    .iter()
                     .map(|n| n.id.clone())
-                    .collect();
+                    .collect()
+                });
                 set_selected_nodes.set(all_ids);
                 set_selected_edge.set(None);
             }
-            "f" | "F" if ev.meta_key() || ev.ctrl_key() => {
-                // Open the search overlay (F99). Seed with an empty query; the
-                // overlay input autofocuses.
+            "f" | "F" | "k" | "K" if ev.meta_key() || ev.ctrl_key() => {
+                // Open the search overlay (F99) on Cmd/Ctrl+F or the command-bar
+                // style Cmd/Ctrl+K. Seed with an empty query; the overlay input
+                // autofocuses.
                 ev.prevent_default();
                 set_search_query.set(Some(String::new()));
             }
+            "F" if ev.shift_key() && selected.len() == 1 => {
+                // Zoom the one selected node up to fill the view for reading.
+                let node = board.with_untracked(|b| {
+                    selected
+                        .iter()
+                        .next()
+                        .and_then(|id| b.nodes.iter().find(|n| &n.id == id).cloned())
+                });
+                if let (Some(node), Some(canvas)) = (node, canvas_ref.get_untracked()) {
+                    let rect = canvas.get_bounding_client_rect();
+                    fly_camera.to(focus_node_camera(&node, rect.width(), rect.height()));
+                }
+            }
             "f" | "F" => {
                 // Fit all nodes into view (F102). No-op on an empty board.
                 if let Some(bbox) = nodes_bounding_box(&board.get_untracked().nodes) {
                     if let Some(canvas) = canvas_ref.get_untracked() {
                         let rect = canvas.get_bounding_client_rect();
-                        let cam = fit_camera(bbox, rect.width(), rect.height(), 0.1);
-                        set_camera.set(cam);
-                        persist_camera_now();
+                        fly_camera.to(fit_camera(bbox, rect.width(), rect.height(), 0.1));
                     }
                 }
             }
@@ -2377,6 +5214,14 @@ pub fn App() -> impl IntoView {
                     persist_camera_now();
                 }
             }
+            "Escape" if cancel_gesture() => {}
+            "Escape" if sticky_connect.get_untracked() != StickyConnect::Off => {
+                let next = sticky_connect.get_untracked().escape().unwrap_or_default();
+                if next == StickyConnect::Off {
+                    show_toast(toast, "Sticky connect off");
+                }
+                sticky_connect.set(next);
+            }
             "Escape" => {
                 set_selected_nodes.set(HashSet::new());
                 set_selected_edge.set(None);
@@ -2385,12 +5230,44 @@ pub fn App() -> impl IntoView {
                 set_selection_box.set(None);
                 set_modal_image.set(None);
                 set_modal_md.set(None);
+                context_menu.set(None);
             }
             _ => {}
         }
     };
 
+    // Right-click on a node opens its context menu (and selects it, unless it's
+    // already part of the selection); elsewhere the browser menu stays suppressed.
+    let on_context_menu = move |ev: web_sys::MouseEvent| {
+        ev.prevent_default();
+        let cam = camera.get_untracked();
+        let Some((world_x, world_y)) = event_world_pos(canvas_ref, &cam, &ev) else {
+            return;
+        };
+        let hit = board.with_untracked(|b| {
+            shown_nodes(
+                b,
+                &hidden_types.get_untracked(),
+                &collapse.read_untracked().hidden,
+            )
+            .into_iter()
+            .rev()
+            .find(|n| n.contains_point(world_x, world_y))
+            .map(|n| n.id.clone())
+        });
+        if let Some(id) = hit {
+            if !selected_nodes.get_untracked().contains(&id) {
+                set_selected_nodes.set([id.clone()].into_iter().collect());
+                set_selected_edge.set(None);
+            }
+            context_menu.set(Some((ev.client_x() as f64, ev.client_y() as f64, id)));
+        }
+    };
+
     let on_paste = move |ev: web_sys::ClipboardEvent| {
+        if settings.get_untracked().read_only {
+            return;
+        }
         // If internal node clipboard was used, keydown already handled it
         if node_clipboard
             .get_untracked()
@@ -2402,12 +5279,46 @@ pub fn App() -> impl IntoView {
 
         ev.prevent_default();
 
+        let (world_x, world_y) = last_mouse_world_pos.get_untracked();
+
         if !is_tauri() {
-            return; // Image paste only works in Tauri mode
+            // Image paste only works in Tauri mode. In the browser, formatted
+            // content becomes an md node and plain text a text node.
+            let Some(data) = ev.clipboard_data() else {
+                return;
+            };
+            let markdown = data
+                .get_data("text/html")
+                .ok()
+                .map(|html| html_to_markdown(&html))
+                .filter(|md| !md.is_empty());
+            let (text, node_type) = match markdown {
+                Some(md) => (md, NodeType::Md),
+                None => match data.get_data("text/plain") {
+                    Ok(text) if !text.trim().is_empty() => {
+                        (text.trim().to_string(), NodeType::Text)
+                    }
+                    _ => return,
+                },
+            };
+            let (width, height) = Node::auto_size(&text);
+            let mut new_node = Node::new(
+                uuid::Uuid::new_v4().to_string(),
+                world_x - width / 2.0,
+                world_y - height / 2.0,
+                text,
+            );
+            new_node.width = width;
+            new_node.height = height;
+            new_node.node_type = node_type;
+            let new_id = new_node.id.clone();
+            dispatch.apply(
+                BoardAction::CreateNode(new_node),
+                Some([new_id].into_iter().collect()),
+            );
+            return;
         }
 
-        let (world_x, world_y) = last_mouse_world_pos.get_untracked();
-
         spawn_local(async move {
             let result = invoke("paste_image", JsValue::NULL).await;
 
@@ -2434,12 +5345,17 @@ pub fn App() -> impl IntoView {
                         width: node_width,
                         height: node_height,
                         text: paste_result.path,
+                        title: None,
                         node_type: NodeType::Image,
                         color: None,
                         tags: Vec::new(),
                         status: None,
                         group: None,
                         priority: None,
+                        collapsed: false,
+                        render_markdown: false,
+                        updated_at: None,
+                        extra: Default::default(),
                     };
                     let new_id = new_node.id.clone();
 
@@ -2462,6 +5378,28 @@ pub fn App() -> impl IntoView {
         }
     };
 
+    // A markdown document is merged into the current board as an outline (one
+    // undo step, selected) starting at the viewport center, with fresh ids,
+    // shifted clear of existing nodes.
+    let import_markdown = move |md: &str| {
+        let (cw, ch) = viewport_size.get_untracked();
+        let origin = camera.get_untracked().screen_to_world(cw / 2.0, ch / 2.0);
+        let outline = markdown_to_board(md, origin);
+        let (mut nodes, edges) = remap_copy(&outline.nodes, &outline.edges, (0.0, 0.0), || {
+            uuid::Uuid::new_v4().to_string()
+        });
+        // Keep the outline off whatever already sits at the viewport center.
+        let existing: Vec<NodeRect> = board.with_untracked(|b| {
+            b.nodes
+                .iter()
+                .map(|n| (n.id.clone(), (n.x, n.y, n.width, n.height)))
+                .collect()
+        });
+        pack_without_overlap(&existing, &mut nodes, origin);
+        let new_ids: HashSet<String> = nodes.iter().map(|n| n.id.clone()).collect();
+        dispatch.apply(BoardAction::PasteNodes { nodes, edges }, Some(new_ids));
+    };
+
     let on_file_selected = move |_ev: web_sys::Event| {
         let input = file_input_ref.get().unwrap();
         let input_el: &web_sys::HtmlInputElement = (*input).unchecked_ref();
@@ -2477,6 +5415,7 @@ pub fn App() -> impl IntoView {
             if let Ok(result) = reader_clone.result() {
                 if let Some(text) = result.as_string() {
                     if let Ok(parsed) = serde_json::from_str::<Board>(&text) {
+                        newer_version.set(parsed.newer_version());
                         set_board.set(parsed);
                         request_save.call();
                     }
@@ -2492,25 +5431,94 @@ pub fn App() -> impl IntoView {
         input_el.set_value("");
     };
 
+    let on_import_md = move |_ev: web_sys::MouseEvent| {
+        if settings.get_untracked().read_only {
+            return;
+        }
+        if let Some(input) = md_input_ref.get() {
+            let el: &web_sys::HtmlElement = &input;
+            el.click();
+        }
+    };
+
+    let on_md_selected = move |_ev: web_sys::Event| {
+        let Some(input) = md_input_ref.get() else {
+            return;
+        };
+        let input_el: &web_sys::HtmlInputElement = (*input).unchecked_ref();
+        let Some(file) = input_el.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        let Ok(reader) = web_sys::FileReader::new() else {
+            return;
+        };
+        let reader_clone = reader.clone();
+        let onload = Closure::wrap(Box::new(move || {
+            if let Some(text) = reader_clone.result().ok().and_then(|r| r.as_string()) {
+                import_markdown(&text);
+            }
+        }) as Box<dyn Fn()>);
+        reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+        onload.forget();
+        let _ = reader.read_as_text(&file);
+        input_el.set_value("");
+    };
+
+    // "Compare…" picks a backup to diff against; with one open, the same button
+    // closes the comparison.
+    let on_compare = move |_ev: web_sys::MouseEvent| {
+        if compare_backup.with_untracked(Option::is_some) {
+            compare_backup.set(None);
+        } else if let Some(input) = backup_input_ref.get() {
+            let el: &web_sys::HtmlElement = &input;
+            el.click();
+        }
+    };
+
+    let on_backup_selected = move |_ev: web_sys::Event| {
+        let Some(input) = backup_input_ref.get() else {
+            return;
+        };
+        let input_el: &web_sys::HtmlInputElement = (*input).unchecked_ref();
+        let Some(file) = input_el.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        let Ok(reader) = web_sys::FileReader::new() else {
+            return;
+        };
+        let reader_clone = reader.clone();
+        let onload = Closure::wrap(Box::new(move || {
+            let text = reader_clone.result().ok().and_then(|r| r.as_string());
+            match text.and_then(|t| serde_json::from_str::<Board>(&t).ok()) {
+                Some(backup) => {
+                    let diff = board.with_untracked(|b| ops::diff_boards(&backup, b));
+                    show_toast(toast, diff_summary(&diff));
+                    compare_backup.set(Some(backup));
+                }
+                None => show_toast(toast, "Not a board file"),
+            }
+        }) as Box<dyn Fn()>);
+        reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+        onload.forget();
+        let _ = reader.read_as_text(&file);
+        input_el.set_value("");
+    };
+
     let on_download = move |_ev: web_sys::MouseEvent| {
         let current_board = board.get_untracked();
         let json = serde_json::to_string_pretty(&current_board).unwrap_or_default();
+        download_text("board.json", "application/json", &json);
+    };
 
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
-
-        let array = js_sys::Array::new();
-        array.push(&JsValue::from_str(&json));
-        let opts = web_sys::BlobPropertyBag::new();
-        opts.set_type("application/json");
-        let blob = web_sys::Blob::new_with_str_sequence_and_options(&array, &opts).unwrap();
-
-        let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
-        let a: web_sys::HtmlAnchorElement = document.create_element("a").unwrap().unchecked_into();
-        a.set_href(&url);
-        a.set_download("board.json");
-        a.click();
-        let _ = web_sys::Url::revoke_object_url(&url);
+    // Graph exports for Graphviz / spreadsheets: pure serializers from the
+    // shared types crate, downloaded the same way as board.json.
+    let on_export_dot = move |_ev: web_sys::MouseEvent| {
+        let dot = board_to_dot(&board.get_untracked());
+        download_text("board.dot", "text/vnd.graphviz", &dot);
+    };
+    let on_export_csv = move |_ev: web_sys::MouseEvent| {
+        let csv = board_to_csv(&board.get_untracked());
+        download_text("board-edges.csv", "text/csv", &csv);
     };
 
     // Export the current viewport as a PNG (F104). The canvas backing store is
@@ -2539,42 +5547,219 @@ pub fn App() -> impl IntoView {
         a.click();
     };
 
+    // Export the board (as shown, unsaved edits included) to `board.pdf` next
+    // to board.json. Desktop only: the PDF writer lives in the backend. `mode`
+    // is "cards" (one node per A4 page) or "board" (everything on one page).
+    // An existing `board.pdf` is only replaced after the user confirms.
+    let export_pdf = move |mode: &'static str| {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ExportPdfArgs {
+            board_json: String,
+            mode: &'static str,
+            overwrite: bool,
+        }
+        let Ok(board_json) = serde_json::to_string(&board.get_untracked()) else {
+            return;
+        };
+        spawn_local(async move {
+            let export = |overwrite: bool| {
+                let args = ExportPdfArgs {
+                    board_json: board_json.clone(),
+                    mode,
+                    overwrite,
+                };
+                try_invoke("export_pdf", serde_wasm_bindgen::to_value(&args).unwrap())
+            };
+            let mut result = export(false).await;
+            let exists = result
+                .as_ref()
+                .is_err_and(|e| e.as_string().as_deref() == Some(PDF_EXISTS_ERROR));
+            if exists {
+                let replace = web_sys::window()
+                    .and_then(|w| {
+                        w.confirm_with_message("board.pdf already exists. Replace it?")
+                            .ok()
+                    })
+                    .unwrap_or(false);
+                if !replace {
+                    return;
+                }
+                result = export(true).await;
+            }
+            match result {
+                Ok(path) => show_toast(
+                    toast,
+                    format!("Saved {}", path.as_string().unwrap_or_default()),
+                ),
+                Err(err) => show_toast(
+                    toast,
+                    format!("PDF export failed: {}", err.as_string().unwrap_or_default()),
+                ),
+            }
+        });
+    };
+
+    // Native tooltip with the full URL while hovering a link node (its canvas
+    // label may be elided).
+    let hovered_link_url = move || {
+        let id = hovered_node.get()?;
+        board.with(|b| {
+            b.nodes
+                .iter()
+                .find(|n| n.id == id && n.node_type == NodeType::Link)
+                .map(|n| n.text.clone())
+        })
+    };
+
     view! {
         <div style="width: 100vw; height: 100vh; overflow: hidden; background: var(--bg); position: relative;">
             <canvas
                 node_ref=canvas_ref
                 tabindex="0"
                 style=move || format!("width: 100%; height: 100%; display: block; cursor: {}; outline: none;", cursor_style.get())
+                title=move || hovered_link_url().unwrap_or_default()
                 on:mousedown=on_mouse_down
                 on:mousemove=on_mouse_move
                 on:mouseup=on_mouse_up
                 on:mouseleave=on_mouse_leave
                 on:wheel=on_wheel
                 on:dblclick=on_double_click
+                on:contextmenu=on_context_menu
                 on:keydown=on_keydown
                 on:paste=on_paste
             />
             <NodeEditor/>
+            <EdgeLabelEditor/>
             <MarkdownOverlays/>
+            <GifOverlays/>
             <ImageModal/>
             <MarkdownModal/>
             <ErrorBanner/>
             <SearchOverlay/>
+            <SelectionToolbar/>
+            <TypeFilter/>
+            <TagFilter/>
             <Minimap/>
+            <VoidOverview/>
+            <SettingsPanel/>
+            <Toast/>
+            <NodeTooltip/>
+            <ContextMenu/>
             <div class="hud" style="position: fixed; top: 12px; right: 12px;">
+                {move || board_path.get().map(|path| view! {
+                    <button
+                        class="hud-btn"
+                        style="color: var(--text-dim); font-family: var(--mono);"
+                        title=format!("{path}\nClick to show in the file manager")
+                        on:click=move |_| {
+                            spawn_local(async move {
+                                if let Err(err) = try_invoke("reveal_board_file", JsValue::NULL).await {
+                                    let reason = err.as_string().unwrap_or_default();
+                                    show_toast(toast, format!("Couldn't show the board file: {reason}"));
+                                }
+                            });
+                        }
+                    >
+                        {elide_middle(&path, BOARD_PATH_DISPLAY_CHARS)}
+                    </button>
+                })}
+                <SaveIndicator/>
                 <Show when=move || !is_tauri()>
                     <button class="hud-btn" on:click=on_upload>"Upload board.json"</button>
                     <button class="hud-btn" on:click=on_download>"Download board.json"</button>
                 </Show>
+                <button class="hud-btn" on:click=on_import_md title="Add a markdown document as an outline of heading nodes">"Import .md"</button>
+                <button
+                    class="hud-btn"
+                    on:click=on_compare
+                    title="Outline what changed since a backup (added green, changed amber, removed red)"
+                >
+                    {move || if compare_backup.with(Option::is_some) { "Clear diff" } else { "Compare\u{2026}" }}
+                </button>
                 <button class="hud-btn" on:click=on_export_png>"Export PNG"</button>
+                <button class="hud-btn" on:click=on_export_dot>"Export DOT"</button>
+                <button class="hud-btn" on:click=on_export_csv>"Export CSV"</button>
+                <Show when=is_tauri>
+                    <button
+                        class="hud-btn"
+                        on:click=move |_| export_pdf("cards")
+                        title="Print each node as its own A4 page"
+                    >
+                        "PDF cards"
+                    </button>
+                    <button
+                        class="hud-btn"
+                        on:click=move |_| export_pdf("board")
+                        title="Print the whole board on a single page"
+                    >
+                        "PDF board"
+                    </button>
+                </Show>
+                <button
+                    class="hud-btn"
+                    style=move || {
+                        if settings.get().read_only {
+                            "color: var(--accent-bright); border-color: var(--accent-line);"
+                        } else {
+                            ""
+                        }
+                    }
+                    title=move || {
+                        if settings.get().read_only {
+                            "Board is read-only — click to unlock"
+                        } else {
+                            "Lock the board against edits"
+                        }
+                    }
+                    on:click=move |_| set_settings.update(|s| s.read_only = !s.read_only)
+                >
+                    {move || if settings.get().read_only { "\u{1f512} Locked" } else { "Lock" }}
+                </button>
+                <button class="hud-btn" on:click=move |_| set_settings_open.update(|o| *o = !*o)>
+                    "Settings"
+                </button>
             </div>
             <Show when=move || !is_tauri()>
                 <input type="file" accept=".json" node_ref=file_input_ref style="display:none"
                        on:change=on_file_selected />
             </Show>
-            <div class="status-line" style="position: fixed; bottom: 12px; left: 12px;">
-                "[DBLCLK] add/edit  [DRAG corner] resize  [SHIFT+DRAG] connect  [CMD+DRAG] box  [CMD+C] copy  [CMD+V] paste  [T] type  [DEL] delete  [CMD+Z] undo  [CMD+SHIFT+Z] redo  [CMD+F] search  [F] fit  [CMD+0] reset zoom  [CMD+A] select all"
-            </div>
+            <input type="file" accept=".md,.markdown,text/markdown" node_ref=md_input_ref
+                   style="display:none" on:change=on_md_selected />
+            <input type="file" accept=".json,.bak" node_ref=backup_input_ref
+                   style="display:none" on:change=on_backup_selected />
+            <Show when=move || settings.get().render_stats>
+                <div class="status-line" style="position: fixed; top: 80px; left: 12px; white-space: pre;">
+                    {move || {
+                        render_stats
+                            .get()
+                            .map(|s| {
+                                format!(
+                                    "render {:.1}ms  avg {:.1}  max {:.1}\n{} nodes  {} edges",
+                                    s.last_ms,
+                                    s.avg_ms,
+                                    s.max_ms,
+                                    s.nodes,
+                                    s.edges,
+                                )
+                            })
+                            .unwrap_or_default()
+                    }}
+                </div>
+            </Show>
+            <Show when=move || !settings.get().hide_help_bar>
+                <div class="status-line" style="position: fixed; bottom: 12px; left: 12px; display: flex; align-items: center; gap: 8px;">
+                    <button
+                        class="hud-btn"
+                        style="padding: 0 6px;"
+                        title="Hide shortcuts (bring back in Settings)"
+                        on:click=move |_| set_settings.update(|s| s.hide_help_bar = true)
+                    >
+                        "\u{00d7}"
+                    </button>
+                    <span>"[DBLCLK] add/edit  [DRAG corner] resize  "{move || settings.get().edge_trigger.hint()}"  [CMD+DRAG] box  [CMD+C] copy  [CMD+V] paste  [T] type  [DEL] delete  [CMD+Z] undo  [CMD+SHIFT+Z] redo  [CMD+F] search  [F] fit  [CMD+0] reset zoom  [CMD+A] select all  [CMD+G] group  [ALT+CLICK] select group"</span>
+                </div>
+            </Show>
         </div>
     }
 }
@@ -2694,37 +5879,406 @@ mod tests {
         }
 
         #[test]
-        fn wrong_shape_json_yields_parse_error() {
-            // Valid JSON, but not a Board shape.
-            let wrong = r#"{"totally": "different", "schema": 42}"#;
-            assert!(matches!(
-                parse_localstorage_board(wrong),
-                LoadOutcome::ParseError(_)
+        fn wrong_shape_json_yields_parse_error() {
+            // Valid JSON, but not a Board shape.
+            let wrong = r#"{"totally": "different", "schema": 42}"#;
+            assert!(matches!(
+                parse_localstorage_board(wrong),
+                LoadOutcome::ParseError(_)
+            ));
+        }
+
+        #[test]
+        fn parse_error_does_not_replace_non_empty_board() {
+            // Simulate the load path's contract: a non-empty board must survive a
+            // ParseError. We only call set_board on Loaded/Absent, never ParseError.
+            let existing = Board {
+                version: None,
+                nodes: vec![Node::new("text".into(), 0.0, 0.0, "keep me".into())],
+                edges: vec![],
+                settings: Default::default(),
+            };
+            let outcome = parse_localstorage_board("{ broken");
+            let mut current = existing.clone();
+            match outcome {
+                LoadOutcome::Loaded(b) => current = b,
+                LoadOutcome::Absent => current = Board::default(),
+                LoadOutcome::ParseError(_) => { /* keep current untouched */ }
+            }
+            assert_eq!(
+                current.nodes.len(),
+                1,
+                "ParseError must not blank the board"
+            );
+            assert_eq!(current.nodes[0].text, "keep me");
+        }
+    }
+
+    mod fetch_queue_tests {
+        use super::*;
+
+        #[test]
+        fn starts_jobs_until_the_limit_then_queues() {
+            let mut q = FetchQueue::new(2);
+            assert_eq!(q.enqueue("a"), Some("a"));
+            assert_eq!(q.enqueue("b"), Some("b"));
+            assert_eq!(q.enqueue("c"), None);
+            assert_eq!(q.enqueue("d"), None);
+            assert_eq!((q.in_flight(), q.pending()), (2, 2));
+        }
+
+        #[test]
+        fn finish_hands_the_slot_to_the_next_job_in_order() {
+            let mut q = FetchQueue::new(1);
+            q.enqueue("a");
+            q.enqueue("b");
+            q.enqueue("c");
+            assert_eq!(q.finish(), Some("b"));
+            assert_eq!((q.in_flight(), q.pending()), (1, 1));
+            assert_eq!(q.finish(), Some("c"));
+            assert_eq!(q.finish(), None);
+            assert_eq!((q.in_flight(), q.pending()), (0, 0));
+        }
+
+        #[test]
+        fn freed_slot_is_reused_by_a_new_job() {
+            let mut q = FetchQueue::new(1);
+            q.enqueue("a");
+            assert_eq!(q.finish(), None);
+            assert_eq!(q.enqueue("b"), Some("b"));
+            assert_eq!(q.in_flight(), 1);
+        }
+
+        #[test]
+        fn extra_finish_and_zero_limit_are_safe() {
+            let mut q: FetchQueue<&str> = FetchQueue::new(0);
+            assert_eq!(q.finish(), None);
+            assert_eq!(q.in_flight(), 0);
+            // A zero limit still lets one job through rather than stalling.
+            assert_eq!(q.enqueue("a"), Some("a"));
+        }
+    }
+
+    mod restored_selection_tests {
+        use super::*;
+
+        fn ids(list: &[&str]) -> HashSet<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        }
+
+        fn board() -> Board {
+            Board {
+                nodes: vec![
+                    Node::new("a".to_string(), 0.0, 0.0, "A".to_string()),
+                    Node::new("b".to_string(), 300.0, 0.0, "B".to_string()),
+                ],
+                edges: vec![Edge {
+                    id: "e".to_string(),
+                    from_node: "a".to_string(),
+                    to_node: "b".to_string(),
+                    label: None,
+                    from_port: None,
+                    to_port: None,
+                }],
+                ..Board::default()
+            }
+        }
+
+        #[test]
+        fn keeps_surviving_selection() {
+            let (nodes, edge) =
+                restored_selection(&board(), &ids(&["a"]), Some("e".to_string()), ids(&["b"]));
+            assert_eq!(nodes, ids(&["a"]));
+            assert_eq!(edge.as_deref(), Some("e"));
+        }
+
+        #[test]
+        fn drops_only_vanished_ids() {
+            let (nodes, edge) = restored_selection(
+                &board(),
+                &ids(&["a", "gone"]),
+                Some("gone-edge".to_string()),
+                ids(&[]),
+            );
+            assert_eq!(nodes, ids(&["a"]));
+            assert_eq!(edge, None);
+        }
+
+        #[test]
+        fn falls_back_to_snapshot_when_nothing_survives() {
+            let (nodes, _) = restored_selection(&board(), &ids(&["gone"]), None, ids(&["b"]));
+            assert_eq!(nodes, ids(&["b"]));
+            let (nodes, _) = restored_selection(&board(), &ids(&[]), None, ids(&["a", "b"]));
+            assert_eq!(nodes, ids(&["a", "b"]));
+        }
+    }
+
+    mod text_edit_undo_tests {
+        use super::*;
+
+        fn apply(history: &mut History<Snapshot>, board: Board, action: BoardAction) -> Board {
+            apply_action(history, (board, HashSet::new()), action, None, 0.0).0
+        }
+
+        fn board() -> Board {
+            Board {
+                nodes: vec![Node::new("a".to_string(), 0.0, 0.0, "Old".to_string())],
+                ..Board::default()
+            }
+        }
+
+        #[test]
+        fn undo_restores_text_before_an_inline_edit() {
+            let mut history = History::new(10);
+            let action = BoardAction::EditText {
+                id: "a".to_string(),
+                text: "New".to_string(),
+            };
+            let edited = apply(&mut history, board(), action);
+            assert_eq!(edited.nodes[0].text, "New");
+
+            let (restored, _) = history.undo((edited, HashSet::new())).unwrap();
+            assert_eq!(restored.nodes[0].text, "Old");
+        }
+
+        #[test]
+        fn undo_restores_text_before_a_markdown_modal_save() {
+            let mut history = History::new(10);
+            let action = BoardAction::EditMarkdown {
+                id: "a".to_string(),
+                text: "# New".to_string(),
+            };
+            let edited = apply(&mut history, board(), action);
+
+            let (restored, _) = history.undo((edited, HashSet::new())).unwrap();
+            assert_eq!(restored.nodes[0].text, "Old");
+            let (redone, _) = history.redo((restored, HashSet::new())).unwrap();
+            assert_eq!(redone.nodes[0].text, "# New");
+        }
+    }
+
+    mod gesture_revert_tests {
+        use super::*;
+
+        fn board() -> Board {
+            Board {
+                nodes: vec![
+                    Node::new("a".to_string(), 0.0, 0.0, "A".to_string()),
+                    Node::new("b".to_string(), 100.0, 100.0, "B".to_string()),
+                ],
+                edges: vec![],
+                ..Board::default()
+            }
+        }
+
+        #[test]
+        fn revert_drag_restores_start_positions() {
+            let mut b = board();
+            for n in &mut b.nodes {
+                n.x += 75.0;
+                n.y -= 20.0;
+            }
+            let starts: HashMap<String, (f64, f64)> =
+                [("a".to_string(), (0.0, 0.0))].into_iter().collect();
+
+            revert_drag(&mut b, &starts);
+
+            assert_eq!((b.nodes[0].x, b.nodes[0].y), (0.0, 0.0));
+            // Nodes outside the drag are left where they are.
+            assert_eq!((b.nodes[1].x, b.nodes[1].y), (175.0, 80.0));
+        }
+
+        #[test]
+        fn revert_resize_restores_original_geometry() {
+            let mut b = board();
+            let resize = ResizeState {
+                is_resizing: true,
+                node_id: Some("b".to_string()),
+                handle: Some(ResizeHandle::TopLeft),
+                original_x: 100.0,
+                original_y: 100.0,
+                original_width: b.nodes[1].width,
+                original_height: b.nodes[1].height,
+                ..ResizeState::default()
+            };
+            let (w, h) = (b.nodes[1].width, b.nodes[1].height);
+            b.nodes[1].x = 60.0;
+            b.nodes[1].y = 40.0;
+            b.nodes[1].width = w + 40.0;
+            b.nodes[1].height = h + 60.0;
+
+            revert_resize(&mut b, &resize);
+
+            let n = &b.nodes[1];
+            assert_eq!((n.x, n.y, n.width, n.height), (100.0, 100.0, w, h));
+        }
+
+        #[test]
+        fn revert_resize_restores_a_group() {
+            let mut b = board();
+            let resize = ResizeState {
+                is_resizing: true,
+                handle: Some(ResizeHandle::BottomRight),
+                group: vec![
+                    ("a".to_string(), (0.0, 0.0, 200.0, 100.0)),
+                    ("b".to_string(), (100.0, 100.0, 200.0, 100.0)),
+                ],
+                ..ResizeState::default()
+            };
+            for n in &mut b.nodes {
+                n.x *= 2.0;
+                n.width = 400.0;
+            }
+
+            revert_resize(&mut b, &resize);
+
+            assert_eq!((b.nodes[0].x, b.nodes[0].width), (0.0, 200.0));
+            assert_eq!((b.nodes[1].x, b.nodes[1].width), (100.0, 200.0));
+        }
+
+        #[test]
+        fn revert_resize_without_node_is_a_no_op() {
+            let mut b = board();
+            b.nodes[0].width = 999.0;
+            revert_resize(&mut b, &ResizeState::default());
+            assert_eq!(b.nodes[0].width, 999.0);
+        }
+    }
+
+    mod is_mutating_shortcut_tests {
+        use super::*;
+
+        #[test]
+        fn editing_keys_are_mutating() {
+            assert!(is_mutating_shortcut("Delete", false, false));
+            assert!(is_mutating_shortcut("Backspace", false, false));
+            assert!(is_mutating_shortcut("t", false, false));
+            assert!(is_mutating_shortcut("s", false, false));
+            assert!(is_mutating_shortcut("]", false, false));
+            assert!(is_mutating_shortcut("c", false, false));
+            assert!(is_mutating_shortcut("L", false, false));
+            assert!(is_mutating_shortcut("v", true, false));
+            assert!(is_mutating_shortcut("z", true, false));
+            assert!(is_mutating_shortcut("g", true, false));
+            assert!(is_mutating_shortcut("d", true, false));
+            assert!(is_mutating_shortcut("ArrowRight", true, true));
+        }
+
+        #[test]
+        fn viewing_keys_are_not_mutating() {
+            assert!(!is_mutating_shortcut("c", true, false));
+            assert!(!is_mutating_shortcut("a", true, false));
+            assert!(!is_mutating_shortcut("f", true, false));
+            assert!(!is_mutating_shortcut("f", false, false));
+            assert!(!is_mutating_shortcut("0", true, false));
+            assert!(!is_mutating_shortcut("Escape", false, false));
+            // Plain arrows and Cmd+S aren't editing shortcuts.
+            assert!(!is_mutating_shortcut("ArrowRight", false, false));
+            assert!(!is_mutating_shortcut("s", true, false));
+        }
+    }
+
+    mod arrow_alignment_tests {
+        use super::*;
+
+        #[test]
+        fn arrows_pick_that_side() {
+            assert_eq!(arrow_alignment("ArrowLeft", false), Some(Alignment::Left));
+            assert_eq!(arrow_alignment("ArrowRight", false), Some(Alignment::Right));
+            assert_eq!(arrow_alignment("ArrowUp", false), Some(Alignment::Top));
+            assert_eq!(arrow_alignment("ArrowDown", false), Some(Alignment::Bottom));
+        }
+
+        #[test]
+        fn shift_centers_along_the_arrow_axis() {
+            assert_eq!(arrow_alignment("ArrowLeft", true), Some(Alignment::CenterX));
+            assert_eq!(
+                arrow_alignment("ArrowRight", true),
+                Some(Alignment::CenterX)
+            );
+            assert_eq!(arrow_alignment("ArrowUp", true), Some(Alignment::CenterY));
+            assert_eq!(arrow_alignment("ArrowDown", true), Some(Alignment::CenterY));
+        }
+
+        #[test]
+        fn other_keys_align_nothing() {
+            assert_eq!(arrow_alignment("a", false), None);
+            assert_eq!(arrow_alignment("Enter", true), None);
+        }
+    }
+
+    mod quota_tests {
+        use super::*;
+
+        #[test]
+        fn empty_board_is_its_bare_json() {
+            assert_eq!(
+                estimate_board_bytes(&Board::default()),
+                r#"{"nodes":[],"edges":[]}"#.len()
+            );
+        }
+
+        #[test]
+        fn estimate_counts_utf16_code_units() {
+            let mut board = Board::default();
+            board
+                .nodes
+                .push(Node::new("n".into(), 0.0, 0.0, "a".into()));
+            let small = estimate_board_bytes(&board);
+            board.nodes[0].text = "a".repeat(1001);
+            assert_eq!(estimate_board_bytes(&board), small + 1000);
+            // Two UTF-8 bytes but one UTF-16 unit each.
+            board.nodes[0].text = "é".repeat(1001);
+            assert_eq!(estimate_board_bytes(&board), small + 1000);
+            // Outside the BMP: a surrogate pair, two units each.
+            board.nodes[0].text = "😀".repeat(1001);
+            assert_eq!(estimate_board_bytes(&board), small + 2001);
+        }
+
+        #[test]
+        fn quota_bands() {
+            assert_eq!(check_quota(0), QuotaCheck::Ok);
+            assert_eq!(check_quota(LOCALSTORAGE_QUOTA_UNITS / 2), QuotaCheck::Ok);
+            assert_eq!(
+                check_quota(LOCALSTORAGE_QUOTA_UNITS * 9 / 10),
+                QuotaCheck::NearLimit(90)
+            );
+            assert_eq!(check_quota(LOCALSTORAGE_QUOTA_UNITS), QuotaCheck::OverLimit);
+        }
+    }
+
+    mod is_gif_tests {
+        use super::*;
+
+        #[test]
+        fn gif_extension_is_detected() {
+            assert!(is_gif("/Users/me/pics/party.gif"));
+            assert!(is_gif("https://example.com/cat.GIF"));
+            assert!(is_gif("~/loop.Gif"));
+        }
+
+        #[test]
+        fn query_and_fragment_are_ignored() {
+            assert!(is_gif("https://example.com/cat.gif?size=large"));
+            assert!(is_gif("https://example.com/cat.gif#frame"));
+            assert!(!is_gif("https://example.com/render?format=.gif.png"));
+        }
+
+        #[test]
+        fn gif_data_urls_are_detected() {
+            assert!(is_gif("data:image/gif;base64,R0lGODlhAQABAAAAACw="));
+            // Generic mime type: fall back to the base64-encoded magic bytes.
+            assert!(is_gif(
+                "data:application/octet-stream;base64,R0lGODlhAQABAAAAACw="
             ));
         }
 
         #[test]
-        fn parse_error_does_not_replace_non_empty_board() {
-            // Simulate the load path's contract: a non-empty board must survive a
-            // ParseError. We only call set_board on Loaded/Absent, never ParseError.
-            let existing = Board {
-                version: None,
-                nodes: vec![Node::new("text".into(), 0.0, 0.0, "keep me".into())],
-                edges: vec![],
-            };
-            let outcome = parse_localstorage_board("{ broken");
-            let mut current = existing.clone();
-            match outcome {
-                LoadOutcome::Loaded(b) => current = b,
-                LoadOutcome::Absent => current = Board::default(),
-                LoadOutcome::ParseError(_) => { /* keep current untouched */ }
-            }
-            assert_eq!(
-                current.nodes.len(),
-                1,
-                "ParseError must not blank the board"
-            );
-            assert_eq!(current.nodes[0].text, "keep me");
+        fn other_images_are_not_gifs() {
+            assert!(!is_gif("/path/to/photo.png"));
+            assert!(!is_gif("https://example.com/gif/photo.jpg"));
+            assert!(!is_gif("data:image/png;base64,iVBORw0KGgo="));
+            assert!(!is_gif(""));
         }
     }
 
@@ -2796,6 +6350,48 @@ mod tests {
         }
     }
 
+    mod is_markdown_url_tests {
+        use super::*;
+
+        #[test]
+        fn http_and_https_md_files() {
+            assert!(is_markdown_url("https://example.com/notes.md"));
+            assert!(is_markdown_url("http://example.com/docs/README.MD"));
+            assert!(is_markdown_url("https://example.com/guide.markdown"));
+        }
+
+        #[test]
+        fn ignores_query_and_fragment() {
+            assert!(is_markdown_url(
+                "https://raw.example.com/a/notes.md?token=abc"
+            ));
+            assert!(is_markdown_url("https://example.com/notes.md#setup"));
+            assert!(!is_markdown_url("https://example.com/page?file=notes.md"));
+        }
+
+        #[test]
+        fn rejects_html_and_other_pages() {
+            assert!(!is_markdown_url("https://example.com/notes.md.html"));
+            assert!(!is_markdown_url("https://example.com/index.html"));
+            assert!(!is_markdown_url("https://example.com/"));
+            assert!(!is_markdown_url("https://notes.md"));
+        }
+
+        #[test]
+        fn rejects_local_paths() {
+            assert!(!is_markdown_url("/path/to/file.md"));
+            assert!(!is_markdown_url("file:///path/to/file.md"));
+            assert!(!is_markdown_url("ftp://example.com/file.md"));
+        }
+
+        #[test]
+        fn md_link_covers_both() {
+            assert!(is_md_link("~/vault/note.md"));
+            assert!(is_md_link("https://example.com/note.md"));
+            assert!(!is_md_link("https://example.com/"));
+        }
+    }
+
     mod public_http_host_tests {
         use super::*;
 
@@ -2860,6 +6456,51 @@ mod tests {
         }
     }
 
+    mod looks_like_url_tests {
+        use super::*;
+
+        #[test]
+        fn accepts_http_and_https_urls() {
+            assert!(looks_like_url("https://example.com"));
+            assert!(looks_like_url("http://example.com"));
+            assert!(looks_like_url("  https://sub.example.co.uk  "));
+            assert!(looks_like_url("http://localhost:8080"));
+            assert!(looks_like_url("http://[::1]:3000/x"));
+        }
+
+        #[test]
+        fn accepts_paths_queries_and_fragments() {
+            assert!(looks_like_url("https://example.com/a/b?q=1&r=two#top"));
+            assert!(looks_like_url("https://user@example.com:8443/path"));
+            assert!(looks_like_url("https://my-site.example.org/"));
+        }
+
+        #[test]
+        fn rejects_sentences() {
+            assert!(!looks_like_url("see https://example.com for details"));
+            assert!(!looks_like_url("just some words"));
+            assert!(!looks_like_url(""));
+        }
+
+        #[test]
+        fn rejects_paths_and_other_schemes() {
+            assert!(!looks_like_url("/Users/me/notes.md"));
+            assert!(!looks_like_url("~/vault/idea.md"));
+            assert!(!looks_like_url("file:///etc/hosts"));
+            assert!(!looks_like_url("ftp://example.com"));
+            assert!(!looks_like_url("example.com"));
+        }
+
+        #[test]
+        fn rejects_malformed_hosts() {
+            assert!(!looks_like_url("https://"));
+            assert!(!looks_like_url("https://nodot"));
+            assert!(!looks_like_url("https://example..com"));
+            assert!(!looks_like_url("https://-bad.com"));
+            assert!(!looks_like_url("https://exa_mple.com"));
+        }
+    }
+
     mod cycle_node_type_tests {
         // `cycle_node_type` moved to the reducer module (interaction.rs) as part of
         // the P1.3 reducer extraction; this asserts the app's view of that behavior.
@@ -2930,82 +6571,436 @@ mod tests {
         }
 
         #[test]
-        fn fully_outside_above() {
-            assert!(!intersects_box(
-                &node_at(10.0, -50.0, 20.0, 20.0),
-                0.0,
-                0.0,
-                100.0,
-                100.0
-            ));
+        fn fully_outside_above() {
+            assert!(!intersects_box(
+                &node_at(10.0, -50.0, 20.0, 20.0),
+                0.0,
+                0.0,
+                100.0,
+                100.0
+            ));
+        }
+
+        #[test]
+        fn fully_outside_below() {
+            assert!(!intersects_box(
+                &node_at(10.0, 200.0, 20.0, 20.0),
+                0.0,
+                0.0,
+                100.0,
+                100.0
+            ));
+        }
+
+        #[test]
+        fn partially_overlapping() {
+            assert!(intersects_box(
+                &node_at(90.0, 90.0, 20.0, 20.0),
+                0.0,
+                0.0,
+                100.0,
+                100.0
+            ));
+        }
+
+        #[test]
+        fn touching_edge() {
+            assert!(intersects_box(
+                &node_at(100.0, 0.0, 20.0, 20.0),
+                0.0,
+                0.0,
+                100.0,
+                100.0
+            ));
+        }
+    }
+
+    mod focus_node_camera_tests {
+        use super::*;
+
+        fn sized(width: f64, height: f64) -> Node {
+            let mut node = Node::new("n".to_string(), 100.0, 200.0, String::new());
+            node.width = width;
+            node.height = height;
+            node
+        }
+
+        fn centered_on(cam: &Camera, node: &Node) -> bool {
+            let (cx, cy) = cam.screen_to_world(500.0, 400.0);
+            let (nx, ny) = node.center();
+            (cx - nx).abs() < 1e-9 && (cy - ny).abs() < 1e-9
+        }
+
+        #[test]
+        fn fills_the_view_with_a_small_margin() {
+            let node = sized(200.0, 100.0);
+            let cam = focus_node_camera(&node, 1_000.0, 800.0);
+            // Width binds: 1000 / (200 * 1.08).
+            assert!((cam.zoom - 1_000.0 / 216.0).abs() < 1e-9);
+            assert!(centered_on(&cam, &node));
+        }
+
+        #[test]
+        fn tiny_nodes_stop_at_max_zoom() {
+            let node = sized(20.0, 10.0);
+            let cam = focus_node_camera(&node, 1_000.0, 800.0);
+            assert_eq!(cam.zoom, 5.0);
+            assert!(centered_on(&cam, &node));
+        }
+
+        #[test]
+        fn huge_nodes_stop_at_min_zoom() {
+            let node = sized(40_000.0, 30_000.0);
+            let cam = focus_node_camera(&node, 1_000.0, 800.0);
+            assert_eq!(cam.zoom, 0.1);
+            assert!(centered_on(&cam, &node));
+        }
+    }
+
+    mod point_near_line_tests {
+        use super::*;
+
+        #[test]
+        fn point_on_line() {
+            assert!(point_near_line(5.0, 5.0, 0.0, 0.0, 10.0, 10.0, 1.0));
+        }
+
+        #[test]
+        fn point_far_from_line() {
+            assert!(!point_near_line(50.0, 50.0, 0.0, 0.0, 10.0, 0.0, 5.0));
+        }
+
+        #[test]
+        fn point_near_midpoint() {
+            assert!(point_near_line(5.0, 1.0, 0.0, 0.0, 10.0, 0.0, 2.0));
+        }
+
+        #[test]
+        fn point_near_endpoint() {
+            assert!(point_near_line(0.5, 0.0, 0.0, 0.0, 10.0, 0.0, 1.0));
+        }
+
+        #[test]
+        fn point_beyond_segment_end() {
+            assert!(!point_near_line(15.0, 0.0, 0.0, 0.0, 10.0, 0.0, 1.0));
+        }
+
+        #[test]
+        fn degenerate_zero_length_line() {
+            assert!(point_near_line(0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0));
+            assert!(!point_near_line(5.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0));
+        }
+
+        #[test]
+        fn curve_hits_its_bulge_not_the_chord() {
+            let ctrl = edge_control_point((0.0, 0.0), (100.0, 0.0), 30.0);
+            assert!(point_near_curve(
+                (50.0, -30.0),
+                (0.0, 0.0),
+                ctrl,
+                (100.0, 0.0),
+                2.0
+            ));
+            assert!(!point_near_curve(
+                (50.0, 0.0),
+                (0.0, 0.0),
+                ctrl,
+                (100.0, 0.0),
+                2.0
+            ));
+        }
+
+        #[test]
+        fn bezier_hits_along_the_curve_not_the_chord() {
+            let curve = [(200.0, 50.0), (300.0, 50.0), (300.0, 250.0), (400.0, 250.0)];
+            // t = 0.25 on the curve, and the chord point straight below it.
+            assert!(point_near_bezier((259.375, 81.25), &curve, 2.0));
+            assert!(!point_near_bezier((259.375, 109.375), &curve, 2.0));
+            assert!(point_near_bezier((400.0, 250.0), &curve, 2.0));
+        }
+    }
+
+    mod save_scheduler_tests {
+        use super::*;
+
+        #[test]
+        fn request_honors_the_configured_interval() {
+            let mut scheduler = SaveScheduler::default();
+            assert_eq!(scheduler.request(0), 0);
+            assert_eq!(scheduler.request(2_000), 2_000);
+            assert_eq!(scheduler.request(u32::MAX), MAX_AUTOSAVE_MS);
+            assert!(scheduler.is_pending());
+        }
+
+        #[test]
+        fn flush_writes_the_latest_state_once() {
+            let mut scheduler = SaveScheduler::default();
+            let mut board = vec!["first"];
+            scheduler.request(220);
+            board.push("second");
+            scheduler.request(220);
+            assert_eq!(
+                scheduler.flush(|| board.clone()),
+                Some(vec!["first", "second"])
+            );
+            // The timer firing after an early flush has nothing left to write.
+            assert_eq!(scheduler.flush(|| board.clone()), None);
+            assert!(!scheduler.is_pending());
+        }
+
+        #[test]
+        fn flush_without_a_request_writes_nothing() {
+            let mut scheduler = SaveScheduler::default();
+            assert_eq!(scheduler.flush(|| 1), None);
+        }
+    }
+
+    mod save_state_tests {
+        use super::*;
+
+        #[test]
+        fn edit_marks_clean_board_dirty() {
+            assert_eq!(SaveState::Saved.edited(), SaveState::Dirty);
+        }
+
+        #[test]
+        fn successful_save_settles_to_saved() {
+            let state = SaveState::Saved.edited().save_started();
+            assert_eq!(state, SaveState::Saving);
+            assert_eq!(state.save_finished(Ok(())), SaveState::Saved);
+        }
+
+        #[test]
+        fn failed_save_reports_error() {
+            let state = SaveState::Dirty
+                .save_started()
+                .save_finished(Err("disk full".into()));
+            assert_eq!(state, SaveState::Failed("disk full".into()));
+            assert_eq!(state.label(), "save failed");
+            // The next edit queues a retry.
+            assert_eq!(state.edited(), SaveState::Dirty);
+        }
+
+        #[test]
+        fn edit_during_save_stays_dirty() {
+            let state = SaveState::Dirty.save_started().edited();
+            assert_eq!(state.save_finished(Ok(())), SaveState::Dirty);
+        }
+    }
+
+    mod markdown_to_board_tests {
+        use super::*;
+
+        fn texts(board: &Board) -> Vec<&str> {
+            board.nodes.iter().map(|n| n.text.as_str()).collect()
+        }
+
+        fn links(board: &Board) -> Vec<(&str, &str)> {
+            board
+                .edges
+                .iter()
+                .map(|e| (e.from_node.as_str(), e.to_node.as_str()))
+                .collect()
+        }
+
+        #[test]
+        fn flat_headings_are_unconnected_siblings() {
+            let board = markdown_to_board("# One\n# Two\n# Three\n", (0.0, 0.0));
+            assert_eq!(texts(&board), ["One", "Two", "Three"]);
+            assert!(board.edges.is_empty());
+            assert!(board.nodes.iter().all(|n| n.x == 0.0));
+            assert!(board.nodes.windows(2).all(|w| w[1].y > w[0].y));
+        }
+
+        #[test]
+        fn nesting_becomes_edges() {
+            let md = "# Root\n## A\n### A1\n## B\n# Other\n";
+            let board = markdown_to_board(md, (0.0, 0.0));
+            assert_eq!(texts(&board), ["Root", "A", "A1", "B", "Other"]);
+            assert_eq!(
+                links(&board),
+                [("md-1", "md-2"), ("md-2", "md-3"), ("md-1", "md-4")]
+            );
+            assert_eq!(board.nodes[2].x, 2.0 * MD_IMPORT_INDENT);
+            assert_eq!(board.nodes[4].x, 0.0);
+        }
+
+        #[test]
+        fn skipped_level_attaches_to_nearest_ancestor() {
+            let board = markdown_to_board("# Top\n### Deep\n", (0.0, 0.0));
+            assert_eq!(links(&board), [("md-1", "md-2")]);
+            assert_eq!(board.nodes[1].x, MD_IMPORT_INDENT);
+        }
+
+        #[test]
+        fn section_body_is_kept_as_markdown() {
+            let md = "# Plan\nShip **v2**.\n\n- docs\n## Later\n";
+            let board = markdown_to_board(md, (0.0, 0.0));
+            assert_eq!(board.nodes[0].text, "Plan\n\nShip **v2**.\n\n- docs");
+            assert!(board.nodes[0].render_markdown);
+            assert!(!board.nodes[1].render_markdown);
+        }
+
+        #[test]
+        fn content_only_is_one_node() {
+            let board = markdown_to_board("Just a paragraph.\n\nAnd another.", (10.0, 20.0));
+            assert_eq!(texts(&board), ["Just a paragraph.\n\nAnd another."]);
+            assert!(board.edges.is_empty());
+            assert_eq!((board.nodes[0].x, board.nodes[0].y), (10.0, 20.0));
+        }
+
+        #[test]
+        fn preamble_before_first_heading_is_its_own_node() {
+            let board = markdown_to_board("Intro\n\n# Title\n", (0.0, 0.0));
+            assert_eq!(texts(&board), ["Intro", "Title"]);
+            assert!(board.edges.is_empty());
+        }
+    }
+
+    mod html_to_markdown_tests {
+        use super::*;
+
+        #[test]
+        fn converts_headings_and_paragraphs() {
+            let html = "<h1>Title</h1><p>First   line\n  wraps</p><h3>Sub</h3><p>Body</p>";
+            assert_eq!(
+                html_to_markdown(html),
+                "# Title\n\nFirst line wraps\n\n### Sub\n\nBody"
+            );
+        }
+
+        #[test]
+        fn converts_links_and_emphasis() {
+            let html = r#"<p>See <a href="https://example.com/?a=1&amp;b=2">the <b>docs</b></a>,
+                <strong>bold </strong>and <em>italic</em> and <code>code</code>.</p>"#;
+            assert_eq!(
+                html_to_markdown(html),
+                "See [the **docs**](https://example.com/?a=1&b=2), **bold** and *italic* and `code`."
+            );
+        }
+
+        #[test]
+        fn converts_nested_lists() {
+            let html = "<ul><li>a<ul><li>b</li><li>c</li></ul></li><li>d</li></ul>\
+                        <ol><li>one</li><li>two<ol><li>deep</li></ol></li></ol>";
+            assert_eq!(
+                html_to_markdown(html),
+                "- a\n    - b\n    - c\n- d\n\n1. one\n2. two\n    1. deep"
+            );
+        }
+
+        #[test]
+        fn strips_unsupported_tags_scripts_and_comments() {
+            let html = "<html><head><meta charset=\"utf-8\"><style>p { color: red }</style></head>\
+                        <body><!--StartFragment--><span class=\"x\">Kept &lt;text&gt;</span> \
+                        <script>alert(1)</script><a href=\"javascript:alert(1)\">plain</a>\
+                        <!--EndFragment--></body></html>";
+            assert_eq!(html_to_markdown(html), "Kept <text> plain");
+        }
+
+        #[test]
+        fn keeps_preformatted_text() {
+            let html = "<pre><code>fn main() {\n    run();\n}</code></pre>";
+            assert_eq!(
+                html_to_markdown(html),
+                "```\nfn main() {\n    run();\n}\n```"
+            );
+        }
+    }
+
+    mod markdown_to_plain_tests {
+        use super::*;
+
+        #[test]
+        fn strips_inline_markup() {
+            assert_eq!(
+                markdown_to_plain("# Title\n\nSome **bold**, *em* and `code`."),
+                "Title\nSome bold, em and code."
+            );
         }
 
         #[test]
-        fn fully_outside_below() {
-            assert!(!intersects_box(
-                &node_at(10.0, 200.0, 20.0, 20.0),
-                0.0,
-                0.0,
-                100.0,
-                100.0
-            ));
+        fn keeps_link_text_not_url() {
+            assert_eq!(
+                markdown_to_plain("See [the docs](https://example.com)"),
+                "See the docs"
+            );
         }
 
         #[test]
-        fn partially_overlapping() {
-            assert!(intersects_box(
-                &node_at(90.0, 90.0, 20.0, 20.0),
-                0.0,
-                0.0,
-                100.0,
-                100.0
-            ));
+        fn bullets_list_items() {
+            assert_eq!(markdown_to_plain("- one\n- two"), "• one\n• two");
         }
 
         #[test]
-        fn touching_edge() {
-            assert!(intersects_box(
-                &node_at(100.0, 0.0, 20.0, 20.0),
-                0.0,
-                0.0,
-                100.0,
-                100.0
-            ));
+        fn drops_raw_html() {
+            assert_eq!(markdown_to_plain("a <b>b</b> c"), "a b c");
         }
-    }
-
-    mod point_near_line_tests {
-        use super::*;
 
         #[test]
-        fn point_on_line() {
-            assert!(point_near_line(5.0, 5.0, 0.0, 0.0, 10.0, 10.0, 1.0));
+        fn joins_soft_breaks_and_collapses_blank_lines() {
+            assert_eq!(
+                markdown_to_plain("line one\nline two\n\n\n\npara"),
+                "line one line two\npara"
+            );
         }
 
         #[test]
-        fn point_far_from_line() {
-            assert!(!point_near_line(50.0, 50.0, 0.0, 0.0, 10.0, 0.0, 5.0));
+        fn plain_text_passes_through() {
+            assert_eq!(markdown_to_plain("just words"), "just words");
+            assert_eq!(markdown_to_plain(""), "");
         }
+    }
+
+    mod markdown_cache_tests {
+        use super::*;
 
         #[test]
-        fn point_near_midpoint() {
-            assert!(point_near_line(5.0, 1.0, 0.0, 0.0, 10.0, 0.0, 2.0));
+        fn identical_text_hits_the_cache() {
+            let cache = MarkdownCache::default();
+            let parses = Cell::new(0);
+            let parse = |md: &str| {
+                parses.set(parses.get() + 1);
+                parse_markdown(md)
+            };
+            let key = markdown_key("# Title");
+            let first = cache.get_or_insert_with(key, "# Title", parse);
+            let second = cache.get_or_insert_with(key, "# Title", parse);
+            assert_eq!(first, second);
+            assert_eq!(parses.get(), 1);
         }
 
         #[test]
-        fn point_near_endpoint() {
-            assert!(point_near_line(0.5, 0.0, 0.0, 0.0, 10.0, 0.0, 1.0));
+        fn changed_text_reparses() {
+            let cache = MarkdownCache::default();
+            assert!(cache
+                .get_or_parse("**old**")
+                .contains("<strong>old</strong>"));
+            assert!(cache
+                .get_or_parse("**new**")
+                .contains("<strong>new</strong>"));
+            assert_eq!(cache.entries.borrow().len(), 2);
         }
 
         #[test]
-        fn point_beyond_segment_end() {
-            assert!(!point_near_line(15.0, 0.0, 0.0, 0.0, 10.0, 0.0, 1.0));
+        fn a_colliding_key_never_returns_another_source() {
+            let cache = MarkdownCache::default();
+            cache.get_or_insert_with(7, "**first**", parse_markdown);
+            let html = cache.get_or_insert_with(7, "**second**", parse_markdown);
+            assert!(html.contains("<strong>second</strong>"), "{html}");
         }
 
         #[test]
-        fn degenerate_zero_length_line() {
-            assert!(point_near_line(0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0));
-            assert!(!point_near_line(5.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0));
+        fn sweep_drops_entries_not_used_since_the_last_sweep() {
+            let cache = MarkdownCache::default();
+            cache.get_or_parse("kept");
+            cache.get_or_parse("stale");
+            cache.sweep();
+            cache.clone().get_or_parse("kept");
+            cache.sweep();
+            let entries = cache.entries.borrow();
+            assert_eq!(entries.len(), 1);
+            assert!(entries.values().all(|e| e.source == "kept"));
         }
     }
 
@@ -3057,53 +7052,31 @@ mod tests {
         }
 
         #[test]
-        fn strips_inline_html_script() {
-            let html = parse_markdown("hello <script>alert(1)</script> world");
-            assert!(!html.contains("<script>"), "raw <script> leaked: {html}");
-            assert!(
-                html.contains("&lt;script&gt;"),
-                "expected escaped script: {html}"
+        fn neutralizes_script_link_and_image_urls() {
+            let html = parse_markdown(
+                "[x](javascript:window.__TAURI__.core.invoke('delete_asset')) \
+                 [y]( JaVaScRiPt:alert(1)) ![i](data:image/svg+xml,<svg>)",
             );
-        }
-    }
-
-    mod node_matches_query_tests {
-        use super::*;
-
-        fn node(text: &str) -> Node {
-            Node::new("n".to_string(), 0.0, 0.0, text.to_string())
-        }
-
-        #[test]
-        fn matches_text_case_insensitive() {
-            let n = node("Pricing Strategy");
-            assert!(node_matches_query(&n, "pricing"));
-            assert!(node_matches_query(&n, "STRATEGY"));
-            assert!(!node_matches_query(&n, "roadmap"));
-        }
-
-        #[test]
-        fn matches_tags() {
-            let mut n = node("body");
-            n.tags = vec!["urgent".to_string(), "v2".to_string()];
-            assert!(node_matches_query(&n, "urgent"));
-            assert!(node_matches_query(&n, "V2"));
-            assert!(!node_matches_query(&n, "v3"));
+            assert_eq!(html.matches("<a href=\"\">").count(), 2, "{html}");
+            assert!(html.contains("<img src=\"\""), "{html}");
+            assert!(!html.to_lowercase().contains("script:"), "{html}");
         }
 
         #[test]
-        fn matches_status() {
-            let mut n = node("body");
-            n.status = Some("in-progress".to_string());
-            assert!(node_matches_query(&n, "progress"));
-            assert!(!node_matches_query(&n, "done"));
+        fn keeps_http_and_relative_urls() {
+            let html = parse_markdown("[a](https://example.com/a:b) [b](notes/c.md#top)");
+            assert!(html.contains("href=\"https://example.com/a:b\""), "{html}");
+            assert!(html.contains("href=\"notes/c.md#top\""), "{html}");
         }
 
         #[test]
-        fn empty_query_matches_nothing() {
-            let n = node("anything");
-            assert!(!node_matches_query(&n, ""));
-            assert!(!node_matches_query(&n, "   \t"));
+        fn strips_inline_html_script() {
+            let html = parse_markdown("hello <script>alert(1)</script> world");
+            assert!(!html.contains("<script>"), "raw <script> leaked: {html}");
+            assert!(
+                html.contains("&lt;script&gt;"),
+                "expected escaped script: {html}"
+            );
         }
     }
 
@@ -3145,6 +7118,138 @@ mod tests {
         }
     }
 
+    mod snap_resize_delta_tests {
+        use super::*;
+
+        #[test]
+        fn bottom_right_lands_the_far_corner_on_the_grid() {
+            let rect = (0.0, 0.0, 200.0, 100.0);
+            let (dx, dy) = snap_resize_delta(rect, ResizeHandle::BottomRight, (37.0, 12.0), 50.0);
+            assert_eq!((dx, dy), (50.0, 0.0));
+            let to = resize_rect(rect, ResizeHandle::BottomRight, dx, dy);
+            assert_eq!(to, (0.0, 0.0, 250.0, 100.0));
+        }
+
+        #[test]
+        fn top_left_snaps_the_origin_and_keeps_the_far_corner() {
+            let rect = (110.0, 60.0, 200.0, 100.0);
+            let (dx, dy) = snap_resize_delta(rect, ResizeHandle::TopLeft, (-18.0, 3.0), 50.0);
+            let (x, y, w, h) = resize_rect(rect, ResizeHandle::TopLeft, dx, dy);
+            assert_eq!((x, y), (100.0, 50.0));
+            assert_eq!((x + w, y + h), (310.0, 160.0));
+        }
+
+        #[test]
+        fn off_grid_nodes_still_snap_only_the_dragged_edges() {
+            let rect = (10.0, 10.0, 200.0, 100.0);
+            let (dx, dy) = snap_resize_delta(rect, ResizeHandle::TopRight, (0.0, 0.0), 50.0);
+            assert_eq!((dx, dy), (-10.0, -10.0));
+        }
+    }
+
+    mod aligned_new_position_tests {
+        use super::*;
+
+        fn last() -> Node {
+            Node::new("last".into(), 100.0, 100.0, String::new())
+        }
+
+        #[test]
+        fn no_previous_node_centers_on_cursor() {
+            assert_eq!(
+                aligned_new_position(None, (500.0, 300.0), 120.0),
+                (400.0, 250.0)
+            );
+        }
+
+        #[test]
+        fn far_from_last_uses_cursor() {
+            let last = last();
+            assert_eq!(
+                aligned_new_position(Some(&last), (900.0, 300.0), 120.0),
+                (800.0, 250.0)
+            );
+            // Right column, but well below the next row.
+            assert_eq!(
+                aligned_new_position(Some(&last), (200.0, 700.0), 120.0),
+                (100.0, 650.0)
+            );
+        }
+
+        #[test]
+        fn near_last_aligns_one_row_below() {
+            let last = last();
+            // Next row would be centered at (200, 270); the cursor is close.
+            assert_eq!(
+                aligned_new_position(Some(&last), (260.0, 240.0), 120.0),
+                (100.0, 220.0)
+            );
+        }
+
+        #[test]
+        fn row_follows_a_resized_last_node() {
+            let mut last = last();
+            last.height = 200.0;
+            assert_eq!(
+                aligned_new_position(Some(&last), (200.0, 380.0), 120.0),
+                (100.0, 320.0)
+            );
+        }
+    }
+
+    mod viewport_visibility_tests {
+        use super::*;
+
+        fn board_at(points: &[(f64, f64)]) -> Board {
+            Board {
+                nodes: points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &(x, y))| Node::new(format!("n{i}"), x, y, String::new()))
+                    .collect(),
+                ..Board::default()
+            }
+        }
+
+        fn camera_at(x: f64, y: f64, zoom: f64) -> Camera {
+            Camera { x, y, zoom }
+        }
+
+        #[test]
+        fn lists_nodes_overlapping_the_view() {
+            let board = board_at(&[(100.0, 100.0), (5_000.0, 0.0), (-300.0, 0.0)]);
+            let visible = nodes_in_viewport(&board, &camera_at(0.0, 0.0, 1.0), 800.0, 600.0);
+            let ids: Vec<&str> = visible.iter().map(|n| n.id.as_str()).collect();
+            // n2 spans -300..-100 at the default width, so it ends left of the view.
+            assert_eq!(ids, ["n0"]);
+        }
+
+        #[test]
+        fn zooming_out_brings_far_nodes_into_view() {
+            let board = board_at(&[(5_000.0, 0.0)]);
+            assert!(lost_in_void(
+                &board,
+                &camera_at(0.0, 0.0, 1.0),
+                800.0,
+                600.0
+            ));
+            assert!(!lost_in_void(
+                &board,
+                &camera_at(0.0, 0.0, 0.1),
+                800.0,
+                600.0
+            ));
+        }
+
+        #[test]
+        fn empty_board_or_unmeasured_view_is_never_lost() {
+            let far = camera_at(10_000.0, 10_000.0, 1.0);
+            assert!(!lost_in_void(&Board::default(), &far, 800.0, 600.0));
+            assert!(!lost_in_void(&board_at(&[(0.0, 0.0)]), &far, 0.0, 0.0));
+            assert!(lost_in_void(&board_at(&[(0.0, 0.0)]), &far, 800.0, 600.0));
+        }
+    }
+
     mod minimap_transform_tests {
         use super::*;
 
@@ -3195,6 +7300,259 @@ mod tests {
         }
     }
 
+    mod alignment_snap_tests {
+        use super::*;
+
+        #[test]
+        fn snaps_left_edge_to_neighbor_left_edge() {
+            // Moving box sits 4px right of the neighbor's left edge, far away in y.
+            let others = [(100.0, 0.0, 300.0, 100.0)];
+            let (dx, dy, guides) =
+                alignment_snap((104.0, 500.0, 254.0, 560.0), &others, ALIGN_SNAP_PX);
+            assert_eq!(dx, -4.0);
+            assert_eq!(dy, 0.0);
+            assert_eq!(guides, vec![Guide::Vertical(100.0)]);
+        }
+
+        #[test]
+        fn snaps_center_to_neighbor_center() {
+            // Neighbor center y = 30; moving center y = 32, edges out of range.
+            let others = [(0.0, 0.0, 100.0, 60.0)];
+            let (dx, dy, guides) =
+                alignment_snap((400.0, 12.0, 437.0, 52.0), &others, ALIGN_SNAP_PX);
+            assert_eq!(dx, 0.0);
+            assert_eq!(dy, -2.0);
+            assert_eq!(guides, vec![Guide::Horizontal(30.0)]);
+        }
+
+        #[test]
+        fn no_snap_outside_threshold() {
+            let others = [(0.0, 0.0, 100.0, 100.0)];
+            let (dx, dy, guides) =
+                alignment_snap((520.0, 520.0, 600.0, 600.0), &others, ALIGN_SNAP_PX);
+            assert_eq!((dx, dy), (0.0, 0.0));
+            assert!(guides.is_empty());
+            let (dx, dy, guides) = alignment_snap((0.0, 0.0, 10.0, 10.0), &[], ALIGN_SNAP_PX);
+            assert_eq!((dx, dy), (0.0, 0.0));
+            assert!(guides.is_empty());
+        }
+
+        #[test]
+        fn picks_the_closest_candidate() {
+            // Left edge is 5px from one neighbor and 1px from another.
+            let others = [(95.0, 900.0, 150.0, 950.0), (101.0, 700.0, 180.0, 750.0)];
+            let (dx, _, guides) = alignment_snap((100.0, 0.0, 160.0, 40.0), &others, ALIGN_SNAP_PX);
+            assert_eq!(dx, 1.0);
+            assert_eq!(guides, vec![Guide::Vertical(101.0)]);
+        }
+    }
+
+    mod zoom_factor_tests {
+        use super::*;
+
+        #[test]
+        fn one_notch_matches_the_classic_step() {
+            assert!((zoom_factor(-100.0, 1.0) - 1.1).abs() < 1e-12);
+            assert!((zoom_factor(100.0, 1.0) - 1.0 / 1.1).abs() < 1e-12);
+            assert_eq!(zoom_factor(0.0, 1.0), 1.0);
+        }
+
+        #[test]
+        fn sensitivity_scales_the_step() {
+            let fast = zoom_factor(-100.0, 2.0);
+            assert!((fast - 1.1 * 1.1).abs() < 1e-12);
+            assert!(zoom_factor(-100.0, 0.5) < zoom_factor(-100.0, 1.0));
+            assert_eq!(zoom_factor(-100.0, 0.0), 1.0);
+        }
+
+        #[test]
+        fn large_deltas_are_clamped_per_event() {
+            assert!((zoom_factor(-10_000.0, 1.0) - MAX_WHEEL_ZOOM_STEP).abs() < 1e-12);
+            assert!((zoom_factor(10_000.0, 1.0) - 1.0 / MAX_WHEEL_ZOOM_STEP).abs() < 1e-12);
+            assert_eq!(zoom_factor(f64::NAN, 1.0), 1.0);
+        }
+    }
+
+    mod suppress_doubleclick_tests {
+        use super::*;
+
+        #[test]
+        fn suppressed_inside_the_window() {
+            assert!(should_suppress_doubleclick(1_000.0, 1_000.0, 300.0));
+            assert!(should_suppress_doubleclick(1_000.0, 1_299.0, 300.0));
+        }
+
+        #[test]
+        fn allowed_from_the_window_boundary_on() {
+            assert!(!should_suppress_doubleclick(1_000.0, 1_300.0, 300.0));
+            assert!(!should_suppress_doubleclick(1_000.0, 5_000.0, 300.0));
+        }
+
+        #[test]
+        fn allowed_with_no_gesture_yet() {
+            assert!(!should_suppress_doubleclick(f64::NEG_INFINITY, 10.0, 300.0));
+        }
+    }
+
+    mod ease_camera_tests {
+        use super::*;
+
+        fn cam(x: f64, y: f64, zoom: f64) -> Camera {
+            Camera { x, y, zoom }
+        }
+
+        #[test]
+        fn starts_and_lands_exactly() {
+            let (from, to) = (cam(0.0, 0.0, 1.0), cam(400.0, -200.0, 2.0));
+            let start = ease_camera(&from, &to, 0.0);
+            assert_eq!((start.x, start.y, start.zoom), (0.0, 0.0, 1.0));
+            let end = ease_camera(&from, &to, 1.0);
+            assert_eq!((end.x, end.y, end.zoom), (400.0, -200.0, 2.0));
+        }
+
+        #[test]
+        fn midpoint_is_halfway_with_geometric_zoom() {
+            let mid = ease_camera(&cam(0.0, 0.0, 0.5), &cam(100.0, 50.0, 2.0), 0.5);
+            assert_eq!((mid.x, mid.y), (50.0, 25.0));
+            assert!((mid.zoom - 1.0).abs() < 1e-12);
+        }
+
+        #[test]
+        fn eases_in_and_out() {
+            let (from, to) = (cam(0.0, 0.0, 1.0), cam(100.0, 0.0, 1.0));
+            let early = ease_camera(&from, &to, 0.25).x;
+            let late = ease_camera(&from, &to, 0.75).x;
+            assert!(early < 25.0 && late > 75.0);
+            assert!((early + late - 100.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn clamps_time_and_zoom() {
+            let (from, to) = (cam(0.0, 0.0, 0.01), cam(10.0, 10.0, 50.0));
+            assert_eq!(ease_camera(&from, &to, -1.0).zoom, 0.1);
+            assert_eq!(ease_camera(&from, &to, 2.0).zoom, 5.0);
+            let mid = ease_camera(&from, &to, 0.5).zoom;
+            assert!((0.1..=5.0).contains(&mid));
+        }
+    }
+
+    mod step_inertia_tests {
+        use super::*;
+
+        #[test]
+        fn decays_to_exactly_zero() {
+            let mut v = (2.0, -1.5);
+            for _ in 0..200 {
+                v = step_inertia(v, 16.0, PAN_FRICTION);
+            }
+            assert_eq!(v, (0.0, 0.0));
+        }
+
+        #[test]
+        fn preserves_direction_while_slowing() {
+            let v0 = (3.0, -1.0);
+            let v1 = step_inertia(v0, 16.0, PAN_FRICTION);
+            assert!(v1.0 > 0.0 && v1.1 < 0.0);
+            assert!(v1.0.hypot(v1.1) < v0.0.hypot(v0.1));
+            // Same factor on both axes: the heading is unchanged.
+            assert!((v1.0 / v1.1 - v0.0 / v0.1).abs() < 1e-9);
+        }
+
+        #[test]
+        fn zero_dt_keeps_velocity_and_slow_input_stops() {
+            assert_eq!(step_inertia((1.0, 0.5), 0.0, PAN_FRICTION), (1.0, 0.5));
+            assert_eq!(step_inertia((0.01, 0.0), 0.0, PAN_FRICTION), (0.0, 0.0));
+            assert_eq!(
+                step_inertia((f64::NAN, 1.0), 16.0, PAN_FRICTION),
+                (0.0, 0.0)
+            );
+        }
+    }
+
+    mod thumbnail_dimensions_tests {
+        use super::*;
+
+        #[test]
+        fn scales_longest_edge_to_max() {
+            assert_eq!(thumbnail_dimensions(1600, 900, 320.0), (320, 180));
+            assert_eq!(thumbnail_dimensions(900, 1600, 320.0), (180, 320));
+        }
+
+        #[test]
+        fn never_upscales() {
+            assert_eq!(thumbnail_dimensions(200, 100, 320.0), (200, 100));
+        }
+
+        #[test]
+        fn degenerate_canvas_keeps_one_pixel() {
+            assert_eq!(thumbnail_dimensions(0, 0, 320.0), (1, 1));
+            assert_eq!(thumbnail_dimensions(10_000, 1, 320.0), (320, 1));
+        }
+    }
+
+    mod diff_summary_tests {
+        use super::*;
+
+        #[test]
+        fn empty_diff_says_no_changes() {
+            assert_eq!(
+                diff_summary(&ops::BoardDiff::default()),
+                "No changes since backup"
+            );
+        }
+
+        #[test]
+        fn moved_and_changed_nodes_count_together() {
+            let diff = ops::BoardDiff {
+                added_nodes: vec!["a".into()],
+                moved_nodes: vec!["m".into()],
+                changed_nodes: vec!["c".into()],
+                removed_edges: vec!["e".into()],
+                ..Default::default()
+            };
+            assert_eq!(
+                diff_summary(&diff),
+                "vs backup: +1 \u{2212}0 ~2 nodes, +0 \u{2212}1 ~0 edges"
+            );
+        }
+    }
+
+    mod scroll_memory_tests {
+        use super::*;
+
+        #[test]
+        fn set_then_get_per_node() {
+            let mut memory = ScrollMemory::default();
+            memory.set("a", 420.0);
+            memory.set("b", 12.5);
+            assert_eq!(memory.get("a"), Some(420.0));
+            assert_eq!(memory.get("b"), Some(12.5));
+            assert_eq!(memory.get("c"), None);
+            memory.set("a", 80.0);
+            assert_eq!(memory.get("a"), Some(80.0));
+        }
+
+        #[test]
+        fn retain_forgets_deleted_nodes() {
+            let mut memory = ScrollMemory::default();
+            memory.set("a", 100.0);
+            memory.set("b", 200.0);
+            memory.retain(&["b", "c"].into_iter().collect());
+            assert_eq!(memory.get("a"), None);
+            assert_eq!(memory.get("b"), Some(200.0));
+        }
+
+        #[test]
+        fn scrolling_back_to_the_top_drops_the_entry() {
+            let mut memory = ScrollMemory::default();
+            memory.set("a", 100.0);
+            memory.set("a", 0.0);
+            assert_eq!(memory.get("a"), None);
+            memory.set("a", f64::NAN);
+            assert_eq!(memory, ScrollMemory::default());
+        }
+    }
+
     mod camera_persist_tests {
         use super::*;
 
@@ -3250,5 +7608,14 @@ mod tests {
             assert_eq!(cam.y, 0.0);
             assert_eq!(cam.zoom, 2.0);
         }
+
+        #[test]
+        fn storage_key_is_per_board_path() {
+            assert_eq!(camera_storage_key(None), CAMERA_KEY_PREFIX);
+            assert_eq!(
+                camera_storage_key(Some("/work/board.json")),
+                format!("{CAMERA_KEY_PREFIX}:/work/board.json")
+            );
+        }
     }
 }
@@ -1,9 +1,14 @@
-use crate::app::is_local_md_file;
+use crate::app::{is_gif, is_md_link, Guide, GRID_SIZE};
+use crate::settings::{EdgeStyle, SelectionStyle};
 use crate::state::{
-    truncate_filename, Board, Camera, LinkPreview, Node, NodeType, RESIZE_HANDLE_SIZE,
+    elide_middle, nearest_port, nodes_bounding_box,
+    ops::{diff_boards, BoardDiff},
+    port_anchor, truncate_filename, Board, Camera, LinkPreview, Node, NodeType, Port,
+    RESIZE_HANDLE_SIZE,
 };
 use std::cell::{Cell, RefCell};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -24,6 +29,10 @@ const BORDER_SELECTED: &str = "#4c90f0"; // = var(--accent)
 const TEXT_COLOR: &str = "#c8d2e0"; // = var(--text)
 const TEXT_DIM: &str = "#8a97a8"; // = var(--text-dim)
 
+// Text on a light custom node fill, where the theme's light text washes out.
+const TEXT_ON_LIGHT: &str = "#0a0e14"; // = var(--bg)
+const TEXT_DIM_ON_LIGHT: &str = "#343d4a";
+
 // Node surfaces — near-monochrome blue-gray family (fallback to OSINT's per-kind
 // accent left-stripe; see PR note). Differences are 1-6 points per channel, so
 // at typical zoom they read near-uniform: a conscious fidelity trade vs OSINT's
@@ -36,14 +45,22 @@ const NODE_BG_MD: &str = "#15131f";
 const NODE_BG_LINK: &str = "#101522";
 const EDGE_COLOR: &str = "rgba(76, 144, 240, 0.45)"; // = var(--accent-line)
 const EDGE_PREVIEW: &str = "#6ba8ff"; // = var(--accent-bright)
+const EDGE_EMPHASIS: &str = "#6ba8ff"; // = var(--accent-bright)
+const EDGE_DIMMED: &str = "rgba(76, 144, 240, 0.15)";
 const SELECT_BOX_FILL: &str = "rgba(76, 144, 240, 0.12)"; // = var(--accent-bg)
 const SELECT_BOX_STROKE: &str = "#4c90f0"; // = var(--accent)
+const GUIDE_COLOR: &str = "#6ba8ff"; // = var(--accent-bright)
+const SELECTION_BOUNDS_STROKE: &str = "rgba(76, 144, 240, 0.5)"; // = --accent @ 50%
 const RESIZE_HANDLE_COLOR: &str = "#6ba8ff"; // = var(--accent-bright)
 const RESIZE_HANDLE_BG: &str = "#0a0e14"; // = var(--bg)
 const EDGE_LABEL_BG: &str = "rgba(17, 22, 31, 0.94)"; // = var(--bg-panel)
 const GROUP_BG: &str = "rgba(76, 144, 240, 0.06)"; // = --accent @ 6% (rgb 76,144,240)
 const GROUP_BORDER: &str = "rgba(76, 144, 240, 0.25)"; // = --accent @ 25% (rgb 76,144,240)
 const GROUP_LABEL_COLOR: &str = "#8a97a8"; // = var(--text-dim)
+                                           // Backup-diff overlay; the selection toolbar's green/amber swatches and --danger.
+const DIFF_ADDED: &str = "#22c55e";
+const DIFF_CHANGED: &str = "#f59e0b";
+const DIFF_REMOVED: &str = "#e76a6e"; // = var(--danger)
 
 // Inter for proportional labels/meta (width is non-load-bearing there).
 const FONT_SANS: &str = "Inter, system-ui, sans-serif";
@@ -173,13 +190,18 @@ fn box_outside_viewport(x0: f64, y0: f64, x1: f64, y1: f64, view_w: f64, view_h:
 }
 
 /// Returns `true` when an edge can be skipped because the screen-space bounding
-/// box spanning its two endpoint node centers is fully outside the viewport.
-/// Edges whose endpoints are missing draw nothing, so they're also "outside".
-/// Using node centers (a superset of the clipped+arrowhead line) means a kept
-/// edge is never wrongly culled.
+/// box spanning its two [`edge_anchors`] (and, for a curved parallel edge, its
+/// control point) is fully outside the viewport. Edges whose endpoints are
+/// missing draw nothing, so they're also "outside". Using node centers (a
+/// superset of the clipped+arrowhead line) means a kept edge is never wrongly
+/// culled; the curve stays inside the triangle of its three points. A
+/// [`EdgeStyle::Curved`] edge is bounded by its four [`edge_bezier`] points
+/// instead, which hold the whole cubic the same way.
 fn edge_outside_viewport(
     node_map: &HashMap<&str, &Node>,
     edge: &crate::state::Edge,
+    bend: f64,
+    style: EdgeStyle,
     camera: &Camera,
     view_w: f64,
     view_h: f64,
@@ -189,15 +211,294 @@ fn edge_outside_viewport(
         node_map.get(edge.to_node.as_str()),
     ) {
         (Some(from), Some(to)) => {
-            let (fx, fy) =
-                camera.world_to_screen(from.x + from.width / 2.0, from.y + from.height / 2.0);
-            let (tx, ty) = camera.world_to_screen(to.x + to.width / 2.0, to.y + to.height / 2.0);
-            box_outside_viewport(fx, fy, tx, ty, view_w, view_h)
+            let hull: Vec<(f64, f64)> = match style {
+                EdgeStyle::Straight => {
+                    let (from_a, to_a) = edge_anchors(from, to, edge.ports());
+                    vec![from_a, to_a, edge_control_point(from_a, to_a, bend)]
+                }
+                EdgeStyle::Curved => edge_bezier(from, to, edge.ports(), bend).to_vec(),
+            };
+            let screen: Vec<(f64, f64)> = hull
+                .iter()
+                .map(|&(x, y)| camera.world_to_screen(x, y))
+                .collect();
+            let (min_x, max_x) = screen
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                    (lo.min(p.0), hi.max(p.0))
+                });
+            let (min_y, max_y) = screen
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                    (lo.min(p.1), hi.max(p.1))
+                });
+            box_outside_viewport(min_x, min_y, max_x, max_y, view_w, view_h)
         }
         _ => true,
     }
 }
 
+/// World-unit gap between the midpoints of neighbouring parallel edges.
+pub const PARALLEL_EDGE_SPACING: f64 = 30.0;
+
+/// Sideways offsets (world units) for `count` edges joining the same pair of
+/// nodes, spread [`PARALLEL_EDGE_SPACING`] apart and centered on the straight
+/// line: one edge stays straight, two bow out to either side, three keep the
+/// middle one straight, and so on.
+pub fn parallel_edge_offsets(count: usize) -> Vec<f64> {
+    let middle = count.saturating_sub(1) as f64 / 2.0;
+    (0..count)
+        .map(|i| (i as f64 - middle) * PARALLEL_EDGE_SPACING)
+        .collect()
+}
+
+/// Bend of every edge that shares its (unordered) node pair with another edge,
+/// keyed by edge id; edges missing from the map draw straight. Parallel edges
+/// take [`parallel_edge_offsets`] in board order. Offsets are measured against
+/// one fixed direction per pair, so an `a → b` and a `b → a` edge with opposite
+/// offsets still bow to opposite sides; the stored bend is relative to each
+/// edge's own `from → to` direction, as [`edge_control_point`] expects.
+pub fn parallel_edge_bends(board: &Board) -> HashMap<&str, f64> {
+    let mut pairs: HashMap<(&str, &str), Vec<&crate::state::Edge>> = HashMap::new();
+    for edge in &board.edges {
+        let (from, to) = (edge.from_node.as_str(), edge.to_node.as_str());
+        if from == to {
+            continue;
+        }
+        pairs
+            .entry((from.min(to), from.max(to)))
+            .or_default()
+            .push(edge);
+    }
+    let mut bends = HashMap::new();
+    for ((low, _), edges) in pairs {
+        if edges.len() < 2 {
+            continue;
+        }
+        for (edge, offset) in edges.iter().zip(parallel_edge_offsets(edges.len())) {
+            let bend = if edge.from_node == low {
+                offset
+            } else {
+                -offset
+            };
+            bends.insert(edge.id.as_str(), bend);
+        }
+    }
+    bends
+}
+
+/// The points a straight edge between `from` and `to` is aimed along: each
+/// end's [`port_anchor`] when `ports` pins it to a side, else the node's
+/// center (the drawn line is then clipped back to the border).
+pub fn edge_anchors(
+    from: &Node,
+    to: &Node,
+    (from_port, to_port): (Option<Port>, Option<Port>),
+) -> ((f64, f64), (f64, f64)) {
+    let anchor = |node: &Node, port: Option<Port>| {
+        port.map_or(node.center(), |port| {
+            port_anchor((node.x, node.y, node.width, node.height), port)
+        })
+    };
+    (anchor(from, from_port), anchor(to, to_port))
+}
+
+/// Control point of the quadratic curve from `from` to `to` whose midpoint sits
+/// `bend` world units to the left of the straight line (as seen travelling
+/// `from → to` on screen, y down). A zero bend gives the line's midpoint, so
+/// the "curve" is the straight line itself.
+pub fn edge_control_point(from: (f64, f64), to: (f64, f64), bend: f64) -> (f64, f64) {
+    let mid = ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0);
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let len = dx.hypot(dy);
+    if len == 0.0 || bend == 0.0 {
+        return mid;
+    }
+    // A quadratic's midpoint is halfway between the chord's midpoint and the
+    // control point, so the control point sits twice as far out.
+    let (nx, ny) = (dy / len, -dx / len);
+    (mid.0 + nx * bend * 2.0, mid.1 + ny * bend * 2.0)
+}
+
+/// Minimum distance (world units) a curved edge's control points are pulled
+/// out from its endpoints, so nodes that nearly touch still get a smooth bend
+/// rather than a kink.
+pub const EDGE_CURVE_MIN_PULL: f64 = 40.0;
+
+/// The cubic bezier an [`EdgeStyle::Curved`] edge follows from `from` to `to`,
+/// in world coordinates: `[start, ctrl1, ctrl2, end]`. It leaves the source
+/// through the middle of the side facing the target and enters the target the
+/// same way, each control point pulled straight out from its endpoint along
+/// that side's normal — horizontally, flowchart style, unless the nodes sit
+/// more above and below each other than side by side. An end pinned by
+/// `ports` uses that side instead. A non-zero `bend` (parallel edges) shifts
+/// both control points sideways so the curve's midpoint lands `bend` units
+/// left of the chord, matching [`edge_control_point`].
+pub fn edge_bezier(
+    from: &Node,
+    to: &Node,
+    (from_port, to_port): (Option<Port>, Option<Port>),
+    bend: f64,
+) -> [(f64, f64); 4] {
+    let (from_c, to_c) = (from.center(), to.center());
+    let (dx, dy) = (to_c.0 - from_c.0, to_c.1 - from_c.1);
+    let (facing_from, facing_to) = if dx.abs() >= dy.abs() {
+        if dx < 0.0 {
+            (Port::Left, Port::Right)
+        } else {
+            (Port::Right, Port::Left)
+        }
+    } else if dy < 0.0 {
+        (Port::Top, Port::Bottom)
+    } else {
+        (Port::Bottom, Port::Top)
+    };
+    let (from_port, to_port) = (
+        from_port.unwrap_or(facing_from),
+        to_port.unwrap_or(facing_to),
+    );
+    let start = port_anchor((from.x, from.y, from.width, from.height), from_port);
+    let end = port_anchor((to.x, to.y, to.width, to.height), to_port);
+    let (out, into) = (from_port.normal(), to_port.normal());
+    let gap = ((end.0 - start.0) * out.0 + (end.1 - start.1) * out.1).abs();
+    let pull = (gap / 2.0).max(EDGE_CURVE_MIN_PULL);
+    let mut ctrl1 = (start.0 + out.0 * pull, start.1 + out.1 * pull);
+    let mut ctrl2 = (end.0 + into.0 * pull, end.1 + into.1 * pull);
+
+    let (chord_x, chord_y) = (end.0 - start.0, end.1 - start.1);
+    let len = chord_x.hypot(chord_y);
+    if bend != 0.0 && len > 0.0 {
+        // A cubic's midpoint moves 3/4 as far as both control points do.
+        let shift = bend / 0.75;
+        let (nx, ny) = (chord_y / len, -chord_x / len);
+        ctrl1 = (ctrl1.0 + nx * shift, ctrl1.1 + ny * shift);
+        ctrl2 = (ctrl2.0 + nx * shift, ctrl2.1 + ny * shift);
+    }
+    [start, ctrl1, ctrl2, end]
+}
+
+/// Point at `t` (`0..=1`) along the cubic bezier `[start, ctrl1, ctrl2, end]`.
+pub fn cubic_point(curve: &[(f64, f64); 4], t: f64) -> (f64, f64) {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    (
+        a * curve[0].0 + b * curve[1].0 + c * curve[2].0 + d * curve[3].0,
+        a * curve[0].1 + b * curve[1].1 + c * curve[2].1 + d * curve[3].1,
+    )
+}
+
+/// Point at `t` (`0..=1`) along the quadratic curve `from` → `to` with control
+/// point `ctrl`.
+pub fn quad_point(from: (f64, f64), ctrl: (f64, f64), to: (f64, f64), t: f64) -> (f64, f64) {
+    let u = 1.0 - t;
+    (
+        u * u * from.0 + 2.0 * u * t * ctrl.0 + t * t * to.0,
+        u * u * from.1 + 2.0 * u * t * ctrl.1 + t * t * to.1,
+    )
+}
+
+/// Frames [`FrameStats`] averages over for the render-stats overlay.
+pub const FRAME_STATS_WINDOW: usize = 60;
+
+/// Rolling render timings for the dev render-stats overlay: the last `cap`
+/// frame durations (ms), oldest dropped first.
+#[derive(Clone, Debug)]
+pub struct FrameStats {
+    samples: VecDeque<f64>,
+    cap: usize,
+}
+
+impl FrameStats {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(cap),
+            cap: cap.max(1),
+        }
+    }
+
+    /// Add one frame's duration, evicting the oldest once the window is full.
+    pub fn record(&mut self, ms: f64) {
+        if self.samples.len() == self.cap {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ms);
+    }
+
+    pub fn last(&self) -> f64 {
+        self.samples.back().copied().unwrap_or(0.0)
+    }
+
+    /// Mean over the window; `0.0` before the first frame.
+    pub fn avg(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Slowest frame in the window; `0.0` before the first frame.
+    pub fn max(&self) -> f64 {
+        self.samples.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+/// Visible nodes drawn per frame while a progressive pass is catching up.
+pub const PROGRESSIVE_BATCH: usize = 400;
+
+/// Render cursor for progressive (chunked) drawing of very large boards.
+///
+/// While a pass is active, each frame draws the next [`PROGRESSIVE_BATCH`]
+/// visible nodes *on top of* the previous frames' output instead of clearing,
+/// so the cold first render of thousands of nodes (text wrapping, image decode)
+/// is spread across frames and the UI stays responsive. Once every visible node
+/// is drawn the pass is caught up and frames go back to full renders.
+///
+/// Culling runs first: the cursor walks only the nodes that survive viewport
+/// culling and collapse hiding, so a zoomed-in view of a huge board is usually
+/// a single batch. A pan or zoom mid-pass [`restart`](Self::restart)s it, which
+/// re-culls against the new viewport and redraws from a cleared canvas.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProgressiveCursor {
+    active: bool,
+    drawn: usize,
+}
+
+impl ProgressiveCursor {
+    /// Begin a progressive pass at the first visible node.
+    pub fn arm(&mut self) {
+        self.active = true;
+        self.drawn = 0;
+    }
+
+    /// Start an in-flight pass over from a cleared canvas. No-op once caught up.
+    pub fn restart(&mut self) {
+        if self.active {
+            self.drawn = 0;
+        }
+    }
+
+    pub fn is_caught_up(&self) -> bool {
+        !self.active
+    }
+
+    /// Indices into this frame's `total` visible nodes to draw now, advancing
+    /// the cursor. A range starting at `0` begins from a cleared canvas; one
+    /// ending at `total` finishes the frame (and the pass). Caught up, every
+    /// frame is the full `0..total`.
+    pub fn next_batch(&mut self, total: usize, batch: usize) -> Range<usize> {
+        if !self.active {
+            return 0..total;
+        }
+        let start = self.drawn.min(total);
+        let end = (start + batch.max(1)).min(total);
+        self.drawn = end;
+        if end == total {
+            self.active = false;
+        }
+        start..end
+    }
+}
+
 /// All inputs to a single [`render_board`] pass, bundled into one named-field
 /// struct. Using named fields (rather than a long positional argument list)
 /// makes a tuple-transposition mistake at the call site impossible: every input
@@ -210,16 +511,51 @@ pub struct RenderState<'a> {
     pub selected_nodes: &'a HashSet<String>,
     pub selected_edge: Option<&'a String>,
     pub editing_node: Option<&'a String>,
+    /// Node under the idle cursor; together with the selection it decides which
+    /// edges are emphasized.
+    pub hovered_node: Option<&'a String>,
+    /// Source armed by sticky multi-connect; outlined until it's disarmed.
+    pub armed_node: Option<&'a String>,
     /// In-progress edge being dragged: `(from_node_id, cursor_screen_x, cursor_screen_y)`.
     pub edge_preview: Option<(Option<&'a String>, f64, f64)>,
     /// Active box-selection rectangle in world coords: `(min_x, min_y, max_x, max_y)`.
     pub selection_box: Option<(f64, f64, f64, f64)>,
+    /// Node corner radius in world units (user setting); `0.0` draws square.
+    pub corner_radius: f64,
+    /// Draw edge labels along the edge direction (user setting) rather than level.
+    pub rotate_edge_labels: bool,
+    /// Opacity of unselected, unemphasized edges (user setting); see [`edge_alpha`].
+    pub edge_opacity: f64,
+    /// Emphasis for selected nodes (user setting); see [`selection_look`].
+    pub selection_style: SelectionStyle,
+    /// Node types toggled off in the type filter; see [`visible_nodes_by_type`].
+    pub hidden_types: &'a HashSet<String>,
+    /// What collapsed nodes hide, derived once per board change.
+    pub collapse: &'a CollapseView,
+    /// Tags toggled on in the tag filter; nodes carrying none of them draw
+    /// faded. See [`tag_dimmed`].
+    pub active_tags: &'a HashSet<String>,
+    /// Straight or curved edges (user setting); see [`edge_bezier`].
+    pub edge_style: EdgeStyle,
+    /// Smart-guide lines (world coords) for the node drag in progress.
+    pub guides: &'a [Guide],
+    /// World-space start positions of the nodes being dragged; each gets a faint
+    /// outline where it started. `None` outside a drag (or with the setting off).
+    pub drag_ghosts: Option<&'a HashMap<String, (f64, f64)>>,
+    /// Outlines for nodes and edges added, changed or removed since the backup
+    /// being compared against. `None` when no comparison is open.
+    pub diff: Option<&'a DiffOverlay>,
     pub image_cache: &'a ImageCache,
     pub link_preview_cache: &'a LinkPreviewCache,
     /// Device-pixel ratio applied by the caller as a context transform
     /// (`ctx.set_transform(dpr,0,0,dpr,0,0)`). All drawing here happens in CSS
     /// pixels, so the on-screen dimensions are `backing-store / dpr`.
     pub dpr: f64,
+    /// Progressive-pass cursor; decides which slice of the visible nodes this
+    /// frame draws (all of them once caught up).
+    pub progress: &'a mut ProgressiveCursor,
+    /// Wall-clock time (unix millis) for fading the recently-edited marker.
+    pub now_ms: f64,
 }
 
 pub fn render_board(state: RenderState) {
@@ -231,11 +567,26 @@ pub fn render_board(state: RenderState) {
         selected_nodes,
         selected_edge,
         editing_node,
+        hovered_node,
+        armed_node,
         edge_preview,
         selection_box,
+        corner_radius,
+        rotate_edge_labels,
+        edge_opacity,
+        selection_style,
+        hidden_types,
+        collapse,
+        active_tags,
+        edge_style,
+        guides,
+        drag_ghosts,
+        diff,
         image_cache,
         link_preview_cache,
         dpr,
+        progress,
+        now_ms,
     } = state;
 
     // The backing store is sized `display * dpr`; the caller has scaled the
@@ -250,59 +601,195 @@ pub fn render_board(state: RenderState) {
     let width = canvas.width() as f64 / dpr;
     let height = canvas.height() as f64 / dpr;
 
-    ctx.set_fill_style_str(BG_COLOR);
-    ctx.fill_rect(0.0, 0.0, width, height);
-
-    draw_grid(ctx, camera, width, height);
-
-    draw_groups(ctx, board, camera);
-
     let node_map: HashMap<&str, &Node> = board.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
 
-    // Evict wrapped-text cache entries for nodes that no longer exist before any
-    // drawing happens, keeping the memo bounded to the live board.
-    {
-        let live_ids: HashSet<&str> = node_map.keys().copied().collect();
-        prune_wrap_cache(&live_ids);
+    // Subtrees under a collapsed node are skipped entirely, as are nodes of a
+    // filtered-out type: their nodes and any edge touching them.
+    let mut hidden = collapse.hidden.clone();
+    if !hidden_types.is_empty() {
+        let shown: HashSet<&str> = visible_nodes_by_type(board, hidden_types)
+            .iter()
+            .map(|n| n.id.as_str())
+            .collect();
+        hidden.extend(
+            board
+                .nodes
+                .iter()
+                .filter(|n| !shown.contains(n.id.as_str()))
+                .map(|n| n.id.clone()),
+        );
     }
 
-    for edge in &board.edges {
-        if edge_outside_viewport(&node_map, edge, camera, width, height) {
-            continue;
+    let visible: Vec<&Node> = drawn_nodes(board, &hidden)
+        .filter(|node| {
+            let (sx, sy) = camera.world_to_screen(node.x, node.y);
+            let (ex, ey) = (
+                sx + node.width * camera.zoom,
+                sy + node.height * camera.zoom,
+            );
+            !box_outside_viewport(sx, sy, ex, ey, width, height)
+        })
+        .collect();
+    let batch = progress.next_batch(visible.len(), PROGRESSIVE_BATCH);
+    let finishes_frame = batch.end == visible.len();
+
+    // Everything under the nodes is drawn once, with the first batch; later
+    // batches of a progressive pass paint on top of it without clearing.
+    if batch.start == 0 {
+        ctx.set_fill_style_str(BG_COLOR);
+        ctx.fill_rect(0.0, 0.0, width, height);
+
+        draw_grid(ctx, camera, width, height);
+
+        draw_groups(ctx, board, camera);
+
+        // Evict wrapped-text cache entries for nodes that no longer exist before
+        // any drawing happens, keeping the memo bounded to the live board.
+        {
+            let live_ids: HashSet<&str> = node_map.keys().copied().collect();
+            prune_wrap_cache(&live_ids);
         }
-        let is_selected = selected_edge == Some(&edge.id);
-        draw_edge(ctx, &node_map, edge, camera, is_selected);
-    }
 
-    if let Some((Some(from_node_id), to_screen_x, to_screen_y)) = edge_preview {
-        draw_edge_preview(
-            ctx,
-            &node_map,
-            from_node_id,
-            to_screen_x,
-            to_screen_y,
-            camera,
-        );
-    }
+        // Edges touching the selected/hovered nodes are emphasized and the rest
+        // dim; with nothing focused every edge draws normally.
+        let mut focus: HashSet<&str> = selected_nodes.iter().map(String::as_str).collect();
+        focus.extend(hovered_node.map(String::as_str));
+        let emphasized = emphasized_edges(board, &focus);
+        let bends = parallel_edge_bends(board);
 
-    for node in &board.nodes {
-        let (sx, sy) = camera.world_to_screen(node.x, node.y);
-        let sw = node.width * camera.zoom;
-        let sh = node.height * camera.zoom;
-        if box_outside_viewport(sx, sy, sx + sw, sy + sh, width, height) {
-            continue;
+        for edge in &board.edges {
+            if hidden.contains(&edge.from_node) || hidden.contains(&edge.to_node) {
+                continue;
+            }
+            let bend = bends.get(edge.id.as_str()).copied().unwrap_or(0.0);
+            if edge_outside_viewport(&node_map, edge, bend, edge_style, camera, width, height) {
+                continue;
+            }
+            let is_selected = selected_edge == Some(&edge.id);
+            let is_emphasized = emphasized.contains(&edge.id);
+            let is_dimmed = !focus.is_empty() && !is_emphasized;
+            // An edge fades with the tag filter only when both of its ends do.
+            let tag_faded = [&edge.from_node, &edge.to_node].iter().all(|id| {
+                node_map
+                    .get(id.as_str())
+                    .is_some_and(|n| tag_dimmed(n, active_tags))
+            });
+            let alpha = edge_alpha(edge_opacity, is_selected, is_emphasized);
+            draw_edge(
+                ctx,
+                &node_map,
+                edge,
+                bend,
+                camera,
+                is_selected,
+                is_emphasized,
+                is_dimmed,
+                if tag_faded {
+                    alpha * TAG_DIM_ALPHA
+                } else {
+                    alpha
+                },
+                edge_style,
+                rotate_edge_labels,
+                board.settings.directed,
+            );
+        }
+
+        if let Some((Some(from_node_id), to_screen_x, to_screen_y)) = edge_preview {
+            // Over a target node the preview snaps to the port the drop would
+            // pin the edge to.
+            let (world_x, world_y) = camera.screen_to_world(to_screen_x, to_screen_y);
+            let (to_screen_x, to_screen_y) = board
+                .nodes
+                .iter()
+                .rev()
+                .find(|n| {
+                    &n.id != from_node_id
+                        && !hidden.contains(n.id.as_str())
+                        && n.contains_point(world_x, world_y)
+                })
+                .map_or((to_screen_x, to_screen_y), |n| {
+                    let rect = (n.x, n.y, n.width, n.height);
+                    let (x, y) = port_anchor(rect, nearest_port(rect, (world_x, world_y)));
+                    camera.world_to_screen(x, y)
+                });
+            draw_edge_preview(
+                ctx,
+                &node_map,
+                from_node_id,
+                to_screen_x,
+                to_screen_y,
+                camera,
+                board.settings.directed,
+            );
         }
+    }
+
+    let individual_handles = show_individual_handles(selected_nodes.len());
+    let selection = selection_look(selection_style);
+    for node in &visible[batch] {
         let is_selected = selected_nodes.contains(&node.id);
         let is_editing = editing_node == Some(&node.id);
+        let faded = tag_dimmed(node, active_tags);
+        if faded {
+            ctx.set_global_alpha(TAG_DIM_ALPHA);
+        }
         draw_node(
             ctx,
             node,
             camera,
             is_selected,
+            is_selected && individual_handles,
             is_editing,
+            corner_radius,
+            &selection,
             image_cache,
             link_preview_cache,
         );
+        if faded {
+            ctx.set_global_alpha(1.0);
+        }
+        if detail_level(camera.zoom) != DetailLevel::Full {
+            continue;
+        }
+        if node.collapsed {
+            let (sx, sy) = camera.world_to_screen(node.x + node.width, node.y + node.height);
+            let count = collapse.folded.get(&node.id).copied().unwrap_or(0);
+            draw_collapsed_badge(ctx, sx, sy, count, camera.zoom);
+        }
+        let recency = recent_edit_alpha(node.updated_at, now_ms);
+        if recency > 0.0 {
+            let (sx, sy) = camera.world_to_screen(node.x, node.y);
+            draw_recent_edit_marker(ctx, sx, sy, recency, camera.zoom);
+        }
+    }
+
+    // Overlays go on top of the last batch only.
+    if !finishes_frame {
+        return;
+    }
+
+    draw_alignment_guides(ctx, camera, guides, width, height);
+
+    if let Some(overlay) = diff {
+        draw_diff_edges(ctx, camera, &overlay.edges);
+        draw_diff_marks(ctx, camera, &overlay.nodes, corner_radius);
+    }
+
+    if let Some(node) = armed_node.and_then(|id| node_map.get(id.as_str())) {
+        draw_armed_outline(ctx, camera, node, corner_radius);
+    }
+
+    if let Some(starts) = drag_ghosts {
+        draw_drag_ghosts(
+            ctx,
+            &ghost_rects(board, starts, camera),
+            corner_radius * camera.zoom,
+        );
+    }
+
+    if let Some((bounds, count)) = selection_bounds(board, selected_nodes) {
+        draw_selection_bounds(ctx, camera, bounds, count);
     }
 
     if let Some((min_x, min_y, max_x, max_y)) = selection_box {
@@ -310,6 +797,88 @@ pub fn render_board(state: RenderState) {
     }
 }
 
+/// Ids of every edge with an endpoint in `node_ids`. Computed once per frame so
+/// `render_board` can emphasize the focused nodes' connections.
+pub fn emphasized_edges(board: &Board, node_ids: &HashSet<&str>) -> HashSet<String> {
+    if node_ids.is_empty() {
+        return HashSet::new();
+    }
+    board
+        .edges
+        .iter()
+        .filter(|e| {
+            node_ids.contains(e.from_node.as_str()) || node_ids.contains(e.to_node.as_str())
+        })
+        .map(|e| e.id.clone())
+        .collect()
+}
+
+/// Nodes whose type isn't toggled off in the type filter, in board (z) order.
+/// `hidden` holds [`NodeType::as_str`] names; `Unknown` nodes go with `text`,
+/// whose `[TEXT]` label they wear. Rendering skips the rest, and hit-testing
+/// only ever looks at these.
+pub fn visible_nodes_by_type<'a>(board: &'a Board, hidden: &HashSet<String>) -> Vec<&'a Node> {
+    board
+        .nodes
+        .iter()
+        .filter(|n| {
+            let kind = match n.node_type {
+                NodeType::Unknown => NodeType::Text,
+                kind => kind,
+            };
+            !hidden.contains(kind.as_str())
+        })
+        .collect()
+}
+
+/// What collapsed nodes hide on a board. Walking the graph is linear in the
+/// edges per collapsed node, so the app derives this once per board change (a
+/// `Memo`) and every frame and hit-test reads it instead.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollapseView {
+    /// Nodes under a collapsed ancestor; see [`Board::collapsed_hidden`].
+    pub hidden: HashSet<String>,
+    /// Size of each collapsed node's downstream subtree, shown on its `+N` badge.
+    pub folded: HashMap<String, usize>,
+}
+
+impl CollapseView {
+    pub fn new(board: &Board) -> Self {
+        let mut view = CollapseView::default();
+        // Same walk as `collapsed_hidden`, keeping each subtree's size too.
+        for node in board.nodes.iter().filter(|n| n.collapsed) {
+            let down = board.downstream_nodes(&node.id);
+            view.folded.insert(node.id.clone(), down.len());
+            if !view.hidden.contains(&node.id) {
+                view.hidden.extend(down);
+            }
+        }
+        view
+    }
+}
+
+/// Nodes the canvas shows, in board (z) order: of a type not toggled off (see
+/// [`visible_nodes_by_type`]) and not under a collapsed ancestor. Hit-testing,
+/// select-all and search only ever look at these.
+pub fn shown_nodes<'a>(
+    board: &'a Board,
+    hidden_types: &HashSet<String>,
+    collapsed: &HashSet<String>,
+) -> Vec<&'a Node> {
+    let mut nodes = visible_nodes_by_type(board, hidden_types);
+    nodes.retain(|n| !collapsed.contains(&n.id));
+    nodes
+}
+
+/// Nodes `render_board` draws, in board (z) order: everything not hidden under
+/// a collapsed ancestor (see [`Board::collapsed_hidden`]).
+fn drawn_nodes<'a: 'h, 'h>(
+    board: &'a Board,
+    hidden: &'h HashSet<String>,
+) -> impl Iterator<Item = &'a Node> + 'h {
+    board.nodes.iter().filter(|n| !hidden.contains(&n.id))
+}
+
 fn draw_groups(ctx: &CanvasRenderingContext2d, board: &Board, camera: &Camera) {
     // Early-out the common case: no grouped nodes means nothing to draw and we
     // skip allocating the bounds map entirely.
@@ -360,7 +929,7 @@ fn draw_groups(ctx: &CanvasRenderingContext2d, board: &Board, camera: &Camera) {
 }
 
 fn draw_grid(ctx: &CanvasRenderingContext2d, camera: &Camera, width: f64, height: f64) {
-    let grid_size = 50.0 * camera.zoom;
+    let grid_size = GRID_SIZE * camera.zoom;
     if grid_size < 10.0 {
         return;
     }
@@ -390,44 +959,229 @@ fn draw_grid(ctx: &CanvasRenderingContext2d, camera: &Camera, width: f64, height
     }
 }
 
+/// Border and shadow of a selected node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelectionLook {
+    /// Border width in screen pixels.
+    pub line_width: f64,
+    pub shadow_blur: f64,
+    /// Downward shadow offset in screen pixels, for an elevated look.
+    pub shadow_offset_y: f64,
+    /// Shadow color; `None` glows in the node's own border color.
+    pub shadow_color: Option<&'static str>,
+}
+
+/// Selection emphasis for the [`SelectionStyle`] setting.
+pub fn selection_look(style: SelectionStyle) -> SelectionLook {
+    match style {
+        SelectionStyle::Glow => SelectionLook {
+            line_width: 1.0,
+            shadow_blur: 8.0,
+            shadow_offset_y: 0.0,
+            shadow_color: None,
+        },
+        SelectionStyle::Shadow => SelectionLook {
+            line_width: 1.5,
+            shadow_blur: 12.0,
+            shadow_offset_y: 4.0,
+            shadow_color: Some("rgba(0, 0, 0, 0.55)"),
+        },
+        SelectionStyle::Outline => SelectionLook {
+            line_width: 2.5,
+            shadow_blur: 0.0,
+            shadow_offset_y: 0.0,
+            shadow_color: None,
+        },
+    }
+}
+
+/// Parse the CSS colors a node's `color` can hold — `#rgb`, `#rgba`,
+/// `#rrggbb`, `#rrggbbaa`, `rgb(…)` or `rgba(…)` with comma- or
+/// space-separated channels — into `(r, g, b, alpha)`. Anything else (named
+/// colors, `hsl()`, typos) is `None`, so the node keeps its type's surface
+/// rather than the canvas silently reusing the previous fill.
+pub fn parse_css_color(color: &str) -> Option<(u8, u8, u8, f64)> {
+    let color = color.trim().to_ascii_lowercase();
+    if let Some(hex) = color.strip_prefix('#') {
+        let nibble = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return match hex.len() {
+            3 | 4 => {
+                let alpha = if hex.len() == 4 { nibble(3)? * 17 } else { 255 };
+                Some((
+                    nibble(0)? * 17,
+                    nibble(1)? * 17,
+                    nibble(2)? * 17,
+                    alpha as f64 / 255.0,
+                ))
+            }
+            6 | 8 => {
+                let alpha = if hex.len() == 8 { byte(6)? } else { 255 };
+                Some((byte(0)?, byte(2)?, byte(4)?, alpha as f64 / 255.0))
+            }
+            _ => None,
+        };
+    }
+    let args = color
+        .strip_prefix("rgba(")
+        .or_else(|| color.strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let parts: Vec<&str> = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let channel = |p: &str| {
+        p.parse::<f64>()
+            .ok()
+            .filter(|v| (0.0..=255.0).contains(v))
+            .map(|v| v.round() as u8)
+    };
+    let alpha = match parts.len() {
+        3 => 1.0,
+        4 => parts[3]
+            .parse::<f64>()
+            .ok()
+            .filter(|a| (0.0..=1.0).contains(a))?,
+        _ => return None,
+    };
+    Some((
+        channel(parts[0])?,
+        channel(parts[1])?,
+        channel(parts[2])?,
+        alpha,
+    ))
+}
+
+/// WCAG relative luminance of a parsed color, composited over the canvas
+/// background when translucent (that is what it ends up drawn on).
+fn relative_luminance((r, g, b, alpha): (u8, u8, u8, f64)) -> f64 {
+    let (bg_r, bg_g, bg_b, _) = parse_css_color(BG_COLOR).unwrap_or((0, 0, 0, 1.0));
+    let linear = |c: u8, bg: u8| {
+        let v = (c as f64 * alpha + bg as f64 * (1.0 - alpha)) / 255.0;
+        if v <= 0.04045 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r, bg_r) + 0.7152 * linear(g, bg_g) + 0.0722 * linear(b, bg_b)
+}
+
+/// Whether text on a `fill` reads better in [`TEXT_ON_LIGHT`] than in the
+/// theme's light [`TEXT_COLOR`]: whichever has the higher WCAG contrast ratio.
+pub fn prefers_dark_text(fill: (u8, u8, u8, f64)) -> bool {
+    let contrast = |a: f64, b: f64| (a.max(b) + 0.05) / (a.min(b) + 0.05);
+    let luminance = |c: &str| parse_css_color(c).map_or(0.0, relative_luminance);
+    let fill = relative_luminance(fill);
+    contrast(fill, luminance(TEXT_ON_LIGHT)) > contrast(fill, luminance(TEXT_COLOR))
+}
+
+/// A node's own `color` as its fill, when [`parse_css_color`] accepts it,
+/// paired with whether text drawn on it should switch to the dark palette.
+/// `None` keeps the type-based surface.
+pub fn custom_fill(node: &Node) -> Option<(&str, bool)> {
+    let color = node.color.as_deref()?;
+    parse_css_color(color).map(|parsed| (color, prefers_dark_text(parsed)))
+}
+
+/// CSS text color for HTML laid over `node` (the markdown overlays): the dark
+/// [`TEXT_ON_LIGHT`] on a light custom fill, like the canvas text, otherwise
+/// the theme's `var(--text)`.
+pub fn overlay_text_color(node: &Node) -> &'static str {
+    match custom_fill(node) {
+        Some((_, true)) => TEXT_ON_LIGHT,
+        _ => "var(--text)",
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn draw_node(
     ctx: &CanvasRenderingContext2d,
     node: &Node,
     camera: &Camera,
     is_selected: bool,
+    show_handles: bool,
     is_editing: bool,
+    corner_radius: f64,
+    selection: &SelectionLook,
     image_cache: &ImageCache,
     link_preview_cache: &LinkPreviewCache,
 ) {
     let (screen_x, screen_y) = camera.world_to_screen(node.x, node.y);
     let screen_width = node.width * camera.zoom;
     let screen_height = node.height * camera.zoom;
+    let detail = detail_level(camera.zoom);
+
+    let fill = custom_fill(node);
+    if detail == DetailLevel::Dots {
+        let fill = if is_selected {
+            BORDER_SELECTED
+        } else {
+            fill.map_or(BORDER_COLOR, |(color, _)| color)
+        };
+        ctx.set_fill_style_str(fill);
+        ctx.fill_rect(
+            screen_x,
+            screen_y,
+            screen_width.max(2.0),
+            screen_height.max(2.0),
+        );
+        return;
+    }
 
-    let bg_color = match node.node_type {
-        NodeType::Idea => NODE_BG_IDEA,
-        NodeType::Note => NODE_BG_NOTE,
-        NodeType::Image => NODE_BG_IMAGE,
-        NodeType::Md => NODE_BG_MD,
-        NodeType::Link => NODE_BG_LINK,
-        NodeType::Text | NodeType::Unknown => NODE_BG_TEXT,
+    let radius = clamp_corner_radius(screen_width, screen_height, corner_radius * camera.zoom);
+
+    let bg_color = fill.map_or(
+        match node.node_type {
+            NodeType::Idea => NODE_BG_IDEA,
+            NodeType::Note => NODE_BG_NOTE,
+            NodeType::Image => NODE_BG_IMAGE,
+            NodeType::Md => NODE_BG_MD,
+            NodeType::Link => NODE_BG_LINK,
+            NodeType::Text | NodeType::Unknown => NODE_BG_TEXT,
+        },
+        |(color, _)| color,
+    );
+    let (text_color, text_dim) = match fill {
+        Some((_, true)) => (TEXT_ON_LIGHT, TEXT_DIM_ON_LIGHT),
+        _ => (TEXT_COLOR, TEXT_DIM),
     };
+    rounded_rect_path(ctx, screen_x, screen_y, screen_width, screen_height, radius);
     ctx.set_fill_style_str(bg_color);
-    ctx.fill_rect(screen_x, screen_y, screen_width, screen_height);
+    ctx.fill();
+
+    // Grouped nodes share the group-box tint so members read as one cluster
+    // even where their enclosing boxes overlap other groups.
+    if node.group.is_some() {
+        ctx.set_fill_style_str(GROUP_BG);
+        ctx.fill();
+    }
 
     if is_selected {
-        let border = node.color.as_deref().unwrap_or(BORDER_SELECTED);
+        // On a custom fill the node's own color would hide the selection.
+        let border = match fill {
+            Some(_) => BORDER_SELECTED,
+            None => node.color.as_deref().unwrap_or(BORDER_SELECTED),
+        };
         ctx.set_stroke_style_str(border);
-        ctx.set_line_width(1.0);
-        ctx.set_shadow_color(border);
-        ctx.set_shadow_blur(8.0);
+        ctx.set_line_width(selection.line_width);
+        ctx.set_shadow_color(selection.shadow_color.unwrap_or(border));
+        ctx.set_shadow_blur(selection.shadow_blur);
+        ctx.set_shadow_offset_y(selection.shadow_offset_y);
     } else {
-        let border = node.color.as_deref().unwrap_or(BORDER_COLOR);
+        let border = fill.map_or(BORDER_COLOR, |(color, _)| color);
         ctx.set_stroke_style_str(border);
         ctx.set_line_width(1.0);
         ctx.set_shadow_blur(0.0);
     }
-    ctx.stroke_rect(screen_x, screen_y, screen_width, screen_height);
+    ctx.stroke();
     ctx.set_shadow_blur(0.0);
+    ctx.set_shadow_offset_y(0.0);
+    ctx.set_line_width(1.0);
+
+    if detail == DetailLevel::Simplified {
+        return;
+    }
 
     match node.node_type {
         NodeType::Image => {
@@ -439,12 +1193,13 @@ fn draw_node(
                 screen_y,
                 screen_width,
                 screen_height,
+                radius,
                 image_cache,
             );
         }
         NodeType::Link => {
-            // Local .md files are rendered via HTML overlay like md nodes
-            if !is_local_md_file(&node.text) {
+            // Local and remote .md files render via HTML overlay like md nodes
+            if !is_md_link(&node.text) {
                 draw_link_content(
                     ctx,
                     node,
@@ -453,6 +1208,7 @@ fn draw_node(
                     screen_y,
                     screen_width,
                     screen_height,
+                    radius,
                     image_cache,
                     link_preview_cache,
                 );
@@ -463,21 +1219,22 @@ fn draw_node(
             // MD nodes render their content via HTML overlay, just show background + label
         }
         NodeType::Text | NodeType::Idea | NodeType::Note | NodeType::Unknown => {
-            if !is_editing {
-                ctx.set_fill_style_str(if is_selected { TEXT_COLOR } else { TEXT_DIM });
+            // Markdown-flagged nodes get their text from the HTML overlay instead.
+            if !is_editing && !node.renders_markdown_inline() {
+                ctx.set_fill_style_str(if is_selected { text_color } else { text_dim });
                 // Bucket the font size to a whole pixel; this is both the rendered
                 // font and the wrap-cache key dimension, so identical buckets reuse
                 // the cached line breaks.
-                let font_px = (12.0 * camera.zoom).max(8.0).round() as u32;
+                let font_px = (TEXT_FONT_PX * camera.zoom).max(8.0).round() as u32;
                 set_font_px(ctx, font_px);
 
-                let padding = 8.0 * camera.zoom;
-                let label_height = 16.0 * camera.zoom;
+                let padding = TEXT_PADDING * camera.zoom;
+                let label_height = TEXT_LABEL_HEIGHT * camera.zoom;
                 let text_x = screen_x + screen_width / 2.0;
                 let text_y = screen_y + label_height + (screen_height - label_height) / 2.0;
                 let max_width = screen_width - 2.0 * padding;
                 let max_height = screen_height - label_height - padding;
-                let line_height = font_px as f64 * 1.4;
+                let line_height = font_px as f64 * (TEXT_LINE_HEIGHT / TEXT_FONT_PX);
 
                 draw_wrapped_text(
                     ctx,
@@ -502,26 +1259,41 @@ fn draw_node(
         NodeType::Link => "[LINK]",
         NodeType::Text | NodeType::Unknown => "[TEXT]",
     };
-    ctx.set_fill_style_str(TEXT_DIM);
+    ctx.set_fill_style_str(text_dim);
     let small_font = (9.0 * camera.zoom).max(6.0);
     ctx.set_font(&format!("{}px {}", small_font, FONT_SANS));
     ctx.set_text_align("left");
     ctx.set_text_baseline("top");
     let pad = 4.0 * camera.zoom;
     let _ = ctx.fill_text(type_indicator, screen_x + pad, screen_y + pad);
+    let measure = |text: &str, fallback: f64| {
+        ctx.measure_text(text)
+            .map(|m| m.width())
+            .unwrap_or(fallback)
+    };
+    // Where the header's next item (priority, then caption) starts.
+    let mut header_x = screen_x + pad + measure(type_indicator, 30.0) + pad;
 
     if let Some(priority) = node.priority {
         let p_text = format!("P{}", priority.clamp(1, 5));
-        let type_width = ctx
-            .measure_text(type_indicator)
-            .map(|m| m.width())
-            .unwrap_or(30.0);
-        let _ = ctx.fill_text(&p_text, screen_x + pad + type_width + pad, screen_y + pad);
+        let _ = ctx.fill_text(&p_text, header_x, screen_y + pad);
+        header_x += measure(&p_text, 12.0) + pad;
     }
 
+    let mut header_right = screen_x + screen_width - pad;
     if let Some(ref status) = node.status {
         ctx.set_text_align("right");
-        let _ = ctx.fill_text(status, screen_x + screen_width - pad, screen_y + pad);
+        let _ = ctx.fill_text(status, header_right, screen_y + pad);
+        header_right -= measure(status, 30.0) + pad;
+        ctx.set_text_align("left");
+    }
+
+    if let Some(caption) = node_caption(node, link_preview_cache) {
+        let room = header_right - header_x;
+        if room > 0.0 {
+            ctx.set_fill_style_str(text_color);
+            let _ = ctx.fill_text_with_max_width(&caption, header_x, screen_y + pad, room);
+        }
     }
 
     if !node.tags.is_empty() {
@@ -538,7 +1310,7 @@ fn draw_node(
         );
     }
 
-    if is_selected {
+    if show_handles {
         draw_resize_handles(
             ctx,
             screen_x,
@@ -550,6 +1322,90 @@ fn draw_node(
     }
 }
 
+/// Label shown in a node's header strip: its own `title`, or for a link node
+/// without one, the page title from its fetched preview.
+fn node_caption(node: &Node, link_preview_cache: &LinkPreviewCache) -> Option<String> {
+    if let Some(title) = node.display_title() {
+        return Some(title.to_string());
+    }
+    if node.node_type != NodeType::Link {
+        return None;
+    }
+    match link_preview_cache.borrow().get(&node.text) {
+        Some(LoadState::Loaded(preview)) => preview
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty() && *t != node.text)
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Letterbox a `natural_w` x `natural_h` image into a `box_w` x `box_h` area:
+/// scale uniformly (up or down) until it touches the box on one axis, then center
+/// it on both. Returns `(offset_x, offset_y, draw_w, draw_h)` relative to the
+/// box's top-left. Degenerate sizes (zero/negative/non-finite) draw nothing.
+fn fit_image(natural_w: f64, natural_h: f64, box_w: f64, box_h: f64) -> (f64, f64, f64, f64) {
+    let dims = [natural_w, natural_h, box_w, box_h];
+    if dims.iter().any(|d| !d.is_finite() || *d <= 0.0) {
+        return (0.0, 0.0, 0.0, 0.0);
+    }
+    let scale = (box_w / natural_w).min(box_h / natural_h);
+    let draw_w = natural_w * scale;
+    let draw_h = natural_h * scale;
+    (
+        (box_w - draw_w) / 2.0,
+        (box_h - draw_h) / 2.0,
+        draw_w,
+        draw_h,
+    )
+}
+
+/// Screen-space `(x, y, w, h)` an image node's picture is fitted into: the node
+/// rect inset by the padding, below the filename label. `None` when the node is
+/// too small to leave any room (e.g. squashed mid-resize), so nothing is drawn.
+fn image_content_rect(
+    screen_x: f64,
+    screen_y: f64,
+    screen_width: f64,
+    screen_height: f64,
+    zoom: f64,
+) -> Option<(f64, f64, f64, f64)> {
+    let padding = 4.0 * zoom;
+    let label_height = 16.0 * zoom;
+    let w = screen_width - 2.0 * padding;
+    let h = screen_height - label_height - 2.0 * padding;
+    (w > 0.0 && h > 0.0).then_some((screen_x + padding, screen_y + label_height + padding, w, h))
+}
+
+/// Clamp a screen-space corner radius to `0..=min(w, h) / 2`, the largest radius
+/// whose arcs still meet on the shorter side. Non-finite input means square.
+fn clamp_corner_radius(w: f64, h: f64, r: f64) -> f64 {
+    if !r.is_finite() {
+        return 0.0;
+    }
+    r.clamp(0.0, (w.min(h) / 2.0).max(0.0))
+}
+
+/// Begin a new path tracing an `x, y, w, h` rectangle with corners rounded by
+/// `r` (clamped via [`clamp_corner_radius`]); the caller fills, strokes, or clips
+/// it. A zero radius traces a plain rectangle.
+fn rounded_rect_path(ctx: &CanvasRenderingContext2d, x: f64, y: f64, w: f64, h: f64, r: f64) {
+    let r = clamp_corner_radius(w, h, r);
+    ctx.begin_path();
+    if r == 0.0 {
+        ctx.rect(x, y, w, h);
+        return;
+    }
+    ctx.move_to(x + r, y);
+    let _ = ctx.arc_to(x + w, y, x + w, y + h, r);
+    let _ = ctx.arc_to(x + w, y + h, x, y + h, r);
+    let _ = ctx.arc_to(x, y + h, x, y, r);
+    let _ = ctx.arc_to(x, y, x + w, y, r);
+    ctx.close_path();
+}
+
 // Rendering primitive; args map 1:1 to draw state (context, node, camera, screen rect, cache).
 #[allow(clippy::too_many_arguments)]
 fn draw_image_content(
@@ -560,6 +1416,7 @@ fn draw_image_content(
     screen_y: f64,
     screen_width: f64,
     screen_height: f64,
+    corner_radius: f64,
     image_cache: &ImageCache,
 ) {
     let url = &node.text;
@@ -567,25 +1424,38 @@ fn draw_image_content(
 
     match cache.get(url) {
         Some(LoadState::Loaded(img)) => {
-            // Image is loaded, draw it
-            let padding = 4.0 * camera.zoom;
-            let label_height = 16.0 * camera.zoom;
-            let img_x = screen_x + padding;
-            let img_y = screen_y + label_height + padding;
-            let img_max_w = screen_width - 2.0 * padding;
-            let img_max_h = screen_height - label_height - 2.0 * padding;
-
             let natural_w = img.natural_width() as f64;
             let natural_h = img.natural_height() as f64;
+            // GIFs animate in an `<img>` overlay (see `GifOverlays`); painting
+            // the static first frame here would show through transparent pixels.
+            let animated = is_gif(url) || is_gif(&img.src());
+            let content =
+                image_content_rect(screen_x, screen_y, screen_width, screen_height, camera.zoom);
 
-            if natural_w > 0.0 && natural_h > 0.0 {
+            if let (Some((img_x, img_y, img_max_w, img_max_h)), false) = (content, animated) {
                 // Scale to fit the available space, allowing upscaling when zoomed in
-                let scale = (img_max_w / natural_w).min(img_max_h / natural_h);
-                let draw_w = natural_w * scale;
-                let draw_h = natural_h * scale;
-                let offset_x = (img_max_w - draw_w) / 2.0;
-                let offset_y = (img_max_h - draw_h) / 2.0;
-
+                let (offset_x, offset_y, draw_w, draw_h) =
+                    fit_image(natural_w, natural_h, img_max_w, img_max_h);
+
+                // Clip to the content box inside the node's (possibly rounded)
+                // outline — the two clips intersect — so the image can't poke
+                // past the corners or over the label, whatever the node's size
+                // mid-resize. Nothing returns between save and restore.
+                // Visual smoke: a rounded image node at high zoom and a node
+                // squashed to its minimum height should both show no overflow.
+                ctx.save();
+                rounded_rect_path(
+                    ctx,
+                    screen_x,
+                    screen_y,
+                    screen_width,
+                    screen_height,
+                    corner_radius,
+                );
+                ctx.clip();
+                ctx.begin_path();
+                ctx.rect(img_x, img_y, img_max_w, img_max_h);
+                ctx.clip();
                 let _ = ctx.draw_image_with_html_image_element_and_dw_and_dh(
                     img,
                     img_x + offset_x,
@@ -593,6 +1463,7 @@ fn draw_image_content(
                     draw_w,
                     draw_h,
                 );
+                ctx.restore();
             }
 
             // Show filename
@@ -661,6 +1532,7 @@ fn draw_link_content(
     screen_y: f64,
     screen_width: f64,
     screen_height: f64,
+    corner_radius: f64,
     image_cache: &ImageCache,
     link_preview_cache: &LinkPreviewCache,
 ) {
@@ -677,15 +1549,22 @@ fn draw_link_content(
     let content_width = screen_width - 2.0 * padding;
     let content_height = content_bottom - content_top - domain_font_size - padding;
 
-    // Use clipping to prevent drawing outside node
+    // Use clipping to prevent drawing outside node (respecting rounded corners)
     ctx.save();
-    ctx.begin_path();
-    ctx.rect(screen_x, screen_y, screen_width, screen_height);
+    rounded_rect_path(
+        ctx,
+        screen_x,
+        screen_y,
+        screen_width,
+        screen_height,
+        corner_radius,
+    );
     ctx.clip();
 
     match cache.get(url) {
         Some(LoadState::Loaded(preview)) => {
             // Draw preview image - OG images usually contain title/desc already
+            let mut drew_image = false;
             if let Some(ref image_url) = preview.image {
                 let img_cache = image_cache.borrow();
                 if let Some(LoadState::Loaded(img)) = img_cache.get(image_url) {
@@ -693,15 +1572,14 @@ fn draw_link_content(
                     let natural_h = img.natural_height() as f64;
 
                     if natural_w > 0.0 && natural_h > 0.0 && content_height > 10.0 {
-                        let scale = (content_width / natural_w).min(content_height / natural_h);
-                        let draw_w = natural_w * scale;
-                        let draw_h = natural_h * scale;
-                        let offset_x = (content_width - draw_w) / 2.0;
+                        drew_image = true;
+                        let (offset_x, offset_y, draw_w, draw_h) =
+                            fit_image(natural_w, natural_h, content_width, content_height);
 
                         let _ = ctx.draw_image_with_html_image_element_and_dw_and_dh(
                             img,
                             content_left + offset_x,
-                            content_top,
+                            content_top + offset_y,
                             draw_w,
                             draw_h,
                         );
@@ -709,16 +1587,53 @@ fn draw_link_content(
                 }
             }
 
-            // Draw domain at bottom
             let domain = preview
                 .site_name
                 .clone()
                 .unwrap_or_else(|| url.split('/').nth(2).unwrap_or(url).to_string());
-            ctx.set_fill_style_str(TEXT_DIM);
-            ctx.set_font(&format!("{}px {}", domain_font_size, FONT_SANS));
-            ctx.set_text_align("right");
-            ctx.set_text_baseline("bottom");
-            let _ = ctx.fill_text(&domain, screen_x + screen_width - padding, content_bottom);
+
+            // No picture to show: the page title (wrapped) with the domain
+            // under it, so a card still says what it links to.
+            let title = preview.title.as_deref().map(str::trim);
+            if let Some(title) = title.filter(|t| !drew_image && !t.is_empty()) {
+                let font_px = (11.0 * camera.zoom).max(7.0).round() as u32;
+                set_font_px(ctx, font_px);
+                let lines = wrap_text_cached(ctx, &node.id, title, content_width, font_px);
+                let layout = link_title_layout(
+                    content_top,
+                    content_bottom,
+                    font_px as f64 * 1.3,
+                    domain_font_size,
+                    lines.len(),
+                );
+                ctx.set_fill_style_str(TEXT_COLOR);
+                ctx.set_text_align("left");
+                ctx.set_text_baseline("top");
+                let shown = layout.title_ys.len();
+                for (i, y) in layout.title_ys.iter().enumerate() {
+                    let line = if i + 1 == shown && lines.len() > shown {
+                        format!("{}\u{2026}", lines[i].trim_end())
+                    } else {
+                        lines[i].clone()
+                    };
+                    let _ = ctx.fill_text_with_max_width(&line, content_left, *y, content_width);
+                }
+                ctx.set_fill_style_str(TEXT_DIM);
+                ctx.set_font(&format!("{}px {}", domain_font_size, FONT_SANS));
+                let _ = ctx.fill_text_with_max_width(
+                    &domain,
+                    content_left,
+                    layout.domain_y,
+                    content_width,
+                );
+            } else {
+                // Draw domain at bottom
+                ctx.set_fill_style_str(TEXT_DIM);
+                ctx.set_font(&format!("{}px {}", domain_font_size, FONT_SANS));
+                ctx.set_text_align("right");
+                ctx.set_text_baseline("bottom");
+                let _ = ctx.fill_text(&domain, screen_x + screen_width - padding, content_bottom);
+            }
         }
         Some(LoadState::Loading) => {
             ctx.set_fill_style_str(TEXT_DIM);
@@ -734,14 +1649,23 @@ fn draw_link_content(
         }
         // Failed preview or not-yet-fetched: fall back to showing the raw URL so
         // the node is still useful (and a failed link doesn't show a stale spinner).
+        // A URL too wide for the node is elided in the middle (host and tail stay
+        // readable) rather than squeezed; the full URL is the canvas tooltip.
         Some(LoadState::Failed) | None => {
             ctx.set_fill_style_str(TEXT_DIM);
             let font_size = (10.0 * camera.zoom).max(7.0);
             ctx.set_font(&format!("{}px {}", font_size, FONT_SANS));
             ctx.set_text_align("center");
             ctx.set_text_baseline("middle");
+            let label = match ctx.measure_text(url) {
+                Ok(m) if m.width() > content_width && m.width() > 0.0 => {
+                    let fit = content_width / m.width() * url.chars().count() as f64;
+                    elide_middle(url, fit.floor() as usize)
+                }
+                _ => url.clone(),
+            };
             let _ = ctx.fill_text_with_max_width(
-                url,
+                &label,
                 screen_x + screen_width / 2.0,
                 screen_y + screen_height / 2.0,
                 content_width,
@@ -752,6 +1676,35 @@ fn draw_link_content(
     ctx.restore();
 }
 
+/// Vertical placement of a link card's text when it shows the page title
+/// instead of a preview image: top `y` of each title line that fits, then of
+/// the domain line beneath them, all screen-space with a `top` text baseline.
+#[derive(Debug, PartialEq)]
+struct LinkTitleLayout {
+    title_ys: Vec<f64>,
+    domain_y: f64,
+}
+
+/// Stack up to `line_count` title lines of `line_height` and a domain line of
+/// `domain_height` between `content_top` and `content_bottom`, dropping title
+/// lines that don't fit and centering the block in the space.
+fn link_title_layout(
+    content_top: f64,
+    content_bottom: f64,
+    line_height: f64,
+    domain_height: f64,
+    line_count: usize,
+) -> LinkTitleLayout {
+    let room = (content_bottom - content_top - domain_height).max(0.0);
+    let shown = line_count.min((room / line_height).floor() as usize);
+    let block = shown as f64 * line_height + domain_height;
+    let top = content_top + ((content_bottom - content_top - block) / 2.0).max(0.0);
+    LinkTitleLayout {
+        title_ys: (0..shown).map(|i| top + i as f64 * line_height).collect(),
+        domain_y: top + shown as f64 * line_height,
+    }
+}
+
 /// Find the point where a line from `from` toward the center of a rectangle
 /// intersects the rectangle boundary.
 fn clip_line_to_rect(
@@ -784,6 +1737,27 @@ fn clip_line_to_rect(
     (rect_cx + t * dx, rect_cy + t * dy)
 }
 
+/// Rotation (radians) that lays an edge label along the segment `from` → `to`
+/// (screen coords) while keeping the text upright: the direction's angle folded
+/// into `(-π/2, π/2]`, so a right-to-left edge reads left-to-right like its
+/// mirror and a vertical edge always reads top-to-bottom. A zero-length segment
+/// stays level.
+fn label_angle(from: (f64, f64), to: (f64, f64)) -> f64 {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    if dx == 0.0 && dy == 0.0 {
+        return 0.0;
+    }
+    let angle = dy.atan2(dx);
+    if angle > FRAC_PI_2 {
+        angle - PI
+    } else if angle <= -FRAC_PI_2 {
+        angle + PI
+    } else {
+        angle
+    }
+}
+
 /// Draw a filled arrowhead triangle at (tip_x, tip_y) pointing in the given angle.
 fn draw_arrowhead(ctx: &CanvasRenderingContext2d, tip_x: f64, tip_y: f64, angle: f64, size: f64) {
     let spread = 0.4; // ~23 degrees
@@ -801,52 +1775,178 @@ fn draw_arrowhead(ctx: &CanvasRenderingContext2d, tip_x: f64, tip_y: f64, angle:
     ctx.fill();
 }
 
-fn draw_edge(
-    ctx: &CanvasRenderingContext2d,
-    node_map: &HashMap<&str, &Node>,
-    edge: &crate::state::Edge,
-    camera: &Camera,
-    is_selected: bool,
-) {
-    let from_node = node_map.get(edge.from_node.as_str());
-    let to_node = node_map.get(edge.to_node.as_str());
-
-    if let (Some(from), Some(to)) = (from_node, to_node) {
-        let from_cx = from.x + from.width / 2.0;
-        let from_cy = from.y + from.height / 2.0;
-        let to_cx = to.x + to.width / 2.0;
-        let to_cy = to.y + to.height / 2.0;
+/// Lowest edge opacity the setting can reach; below this edges all but vanish.
+const MIN_EDGE_OPACITY: f64 = 0.1;
 
-        // Clip line to node boundaries (world coordinates)
-        let (from_bx, from_by) = clip_line_to_rect(
-            to_cx,
-            to_cy,
-            from_cx,
-            from_cy,
-            from.width / 2.0,
-            from.height / 2.0,
-        );
-        let (to_bx, to_by) = clip_line_to_rect(
-            from_cx,
-            from_cy,
-            to_cx,
-            to_cy,
-            to.width / 2.0,
-            to.height / 2.0,
-        );
+/// Global alpha an edge is drawn with. Plain and dimmed edges use the user's
+/// opacity setting (clamped to [`MIN_EDGE_OPACITY`]..=1); the selected edge and
+/// edges emphasized by the selection or hover stay fully opaque so the edges
+/// being worked on never fade.
+pub fn edge_alpha(opacity: f64, is_selected: bool, is_emphasized: bool) -> f64 {
+    if is_selected || is_emphasized || opacity.is_nan() {
+        1.0
+    } else {
+        opacity.clamp(MIN_EDGE_OPACITY, 1.0)
+    }
+}
 
-        let (from_sx, from_sy) = camera.world_to_screen(from_bx, from_by);
-        let (to_sx, to_sy) = camera.world_to_screen(to_bx, to_by);
+/// Global alpha for nodes (and edges between two such nodes) that the tag
+/// filter fades out.
+pub const TAG_DIM_ALPHA: f64 = 0.25;
+
+/// Whether the tag filter fades `node`: some tags are active and the node
+/// carries none of them. An empty filter fades nothing.
+pub fn tag_dimmed(node: &Node, active: &HashSet<String>) -> bool {
+    !active.is_empty() && !node.tags.iter().any(|t| active.contains(t))
+}
+
+/// Shape of a drawn edge between its two endpoints.
+#[derive(Clone, Copy)]
+enum EdgeShape {
+    Line,
+    /// Quadratic with this control point (a bowed parallel edge).
+    Quad((f64, f64)),
+    /// Cubic with these control points ([`EdgeStyle::Curved`]).
+    Cubic((f64, f64), (f64, f64)),
+}
+
+impl EdgeShape {
+    /// The same shape with its control points moved by `f` (world → screen).
+    fn map(self, f: impl Fn((f64, f64)) -> (f64, f64)) -> Self {
+        match self {
+            EdgeShape::Line => EdgeShape::Line,
+            EdgeShape::Quad(ctrl) => EdgeShape::Quad(f(ctrl)),
+            EdgeShape::Cubic(ctrl1, ctrl2) => EdgeShape::Cubic(f(ctrl1), f(ctrl2)),
+        }
+    }
+
+    /// Point halfway along the shape from `from` to `to`, where labels sit.
+    fn midpoint(self, from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+        match self {
+            EdgeShape::Line => ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0),
+            EdgeShape::Quad(ctrl) => quad_point(from, ctrl, to, 0.5),
+            EdgeShape::Cubic(ctrl1, ctrl2) => cubic_point(&[from, ctrl1, ctrl2, to], 0.5),
+        }
+    }
+}
+
+/// World-space path of an edge from `from` to `to` in `style`, bowed `bend`
+/// units sideways: its two drawn endpoints (clipped to the node borders or on
+/// their ports) and the shape between them.
+fn edge_path(
+    from: &Node,
+    to: &Node,
+    edge: &crate::state::Edge,
+    bend: f64,
+    style: EdgeStyle,
+) -> ((f64, f64), (f64, f64), EdgeShape) {
+    match style {
+        EdgeStyle::Curved => {
+            let [start, ctrl1, ctrl2, end] = edge_bezier(from, to, edge.ports(), bend);
+            (start, end, EdgeShape::Cubic(ctrl1, ctrl2))
+        }
+        EdgeStyle::Straight => {
+            let (from_a, to_a) = edge_anchors(from, to, edge.ports());
+            let (ctrl_x, ctrl_y) = edge_control_point(from_a, to_a, bend);
+
+            // Clip unpinned ends to node boundaries along the direction toward
+            // the control point: the far end's anchor for a straight edge, the
+            // curve's tangent for a bent one. A port anchor already sits on the
+            // border.
+            let clip = |node: &Node, port: Option<Port>, anchor: (f64, f64)| match port {
+                Some(_) => anchor,
+                None => clip_line_to_rect(
+                    ctrl_x,
+                    ctrl_y,
+                    anchor.0,
+                    anchor.1,
+                    node.width / 2.0,
+                    node.height / 2.0,
+                ),
+            };
+            let shape = if bend == 0.0 {
+                EdgeShape::Line
+            } else {
+                EdgeShape::Quad((ctrl_x, ctrl_y))
+            };
+            (
+                clip(from, edge.from_port, from_a),
+                clip(to, edge.to_port, to_a),
+                shape,
+            )
+        }
+    }
+}
+
+/// World point where `draw_edge` centers `edge`'s label: halfway along its
+/// drawn path. `None` when either endpoint is missing from the board.
+pub fn edge_label_point(
+    board: &Board,
+    edge: &crate::state::Edge,
+    style: EdgeStyle,
+) -> Option<(f64, f64)> {
+    let node = |id: &str| board.nodes.iter().find(|n| n.id == id);
+    let (from, to) = (node(&edge.from_node)?, node(&edge.to_node)?);
+    let bend = parallel_edge_bends(board)
+        .get(edge.id.as_str())
+        .copied()
+        .unwrap_or(0.0);
+    let (start, end, shape) = edge_path(from, to, edge, bend, style);
+    Some(shape.midpoint(start, end))
+}
+
+/// Draw one edge in `style`, bowed `bend` world units sideways (see
+/// [`edge_control_point`] and [`edge_bezier`]) when it has parallel siblings.
+#[allow(clippy::too_many_arguments)]
+fn draw_edge(
+    ctx: &CanvasRenderingContext2d,
+    node_map: &HashMap<&str, &Node>,
+    edge: &crate::state::Edge,
+    bend: f64,
+    camera: &Camera,
+    is_selected: bool,
+    is_emphasized: bool,
+    is_dimmed: bool,
+    alpha: f64,
+    style: EdgeStyle,
+    rotate_label: bool,
+    directed: bool,
+) {
+    let from_node = node_map.get(edge.from_node.as_str());
+    let to_node = node_map.get(edge.to_node.as_str());
 
-        let angle = (to_sy - from_sy).atan2(to_sx - from_sx);
+    if let (Some(from), Some(to)) = (from_node, to_node) {
+        let to_screen = |(x, y): (f64, f64)| camera.world_to_screen(x, y);
+        let (start, end, shape) = edge_path(from, to, edge, bend, style);
+        let ((from_sx, from_sy), (to_sx, to_sy)) = (to_screen(start), to_screen(end));
+        let shape = shape.map(to_screen);
+
+        // Arrowheads follow the curve's tangent at the target.
+        let (tangent_x, tangent_y) = match shape {
+            EdgeShape::Line => (from_sx, from_sy),
+            EdgeShape::Quad(ctrl) => ctrl,
+            EdgeShape::Cubic(_, ctrl2) => ctrl2,
+        };
+        let angle = (to_sy - tangent_y).atan2(to_sx - tangent_x);
         let arrow_size = (10.0 * camera.zoom).clamp(5.0, 20.0);
 
+        ctx.save();
+        ctx.set_global_alpha(alpha);
+
         if is_selected {
             ctx.set_stroke_style_str(BORDER_SELECTED);
             ctx.set_fill_style_str(BORDER_SELECTED);
             ctx.set_line_width(2.0);
             ctx.set_shadow_color(BORDER_SELECTED);
             ctx.set_shadow_blur(8.0);
+        } else if is_emphasized {
+            ctx.set_stroke_style_str(EDGE_EMPHASIS);
+            ctx.set_fill_style_str(EDGE_EMPHASIS);
+            ctx.set_line_width(2.0);
+        } else if is_dimmed {
+            ctx.set_stroke_style_str(EDGE_DIMMED);
+            ctx.set_fill_style_str(EDGE_DIMMED);
+            ctx.set_line_width(1.0);
         } else {
             ctx.set_stroke_style_str(EDGE_COLOR);
             ctx.set_fill_style_str(EDGE_COLOR);
@@ -855,16 +1955,27 @@ fn draw_edge(
 
         ctx.begin_path();
         ctx.move_to(from_sx, from_sy);
-        ctx.line_to(to_sx, to_sy);
+        match shape {
+            EdgeShape::Line => ctx.line_to(to_sx, to_sy),
+            EdgeShape::Quad((cx, cy)) => ctx.quadratic_curve_to(cx, cy, to_sx, to_sy),
+            EdgeShape::Cubic((c1x, c1y), (c2x, c2y)) => {
+                ctx.bezier_curve_to(c1x, c1y, c2x, c2y, to_sx, to_sy)
+            }
+        }
         ctx.stroke();
 
-        draw_arrowhead(ctx, to_sx, to_sy, angle, arrow_size);
+        if directed {
+            draw_arrowhead(ctx, to_sx, to_sy, angle, arrow_size);
+        }
 
         ctx.set_shadow_blur(0.0);
 
-        if let Some(ref label) = edge.label {
-            let mid_x = (from_sx + to_sx) / 2.0;
-            let mid_y = (from_sy + to_sy) / 2.0;
+        let label = edge
+            .label
+            .as_ref()
+            .filter(|_| detail_level(camera.zoom) == DetailLevel::Full);
+        if let Some(label) = label {
+            let (mid_x, mid_y) = shape.midpoint((from_sx, from_sy), (to_sx, to_sy));
             let label_font_size = (10.0 * camera.zoom).max(7.0);
             ctx.set_font(&format!("{}px {}", label_font_size, FONT_SANS));
             let text_metrics = ctx.measure_text(label).ok();
@@ -872,14 +1983,25 @@ fn draw_edge(
             let pill_h = label_font_size + 6.0;
             let pill_w = text_w + 10.0;
 
+            // Draw around the midpoint as the origin so a rotated label spins
+            // in place; level labels just skip the rotation.
+            ctx.save();
+            let _ = ctx.translate(mid_x, mid_y);
+            if rotate_label {
+                let _ = ctx.rotate(label_angle((from_sx, from_sy), (to_sx, to_sy)));
+            }
+
             ctx.set_fill_style_str(EDGE_LABEL_BG);
-            ctx.fill_rect(mid_x - pill_w / 2.0, mid_y - pill_h / 2.0, pill_w, pill_h);
+            ctx.fill_rect(-pill_w / 2.0, -pill_h / 2.0, pill_w, pill_h);
 
             ctx.set_fill_style_str(TEXT_DIM);
             ctx.set_text_align("center");
             ctx.set_text_baseline("middle");
-            let _ = ctx.fill_text(label, mid_x, mid_y);
+            let _ = ctx.fill_text(label, 0.0, 0.0);
+            ctx.restore();
         }
+
+        ctx.restore();
     }
 }
 
@@ -890,6 +2012,7 @@ fn draw_edge_preview(
     to_screen_x: f64,
     to_screen_y: f64,
     camera: &Camera,
+    directed: bool,
 ) {
     if let Some(from) = node_map.get(from_node_id) {
         let from_cx = from.x + from.width / 2.0;
@@ -918,7 +2041,9 @@ fn draw_edge_preview(
         ctx.line_to(to_screen_x, to_screen_y);
         ctx.stroke();
 
-        draw_arrowhead(ctx, to_screen_x, to_screen_y, angle, arrow_size);
+        if directed {
+            draw_arrowhead(ctx, to_screen_x, to_screen_y, angle, arrow_size);
+        }
     }
 }
 
@@ -943,6 +2068,436 @@ fn draw_selection_box(
     ctx.stroke_rect(screen_min_x, screen_min_y, width, height);
 }
 
+/// Whether each selected node gets its own corner handles: only for a single
+/// selection. A multi-selection resizes as a group through handles on its
+/// bounding box instead, which also keeps large selections from drawing four
+/// handles per node.
+pub fn show_individual_handles(selected_count: usize) -> bool {
+    selected_count == 1
+}
+
+/// World-space union `(min_x, min_y, max_x, max_y)` of the selected nodes (see
+/// [`nodes_bounding_box`]) with how many of them it covers, or `None` unless at
+/// least two of them are on the board. Read from the live board every frame, so
+/// it tracks drags and box-select results.
+pub fn selection_bounds(
+    board: &Board,
+    selected: &HashSet<String>,
+) -> Option<((f64, f64, f64, f64), usize)> {
+    if selected.len() < 2 {
+        return None;
+    }
+    let nodes: Vec<Node> = board
+        .nodes
+        .iter()
+        .filter(|n| selected.contains(&n.id))
+        .cloned()
+        .collect();
+    if nodes.len() < 2 {
+        return None;
+    }
+    nodes_bounding_box(&nodes).map(|bounds| (bounds, nodes.len()))
+}
+
+/// Screen rects `(x, y, width, height)` for the drag ghosts: each dragged node's
+/// current size placed at its start position. Nodes that haven't moved away
+/// from their start get none, since the ghost would sit under the node itself.
+pub fn ghost_rects(
+    board: &Board,
+    starts: &HashMap<String, (f64, f64)>,
+    camera: &Camera,
+) -> Vec<(f64, f64, f64, f64)> {
+    board
+        .nodes
+        .iter()
+        .filter_map(|n| {
+            let &(x, y) = starts.get(&n.id)?;
+            if (x, y) == (n.x, n.y) {
+                return None;
+            }
+            let (sx, sy) = camera.world_to_screen(x, y);
+            Some((sx, sy, n.width * camera.zoom, n.height * camera.zoom))
+        })
+        .collect()
+}
+
+/// Glowing accent ring around the sticky-connect source, a few pixels
+/// outside its border so it reads as "armed" rather than "selected".
+fn draw_armed_outline(ctx: &CanvasRenderingContext2d, camera: &Camera, node: &Node, radius: f64) {
+    let (x, y) = camera.world_to_screen(node.x, node.y);
+    let pad = 4.0;
+    ctx.save();
+    ctx.set_stroke_style_str(EDGE_EMPHASIS);
+    ctx.set_line_width(2.0);
+    ctx.set_shadow_color(EDGE_EMPHASIS);
+    ctx.set_shadow_blur(8.0);
+    rounded_rect_path(
+        ctx,
+        x - pad,
+        y - pad,
+        node.width * camera.zoom + pad * 2.0,
+        node.height * camera.zoom + pad * 2.0,
+        radius * camera.zoom + pad,
+    );
+    ctx.stroke();
+    ctx.restore();
+}
+
+/// How a node or edge differs from the backup the board is being compared
+/// against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffMark {
+    /// Only on the current board.
+    Added,
+    /// Moved, resized or edited since the backup.
+    Changed,
+    /// Only in the backup; outlined where it used to be.
+    Removed,
+}
+
+/// A [`DiffMark`] and the world-space `(x, y, width, height)` it outlines.
+pub type DiffRect = (DiffMark, (f64, f64, f64, f64));
+
+/// A [`DiffMark`] on an edge and the world-space center-to-center segment it
+/// traces.
+pub type DiffSegment = (DiffMark, (f64, f64), (f64, f64));
+
+/// Everything the backup-diff overlay draws. Diffing walks both boards, so the
+/// app derives this once per change to the board or the backup (a `Memo`)
+/// rather than every frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiffOverlay {
+    pub nodes: Vec<DiffRect>,
+    pub edges: Vec<DiffSegment>,
+}
+
+impl DiffOverlay {
+    pub fn new(board: &Board, backup: &Board) -> Self {
+        let diff = diff_boards(backup, board);
+        DiffOverlay {
+            nodes: diff_marks(board, backup, &diff),
+            edges: diff_edge_marks(board, backup, &diff),
+        }
+    }
+}
+
+fn id_set(ids: &[String]) -> HashSet<&str> {
+    ids.iter().map(String::as_str).collect()
+}
+
+/// World-space `(x, y, width, height)` outline for every node `diff` flags:
+/// added and changed nodes where they are on `board` now, removed ones where
+/// they were on `backup`. Removed come first so live nodes' marks draw on top.
+pub fn diff_marks(board: &Board, backup: &Board, diff: &BoardDiff) -> Vec<DiffRect> {
+    let rect = |n: &Node| (n.x, n.y, n.width, n.height);
+    let removed_ids = id_set(&diff.removed_nodes);
+    let added = id_set(&diff.added_nodes);
+    let changed: HashSet<&str> = diff
+        .moved_nodes
+        .iter()
+        .chain(&diff.changed_nodes)
+        .map(String::as_str)
+        .collect();
+    let removed = backup
+        .nodes
+        .iter()
+        .filter(|n| removed_ids.contains(n.id.as_str()))
+        .map(|n| (DiffMark::Removed, rect(n)));
+    let live = board.nodes.iter().filter_map(|n| {
+        let mark = if added.contains(n.id.as_str()) {
+            DiffMark::Added
+        } else if changed.contains(n.id.as_str()) {
+            DiffMark::Changed
+        } else {
+            return None;
+        };
+        Some((mark, rect(n)))
+    });
+    removed.chain(live).collect()
+}
+
+/// Center-to-center segment for every edge `diff` flags: added and changed
+/// edges between their endpoints on `board`, removed ones between where their
+/// endpoints were on `backup`. Edges with a missing endpoint are skipped.
+pub fn diff_edge_marks(board: &Board, backup: &Board, diff: &BoardDiff) -> Vec<DiffSegment> {
+    fn segments<'a>(
+        board: &'a Board,
+        flagged: impl Fn(&str) -> Option<DiffMark> + 'a,
+    ) -> impl Iterator<Item = DiffSegment> + 'a {
+        let centers: HashMap<&str, (f64, f64)> = board
+            .nodes
+            .iter()
+            .map(|n| (n.id.as_str(), n.center()))
+            .collect();
+        board.edges.iter().filter_map(move |e| {
+            let mark = flagged(&e.id)?;
+            Some((
+                mark,
+                *centers.get(e.from_node.as_str())?,
+                *centers.get(e.to_node.as_str())?,
+            ))
+        })
+    }
+    let removed = id_set(&diff.removed_edges);
+    let added = id_set(&diff.added_edges);
+    let changed = id_set(&diff.changed_edges);
+    let mut marks: Vec<DiffSegment> = segments(backup, |id| {
+        removed.contains(id).then_some(DiffMark::Removed)
+    })
+    .collect();
+    marks.extend(segments(board, |id| {
+        if added.contains(id) {
+            Some(DiffMark::Added)
+        } else {
+            changed.contains(id).then_some(DiffMark::Changed)
+        }
+    }));
+    marks
+}
+
+/// Stroke style for a [`DiffMark`]: its color and dash pattern (removed marks
+/// are dashed).
+fn diff_style(mark: DiffMark) -> (&'static str, js_sys::Array) {
+    match mark {
+        DiffMark::Added => (DIFF_ADDED, js_sys::Array::new()),
+        DiffMark::Changed => (DIFF_CHANGED, js_sys::Array::new()),
+        DiffMark::Removed => (DIFF_REMOVED, js_sys::Array::of2(&6.0.into(), &4.0.into())),
+    }
+}
+
+/// Color-coded lines for [`diff_edge_marks`], styled like the node outlines.
+fn draw_diff_edges(ctx: &CanvasRenderingContext2d, camera: &Camera, marks: &[DiffSegment]) {
+    if marks.is_empty() {
+        return;
+    }
+    ctx.save();
+    ctx.set_line_width(2.0);
+    for &(mark, from, to) in marks {
+        let (color, dash) = diff_style(mark);
+        ctx.set_stroke_style_str(color);
+        let _ = ctx.set_line_dash(&dash);
+        let (fx, fy) = camera.world_to_screen(from.0, from.1);
+        let (tx, ty) = camera.world_to_screen(to.0, to.1);
+        ctx.begin_path();
+        ctx.move_to(fx, fy);
+        ctx.line_to(tx, ty);
+        ctx.stroke();
+    }
+    ctx.restore();
+}
+
+/// Color-coded outlines for [`diff_marks`]: green added, amber changed, dashed
+/// red where a removed node used to be.
+fn draw_diff_marks(
+    ctx: &CanvasRenderingContext2d,
+    camera: &Camera,
+    marks: &[DiffRect],
+    corner_radius: f64,
+) {
+    let pad = 3.0;
+    ctx.save();
+    ctx.set_line_width(2.0);
+    for &(mark, (x, y, w, h)) in marks {
+        let (sx, sy) = camera.world_to_screen(x, y);
+        let (color, dash) = diff_style(mark);
+        ctx.set_stroke_style_str(color);
+        let _ = ctx.set_line_dash(&dash);
+        rounded_rect_path(
+            ctx,
+            sx - pad,
+            sy - pad,
+            w * camera.zoom + pad * 2.0,
+            h * camera.zoom + pad * 2.0,
+            corner_radius * camera.zoom + pad,
+        );
+        ctx.stroke();
+    }
+    ctx.restore();
+}
+
+/// Faint dashed outlines where the dragged nodes started.
+fn draw_drag_ghosts(ctx: &CanvasRenderingContext2d, rects: &[(f64, f64, f64, f64)], radius: f64) {
+    if rects.is_empty() {
+        return;
+    }
+    ctx.save();
+    ctx.set_stroke_style_str(SELECTION_BOUNDS_STROKE);
+    ctx.set_line_width(1.0);
+    let dash = js_sys::Array::of2(&4.0.into(), &4.0.into());
+    let _ = ctx.set_line_dash(&dash);
+    for &(x, y, w, h) in rects {
+        rounded_rect_path(ctx, x, y, w, h, radius);
+        ctx.stroke();
+    }
+    ctx.restore();
+}
+
+/// Screen-space gap between the selected nodes and their dashed outline (where
+/// the group resize handles sit).
+pub const SELECTION_BOUNDS_PAD: f64 = 6.0;
+
+/// Faint dashed outline around a multi-selection with group resize handles on
+/// its corners and an "N selected" caption under its bottom-left corner (the
+/// selection toolbar sits above it).
+fn draw_selection_bounds(
+    ctx: &CanvasRenderingContext2d,
+    camera: &Camera,
+    (min_x, min_y, max_x, max_y): (f64, f64, f64, f64),
+    count: usize,
+) {
+    let (left, top) = camera.world_to_screen(min_x, min_y);
+    let (right, bottom) = camera.world_to_screen(max_x, max_y);
+    let pad = SELECTION_BOUNDS_PAD;
+
+    ctx.save();
+    ctx.set_stroke_style_str(SELECTION_BOUNDS_STROKE);
+    ctx.set_line_width(1.0);
+    let dash = js_sys::Array::of2(&4.0.into(), &4.0.into());
+    let _ = ctx.set_line_dash(&dash);
+    ctx.stroke_rect(
+        left - pad,
+        top - pad,
+        right - left + pad * 2.0,
+        bottom - top + pad * 2.0,
+    );
+    ctx.restore();
+    draw_resize_handles(
+        ctx,
+        left - pad,
+        top - pad,
+        right - left + pad * 2.0,
+        bottom - top + pad * 2.0,
+        camera.zoom,
+    );
+
+    ctx.set_font(&format!("11px {}", FONT_MONO));
+    ctx.set_fill_style_str(TEXT_DIM);
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("top");
+    let _ = ctx.fill_text(&format!("{count} selected"), left - pad, bottom + pad + 4.0);
+}
+
+/// "+N" chip hanging off a collapsed node's bottom-right corner, `N` being the
+/// number of nodes folded away beneath it.
+fn draw_collapsed_badge(
+    ctx: &CanvasRenderingContext2d,
+    right: f64,
+    bottom: f64,
+    count: usize,
+    zoom: f64,
+) {
+    let label = format!("+{count}");
+    let font = (9.0 * zoom).max(6.0);
+    ctx.set_font(&format!("{}px {}", font, FONT_SANS));
+    let text_width = ctx
+        .measure_text(&label)
+        .map(|m| m.width())
+        .unwrap_or(font * 2.0);
+    let pad = 3.0 * zoom.max(0.5);
+    let chip_w = text_width + pad * 2.0;
+    let chip_h = font + pad * 2.0;
+    let x = right - chip_w / 2.0;
+    let y = bottom - chip_h / 2.0;
+
+    ctx.set_fill_style_str(BORDER_SELECTED);
+    ctx.fill_rect(x, y, chip_w, chip_h);
+    ctx.set_fill_style_str(BG_COLOR);
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+    let _ = ctx.fill_text(&label, x + chip_w / 2.0, y + chip_h / 2.0);
+}
+
+/// Zoom below which nodes drop their text, content, and indicators.
+pub const SIMPLIFIED_ZOOM: f64 = 0.4;
+/// Zoom below which nodes shrink to flat filled rectangles.
+pub const DOTS_ZOOM: f64 = 0.2;
+
+/// How much of each node `draw_node` paints at a given zoom. Far out, text is
+/// unreadable anyway, and skipping wrapping/`measure_text` keeps big boards fast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DetailLevel {
+    /// Everything: content, text, badges, resize handles.
+    Full,
+    /// Background and border only.
+    Simplified,
+    /// A flat rectangle in the node's border color.
+    Dots,
+}
+
+/// The [`DetailLevel`] for `zoom`. Each threshold belongs to the richer level.
+pub fn detail_level(zoom: f64) -> DetailLevel {
+    if zoom >= SIMPLIFIED_ZOOM {
+        DetailLevel::Full
+    } else if zoom >= DOTS_ZOOM {
+        DetailLevel::Simplified
+    } else {
+        DetailLevel::Dots
+    }
+}
+
+/// How long after an edit the recently-edited marker takes to fade out.
+pub const RECENT_EDIT_FADE_MS: f64 = 10.0 * 60.0 * 1000.0;
+
+/// Opacity of the recently-edited marker for a node last edited at
+/// `updated_at`: `1.0` right after the edit, fading linearly to `0.0` over
+/// [`RECENT_EDIT_FADE_MS`]. Never-edited nodes get `0.0`; a timestamp in the
+/// future (clock skew) counts as just edited.
+pub fn recent_edit_alpha(updated_at: Option<i64>, now_ms: f64) -> f64 {
+    let Some(at) = updated_at else {
+        return 0.0;
+    };
+    let age = (now_ms - at as f64).max(0.0);
+    (1.0 - age / RECENT_EDIT_FADE_MS).max(0.0)
+}
+
+/// Small accent dot on a node's top-left corner, drawn at `alpha` opacity.
+fn draw_recent_edit_marker(
+    ctx: &CanvasRenderingContext2d,
+    left: f64,
+    top: f64,
+    alpha: f64,
+    zoom: f64,
+) {
+    let radius = (3.0 * zoom).max(2.0);
+    ctx.set_global_alpha(alpha);
+    ctx.set_fill_style_str(EDGE_EMPHASIS);
+    ctx.begin_path();
+    let _ = ctx.arc(left, top, radius, 0.0, std::f64::consts::TAU);
+    ctx.fill();
+    ctx.set_global_alpha(1.0);
+}
+
+/// Full-viewport hairlines marking the edge/center a dragged node snapped to.
+fn draw_alignment_guides(
+    ctx: &CanvasRenderingContext2d,
+    camera: &Camera,
+    guides: &[Guide],
+    width: f64,
+    height: f64,
+) {
+    if guides.is_empty() {
+        return;
+    }
+    ctx.set_stroke_style_str(GUIDE_COLOR);
+    ctx.set_line_width(1.0);
+    ctx.begin_path();
+    for guide in guides {
+        match *guide {
+            Guide::Vertical(x) => {
+                let (sx, _) = camera.world_to_screen(x, 0.0);
+                ctx.move_to(sx, 0.0);
+                ctx.line_to(sx, height);
+            }
+            Guide::Horizontal(y) => {
+                let (_, sy) = camera.world_to_screen(0.0, y);
+                ctx.move_to(0.0, sy);
+                ctx.line_to(width, sy);
+            }
+        }
+    }
+    ctx.stroke();
+}
+
 fn draw_resize_handles(
     ctx: &CanvasRenderingContext2d,
     screen_x: f64,
@@ -1007,6 +2562,18 @@ fn draw_resize_handles(
 
 /// Wrap text into multiple lines that fit within max_width
 fn wrap_text(ctx: &CanvasRenderingContext2d, text: &str, max_width: f64) -> Vec<String> {
+    wrap_lines(text, max_width, |line| {
+        ctx.measure_text(line)
+            .unwrap_or_else(|_| ctx.measure_text("").unwrap())
+            .width()
+    })
+}
+
+/// The line breaking behind [`wrap_text`], with the width of a candidate line
+/// supplied by `measure`: split on `\n` first, then greedily word-pack; a
+/// single word wider than the line keeps a line of its own rather than being
+/// dropped. Never returns an empty Vec.
+pub fn wrap_lines(text: &str, max_width: f64, measure: impl Fn(&str) -> f64) -> Vec<String> {
     let mut lines: Vec<String> = Vec::new();
 
     // Split by explicit newlines first
@@ -1031,11 +2598,7 @@ fn wrap_text(ctx: &CanvasRenderingContext2d, text: &str, max_width: f64) -> Vec<
                 format!("{} {}", current_line, word)
             };
 
-            let metrics = ctx
-                .measure_text(&test_line)
-                .unwrap_or_else(|_| ctx.measure_text("").unwrap());
-
-            if metrics.width() <= max_width || current_line.is_empty() {
+            if measure(&test_line) <= max_width || current_line.is_empty() {
                 current_line = test_line;
             } else {
                 lines.push(current_line);
@@ -1055,6 +2618,48 @@ fn wrap_text(ctx: &CanvasRenderingContext2d, text: &str, max_width: f64) -> Vec<
     lines
 }
 
+/// Body-text layout `draw_node` gives text-like nodes, in world units (it
+/// scales each by the zoom): font size, line height, the padding at the sides
+/// and bottom, and the `[TYPE]` label band above the text.
+const TEXT_FONT_PX: f64 = 12.0;
+const TEXT_LINE_HEIGHT: f64 = TEXT_FONT_PX * 1.4;
+const TEXT_PADDING: f64 = 8.0;
+const TEXT_LABEL_HEIGHT: f64 = 16.0;
+
+/// Whether `draw_node` wraps `node.text` onto the canvas itself (text, idea
+/// and note nodes not rendered as markdown) — the nodes whose text a resize
+/// can clip.
+pub fn draws_wrapped_text(node: &Node) -> bool {
+    matches!(
+        node.node_type,
+        NodeType::Text | NodeType::Idea | NodeType::Note | NodeType::Unknown
+    ) && !node.renders_markdown_inline()
+}
+
+/// Narrowest node width (world units) that keeps the longest word of `text`
+/// on one line, as measured by `measure` at the 12px body font.
+pub fn width_for_text(text: &str, measure: impl Fn(&str) -> f64) -> f64 {
+    let widest = text.split_whitespace().map(measure).fold(0.0_f64, f64::max);
+    widest + 2.0 * TEXT_PADDING
+}
+
+/// Shortest node height (world units) that shows every line of `text` once
+/// wrapped to a node `width` wide, with `measure` giving line widths at the
+/// 12px body font. Exact at 100% zoom; other zooms round the font to a whole
+/// pixel, so line breaks can shift slightly.
+pub fn height_for_text(text: &str, width: f64, measure: impl Fn(&str) -> f64) -> f64 {
+    let lines = wrap_lines(text, width - 2.0 * TEXT_PADDING, measure).len();
+    TEXT_LABEL_HEIGHT + TEXT_PADDING + lines as f64 * TEXT_LINE_HEIGHT
+}
+
+/// Text measurer for [`width_for_text`] / [`height_for_text`]: sets `ctx` to
+/// the body font at 100% zoom and measures with it. The next render sets its
+/// own font, so this never leaks into drawing.
+pub fn body_text_measure(ctx: &CanvasRenderingContext2d) -> impl Fn(&str) -> f64 + '_ {
+    ctx.set_font(&format!("{}px {}", TEXT_FONT_PX, FONT_MONO));
+    move |line| ctx.measure_text(line).map_or(0.0, |m| m.width())
+}
+
 /// Draw wrapped text centered in a box. Uses the memoized [`wrap_text_cached`],
 /// so a frame that doesn't change a node's text/width/zoom does no word
 /// measurement at all.
@@ -1293,8 +2898,18 @@ mod tests {
                 from_node: "a".into(),
                 to_node: "b".into(),
                 label: None,
+                from_port: None,
+                to_port: None,
             };
-            assert!(!edge_outside_viewport(&map, &edge, &Camera::new(), W, H));
+            assert!(!edge_outside_viewport(
+                &map,
+                &edge,
+                0.0,
+                EdgeStyle::Straight,
+                &Camera::new(),
+                W,
+                H
+            ));
         }
 
         #[test]
@@ -1307,8 +2922,18 @@ mod tests {
                 from_node: "a".into(),
                 to_node: "b".into(),
                 label: None,
+                from_port: None,
+                to_port: None,
             };
-            assert!(edge_outside_viewport(&map, &edge, &Camera::new(), W, H));
+            assert!(edge_outside_viewport(
+                &map,
+                &edge,
+                0.0,
+                EdgeStyle::Straight,
+                &Camera::new(),
+                W,
+                H
+            ));
         }
 
         #[test]
@@ -1320,8 +2945,18 @@ mod tests {
                 from_node: "a".into(),
                 to_node: "missing".into(),
                 label: None,
+                from_port: None,
+                to_port: None,
             };
-            assert!(edge_outside_viewport(&map, &edge, &Camera::new(), W, H));
+            assert!(edge_outside_viewport(
+                &map,
+                &edge,
+                0.0,
+                EdgeStyle::Straight,
+                &Camera::new(),
+                W,
+                H
+            ));
         }
 
         #[test]
@@ -1337,8 +2972,1121 @@ mod tests {
                 from_node: "a".into(),
                 to_node: "b".into(),
                 label: None,
+                from_port: None,
+                to_port: None,
+            };
+            assert!(!edge_outside_viewport(
+                &map,
+                &edge,
+                0.0,
+                EdgeStyle::Straight,
+                &Camera::new(),
+                W,
+                H
+            ));
+        }
+    }
+
+    mod collapse_tests {
+        use super::*;
+        use crate::state::Edge;
+
+        fn edge(id: &str, from: &str, to: &str) -> Edge {
+            Edge {
+                id: id.into(),
+                from_node: from.into(),
+                to_node: to.into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            }
+        }
+
+        fn tree() -> Board {
+            // root -> child -> grandchild, plus an unrelated node.
+            Board {
+                version: None,
+                nodes: ["root", "child", "grandchild", "other"]
+                    .iter()
+                    .map(|id| Node::new(id.to_string(), 0.0, 0.0, String::new()))
+                    .collect(),
+                edges: vec![
+                    edge("e1", "root", "child"),
+                    edge("e2", "child", "grandchild"),
+                ],
+                settings: Default::default(),
+            }
+        }
+
+        fn drawn_ids(board: &Board) -> Vec<&str> {
+            let hidden = board.collapsed_hidden();
+            drawn_nodes(board, &hidden).map(|n| n.id.as_str()).collect()
+        }
+
+        #[test]
+        fn expanded_board_draws_everything() {
+            let board = tree();
+            assert_eq!(drawn_ids(&board), ["root", "child", "grandchild", "other"]);
+        }
+
+        #[test]
+        fn collapsed_root_excludes_downstream_nodes() {
+            let mut board = tree();
+            board.nodes[0].collapsed = true;
+            assert_eq!(drawn_ids(&board), ["root", "other"]);
+        }
+
+        #[test]
+        fn collapse_view_matches_collapsed_hidden_and_counts_subtrees() {
+            let mut board = tree();
+            assert_eq!(CollapseView::new(&board), CollapseView::default());
+            board.nodes[0].collapsed = true;
+            board.nodes[1].collapsed = true;
+            let view = CollapseView::new(&board);
+            assert_eq!(view.hidden, board.collapsed_hidden());
+            assert_eq!(view.folded["root"], 2);
+            assert_eq!(view.folded["child"], 1);
+        }
+
+        #[test]
+        fn shown_nodes_drops_collapsed_and_filtered_nodes() {
+            let mut board = tree();
+            board.nodes[0].collapsed = true;
+            board.nodes[3].node_type = NodeType::Image;
+            let view = CollapseView::new(&board);
+            let ids = |hidden_types: &[&str]| -> Vec<String> {
+                let hidden_types: HashSet<String> =
+                    hidden_types.iter().map(|t| t.to_string()).collect();
+                shown_nodes(&board, &hidden_types, &view.hidden)
+                    .iter()
+                    .map(|n| n.id.clone())
+                    .collect()
             };
-            assert!(!edge_outside_viewport(&map, &edge, &Camera::new(), W, H));
+            assert_eq!(ids(&[]), ["root", "other"]);
+            assert_eq!(ids(&["image"]), ["root"]);
+        }
+    }
+
+    mod type_filter_tests {
+        use super::*;
+
+        fn typed(id: &str, kind: NodeType) -> Node {
+            let mut n = Node::new(id.to_string(), 0.0, 0.0, String::new());
+            n.node_type = kind;
+            n
+        }
+
+        fn mixed() -> Board {
+            Board {
+                nodes: vec![
+                    typed("t", NodeType::Text),
+                    typed("i", NodeType::Image),
+                    typed("l", NodeType::Link),
+                    typed("u", NodeType::Unknown),
+                ],
+                ..Default::default()
+            }
+        }
+
+        fn visible_ids(board: &Board, hidden: &[&str]) -> Vec<String> {
+            let hidden: HashSet<String> = hidden.iter().map(|t| t.to_string()).collect();
+            visible_nodes_by_type(board, &hidden)
+                .iter()
+                .map(|n| n.id.clone())
+                .collect()
+        }
+
+        #[test]
+        fn nothing_hidden_keeps_every_node_in_order() {
+            assert_eq!(visible_ids(&mixed(), &[]), ["t", "i", "l", "u"]);
+        }
+
+        #[test]
+        fn hidden_types_drop_out() {
+            assert_eq!(visible_ids(&mixed(), &["image"]), ["t", "l", "u"]);
+            assert_eq!(
+                visible_ids(&mixed(), &["text", "idea", "note", "image", "md"]),
+                ["l"]
+            );
+        }
+
+        #[test]
+        fn unknown_nodes_follow_text() {
+            assert_eq!(visible_ids(&mixed(), &["text"]), ["i", "l"]);
+        }
+    }
+
+    mod emphasized_edges_tests {
+        use super::*;
+        use crate::state::Edge;
+
+        fn board() -> Board {
+            let edge = |id: &str, from: &str, to: &str| Edge {
+                id: id.into(),
+                from_node: from.into(),
+                to_node: to.into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            };
+            Board {
+                version: None,
+                nodes: ["a", "b", "c", "d"]
+                    .iter()
+                    .map(|id| Node::new(id.to_string(), 0.0, 0.0, String::new()))
+                    .collect(),
+                edges: vec![
+                    edge("ab", "a", "b"),
+                    edge("bc", "b", "c"),
+                    edge("cd", "c", "d"),
+                ],
+                settings: Default::default(),
+            }
+        }
+
+        #[test]
+        fn empty_focus_emphasizes_nothing() {
+            assert!(emphasized_edges(&board(), &HashSet::new()).is_empty());
+        }
+
+        #[test]
+        fn matches_incoming_and_outgoing_edges() {
+            let focus: HashSet<&str> = ["b"].into_iter().collect();
+            let ids = emphasized_edges(&board(), &focus);
+            assert_eq!(ids.len(), 2);
+            assert!(ids.contains("ab") && ids.contains("bc"));
+        }
+
+        #[test]
+        fn unions_multiple_focused_nodes() {
+            let focus: HashSet<&str> = ["a", "d"].into_iter().collect();
+            let ids = emphasized_edges(&board(), &focus);
+            assert_eq!(ids.len(), 2);
+            assert!(ids.contains("ab") && ids.contains("cd"));
+        }
+    }
+
+    mod parallel_edge_tests {
+        use super::*;
+        use crate::state::Edge;
+
+        fn edge(id: &str, from: &str, to: &str) -> Edge {
+            Edge {
+                id: id.into(),
+                from_node: from.into(),
+                to_node: to.into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            }
+        }
+
+        #[test]
+        fn offsets_fan_out_around_the_straight_line() {
+            assert_eq!(parallel_edge_offsets(1), vec![0.0]);
+            assert_eq!(parallel_edge_offsets(2), vec![-15.0, 15.0]);
+            assert_eq!(parallel_edge_offsets(3), vec![-30.0, 0.0, 30.0]);
+            assert_eq!(parallel_edge_offsets(4), vec![-45.0, -15.0, 15.0, 45.0]);
+            assert!(parallel_edge_offsets(0).is_empty());
+        }
+
+        #[test]
+        fn only_parallel_edges_bend() {
+            let board = Board {
+                edges: vec![
+                    edge("ab", "a", "b"),
+                    edge("ba", "b", "a"),
+                    edge("bc", "b", "c"),
+                    edge("cc", "c", "c"),
+                ],
+                ..Default::default()
+            };
+            let bends = parallel_edge_bends(&board);
+            assert_eq!(bends.len(), 2);
+            // Opposite directions with opposite offsets land on opposite sides,
+            // i.e. the same sign relative to each edge's own direction.
+            assert_eq!(bends["ab"], -15.0);
+            assert_eq!(bends["ba"], -15.0);
+        }
+
+        #[test]
+        fn control_point_sits_twice_the_bend_out() {
+            assert_eq!(
+                edge_control_point((0.0, 0.0), (100.0, 0.0), 0.0),
+                (50.0, 0.0)
+            );
+            // Travelling right, positive bends go up the screen.
+            assert_eq!(
+                edge_control_point((0.0, 0.0), (100.0, 0.0), 15.0),
+                (50.0, -30.0)
+            );
+            let ctrl = edge_control_point((0.0, 0.0), (100.0, 0.0), 15.0);
+            assert_eq!(
+                quad_point((0.0, 0.0), ctrl, (100.0, 0.0), 0.5),
+                (50.0, -15.0)
+            );
+            assert_eq!(edge_control_point((5.0, 5.0), (5.0, 5.0), 15.0), (5.0, 5.0));
+        }
+    }
+
+    mod edge_bezier_tests {
+        use super::*;
+
+        fn at(x: f64, y: f64) -> Node {
+            let mut n = Node::new(format!("{x},{y}"), x, y, String::new());
+            n.width = 200.0;
+            n.height = 100.0;
+            n
+        }
+
+        #[test]
+        fn side_by_side_nodes_curve_horizontally_between_facing_sides() {
+            let curve = edge_bezier(&at(0.0, 0.0), &at(400.0, 200.0), (None, None), 0.0);
+            assert_eq!(
+                curve,
+                [(200.0, 50.0), (300.0, 50.0), (300.0, 250.0), (400.0, 250.0)]
+            );
+            assert_eq!(cubic_point(&curve, 0.5), (300.0, 150.0));
+            assert_eq!(cubic_point(&curve, 0.0), curve[0]);
+            assert_eq!(cubic_point(&curve, 1.0), curve[3]);
+        }
+
+        #[test]
+        fn leftward_and_stacked_nodes_use_the_facing_sides() {
+            let left = edge_bezier(&at(0.0, 0.0), &at(-400.0, 0.0), (None, None), 0.0);
+            assert_eq!(
+                left,
+                [(0.0, 50.0), (-100.0, 50.0), (-100.0, 50.0), (-200.0, 50.0)]
+            );
+            let below = edge_bezier(&at(0.0, 0.0), &at(0.0, 400.0), (None, None), 0.0);
+            assert_eq!(
+                below,
+                [
+                    (100.0, 100.0),
+                    (100.0, 250.0),
+                    (100.0, 250.0),
+                    (100.0, 400.0)
+                ]
+            );
+        }
+
+        #[test]
+        fn close_nodes_keep_the_minimum_pull() {
+            let curve = edge_bezier(&at(0.0, 0.0), &at(220.0, 0.0), (None, None), 0.0);
+            assert_eq!(curve[1], (200.0 + EDGE_CURVE_MIN_PULL, 50.0));
+            assert_eq!(curve[2], (220.0 - EDGE_CURVE_MIN_PULL, 50.0));
+        }
+
+        #[test]
+        fn bend_moves_the_midpoint_sideways_by_the_bend() {
+            let curve = edge_bezier(&at(0.0, 0.0), &at(400.0, 0.0), (None, None), 15.0);
+            let (x, y) = cubic_point(&curve, 0.5);
+            assert!((x - 300.0).abs() < 1e-9);
+            // Travelling right, positive bends go up the screen.
+            assert!((y - 35.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn pinned_ports_override_the_facing_sides() {
+            let curve = edge_bezier(
+                &at(0.0, 0.0),
+                &at(400.0, 0.0),
+                (Some(Port::Bottom), Some(Port::Top)),
+                0.0,
+            );
+            assert_eq!(curve[0], (100.0, 100.0));
+            assert_eq!(curve[3], (500.0, 0.0));
+            // Each control point leaves straight out of its side.
+            assert_eq!(curve[1].0, 100.0);
+            assert!(curve[1].1 > 100.0);
+            assert_eq!(curve[2].0, 500.0);
+            assert!(curve[2].1 < 0.0);
+        }
+
+        #[test]
+        fn anchors_are_centers_unless_pinned() {
+            let (from, to) = (at(0.0, 0.0), at(400.0, 0.0));
+            assert_eq!(
+                edge_anchors(&from, &to, (None, None)),
+                ((100.0, 50.0), (500.0, 50.0))
+            );
+            assert_eq!(
+                edge_anchors(&from, &to, (Some(Port::Top), None)),
+                ((100.0, 0.0), (500.0, 50.0))
+            );
+        }
+    }
+
+    mod edge_label_point_tests {
+        use super::*;
+        use crate::state::Edge;
+
+        fn board(edges: Vec<Edge>) -> Board {
+            let mut a = Node::new("a".into(), 0.0, 0.0, String::new());
+            let mut b = Node::new("b".into(), 400.0, 0.0, String::new());
+            (a.width, a.height, b.width, b.height) = (200.0, 100.0, 200.0, 100.0);
+            Board {
+                version: None,
+                nodes: vec![a, b],
+                edges,
+                settings: Default::default(),
+            }
+        }
+
+        fn edge(id: &str, from: &str, to: &str) -> Edge {
+            Edge {
+                id: id.into(),
+                from_node: from.into(),
+                to_node: to.into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            }
+        }
+
+        #[test]
+        fn label_sits_halfway_between_the_borders() {
+            let b = board(vec![edge("e", "a", "b")]);
+            for style in [EdgeStyle::Straight, EdgeStyle::Curved] {
+                assert_eq!(
+                    edge_label_point(&b, &b.edges[0], style),
+                    Some((300.0, 50.0))
+                );
+            }
+        }
+
+        #[test]
+        fn parallel_edges_label_their_own_bow() {
+            let b = board(vec![edge("e1", "a", "b"), edge("e2", "a", "b")]);
+            let first = edge_label_point(&b, &b.edges[0], EdgeStyle::Straight).unwrap();
+            let second = edge_label_point(&b, &b.edges[1], EdgeStyle::Straight).unwrap();
+            assert!((first.1 - second.1).abs() > PARALLEL_EDGE_SPACING - 1e-9);
+        }
+
+        #[test]
+        fn dangling_edge_has_no_label_point() {
+            let b = board(vec![edge("e", "a", "ghost")]);
+            assert_eq!(edge_label_point(&b, &b.edges[0], EdgeStyle::Straight), None);
+        }
+    }
+
+    mod node_fill_tests {
+        use super::*;
+
+        #[test]
+        fn parses_hex_forms() {
+            assert_eq!(parse_css_color("#22c55e"), Some((0x22, 0xc5, 0x5e, 1.0)));
+            assert_eq!(parse_css_color("#FFF"), Some((255, 255, 255, 1.0)));
+            assert_eq!(
+                parse_css_color(" #00000080 ").map(|c| c.3),
+                Some(128.0 / 255.0)
+            );
+            assert_eq!(parse_css_color("#f008").map(|c| c.3), Some(136.0 / 255.0));
+        }
+
+        #[test]
+        fn parses_rgb_functions() {
+            assert_eq!(parse_css_color("rgb(1, 2, 3)"), Some((1, 2, 3, 1.0)));
+            assert_eq!(parse_css_color("RGBA(1,2,3,0.5)"), Some((1, 2, 3, 0.5)));
+            assert_eq!(parse_css_color("rgb(1 2 3 / 0.25)"), Some((1, 2, 3, 0.25)));
+        }
+
+        #[test]
+        fn rejects_unusable_strings() {
+            for bad in [
+                "",
+                "red",
+                "#12345",
+                "#ggg",
+                "rgb(1, 2)",
+                "rgb(300, 0, 0)",
+                "hsl(0, 0%, 0%)",
+                "rgba(1, 2, 3, 2)",
+                "#000\"/>",
+            ] {
+                assert_eq!(parse_css_color(bad), None, "{bad}");
+            }
+        }
+
+        #[test]
+        fn light_fills_switch_to_dark_text() {
+            assert!(prefers_dark_text((255, 255, 255, 1.0)));
+            assert!(prefers_dark_text((0xf5, 0x9e, 0x0b, 1.0)));
+            assert!(!prefers_dark_text((0x11, 0x16, 0x1f, 1.0)));
+            // Barely-there white over the dark canvas is still a dark surface.
+            assert!(!prefers_dark_text((255, 255, 255, 0.05)));
+        }
+
+        #[test]
+        fn custom_fill_needs_a_valid_color() {
+            let mut node = Node::new("n".into(), 0.0, 0.0, String::new());
+            assert_eq!(custom_fill(&node), None);
+            node.color = Some("not a color".into());
+            assert_eq!(custom_fill(&node), None);
+            node.color = Some("#ffffff".into());
+            assert_eq!(custom_fill(&node), Some(("#ffffff", true)));
+            node.color = Some("#1e293b".into());
+            assert_eq!(custom_fill(&node), Some(("#1e293b", false)));
+        }
+
+        #[test]
+        fn overlay_text_darkens_on_light_fills() {
+            let mut node = Node::new("n".into(), 0.0, 0.0, String::new());
+            assert_eq!(overlay_text_color(&node), "var(--text)");
+            node.color = Some("#ffffff".into());
+            assert_eq!(overlay_text_color(&node), TEXT_ON_LIGHT);
+            node.color = Some("#1e293b".into());
+            assert_eq!(overlay_text_color(&node), "var(--text)");
+        }
+    }
+
+    mod selection_look_tests {
+        use super::*;
+
+        #[test]
+        fn glow_keeps_the_classic_look() {
+            let look = selection_look(SelectionStyle::Glow);
+            assert_eq!((look.line_width, look.shadow_blur), (1.0, 8.0));
+            assert_eq!(look.shadow_color, None);
+        }
+
+        #[test]
+        fn styles_resolve_differently() {
+            let glow = selection_look(SelectionStyle::Glow);
+            let shadow = selection_look(SelectionStyle::Shadow);
+            let outline = selection_look(SelectionStyle::Outline);
+            assert_ne!(glow, shadow);
+            assert_ne!(glow, outline);
+            assert_ne!(shadow, outline);
+            assert!(shadow.shadow_offset_y > 0.0 && shadow.shadow_color.is_some());
+            assert_eq!(outline.shadow_blur, 0.0);
+        }
+    }
+
+    mod tag_dim_tests {
+        use super::*;
+
+        fn tagged(tags: &[&str]) -> Node {
+            let mut n = Node::new("n".to_string(), 0.0, 0.0, String::new());
+            n.tags = tags.iter().map(|t| t.to_string()).collect();
+            n
+        }
+
+        fn active(tags: &[&str]) -> HashSet<String> {
+            tags.iter().map(|t| t.to_string()).collect()
+        }
+
+        #[test]
+        fn empty_filter_dims_nothing() {
+            assert!(!tag_dimmed(&tagged(&[]), &active(&[])));
+            assert!(!tag_dimmed(&tagged(&["a"]), &active(&[])));
+        }
+
+        #[test]
+        fn any_active_tag_keeps_a_node_lit() {
+            assert!(!tag_dimmed(&tagged(&["a", "b"]), &active(&["b", "c"])));
+        }
+
+        #[test]
+        fn nodes_without_an_active_tag_dim() {
+            assert!(tag_dimmed(&tagged(&["a"]), &active(&["b"])));
+            assert!(tag_dimmed(&tagged(&[]), &active(&["b"])));
+        }
+    }
+
+    mod edge_alpha_tests {
+        use super::*;
+
+        #[test]
+        fn plain_edges_use_the_setting() {
+            assert_eq!(edge_alpha(0.4, false, false), 0.4);
+            assert_eq!(edge_alpha(1.0, false, false), 1.0);
+        }
+
+        #[test]
+        fn selected_and_emphasized_edges_stay_opaque() {
+            assert_eq!(edge_alpha(0.3, true, false), 1.0);
+            assert_eq!(edge_alpha(0.3, false, true), 1.0);
+        }
+
+        #[test]
+        fn clamps_out_of_range_settings() {
+            assert_eq!(edge_alpha(0.0, false, false), MIN_EDGE_OPACITY);
+            assert_eq!(edge_alpha(3.0, false, false), 1.0);
+            assert_eq!(edge_alpha(f64::NAN, false, false), 1.0);
+        }
+    }
+
+    mod fit_image_tests {
+        use super::*;
+
+        #[test]
+        fn wide_image_is_centered_vertically() {
+            // 400x100 into 200x200 → scale 0.5 → 200x50, 75px bars top and bottom.
+            assert_eq!(
+                fit_image(400.0, 100.0, 200.0, 200.0),
+                (0.0, 75.0, 200.0, 50.0)
+            );
+        }
+
+        #[test]
+        fn tall_image_is_centered_horizontally() {
+            // 100x400 into 200x200 → scale 0.5 → 50x200, 75px bars left and right.
+            assert_eq!(
+                fit_image(100.0, 400.0, 200.0, 200.0),
+                (75.0, 0.0, 50.0, 200.0)
+            );
+        }
+
+        #[test]
+        fn square_image_upscales_into_wide_box() {
+            // 50x50 into 300x100 → scale 2 → 100x100, centered horizontally.
+            assert_eq!(
+                fit_image(50.0, 50.0, 300.0, 100.0),
+                (100.0, 0.0, 100.0, 100.0)
+            );
+        }
+
+        #[test]
+        fn degenerate_sizes_draw_nothing() {
+            assert_eq!(fit_image(0.0, 50.0, 100.0, 100.0), (0.0, 0.0, 0.0, 0.0));
+            assert_eq!(fit_image(50.0, 50.0, -1.0, 100.0), (0.0, 0.0, 0.0, 0.0));
+            assert_eq!(
+                fit_image(f64::NAN, 50.0, 100.0, 100.0),
+                (0.0, 0.0, 0.0, 0.0)
+            );
+        }
+    }
+
+    mod link_title_layout_tests {
+        use super::*;
+
+        #[test]
+        fn title_and_domain_stack_centered_in_the_node() {
+            // 100px of content, two 13px title lines + a 9px domain = 35px block.
+            let layout = link_title_layout(16.0, 116.0, 13.0, 9.0, 2);
+            assert_eq!(layout.title_ys, [48.5, 61.5]);
+            assert_eq!(layout.domain_y, 74.5);
+            assert!(layout.domain_y + 9.0 <= 116.0);
+        }
+
+        #[test]
+        fn long_titles_keep_only_the_lines_that_fit() {
+            // 40px: the domain takes 9, leaving room for two 13px lines.
+            let layout = link_title_layout(0.0, 40.0, 13.0, 9.0, 6);
+            assert_eq!(layout.title_ys.len(), 2);
+            assert!(layout.domain_y + 9.0 <= 40.0);
+        }
+
+        #[test]
+        fn a_cramped_card_shows_just_the_domain() {
+            let layout = link_title_layout(0.0, 12.0, 13.0, 9.0, 3);
+            assert!(layout.title_ys.is_empty());
+            assert_eq!(layout.domain_y, 1.5);
+        }
+    }
+
+    mod image_content_rect_tests {
+        use super::*;
+
+        #[test]
+        fn insets_below_the_label() {
+            assert_eq!(
+                image_content_rect(10.0, 20.0, 200.0, 100.0, 1.0),
+                Some((14.0, 40.0, 192.0, 76.0))
+            );
+            assert_eq!(
+                image_content_rect(0.0, 0.0, 200.0, 100.0, 2.0),
+                Some((8.0, 40.0, 184.0, 52.0))
+            );
+        }
+
+        #[test]
+        fn too_small_a_node_has_no_content_box() {
+            assert_eq!(image_content_rect(0.0, 0.0, 200.0, 24.0, 1.0), None);
+            assert_eq!(image_content_rect(0.0, 0.0, 8.0, 100.0, 1.0), None);
+        }
+    }
+
+    mod label_angle_tests {
+        use super::*;
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+        fn close(a: f64, b: f64) -> bool {
+            (a - b).abs() < 1e-9
+        }
+
+        #[test]
+        fn left_to_right_keeps_the_edge_angle() {
+            assert!(close(label_angle((0.0, 0.0), (10.0, 0.0)), 0.0));
+            assert!(close(label_angle((0.0, 0.0), (10.0, 10.0)), FRAC_PI_4));
+            assert!(close(label_angle((0.0, 0.0), (10.0, -10.0)), -FRAC_PI_4));
+        }
+
+        #[test]
+        fn right_to_left_flips_to_read_upright() {
+            assert!(close(label_angle((10.0, 0.0), (0.0, 0.0)), 0.0));
+            assert!(close(label_angle((10.0, 10.0), (0.0, 0.0)), FRAC_PI_4));
+            assert!(close(label_angle((10.0, -10.0), (0.0, 0.0)), -FRAC_PI_4));
+        }
+
+        #[test]
+        fn vertical_reads_the_same_either_way() {
+            assert!(close(label_angle((0.0, 0.0), (0.0, 10.0)), FRAC_PI_2));
+            assert!(close(label_angle((0.0, 10.0), (0.0, 0.0)), FRAC_PI_2));
+        }
+
+        #[test]
+        fn zero_length_stays_level() {
+            assert_eq!(label_angle((3.0, 4.0), (3.0, 4.0)), 0.0);
+        }
+    }
+
+    mod corner_radius_tests {
+        use super::*;
+
+        #[test]
+        fn radius_within_bounds_is_kept() {
+            assert_eq!(clamp_corner_radius(200.0, 100.0, 12.0), 12.0);
+        }
+
+        #[test]
+        fn radius_clamps_to_half_the_shorter_side() {
+            assert_eq!(clamp_corner_radius(200.0, 40.0, 50.0), 20.0);
+            assert_eq!(clamp_corner_radius(30.0, 400.0, 50.0), 15.0);
+        }
+
+        #[test]
+        fn negative_or_non_finite_radius_is_square() {
+            assert_eq!(clamp_corner_radius(200.0, 100.0, -4.0), 0.0);
+            assert_eq!(clamp_corner_radius(200.0, 100.0, f64::NAN), 0.0);
+            assert_eq!(clamp_corner_radius(-10.0, 100.0, 5.0), 0.0);
+        }
+    }
+
+    mod progressive_cursor_tests {
+        use super::*;
+
+        /// Drive `cursor` over a stand-in node list until it catches up,
+        /// returning the ids drawn by each frame.
+        fn frames(cursor: &mut ProgressiveCursor, nodes: &[u32], batch: usize) -> Vec<Vec<u32>> {
+            let mut out = Vec::new();
+            loop {
+                out.push(nodes[cursor.next_batch(nodes.len(), batch)].to_vec());
+                if cursor.is_caught_up() {
+                    return out;
+                }
+            }
+        }
+
+        #[test]
+        fn armed_pass_draws_each_node_once_in_order() {
+            let nodes: Vec<u32> = (0..10).collect();
+            let mut cursor = ProgressiveCursor::default();
+            cursor.arm();
+
+            let drawn = frames(&mut cursor, &nodes, 4);
+
+            assert_eq!(drawn, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]);
+        }
+
+        #[test]
+        fn caught_up_cursor_renders_everything_each_frame() {
+            let mut cursor = ProgressiveCursor::default();
+            assert!(cursor.is_caught_up());
+            assert_eq!(cursor.next_batch(10, 4), 0..10);
+            assert_eq!(cursor.next_batch(10, 4), 0..10);
+        }
+
+        #[test]
+        fn small_board_catches_up_in_one_frame() {
+            let mut cursor = ProgressiveCursor::default();
+            cursor.arm();
+            assert_eq!(cursor.next_batch(3, 4), 0..3);
+            assert!(cursor.is_caught_up());
+        }
+
+        #[test]
+        fn restart_begins_again_from_a_cleared_frame() {
+            let mut cursor = ProgressiveCursor::default();
+            cursor.arm();
+            assert_eq!(cursor.next_batch(10, 4), 0..4);
+            assert_eq!(cursor.next_batch(10, 4), 4..8);
+
+            // Pan/zoom mid-pass: the new viewport culls to a different count.
+            cursor.restart();
+            assert_eq!(cursor.next_batch(6, 4), 0..4);
+            assert_eq!(cursor.next_batch(6, 4), 4..6);
+            assert!(cursor.is_caught_up());
+
+            // Once caught up, restart leaves full renders alone.
+            cursor.restart();
+            assert_eq!(cursor.next_batch(6, 4), 0..6);
+        }
+
+        #[test]
+        fn empty_viewport_catches_up_immediately() {
+            let mut cursor = ProgressiveCursor::default();
+            cursor.arm();
+            assert_eq!(cursor.next_batch(0, 4), 0..0);
+            assert!(cursor.is_caught_up());
+        }
+    }
+
+    mod recent_edit_tests {
+        use super::*;
+
+        #[test]
+        fn never_edited_nodes_have_no_marker() {
+            assert_eq!(recent_edit_alpha(None, 1_000.0), 0.0);
+        }
+
+        #[test]
+        fn marker_fades_linearly_over_the_window() {
+            let at = 1_000_000;
+            let now = at as f64;
+            assert_eq!(recent_edit_alpha(Some(at), now), 1.0);
+            assert!(
+                (recent_edit_alpha(Some(at), now + RECENT_EDIT_FADE_MS / 2.0) - 0.5).abs() < 1e-9
+            );
+            assert_eq!(recent_edit_alpha(Some(at), now + RECENT_EDIT_FADE_MS), 0.0);
+            assert_eq!(
+                recent_edit_alpha(Some(at), now + 2.0 * RECENT_EDIT_FADE_MS),
+                0.0
+            );
+        }
+
+        #[test]
+        fn future_timestamp_counts_as_just_edited() {
+            assert_eq!(recent_edit_alpha(Some(5_000), 1_000.0), 1.0);
+        }
+    }
+
+    mod frame_stats_tests {
+        use super::*;
+
+        #[test]
+        fn empty_stats_are_zero() {
+            let stats = FrameStats::new(4);
+            assert_eq!((stats.last(), stats.avg(), stats.max()), (0.0, 0.0, 0.0));
+        }
+
+        #[test]
+        fn tracks_last_avg_and_max() {
+            let mut stats = FrameStats::new(4);
+            for ms in [2.0, 8.0, 5.0] {
+                stats.record(ms);
+            }
+            assert_eq!(stats.last(), 5.0);
+            assert_eq!(stats.avg(), 5.0);
+            assert_eq!(stats.max(), 8.0);
+        }
+
+        #[test]
+        fn window_drops_the_oldest_samples() {
+            let mut stats = FrameStats::new(2);
+            for ms in [100.0, 4.0, 6.0] {
+                stats.record(ms);
+            }
+            assert_eq!(stats.avg(), 5.0);
+            assert_eq!(stats.max(), 6.0, "the 100ms frame aged out");
+        }
+    }
+
+    mod selection_bounds_tests {
+        use super::*;
+
+        fn board() -> Board {
+            let mut a = Node::new("a".to_string(), 0.0, 10.0, "A".to_string());
+            a.width = 100.0;
+            a.height = 50.0;
+            let mut b = Node::new("b".to_string(), 300.0, -40.0, "B".to_string());
+            b.width = 80.0;
+            b.height = 200.0;
+            let c = Node::new("c".to_string(), 1000.0, 1000.0, "C".to_string());
+            Board {
+                nodes: vec![a, b, c],
+                ..Board::default()
+            }
+        }
+
+        fn ids(list: &[&str]) -> HashSet<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        }
+
+        #[test]
+        fn union_of_selected_nodes() {
+            assert_eq!(
+                selection_bounds(&board(), &ids(&["a", "b"])),
+                Some(((0.0, -40.0, 380.0, 160.0), 2))
+            );
+        }
+
+        #[test]
+        fn single_selection_has_no_bounds() {
+            assert_eq!(selection_bounds(&board(), &ids(&["a"])), None);
+        }
+
+        #[test]
+        fn ids_missing_from_the_board_do_not_count() {
+            assert_eq!(selection_bounds(&board(), &ids(&["a", "gone"])), None);
+            assert_eq!(
+                selection_bounds(&board(), &ids(&["a", "b", "gone"])).map(|(_, n)| n),
+                Some(2)
+            );
+        }
+    }
+
+    mod show_individual_handles_tests {
+        use super::*;
+
+        #[test]
+        fn only_a_single_selection_gets_node_handles() {
+            assert!(!show_individual_handles(0));
+            assert!(show_individual_handles(1));
+            assert!(!show_individual_handles(2));
+            assert!(!show_individual_handles(500));
+        }
+    }
+
+    mod diff_marks_tests {
+        use super::*;
+        use crate::state::Edge;
+
+        #[test]
+        fn marks_added_changed_and_removed_nodes() {
+            let backup = Board {
+                nodes: vec![
+                    Node::new("kept".into(), 0.0, 0.0, "Same".into()),
+                    Node::new("edited".into(), 0.0, 200.0, "Old".into()),
+                    Node::new("gone".into(), 500.0, 0.0, "Bye".into()),
+                ],
+                ..Board::default()
+            };
+            let mut board = backup.clone();
+            board.nodes.remove(2);
+            board.nodes[1].text = "New".into();
+            board
+                .nodes
+                .push(Node::new("fresh".into(), 900.0, 0.0, "Hi".into()));
+            let diff = diff_boards(&backup, &board);
+
+            let marks: Vec<(DiffMark, f64)> = diff_marks(&board, &backup, &diff)
+                .into_iter()
+                .map(|(mark, (x, ..))| (mark, x))
+                .collect();
+            assert_eq!(
+                marks,
+                [
+                    (DiffMark::Removed, 500.0),
+                    (DiffMark::Changed, 0.0),
+                    (DiffMark::Added, 900.0),
+                ]
+            );
+        }
+
+        #[test]
+        fn marks_added_changed_and_removed_edges() {
+            let edge = |id: &str, from: &str, to: &str| Edge {
+                id: id.into(),
+                from_node: from.into(),
+                to_node: to.into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            };
+            let backup = Board {
+                nodes: vec![
+                    Node::new("a".into(), 0.0, 0.0, "A".into()),
+                    Node::new("b".into(), 400.0, 0.0, "B".into()),
+                    Node::new("c".into(), 800.0, 0.0, "C".into()),
+                ],
+                edges: vec![
+                    edge("same", "a", "b"),
+                    edge("relabeled", "b", "c"),
+                    edge("gone", "a", "c"),
+                ],
+                ..Board::default()
+            };
+            let mut board = backup.clone();
+            board.edges.remove(2);
+            board.edges[1].label = Some("now".into());
+            board.edges.push(edge("fresh", "c", "a"));
+            board.nodes[2].x = 1000.0;
+
+            let overlay = DiffOverlay::new(&board, &backup);
+            let marks: Vec<(DiffMark, f64)> = overlay
+                .edges
+                .iter()
+                .map(|&(mark, (fx, _), (tx, _))| (mark, tx - fx))
+                .collect();
+            // `gone` spans a..c where they were; the others where they are now.
+            assert_eq!(
+                marks,
+                [
+                    (DiffMark::Removed, 800.0),
+                    (DiffMark::Changed, 600.0),
+                    (DiffMark::Added, -1000.0),
+                ]
+            );
+            assert_eq!(overlay.nodes.len(), 1);
+        }
+    }
+
+    mod ghost_rects_tests {
+        use super::*;
+
+        #[test]
+        fn ghost_sits_at_start_position_in_screen_space() {
+            let mut node = Node::new("a".to_string(), 300.0, 120.0, "A".to_string());
+            node.width = 100.0;
+            node.height = 50.0;
+            let board = Board {
+                nodes: vec![node, Node::new("b".to_string(), 0.0, 0.0, "B".to_string())],
+                ..Board::default()
+            };
+            let camera = Camera {
+                x: -20.0,
+                y: 10.0,
+                zoom: 2.0,
+            };
+            let starts: HashMap<String, (f64, f64)> =
+                [("a".to_string(), (40.0, 60.0))].into_iter().collect();
+
+            let (sx, sy) = camera.world_to_screen(40.0, 60.0);
+            assert_eq!((sx, sy), (120.0, 100.0));
+            assert_eq!(
+                ghost_rects(&board, &starts, &camera),
+                vec![(sx, sy, 200.0, 100.0)]
+            );
+        }
+
+        #[test]
+        fn unmoved_nodes_have_no_ghost() {
+            let board = Board {
+                nodes: vec![Node::new("a".to_string(), 5.0, 5.0, "A".to_string())],
+                ..Board::default()
+            };
+            let starts = [("a".to_string(), (5.0, 5.0))].into_iter().collect();
+            assert!(ghost_rects(&board, &starts, &Camera::default()).is_empty());
+        }
+    }
+
+    mod detail_level_tests {
+        use super::*;
+
+        #[test]
+        fn normal_zoom_is_full_detail() {
+            assert_eq!(detail_level(1.0), DetailLevel::Full);
+            assert_eq!(detail_level(5.0), DetailLevel::Full);
+        }
+
+        #[test]
+        fn thresholds_belong_to_the_richer_level() {
+            assert_eq!(detail_level(SIMPLIFIED_ZOOM), DetailLevel::Full);
+            assert_eq!(
+                detail_level(SIMPLIFIED_ZOOM - 0.01),
+                DetailLevel::Simplified
+            );
+            assert_eq!(detail_level(DOTS_ZOOM), DetailLevel::Simplified);
+            assert_eq!(detail_level(DOTS_ZOOM - 0.01), DetailLevel::Dots);
+        }
+
+        #[test]
+        fn minimum_zoom_draws_dots() {
+            assert_eq!(detail_level(0.1), DetailLevel::Dots);
+        }
+    }
+
+    mod text_fit_tests {
+        use super::*;
+        use crate::interaction::resize_rect_min;
+        use crate::state::ResizeHandle;
+
+        /// A fixed-pitch stand-in for `measureText`: 7 units per character.
+        fn mono(line: &str) -> f64 {
+            line.chars().count() as f64 * 7.0
+        }
+
+        const LONG: &str = "one two three four five six seven eight nine ten \
+                            eleven twelve thirteen fourteen fifteen sixteen";
+
+        #[test]
+        fn wrap_lines_packs_words_and_keeps_paragraphs() {
+            assert_eq!(wrap_lines("aa bb cc", 35.0, mono), vec!["aa bb", "cc"]);
+            assert_eq!(wrap_lines("a\n\nb", 100.0, mono), vec!["a", "", "b"]);
+            assert_eq!(wrap_lines("", 100.0, mono), vec![""]);
+        }
+
+        #[test]
+        fn overlong_word_keeps_its_own_line() {
+            assert_eq!(
+                wrap_lines("a enormousword b", 35.0, mono),
+                vec!["a", "enormousword", "b"]
+            );
+        }
+
+        #[test]
+        fn width_for_text_fits_the_longest_word() {
+            assert_eq!(
+                width_for_text("hi fourteen", mono),
+                8.0 * 7.0 + 2.0 * TEXT_PADDING
+            );
+        }
+
+        #[test]
+        fn height_for_text_grows_with_line_count() {
+            let one = height_for_text("short", 200.0, mono);
+            let many = height_for_text(LONG, 200.0, mono);
+            assert!(many > one);
+            assert_eq!(
+                many - one,
+                (wrap_lines(LONG, 200.0 - 2.0 * TEXT_PADDING, mono).len() - 1) as f64
+                    * TEXT_LINE_HEIGHT
+            );
+        }
+
+        #[test]
+        fn resize_stops_at_the_text_height() {
+            let fit = height_for_text(LONG, 200.0, mono);
+            let rect = resize_rect_min(
+                (0.0, 0.0, 200.0, 400.0),
+                ResizeHandle::BottomRight,
+                0.0,
+                -500.0,
+                width_for_text(LONG, mono),
+                |w| height_for_text(LONG, w, mono),
+            );
+            assert_eq!(rect, (0.0, 0.0, 200.0, fit));
+        }
+
+        #[test]
+        fn narrowing_clamps_width_and_rewraps_height() {
+            let min_width = width_for_text(LONG, mono);
+            let (x, _, width, height) = resize_rect_min(
+                (0.0, 0.0, 200.0, 50.0),
+                ResizeHandle::TopLeft,
+                500.0,
+                0.0,
+                min_width,
+                |w| height_for_text(LONG, w, mono),
+            );
+            assert_eq!(width, min_width);
+            assert_eq!(x, 200.0 - min_width);
+            assert_eq!(height, height_for_text(LONG, min_width, mono));
+        }
+
+        #[test]
+        fn growing_past_the_text_is_unaffected() {
+            let rect = resize_rect_min(
+                (0.0, 0.0, 200.0, 100.0),
+                ResizeHandle::BottomRight,
+                50.0,
+                300.0,
+                width_for_text("short", mono),
+                |w| height_for_text("short", w, mono),
+            );
+            assert_eq!(rect, (0.0, 0.0, 250.0, 400.0));
         }
     }
 }
@@ -0,0 +1,117 @@
+use crate::app::{download_text, BoardDataCtx, EditingCtx, SelectionCtx, SettingsCtx};
+use crate::interaction::BoardAction;
+use crate::query::{select_by_tag, select_by_type};
+use crate::state::subtree_to_markdown;
+use leptos::prelude::*;
+use std::collections::HashSet;
+
+/// Right-click menu for a node, opened at the pointer by the canvas
+/// `contextmenu` handler. Reads `context_menu` (screen position + node id) from
+/// [`EditingCtx`]; a click anywhere outside the menu, or Escape, closes it.
+/// Besides the export it offers bulk selection: every node of this node's type,
+/// and every node sharing one of its tags. On a grouped node it also toggles
+/// auto-stacking of that group into a column.
+#[component]
+pub fn ContextMenu() -> impl IntoView {
+    let board_ctx = use_context::<BoardDataCtx>().unwrap();
+    let editing = use_context::<EditingCtx>().unwrap();
+    let context_menu = editing.context_menu;
+    let settings = use_context::<SettingsCtx>().unwrap().settings;
+    let selection = use_context::<SelectionCtx>().unwrap();
+    let select = move |ids: HashSet<String>| {
+        selection.set_selected_nodes.set(ids);
+        selection.set_selected_edge.set(None);
+        context_menu.set(None);
+    };
+
+    move || {
+        context_menu.get().and_then(|(x, y, node_id)| {
+            let (node_type, tags, group) = board_ctx.board.with_untracked(|b| {
+                b.nodes
+                    .iter()
+                    .find(|n| n.id == node_id)
+                    .map(|n| (n.node_type, n.tags.clone(), n.group.clone()))
+            })?;
+            let export_subtree = move |_| {
+                let markdown = subtree_to_markdown(&board_ctx.board.get_untracked(), &node_id);
+                download_text("subtree.md", "text/markdown", &markdown);
+                context_menu.set(None);
+            };
+            let select_type = move |_| {
+                select(
+                    board_ctx
+                        .board
+                        .with_untracked(|b| select_by_type(b, node_type)),
+                );
+            };
+            let tag_items = tags
+                .into_iter()
+                .map(|tag| {
+                    let label = format!("Select all #{tag}");
+                    let on_click = move |_| {
+                        select(board_ctx.board.with_untracked(|b| select_by_tag(b, &tag)));
+                    };
+                    view! {
+                        <button class="hud-btn" style="text-align: left;" on:click=on_click>
+                            {label}
+                        </button>
+                    }
+                })
+                .collect_view();
+            let stack_item = group
+                .filter(|_| !settings.get_untracked().read_only)
+                .map(|group| {
+                    let stacked = board_ctx
+                        .board
+                        .with_untracked(|b| b.settings.is_stacked(&group));
+                    let label = if stacked {
+                        format!("Stop auto-stacking {group}")
+                    } else {
+                        format!("Auto-stack {group} as a column")
+                    };
+                    let on_click = move |_| {
+                        editing.dispatch.apply(
+                            BoardAction::SetGroupStacked {
+                                group: group.clone(),
+                                stacked: !stacked,
+                            },
+                            None,
+                        );
+                        context_menu.set(None);
+                    };
+                    view! {
+                        <button class="hud-btn" style="text-align: left;" on:click=on_click>
+                            {label}
+                        </button>
+                    }
+                });
+            Some(view! {
+                <div
+                    style="position: fixed; inset: 0; z-index: 140;"
+                    on:mousedown=move |_| context_menu.set(None)
+                    on:contextmenu=move |ev| {
+                        ev.prevent_default();
+                        context_menu.set(None);
+                    }
+                />
+                <div
+                    class="modal"
+                    style=format!(
+                        "position: fixed; left: {x}px; top: {y}px; z-index: 141; padding: 4px; \
+                         display: flex; flex-direction: column; \
+                         font-family: var(--mono); font-size: 12px; color: var(--text);"
+                    )
+                >
+                    <button class="hud-btn" style="text-align: left;" on:click=export_subtree>
+                        "Export subtree as Markdown"
+                    </button>
+                    <button class="hud-btn" style="text-align: left;" on:click=select_type>
+                        {format!("Select all {} nodes", node_type.label())}
+                    </button>
+                    {tag_items}
+                    {stack_item}
+                </div>
+            })
+        })
+    }
+}
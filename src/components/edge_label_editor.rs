@@ -0,0 +1,96 @@
+use crate::app::{BoardDataCtx, EditingCtx, SettingsCtx};
+use crate::canvas::edge_label_point;
+use crate::interaction::BoardAction;
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Width of the label input, in CSS pixels.
+const EDITOR_WIDTH: f64 = 180.0;
+
+/// Inline editor for an edge's label, opened by double-clicking the edge. Like
+/// [`NodeEditor`](super::NodeEditor) it is an `<input>` laid over the canvas,
+/// here centered where `draw_edge` puts the label pill. Enter or blur commits
+/// through the reducer (one undo step, and only for a real change); a blank
+/// label removes it; Escape cancels.
+#[component]
+pub fn EdgeLabelEditor() -> impl IntoView {
+    let board_ctx = use_context::<BoardDataCtx>().unwrap();
+    let ctx = use_context::<EditingCtx>().unwrap();
+    let settings_ctx = use_context::<SettingsCtx>().unwrap();
+
+    move || {
+        let edge_id = ctx.editing_edge.get()?;
+        let b = board_ctx.board.get();
+        let cam = board_ctx.camera.get();
+        let style = settings_ctx.settings.with(|s| s.edge_style);
+        let edge = b.edges.iter().find(|e| e.id == edge_id)?;
+        let (mid_x, mid_y) = edge_label_point(&b, edge, style)?;
+        let (screen_x, screen_y) = cam.world_to_screen(mid_x, mid_y);
+        let font_size = (12.0 * cam.zoom).clamp(10.0, 18.0);
+        let initial_label = edge.label.clone().unwrap_or_default();
+
+        let commit = {
+            let initial_label = initial_label.clone();
+            let edge_id = edge_id.clone();
+            move |label: String| {
+                if label.trim() != initial_label {
+                    ctx.dispatch.apply(
+                        BoardAction::EditEdgeLabel {
+                            id: edge_id.clone(),
+                            label,
+                        },
+                        None,
+                    );
+                }
+            }
+        };
+        let commit_on_blur = commit.clone();
+
+        let on_blur = move |ev: web_sys::FocusEvent| {
+            // Escape closes the editor first, so its blur commits nothing.
+            if ctx.editing_edge.get_untracked().is_none() {
+                return;
+            }
+            if let Some(input) = ev
+                .target()
+                .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok())
+            {
+                commit_on_blur(input.value());
+            }
+            ctx.editing_edge.set(None);
+        };
+
+        let on_keydown = move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+            "Enter" => {
+                if let Some(input) = ev
+                    .target()
+                    .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok())
+                {
+                    commit(input.value());
+                }
+                ctx.editing_edge.set(None);
+            }
+            "Escape" => ctx.editing_edge.set(None),
+            _ => {}
+        };
+
+        Some(view! {
+            <input
+                type="text"
+                value=initial_label
+                placeholder="Edge label"
+                autofocus=true
+                style=format!(
+                    "position: absolute; left: {}px; top: {}px; width: {}px; \
+                     transform: translate(-50%, -50%); font-size: {}px; text-align: center; \
+                     background: var(--bg-elev); color: var(--text); \
+                     border: 1px solid var(--accent); outline: none; box-sizing: border-box; \
+                     font-family: inherit; padding: 2px 6px; z-index: 50;",
+                    screen_x, screen_y, EDITOR_WIDTH, font_size
+                )
+                on:blur=on_blur
+                on:keydown=on_keydown
+            />
+        })
+    }
+}
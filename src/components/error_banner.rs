@@ -1,46 +1,104 @@
 use crate::app::EditingCtx;
+use crate::state::CURRENT_BOARD_VERSION;
 use leptos::prelude::*;
 
-/// Non-blocking banner that surfaces a board.json parse error.
+/// Non-blocking banner that surfaces a board.json parse error, a failed save,
+/// or a board written by a newer version of the app.
 ///
-/// Reads `load_error` from [`EditingCtx`]. While set, it renders a dismissible
-/// banner explaining that the board failed to parse and that the current
-/// in-memory board is being preserved. It clears automatically on the next
-/// successful load (the load path resets `load_error` to `None`).
+/// Reads `load_error`, `save_error` and `newer_version` from [`EditingCtx`]. A parse error
+/// renders a dismissible banner explaining that the board failed to parse and
+/// that the current in-memory board is being preserved. A newer schema version
+/// renders a milder warning: the board loaded, but fields this build doesn't
+/// understand won't display (they are still kept on save). Both clear
+/// automatically on the next successful load of a current-version board. A
+/// save error (browser storage full, or a failed desktop write) stays until a later save succeeds.
 #[component]
 pub fn ErrorBanner() -> impl IntoView {
     let ctx = use_context::<EditingCtx>().unwrap();
     let load_error = ctx.load_error;
+    let newer_version = ctx.newer_version;
+    let save_error = ctx.save_error;
 
     move || {
-        load_error.get().map(|msg| {
+        if let Some(msg) = load_error.get() {
+            return Some(
+                view! {
+                    <Banner danger=true on_dismiss=Callback::new(move |_| load_error.set(None))>
+                        <ErrorTitle title="Failed to load board.json — current board preserved" msg/>
+                    </Banner>
+                }
+                .into_any(),
+            );
+        }
+        if let Some(msg) = save_error.get() {
+            return Some(
+                view! {
+                    <Banner danger=true on_dismiss=Callback::new(move |_| save_error.set(None))>
+                        <ErrorTitle title="Failed to save board" msg/>
+                    </Banner>
+                }
+                .into_any(),
+            );
+        }
+        newer_version.get().map(|version| {
             view! {
-                <div style="position: fixed; top: 12px; left: 50%; transform: translateX(-50%); \
-                            max-width: 80vw; z-index: 200; background: var(--danger-bg); \
-                            border: 1px solid var(--danger-line); border-radius: var(--radius); \
-                            padding: 10px 14px; color: var(--danger-text); \
-                            font-family: var(--mono); \
-                            font-size: 12px; line-height: 1.5; \
-                            box-shadow: var(--panel-shadow); \
-                            display: flex; align-items: flex-start; gap: 12px;">
-                    <div style="flex: 1;">
-                        <div style="font-weight: bold; color: var(--danger); margin-bottom: 4px;">
-                            "Failed to load board.json — current board preserved"
-                        </div>
-                        <div style="color: var(--danger-text); word-break: break-word;">
-                            {msg}
-                        </div>
-                    </div>
-                    <button
-                        style="background: transparent; border: 1px solid var(--danger-line); color: var(--danger-text); \
-                               border-radius: var(--radius); cursor: pointer; padding: 2px 8px; \
-                               font-family: inherit; font-size: 12px;"
-                        on:click=move |_| load_error.set(None)
-                    >
-                        "Dismiss"
-                    </button>
-                </div>
+                <Banner danger=false on_dismiss=Callback::new(move |_| newer_version.set(None))>
+                    "This board was created by a newer version (board version "
+                    {version}
+                    ", this build supports "
+                    {CURRENT_BOARD_VERSION}
+                    "); some data may not display."
+                </Banner>
             }
+            .into_any()
         })
     }
 }
+
+/// The fixed top-center banner box with a "Dismiss" button. `danger` picks the
+/// red error palette over the neutral panel one.
+#[component]
+fn Banner(danger: bool, on_dismiss: Callback<()>, children: Children) -> impl IntoView {
+    let (background, line, text) = if danger {
+        (
+            "var(--danger-bg)",
+            "var(--danger-line)",
+            "var(--danger-text)",
+        )
+    } else {
+        ("var(--bg-panel)", "var(--border-strong)", "var(--text)")
+    };
+    view! {
+        <div style=format!(
+            "position: fixed; top: 12px; left: 50%; transform: translateX(-50%); \
+             max-width: 80vw; z-index: 200; background: {background}; \
+             border: 1px solid {line}; border-radius: var(--radius); \
+             padding: 10px 14px; color: {text}; \
+             font-family: var(--mono); \
+             font-size: 12px; line-height: 1.5; \
+             box-shadow: var(--panel-shadow); \
+             display: flex; align-items: flex-start; gap: 12px;"
+        )>
+            <div style="flex: 1;">{children()}</div>
+            <button
+                style=format!(
+                    "background: transparent; border: 1px solid {line}; color: {text}; \
+                     border-radius: var(--radius); cursor: pointer; padding: 2px 8px; \
+                     font-family: inherit; font-size: 12px;"
+                )
+                on:click=move |_| on_dismiss.run(())
+            >
+                "Dismiss"
+            </button>
+        </div>
+    }
+}
+
+/// A bold error headline over the error's message, for a danger [`Banner`].
+#[component]
+fn ErrorTitle(title: &'static str, msg: String) -> impl IntoView {
+    view! {
+        <div style="font-weight: bold; color: var(--danger); margin-bottom: 4px;">{title}</div>
+        <div style="color: var(--danger-text); word-break: break-word;">{msg}</div>
+    }
+}
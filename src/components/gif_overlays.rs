@@ -0,0 +1,62 @@
+use crate::app::{BoardDataCtx, EditingCtx};
+use crate::canvas::{shown_nodes, tag_dimmed, TAG_DIM_ALPHA};
+use crate::state::NodeType;
+use leptos::prelude::*;
+
+/// Animated `<img>` overlays for GIF image nodes.
+///
+/// The canvas can only paint a GIF's first frame, so `draw_image_content` skips
+/// GIFs and this component shows them instead. Positioning follows
+/// [`MarkdownOverlays`](super::MarkdownOverlays): each overlay is laid out at
+/// world size below the node's label and scaled by the camera zoom with a
+/// top-left transform origin, so it tracks pan/zoom without reflowing. The
+/// image area matches the canvas renderer's (4px padding under a 16px label),
+/// and `object-fit: contain` mirrors its aspect-preserving fit. Overlays ignore
+/// pointer events so the canvas still handles selection, drag and resize.
+#[component]
+pub fn GifOverlays() -> impl IntoView {
+    let board_ctx = use_context::<BoardDataCtx>().unwrap();
+    let gif_sources = use_context::<EditingCtx>().unwrap().gif_sources;
+
+    move || {
+        let b = board_ctx.board.get();
+        let cam = board_ctx.camera.get();
+        let sources = gif_sources.get();
+        // Fade along with the node the tag filter dims on the canvas.
+        let active_tags = board_ctx.active_tags.get();
+        // Nodes hidden by the type filter or a collapsed ancestor get no overlay.
+        let hidden_types = board_ctx.hidden_types.get();
+        let collapse = board_ctx.collapse.get();
+
+        shown_nodes(&b, &hidden_types, &collapse.hidden)
+            .into_iter()
+            .filter(|n| n.node_type == NodeType::Image)
+            .filter_map(|node| {
+                let src = sources.get(&node.text)?.clone();
+                let (screen_x, screen_y) = cam.world_to_screen(node.x, node.y);
+                let padding = 4.0;
+                let label_height = 16.0;
+                let base_w = (node.width - 2.0 * padding).max(0.0);
+                let base_h = (node.height - label_height - 2.0 * padding).max(0.0);
+
+                Some(view! {
+                    <img
+                        src=src
+                        style=format!(
+                            "position: absolute; left: {}px; top: {}px; \
+                             width: {}px; height: {}px; object-fit: contain; \
+                             transform: scale({}); transform-origin: top left; \
+                             pointer-events: none; opacity: {};",
+                            screen_x + padding * cam.zoom,
+                            screen_y + (label_height + padding) * cam.zoom,
+                            base_w,
+                            base_h,
+                            cam.zoom,
+                            if tag_dimmed(node, &active_tags) { TAG_DIM_ALPHA } else { 1.0 }
+                        )
+                    />
+                })
+            })
+            .collect::<Vec<_>>()
+    }
+}
@@ -8,12 +8,22 @@ pub fn ImageModal() -> impl IntoView {
     move || {
         ctx.modal_image.get().map(|image_url| {
             let set_modal_image = ctx.set_modal_image;
+            // Clicking the image or the backdrop closes, but only when the press
+            // began on the element the click resolves to: a drag that starts on
+            // the image and is released over the backdrop (the click then lands
+            // on their common ancestor, the backdrop) must not close.
+            let pressed_target = StoredValue::new_local(None::<web_sys::EventTarget>);
             view! {
                 <div
                     style="position: fixed; inset: 0; background: rgba(0,0,0,0.9); \
                            display: flex; align-items: center; justify-content: center; \
                            z-index: 1000; cursor: pointer;"
-                    on:click=move |_| set_modal_image.set(None)
+                    on:mousedown=move |ev: web_sys::MouseEvent| pressed_target.set_value(ev.target())
+                    on:click=move |ev: web_sys::MouseEvent| {
+                        if pressed_target.get_value() == ev.target() {
+                            set_modal_image.set(None);
+                        }
+                    }
                 >
                     <button
                         style="position: fixed; top: 16px; right: 16px; z-index: 1001; \
@@ -1,6 +1,6 @@
-use crate::app::{is_local_md_file, parse_markdown, BoardDataCtx, EditingCtx};
+use crate::app::{is_md_link, parse_markdown, BoardDataCtx, EditingCtx, SettingsCtx};
 use crate::canvas::LoadState;
-use crate::interaction::BoardAction;
+use crate::interaction::{text_changed, BoardAction};
 use crate::state::NodeType;
 use leptos::prelude::*;
 
@@ -8,19 +8,52 @@ use leptos::prelude::*;
 pub fn MarkdownModal() -> impl IntoView {
     let board_ctx = use_context::<BoardDataCtx>().unwrap();
     let ctx = use_context::<EditingCtx>().unwrap();
+    let settings = use_context::<SettingsCtx>().unwrap().settings;
+    let scroll_ref = NodeRef::<leptos::html::Div>::new();
+
+    // Reopening a note in view mode puts it back where it was last scrolled to.
+    // The offset is applied a frame after mount so the rendered markdown has
+    // its full height; tracking the `.md` cache retries once a linked file
+    // finishes loading and the content is finally tall enough to scroll.
+    Effect::new(move || {
+        let Some((node_id, false)) = ctx.modal_md.get() else {
+            return;
+        };
+        ctx.md_file_cache.track();
+        let Some(el) = scroll_ref.get() else {
+            return;
+        };
+        if let Some(top) = ctx.md_scroll.with_value(|m| m.get(&node_id)) {
+            request_animation_frame(move || el.set_scroll_top(top as i32));
+        }
+    });
 
     move || {
         if let Some((node_id, is_editing)) = ctx.modal_md.get() {
             let node_id_for_edit = node_id.clone();
             let node_id_for_save = node_id.clone();
             let node_id_for_content = node_id.clone();
+            let node_id_for_scroll = node_id.clone();
+            // Close only when the press *started* on the backdrop. A `click` whose
+            // mousedown and mouseup land on different elements is dispatched to
+            // their nearest common ancestor, so a text-selection drag from the
+            // content released over the backdrop clicks the backdrop itself — the
+            // content's `stop_propagation` never sees it.
+            let pressed_on_backdrop = StoredValue::new(false);
 
             Some(view! {
                 <div
                     style="position: fixed; inset: 0; background: rgba(0,0,0,0.9); \
                            display: flex; align-items: center; justify-content: center; \
                            z-index: 1000;"
-                    on:click=move |_| ctx.set_modal_md.set(None)
+                    on:mousedown=move |ev: web_sys::MouseEvent| {
+                        pressed_on_backdrop.set_value(ev.target() == ev.current_target());
+                    }
+                    on:click=move |ev: web_sys::MouseEvent| {
+                        if pressed_on_backdrop.get_value() && ev.target() == ev.current_target() {
+                            ctx.set_modal_md.set(None);
+                        }
+                    }
                 >
                     <div
                         class="modal"
@@ -56,16 +89,25 @@ pub fn MarkdownModal() -> impl IntoView {
                                                    font-family: inherit; font-size: 12px; font-weight: bold;"
                                             on:click=move |_| {
                                                 let new_content = ctx.md_edit_text.get_untracked();
+                                                let changed = board_ctx.board.with_untracked(|b| {
+                                                    b.nodes
+                                                        .iter()
+                                                        .find(|n| n.id == node_id_save)
+                                                        .is_some_and(|n| text_changed(&n.text, &new_content))
+                                                });
                                                 // Dispatch through the reducer so the
                                                 // commit snapshots undo history
-                                                // (fixes undo dropping edits, F52/F109).
-                                                ctx.dispatch.apply(
-                                                    BoardAction::EditMarkdown {
-                                                        id: node_id_save.clone(),
-                                                        text: new_content,
-                                                    },
-                                                    None,
-                                                );
+                                                // (fixes undo dropping edits, F52/F109);
+                                                // saving unchanged text records nothing.
+                                                if changed {
+                                                    ctx.dispatch.apply(
+                                                        BoardAction::EditMarkdown {
+                                                            id: node_id_save.clone(),
+                                                            text: new_content,
+                                                        },
+                                                        None,
+                                                    );
+                                                }
 
                                                 ctx.set_modal_md.set(Some((node_id_save.clone(), false)));
                                             }
@@ -77,10 +119,12 @@ pub fn MarkdownModal() -> impl IntoView {
                                     let b = board_ctx.board.get();
                                     let is_md_link = b.nodes.iter()
                                         .find(|n| n.id == node_id)
-                                        .map(|n| n.node_type == NodeType::Link && is_local_md_file(&n.text))
+                                        .map(|n| n.node_type == NodeType::Link && is_md_link(&n.text))
                                         .unwrap_or(false);
 
-                                    if is_md_link {
+                                    // Linked files are never editable here; a locked
+                                    // board keeps every note view-only.
+                                    if is_md_link || settings.get_untracked().read_only {
                                         view! {
                                             <span style="color: var(--accent-bright); font-size: 11px;">"[read-only]"</span>
                                         }.into_any()
@@ -119,7 +163,22 @@ pub fn MarkdownModal() -> impl IntoView {
                                 "\u{00d7}"
                             </button>
                         </div>
-                        <div style="flex: 1; overflow-y: auto; min-height: 0;">
+                        <div
+                            style="flex: 1; overflow-y: auto; min-height: 0;"
+                            node_ref=scroll_ref
+                            on:scroll=move |_| {
+                                // Remember every scroll rather than hooking each
+                                // close path (backdrop, ×, Escape); the editor's
+                                // textarea scrolls itself, not this container.
+                                if is_editing {
+                                    return;
+                                }
+                                if let Some(el) = scroll_ref.get_untracked() {
+                                    let top = el.scroll_top() as f64;
+                                    ctx.md_scroll.update_value(|m| m.set(&node_id_for_scroll, top));
+                                }
+                            }
+                        >
                             {move || {
                                 let nid = node_id_for_content.clone();
                                 if is_editing {
@@ -142,7 +201,7 @@ pub fn MarkdownModal() -> impl IntoView {
                                     let content = b.nodes.iter()
                                         .find(|n| n.id == nid)
                                         .map(|n| {
-                                            if n.node_type == NodeType::Link && is_local_md_file(&n.text) {
+                                            if n.node_type == NodeType::Link && is_md_link(&n.text) {
                                                 match md_cache_content.get(&n.text) {
                                                     Some(LoadState::Loaded(c)) => c.clone(),
                                                     Some(LoadState::Failed) => "*Failed to load file.*".to_string(),
@@ -1,5 +1,5 @@
-use crate::app::{minimap_transform, nodes_bounding_box, BoardDataCtx};
-use crate::state::Camera;
+use crate::app::{lost_in_void, minimap_transform, nodes_bounding_box, BoardDataCtx, FlyCamera};
+use crate::state::{Board, Camera};
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
@@ -9,6 +9,10 @@ const MINIMAP_W: f64 = 200.0;
 const MINIMAP_H: f64 = 140.0;
 const MINIMAP_PAD: f64 = 8.0;
 
+/// Size of the centered overview shown when no node is on screen.
+const OVERVIEW_W: f64 = 360.0;
+const OVERVIEW_H: f64 = 240.0;
+
 /// A small overview canvas pinned to the bottom-right. It draws every node as a
 /// scaled rectangle plus a rectangle marking the current viewport, and recenters
 /// the main camera when clicked (F101). Hidden when the board is empty.
@@ -22,7 +26,7 @@ pub fn Minimap() -> impl IntoView {
     let ctx = use_context::<BoardDataCtx>().unwrap();
     let board = ctx.board;
     let camera = ctx.camera;
-    let set_camera = ctx.set_camera;
+    let fly_camera = ctx.fly_camera;
     let viewport_size = ctx.viewport_size;
 
     let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
@@ -31,103 +35,37 @@ pub fn Minimap() -> impl IntoView {
     Effect::new(move || {
         let current_board = board.get();
         let cam = camera.get();
-        let (vw, vh) = viewport_size.get();
-
-        let Some(canvas) = canvas_ref.get() else {
+        let viewport = viewport_size.get();
+        let Some(c) = sized_context(canvas_ref, MINIMAP_W, MINIMAP_H) else {
             return;
         };
-        let canvas_el: &HtmlCanvasElement = &canvas;
-
-        // Size the backing store to the fixed CSS dimensions (1:1; the minimap is
-        // small and detail is not the point, so we skip DPR scaling here).
-        let bw = MINIMAP_W as u32;
-        let bh = MINIMAP_H as u32;
-        if canvas_el.width() != bw {
-            canvas_el.set_width(bw);
-        }
-        if canvas_el.height() != bh {
-            canvas_el.set_height(bh);
-        }
-
-        let Ok(Some(ctx_obj)) = canvas_el.get_context("2d") else {
-            return;
-        };
-        let Ok(c) = ctx_obj.dyn_into::<CanvasRenderingContext2d>() else {
-            return;
-        };
-
-        // Background (= var(--bg-panel)). Canvas2D can't read CSS vars, so this
-        // mirrors the styles.css token as a literal.
-        c.set_fill_style_str("rgba(17, 22, 31, 0.94)");
-        c.fill_rect(0.0, 0.0, MINIMAP_W, MINIMAP_H);
-
-        let Some(bbox) = nodes_bounding_box(&current_board.nodes) else {
-            return;
-        };
-        let (scale, off_x, off_y) = minimap_transform(bbox, MINIMAP_W, MINIMAP_H, MINIMAP_PAD);
-
-        // Node rectangles (accent @ 0.5 = var(--accent) #4c90f0; legible over the
-        // --bg-panel minimap surface).
-        c.set_fill_style_str("rgba(76, 144, 240, 0.5)");
-        for n in &current_board.nodes {
-            let x = n.x * scale + off_x;
-            let y = n.y * scale + off_y;
-            let w = (n.width * scale).max(1.0);
-            let h = (n.height * scale).max(1.0);
-            c.fill_rect(x, y, w, h);
-        }
-
-        // Viewport rectangle: the visible world region is
-        // (camera.x, camera.y) .. + (vw/zoom, vh/zoom).
-        if vw > 0.0 && vh > 0.0 && cam.zoom > 0.0 {
-            let vx = cam.x * scale + off_x;
-            let vy = cam.y * scale + off_y;
-            let vrw = (vw / cam.zoom) * scale;
-            let vrh = (vh / cam.zoom) * scale;
-            // Viewport rect (accent @ 0.95 = var(--accent) #4c90f0).
-            c.set_stroke_style_str("rgba(76, 144, 240, 0.95)");
-            c.set_line_width(1.5);
-            c.stroke_rect(vx, vy, vrw, vrh);
-        }
+        let bbox = nodes_bounding_box(&current_board.nodes);
+        draw_overview(
+            &c,
+            &current_board,
+            &cam,
+            viewport,
+            bbox,
+            MINIMAP_W,
+            MINIMAP_H,
+        );
     });
 
     // Click-to-recenter: translate the click position back into world coords and
     // move the camera so that world point sits at the viewport center.
     let on_click = move |ev: web_sys::MouseEvent| {
-        let Some(canvas) = canvas_ref.get_untracked() else {
+        let bbox = board.with_untracked(|b| nodes_bounding_box(&b.nodes));
+        let Some((world_x, world_y)) = click_to_world(canvas_ref, &ev, bbox, MINIMAP_W, MINIMAP_H)
+        else {
             return;
         };
-        let rect = canvas.get_bounding_client_rect();
-        let mx = ev.client_x() as f64 - rect.left();
-        let my = ev.client_y() as f64 - rect.top();
-
-        let current_board = board.get_untracked();
-        let Some(bbox) = nodes_bounding_box(&current_board.nodes) else {
-            return;
-        };
-        let (scale, off_x, off_y) = minimap_transform(bbox, MINIMAP_W, MINIMAP_H, MINIMAP_PAD);
-        if scale <= 0.0 {
-            return;
-        }
-        // Inverse of the forward mapping `world * scale + off`.
-        let world_x = (mx - off_x) / scale;
-        let world_y = (my - off_y) / scale;
-
-        let (vw, vh) = viewport_size.get_untracked();
-        set_camera.update(|c: &mut Camera| {
-            let half_w = if c.zoom > 0.0 {
-                (vw / c.zoom) / 2.0
-            } else {
-                0.0
-            };
-            let half_h = if c.zoom > 0.0 {
-                (vh / c.zoom) / 2.0
-            } else {
-                0.0
-            };
-            c.x = world_x - half_w;
-            c.y = world_y - half_h;
-        });
+        center_camera_on(
+            fly_camera,
+            &camera.get_untracked(),
+            viewport_size.get_untracked(),
+            world_x,
+            world_y,
+        );
     };
 
     let container_style = format!(
@@ -154,3 +92,222 @@ pub fn Minimap() -> impl IntoView {
         }
     }
 }
+
+/// A larger, centered minimap that appears only while no node is on screen
+/// ([`lost_in_void`]) — after panning or zooming off into empty canvas. Its
+/// bounds take in the viewport as well as the board, so the marker for where
+/// you are is always visible; clicking jumps to the node nearest the click (or
+/// the clicked spot when there is none). It goes away as soon as any node is in
+/// view again.
+#[component]
+pub fn VoidOverview() -> impl IntoView {
+    let ctx = use_context::<BoardDataCtx>().unwrap();
+    let board = ctx.board;
+    let camera = ctx.camera;
+    let fly_camera = ctx.fly_camera;
+    let viewport_size = ctx.viewport_size;
+
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+    let lost = Memo::new(move |_| {
+        let (vw, vh) = viewport_size.get();
+        board.with(|b| camera.with(|cam| lost_in_void(b, cam, vw, vh)))
+    });
+
+    Effect::new(move || {
+        if !lost.get() {
+            return;
+        }
+        let current_board = board.get();
+        let cam = camera.get();
+        let viewport = viewport_size.get();
+        let Some(c) = sized_context(canvas_ref, OVERVIEW_W, OVERVIEW_H) else {
+            return;
+        };
+        let bbox = overview_bounds(&current_board, &cam, viewport);
+        draw_overview(
+            &c,
+            &current_board,
+            &cam,
+            viewport,
+            bbox,
+            OVERVIEW_W,
+            OVERVIEW_H,
+        );
+    });
+
+    let on_click = move |ev: web_sys::MouseEvent| {
+        let viewport = viewport_size.get_untracked();
+        let bbox = board.with_untracked(|b| overview_bounds(b, &camera.get_untracked(), viewport));
+        let Some((world_x, world_y)) =
+            click_to_world(canvas_ref, &ev, bbox, OVERVIEW_W, OVERVIEW_H)
+        else {
+            return;
+        };
+        let target = board.with_untracked(|b| {
+            b.nodes
+                .iter()
+                .map(|n| (n.x + n.width / 2.0, n.y + n.height / 2.0))
+                .min_by(|a, b| {
+                    let da = (a.0 - world_x).hypot(a.1 - world_y);
+                    let db = (b.0 - world_x).hypot(b.1 - world_y);
+                    da.total_cmp(&db)
+                })
+        });
+        let (x, y) = target.unwrap_or((world_x, world_y));
+        center_camera_on(fly_camera, &camera.get_untracked(), viewport, x, y);
+    };
+
+    let container_style = format!(
+        "position: fixed; left: 50%; top: 50%; transform: translate(-50%, -50%); \
+         width: {}px; z-index: 95; display: flex; flex-direction: column; gap: 4px; \
+         padding: 8px; font-family: var(--mono); font-size: 12px; color: var(--text-dim);",
+        OVERVIEW_W
+    );
+
+    move || {
+        lost.get().then(|| {
+            view! {
+                <div class="modal" style=container_style.clone()>
+                    <span>"Nothing in view \u{2014} click to jump back"</span>
+                    <canvas
+                        node_ref=canvas_ref
+                        style=format!(
+                            "width: {}px; height: {}px; display: block; cursor: pointer;",
+                            OVERVIEW_W,
+                            OVERVIEW_H,
+                        )
+                        on:click=on_click
+                    />
+                </div>
+            }
+        })
+    }
+}
+
+/// The mounted canvas's 2D context, with its backing store sized 1:1 to `w` x
+/// `h` (overviews are small and detail is not the point, so no DPR scaling).
+fn sized_context(
+    canvas_ref: NodeRef<leptos::html::Canvas>,
+    w: f64,
+    h: f64,
+) -> Option<CanvasRenderingContext2d> {
+    let canvas = canvas_ref.get()?;
+    let canvas_el: &HtmlCanvasElement = &canvas;
+    if canvas_el.width() != w as u32 {
+        canvas_el.set_width(w as u32);
+    }
+    if canvas_el.height() != h as u32 {
+        canvas_el.set_height(h as u32);
+    }
+    canvas_el
+        .get_context("2d")
+        .ok()
+        .flatten()?
+        .dyn_into::<CanvasRenderingContext2d>()
+        .ok()
+}
+
+/// World region shown by the centered overview: the board's bounding box
+/// grown to include the visible viewport.
+fn overview_bounds(
+    board: &Board,
+    cam: &Camera,
+    (vw, vh): (f64, f64),
+) -> Option<(f64, f64, f64, f64)> {
+    let (min_x, min_y, max_x, max_y) = nodes_bounding_box(&board.nodes)?;
+    let (view_x1, view_y1) = cam.screen_to_world(vw, vh);
+    Some((
+        min_x.min(cam.x),
+        min_y.min(cam.y),
+        max_x.max(view_x1),
+        max_y.max(view_y1),
+    ))
+}
+
+/// Draw the overview background, each node as a scaled rectangle, and the
+/// viewport outline, fitting world region `bbox` into a `w` x `h` canvas.
+fn draw_overview(
+    c: &CanvasRenderingContext2d,
+    board: &Board,
+    cam: &Camera,
+    (vw, vh): (f64, f64),
+    bbox: Option<(f64, f64, f64, f64)>,
+    w: f64,
+    h: f64,
+) {
+    // Background (= var(--bg-panel)). Canvas2D can't read CSS vars, so this
+    // mirrors the styles.css token as a literal.
+    c.set_fill_style_str("rgba(17, 22, 31, 0.94)");
+    c.fill_rect(0.0, 0.0, w, h);
+
+    let Some(bbox) = bbox else {
+        return;
+    };
+    let (scale, off_x, off_y) = minimap_transform(bbox, w, h, MINIMAP_PAD);
+
+    // Node rectangles (accent @ 0.5 = var(--accent) #4c90f0; legible over the
+    // --bg-panel minimap surface).
+    c.set_fill_style_str("rgba(76, 144, 240, 0.5)");
+    for n in &board.nodes {
+        let x = n.x * scale + off_x;
+        let y = n.y * scale + off_y;
+        let w = (n.width * scale).max(1.0);
+        let h = (n.height * scale).max(1.0);
+        c.fill_rect(x, y, w, h);
+    }
+
+    // Viewport rectangle: the visible world region is
+    // (camera.x, camera.y) .. + (vw/zoom, vh/zoom).
+    if vw > 0.0 && vh > 0.0 && cam.zoom > 0.0 {
+        let vx = cam.x * scale + off_x;
+        let vy = cam.y * scale + off_y;
+        let vrw = (vw / cam.zoom) * scale;
+        let vrh = (vh / cam.zoom) * scale;
+        // Viewport rect (accent @ 0.95 = var(--accent) #4c90f0).
+        c.set_stroke_style_str("rgba(76, 144, 240, 0.95)");
+        c.set_line_width(1.5);
+        c.stroke_rect(vx, vy, vrw, vrh);
+    }
+}
+
+/// Translate a click on an overview canvas back into world coords, inverting
+/// the forward mapping `world * scale + off` for region `bbox`.
+fn click_to_world(
+    canvas_ref: NodeRef<leptos::html::Canvas>,
+    ev: &web_sys::MouseEvent,
+    bbox: Option<(f64, f64, f64, f64)>,
+    w: f64,
+    h: f64,
+) -> Option<(f64, f64)> {
+    let canvas = canvas_ref.get_untracked()?;
+    let rect = canvas.get_bounding_client_rect();
+    let mx = ev.client_x() as f64 - rect.left();
+    let my = ev.client_y() as f64 - rect.top();
+
+    let (scale, off_x, off_y) = minimap_transform(bbox?, w, h, MINIMAP_PAD);
+    if scale <= 0.0 {
+        return None;
+    }
+    Some(((mx - off_x) / scale, (my - off_y) / scale))
+}
+
+/// Fly the camera (at its current zoom `cam.zoom`) so world point
+/// `(world_x, world_y)` sits at the center of the `(vw, vh)` viewport.
+fn center_camera_on(
+    fly_camera: FlyCamera,
+    cam: &Camera,
+    (vw, vh): (f64, f64),
+    world_x: f64,
+    world_y: f64,
+) {
+    let (half_w, half_h) = if cam.zoom > 0.0 {
+        ((vw / cam.zoom) / 2.0, (vh / cam.zoom) / 2.0)
+    } else {
+        (0.0, 0.0)
+    };
+    fly_camera.to(Camera {
+        x: world_x - half_w,
+        y: world_y - half_h,
+        zoom: cam.zoom,
+    });
+}
@@ -1,15 +1,37 @@
+mod context_menu;
+mod edge_label_editor;
 mod error_banner;
+mod gif_overlays;
 mod image_modal;
 mod markdown_modal;
 mod markdown_overlays;
 mod minimap;
 mod node_editor;
+mod node_tooltip;
+mod save_indicator;
 mod search_overlay;
+mod selection_toolbar;
+mod settings_panel;
+mod tag_filter;
+mod tag_suggestions;
+mod toast;
+mod type_filter;
 
+pub use context_menu::ContextMenu;
+pub use edge_label_editor::EdgeLabelEditor;
 pub use error_banner::ErrorBanner;
+pub use gif_overlays::GifOverlays;
 pub use image_modal::ImageModal;
 pub use markdown_modal::MarkdownModal;
 pub use markdown_overlays::MarkdownOverlays;
-pub use minimap::Minimap;
+pub use minimap::{Minimap, VoidOverview};
 pub use node_editor::NodeEditor;
+pub use node_tooltip::NodeTooltip;
+pub use save_indicator::SaveIndicator;
 pub use search_overlay::SearchOverlay;
+pub use selection_toolbar::SelectionToolbar;
+pub use settings_panel::SettingsPanel;
+pub use tag_filter::TagFilter;
+pub use tag_suggestions::TagSuggestions;
+pub use toast::{show_toast, Toast};
+pub use type_filter::TypeFilter;
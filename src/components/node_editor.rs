@@ -1,5 +1,5 @@
-use crate::app::{BoardDataCtx, EditingCtx};
-use crate::interaction::BoardAction;
+use crate::app::{looks_like_url, BoardDataCtx, EditingCtx, SettingsCtx};
+use crate::interaction::{text_changed, BoardAction};
 use crate::state::NodeType;
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
@@ -8,6 +8,7 @@ use wasm_bindgen::JsCast;
 pub fn NodeEditor() -> impl IntoView {
     let board_ctx = use_context::<BoardDataCtx>().unwrap();
     let ctx = use_context::<EditingCtx>().unwrap();
+    let settings_ctx = use_context::<SettingsCtx>().unwrap();
 
     move || {
         if let Some(node_id) = ctx.editing_node.get() {
@@ -20,23 +21,30 @@ pub fn NodeEditor() -> impl IntoView {
                 let font_size = (14.0 * cam.zoom).max(8.0);
                 let initial_text = node.text.clone();
                 let is_md = node.node_type == NodeType::Md;
+                let is_plain_text = node.node_type == NodeType::Text;
 
                 if is_md {
+                    // Dispatch through the reducer so the commit snapshots undo
+                    // history (fixes undo dropping typed text, F52/F109) — but only
+                    // for a real change, so opening and closing the editor (or the
+                    // blur that follows an Escape commit) adds no empty undo step.
+                    let commit = {
+                        let initial_text = initial_text.clone();
+                        move |id: String, text: String| {
+                            if text_changed(&initial_text, &text) {
+                                ctx.dispatch
+                                    .apply(BoardAction::EditMarkdown { id, text }, None);
+                            }
+                        }
+                    };
+                    let commit_on_blur = commit.clone();
+
                     let node_id_for_blur = node_id.clone();
                     let on_blur_textarea = move |ev: web_sys::FocusEvent| {
                         if let Some(target) = ev.target() {
                             if let Ok(textarea) = target.dyn_into::<web_sys::HtmlTextAreaElement>()
                             {
-                                let new_text = textarea.value();
-                                // Dispatch through the reducer so the commit snapshots
-                                // undo history (fixes undo dropping typed text, F52/F109).
-                                ctx.dispatch.apply(
-                                    BoardAction::EditMarkdown {
-                                        id: node_id_for_blur.clone(),
-                                        text: new_text,
-                                    },
-                                    None,
-                                );
+                                commit_on_blur(node_id_for_blur.clone(), textarea.value());
                             }
                         }
                         ctx.set_editing_node.set(None);
@@ -49,14 +57,7 @@ pub fn NodeEditor() -> impl IntoView {
                                 if let Ok(textarea) =
                                     target.dyn_into::<web_sys::HtmlTextAreaElement>()
                                 {
-                                    let new_text = textarea.value();
-                                    ctx.dispatch.apply(
-                                        BoardAction::EditMarkdown {
-                                            id: node_id_for_keydown.clone(),
-                                            text: new_text,
-                                        },
-                                        None,
-                                    );
+                                    commit(node_id_for_keydown.clone(), textarea.value());
                                 }
                             }
                             ctx.set_editing_node.set(None);
@@ -79,20 +80,37 @@ pub fn NodeEditor() -> impl IntoView {
                         >{initial_text}</textarea>
                     }.into_any());
                 } else {
+                    // Dispatch through the reducer so the commit snapshots undo
+                    // history (fixes undo dropping typed text, F52/F109); an
+                    // unchanged commit records nothing. A plain text node
+                    // committed as a bare URL becomes a link node when
+                    // auto-linking is on — still a single undo step.
+                    let unchanged_text = initial_text.clone();
+                    let commit = move |id: String, text: String| {
+                        if !text_changed(&unchanged_text, &text) {
+                            return;
+                        }
+                        let action = if is_plain_text
+                            && settings_ctx.settings.get_untracked().auto_link_urls
+                            && looks_like_url(&text)
+                        {
+                            BoardAction::EditTextAsLink {
+                                id,
+                                text: text.trim().to_string(),
+                            }
+                        } else {
+                            BoardAction::EditText { id, text }
+                        };
+                        ctx.dispatch.apply(action, None);
+                    };
+
+                    let commit_on_blur = commit.clone();
+
                     let node_id_for_blur = node_id.clone();
                     let on_blur = move |ev: web_sys::FocusEvent| {
                         if let Some(target) = ev.target() {
                             if let Ok(input) = target.dyn_into::<web_sys::HtmlInputElement>() {
-                                let new_text = input.value();
-                                // Dispatch through the reducer so the commit snapshots
-                                // undo history (fixes undo dropping typed text, F52/F109).
-                                ctx.dispatch.apply(
-                                    BoardAction::EditText {
-                                        id: node_id_for_blur.clone(),
-                                        text: new_text,
-                                    },
-                                    None,
-                                );
+                                commit_on_blur(node_id_for_blur.clone(), input.value());
                             }
                         }
                         ctx.set_editing_node.set(None);
@@ -103,14 +121,7 @@ pub fn NodeEditor() -> impl IntoView {
                         "Enter" => {
                             if let Some(target) = ev.target() {
                                 if let Ok(input) = target.dyn_into::<web_sys::HtmlInputElement>() {
-                                    let new_text = input.value();
-                                    ctx.dispatch.apply(
-                                        BoardAction::EditText {
-                                            id: node_id_for_keydown.clone(),
-                                            text: new_text,
-                                        },
-                                        None,
-                                    );
+                                    commit(node_id_for_keydown.clone(), input.value());
                                     ctx.set_editing_node.set(None);
                                 }
                             }
@@ -0,0 +1,32 @@
+use crate::app::EditingCtx;
+use leptos::prelude::*;
+
+/// Offset from the cursor so the tooltip doesn't sit under the pointer.
+const CURSOR_OFFSET_PX: f64 = 14.0;
+
+/// Floating box with a hovered node's full text, for content the canvas clips
+/// or elides. The app arms it after the cursor rests on a node and clears it on
+/// any move off the node, click, drag or zoom; see `tooltip` in [`EditingCtx`].
+#[component]
+pub fn NodeTooltip() -> impl IntoView {
+    let tooltip = use_context::<EditingCtx>().unwrap().tooltip;
+
+    move || {
+        tooltip.get().map(|(x, y, text)| {
+            view! {
+                <div style=format!(
+                    "position: fixed; left: {}px; top: {}px; z-index: 180; max-width: 360px; \
+                     max-height: 240px; overflow: hidden; white-space: pre-wrap; \
+                     background: var(--bg-panel); border: 1px solid var(--border-strong); \
+                     border-radius: var(--radius); padding: 6px 10px; color: var(--text); \
+                     font-size: 12px; line-height: 1.4; \
+                     box-shadow: var(--panel-shadow); pointer-events: none;",
+                    x + CURSOR_OFFSET_PX,
+                    y + CURSOR_OFFSET_PX,
+                )>
+                    {text}
+                </div>
+            }
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use crate::app::{BoardDataCtx, SaveState};
+use leptos::prelude::*;
+
+/// Small "saved / unsaved / saving… / save failed" label at the head of the HUD,
+/// driven by `save_state` in [`BoardDataCtx`]. A failure is tinted and carries
+/// the error as its tooltip; the full message is also in the error banner.
+#[component]
+pub fn SaveIndicator() -> impl IntoView {
+    let save_state = use_context::<BoardDataCtx>().unwrap().save_state;
+
+    view! {
+        <span
+            class="status-line"
+            style=move || match save_state.get() {
+                SaveState::Failed(_) => "color: var(--danger);",
+                SaveState::Saved => "",
+                SaveState::Dirty | SaveState::Saving => "color: var(--text);",
+            }
+            title=move || match save_state.get() {
+                SaveState::Failed(msg) => msg,
+                _ => String::new(),
+            }
+        >
+            {move || save_state.get().label()}
+        </span>
+    }
+}
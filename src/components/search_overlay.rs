@@ -1,5 +1,7 @@
-use crate::app::{node_matches_query, BoardDataCtx, SelectionCtx};
-use crate::state::Camera;
+use crate::app::{fit_camera, focus_node_camera, nodes_bounding_box, BoardDataCtx, SelectionCtx};
+use crate::canvas::shown_nodes;
+use crate::query::{parse_query, result_label, search_hits, Query, SEARCH_RESULT_LIMIT};
+use crate::state::{Board, Camera, Node};
 use leptos::prelude::*;
 use std::collections::HashSet;
 use wasm_bindgen::JsCast;
@@ -8,15 +10,7 @@ use wasm_bindgen::JsCast;
 /// current zoom. Returns the repositioned camera, or `None` if the canvas
 /// element can't be measured (so the caller leaves the camera untouched).
 fn center_camera_on(cam: &Camera, wx: f64, wy: f64) -> Option<Camera> {
-    let canvas = web_sys::window()?
-        .document()?
-        .query_selector("canvas")
-        .ok()
-        .flatten()?
-        .dyn_into::<web_sys::HtmlCanvasElement>()
-        .ok()?;
-    let rect = canvas.get_bounding_client_rect();
-    let (cw, ch) = (rect.width(), rect.height());
+    let (cw, ch) = canvas_size()?;
     let zoom = if cam.zoom.is_finite() && cam.zoom > 0.0 {
         cam.zoom
     } else {
@@ -29,28 +23,72 @@ fn center_camera_on(cam: &Camera, wx: f64, wy: f64) -> Option<Camera> {
     })
 }
 
-/// Cmd/Ctrl+F search overlay (P2.4 / F99).
+/// CSS size of the live canvas element, or `None` if it can't be found.
+fn canvas_size() -> Option<(f64, f64)> {
+    let canvas = web_sys::window()?
+        .document()?
+        .query_selector("canvas")
+        .ok()
+        .flatten()?
+        .dyn_into::<web_sys::HtmlCanvasElement>()
+        .ok()?;
+    let rect = canvas.get_bounding_client_rect();
+    Some((rect.width(), rect.height()))
+}
+
+/// Frame `nodes` in the live canvas viewport (same 10% margin as the `F` key).
+/// Returns `None` for an empty slice or an unmeasurable canvas.
+fn fit_camera_to(nodes: &[Node]) -> Option<Camera> {
+    let bbox = nodes_bounding_box(nodes)?;
+    let (cw, ch) = canvas_size()?;
+    Some(fit_camera(bbox, cw, ch, 0.1))
+}
+
+/// [`search_hits`] limited to nodes the canvas shows: anything hidden by the
+/// type filter or a collapsed ancestor can't be highlighted, listed or jumped to.
+fn shown_hits<'a>(
+    board: &'a Board,
+    query: &Query,
+    hidden_types: &HashSet<String>,
+    collapsed: &HashSet<String>,
+) -> Vec<&'a Node> {
+    let shown: HashSet<&str> = shown_nodes(board, hidden_types, collapsed)
+        .iter()
+        .map(|n| n.id.as_str())
+        .collect();
+    let mut hits = search_hits(board, query);
+    hits.retain(|n| shown.contains(n.id.as_str()));
+    hits
+}
+
+/// Cmd/Ctrl+F (or Cmd/Ctrl+K) search overlay (P2.4 / F99).
 ///
 /// While `search_query` is `Some`, renders a floating input. On every keystroke
-/// it filters the board by text/tags/status via [`node_matches_query`] and writes
-/// the matching node ids into `selected_nodes` so they render with the existing
-/// selection highlight. Enter recenters the camera on the first match (board
-/// order); Escape closes the overlay and clears the highlight.
+/// it parses the input as a [`crate::query`] (`type:idea tag:urgent roadmap`)
+/// and writes the matching node ids into `selected_nodes` so they render with
+/// the existing selection highlight. Enter recenters the camera on the most
+/// recently edited match (board order among never-edited ones), Shift+Enter
+/// fits all matches; Escape closes the overlay
+/// and clears the highlight. The first [`SEARCH_RESULT_LIMIT`] matches are
+/// listed below the input; clicking one selects just that node, zooms to it
+/// and closes the overlay.
 #[component]
 pub fn SearchOverlay() -> impl IntoView {
     let board_ctx = use_context::<BoardDataCtx>().unwrap();
     let sel_ctx = use_context::<SelectionCtx>().unwrap();
 
     // Recompute matches for `query`, push them into the selection highlight, and
-    // return the ids in board order (so "first match" is deterministic).
+    // return the ids most recently edited first, then in board order (so "first
+    // match" is deterministic).
     let apply_matches = move |query: &str| -> Vec<String> {
-        let board = board_ctx.board.get_untracked();
-        let ids: Vec<String> = board
-            .nodes
-            .iter()
-            .filter(|n| node_matches_query(n, query))
-            .map(|n| n.id.clone())
-            .collect();
+        let hidden_types = board_ctx.hidden_types.get_untracked();
+        let collapse = board_ctx.collapse.get_untracked();
+        let ids: Vec<String> = board_ctx.board.with_untracked(|b| {
+            shown_hits(b, &parse_query(query), &hidden_types, &collapse.hidden)
+                .iter()
+                .map(|n| n.id.clone())
+                .collect()
+        });
         let set: HashSet<String> = ids.iter().cloned().collect();
         sel_ctx.set_selected_nodes.set(set);
         ids
@@ -71,13 +109,23 @@ pub fn SearchOverlay() -> impl IntoView {
             ev.prevent_default();
             let query = sel_ctx.search_query.get_untracked().unwrap_or_default();
             let ids = apply_matches(&query);
-            if let Some(first_id) = ids.first() {
+            if ev.shift_key() {
+                let board = board_ctx.board.get_untracked();
+                let hits: Vec<Node> = board
+                    .nodes
+                    .into_iter()
+                    .filter(|n| ids.contains(&n.id))
+                    .collect();
+                if let Some(next) = fit_camera_to(&hits) {
+                    board_ctx.fly_camera.to(next);
+                }
+            } else if let Some(first_id) = ids.first() {
                 let board = board_ctx.board.get_untracked();
                 if let Some(node) = board.nodes.iter().find(|n| &n.id == first_id) {
                     let (wx, wy) = node.center();
                     let cam = board_ctx.camera.get_untracked();
                     if let Some(next) = center_camera_on(&cam, wx, wy) {
-                        board_ctx.set_camera.set(next);
+                        board_ctx.fly_camera.to(next);
                     }
                 }
             }
@@ -90,25 +138,76 @@ pub fn SearchOverlay() -> impl IntoView {
         _ => {}
     };
 
+    // Jump to one result: select only it, zoom to it as Shift+F does, and close
+    // the overlay (leaving the selection in place, unlike Escape).
+    let jump_to = move |id: String| {
+        let node = board_ctx
+            .board
+            .with_untracked(|b| b.nodes.iter().find(|n| n.id == id).cloned());
+        if let Some(node) = node {
+            if let Some((cw, ch)) = canvas_size() {
+                board_ctx.fly_camera.to(focus_node_camera(&node, cw, ch));
+            }
+            sel_ctx.set_selected_nodes.set(HashSet::from([id]));
+            sel_ctx.set_search_query.set(None);
+        }
+    };
+
+    let results = move || {
+        let query = parse_query(&sel_ctx.search_query.get().unwrap_or_default());
+        let hidden_types = board_ctx.hidden_types.get();
+        let collapse = board_ctx.collapse.get();
+        let rows: Vec<(String, String)> = board_ctx.board.with(|b| {
+            shown_hits(b, &query, &hidden_types, &collapse.hidden)
+                .into_iter()
+                .take(SEARCH_RESULT_LIMIT)
+                .map(|n| (n.id.clone(), result_label(n)))
+                .collect()
+        });
+        (!rows.is_empty()).then(|| {
+            let items = rows
+                .into_iter()
+                .map(|(id, label)| {
+                    view! {
+                        <button
+                            class="hud-btn"
+                            style="text-align: left; overflow: hidden; white-space: nowrap; \
+                                   text-overflow: ellipsis;"
+                            on:click=move |_| jump_to(id.clone())
+                        >
+                            {label}
+                        </button>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div style="display: flex; flex-direction: column; width: 100%;">{items}</div>
+            }
+        })
+    };
+
     move || {
         sel_ctx.search_query.get().map(|query| {
             view! {
                 <div class="modal" style="position: fixed; top: 16px; left: 50%; transform: translateX(-50%); \
-                            z-index: 250; padding: 8px 10px; display: flex; align-items: center; \
-                            gap: 8px;">
-                    <span style="color: var(--text-dim); font-family: var(--mono); \
-                                 font-size: 12px;">"search"</span>
-                    <input
-                        class="modal-input"
-                        type="text"
-                        value=query
-                        autofocus=true
-                        placeholder="text, tag, or status…"
-                        style="padding: 6px 10px; width: 280px; \
-                               font-family: var(--mono); font-size: 13px;"
-                        on:input=on_input
-                        on:keydown=on_keydown
-                    />
+                            z-index: 250; padding: 8px 10px; display: flex; flex-direction: column; \
+                            gap: 6px; font-family: var(--mono); font-size: 12px;">
+                    <div style="display: flex; align-items: center; gap: 8px;">
+                        <span style="color: var(--text-dim); font-family: var(--mono); \
+                                     font-size: 12px;">"search"</span>
+                        <input
+                            class="modal-input"
+                            type="text"
+                            value=query
+                            autofocus=true
+                            placeholder="text, type:idea, tag:x, status:x, priority:1…"
+                            style="padding: 6px 10px; width: 280px; \
+                                   font-family: var(--mono); font-size: 13px;"
+                            on:input=on_input
+                            on:keydown=on_keydown
+                        />
+                    </div>
+                    {results}
                 </div>
             }
         })
@@ -0,0 +1,229 @@
+use super::TagSuggestions;
+use crate::app::{nodes_bounding_box, BoardDataCtx, EditingCtx, SelectionCtx, SettingsCtx};
+use crate::interaction::{Alignment, BoardAction};
+use crate::state::{Node, NodeType};
+use leptos::prelude::*;
+
+/// Border colors offered by the toolbar: the skill's visual-language palette
+/// (amber actor, green, cyan datastore, violet infra, red, neutral gray).
+pub const SWATCHES: [&str; 6] = [
+    "#f59e0b", "#22c55e", "#06b6d4", "#8b5cf6", "#ef4444", "#9ca3af",
+];
+
+/// Node types the toolbar can set (everything but the `Unknown` fallback).
+pub(crate) const TYPES: [NodeType; 6] = [
+    NodeType::Text,
+    NodeType::Idea,
+    NodeType::Note,
+    NodeType::Image,
+    NodeType::Md,
+    NodeType::Link,
+];
+
+/// Screen-space gap between the toolbar's bottom and the selection's top edge.
+const TOOLBAR_OFFSET_PX: f64 = 12.0;
+
+const ALIGNMENTS: [(Alignment, &str, &str); 6] = [
+    (Alignment::Left, "⇤", "Align left"),
+    (Alignment::CenterX, "↔", "Align centers horizontally"),
+    (Alignment::Right, "⇥", "Align right"),
+    (Alignment::Top, "⤒", "Align top"),
+    (Alignment::CenterY, "↕", "Align centers vertically"),
+    (Alignment::Bottom, "⤓", "Align bottom"),
+];
+
+/// Contextual batch-edit toolbar for a multi-selection.
+///
+/// Rendered only while 2+ nodes are selected on an unlocked board, so it never
+/// sits over the canvas otherwise. It floats centered above the selection's bounding box (tracking
+/// pan/zoom and drags) and routes every action through the shared
+/// [`Dispatcher`](crate::app::Dispatcher), so each click is one undo step —
+/// the same commands the keyboard shortcuts use.
+#[component]
+pub fn SelectionToolbar() -> impl IntoView {
+    let board_ctx = use_context::<BoardDataCtx>().unwrap();
+    let sel_ctx = use_context::<SelectionCtx>().unwrap();
+    let dispatch = use_context::<EditingCtx>().unwrap().dispatch;
+    let settings = use_context::<SettingsCtx>().unwrap().settings;
+
+    // Every toolbar control edits the board, so a locked board hides it.
+    let shown = Memo::new(move |_| {
+        sel_ctx.selected_nodes.with(|s| s.len() >= 2) && !settings.with(|s| s.read_only)
+    });
+    // Selected ids in board order, read at click time.
+    let selected_ids = move || -> Vec<String> {
+        let selected = sel_ctx.selected_nodes.get_untracked();
+        board_ctx
+            .board
+            .get_untracked()
+            .nodes
+            .iter()
+            .filter(|n| selected.contains(&n.id))
+            .map(|n| n.id.clone())
+            .collect()
+    };
+    // The "+tag" input's text, shared with its autocomplete dropdown.
+    let tag_query = RwSignal::new(String::new());
+    let add_tag = move |tag: String| {
+        if !tag.trim().is_empty() {
+            dispatch.apply(
+                BoardAction::AddTag {
+                    ids: selected_ids(),
+                    tag,
+                },
+                None,
+            );
+        }
+        tag_query.set(String::new());
+    };
+    let position = move || {
+        let selected = sel_ctx.selected_nodes.get();
+        let cam = board_ctx.camera.get();
+        let nodes: Vec<Node> = board_ctx.board.with(|b| {
+            b.nodes
+                .iter()
+                .filter(|n| selected.contains(&n.id))
+                .cloned()
+                .collect()
+        });
+        let (left, top) = nodes_bounding_box(&nodes)
+            .map(|(min_x, min_y, max_x, _)| cam.world_to_screen((min_x + max_x) / 2.0, min_y))
+            .unwrap_or_default();
+        format!(
+            "position: fixed; left: {left}px; top: {}px; transform: translate(-50%, -100%); \
+             z-index: 120; padding: 6px 8px; display: flex; align-items: center; gap: 6px; \
+             font-family: var(--mono); font-size: 12px; color: var(--text);",
+            top - TOOLBAR_OFFSET_PX
+        )
+    };
+
+    move || {
+        shown.get().then(|| {
+            view! {
+                <div class="modal" style=position>
+                    {SWATCHES
+                        .iter()
+                        .map(|&color| {
+                            view! {
+                                <button
+                                    title=format!("Color {color}")
+                                    style=format!(
+                                        "width: 16px; height: 16px; padding: 0; cursor: pointer; \
+                                         background: {color}; border: 1px solid var(--border-strong);"
+                                    )
+                                    on:click=move |_| {
+                                        dispatch.apply(
+                                            BoardAction::SetColor {
+                                                ids: selected_ids(),
+                                                color: Some(color.to_string()),
+                                            },
+                                            None,
+                                        );
+                                    }
+                                />
+                            }
+                        })
+                        .collect_view()}
+                    <button
+                        class="hud-btn"
+                        style="padding: 2px 6px;"
+                        title="Clear color"
+                        on:click=move |_| {
+                            dispatch.apply(
+                                BoardAction::SetColor {
+                                    ids: selected_ids(),
+                                    color: None,
+                                },
+                                None,
+                            );
+                        }
+                    >
+                        "\u{00d7}"
+                    </button>
+                    <select
+                        class="modal-input"
+                        style="font-family: var(--mono); font-size: 12px; padding: 2px 4px;"
+                        title="Set type"
+                        on:change=move |ev| {
+                            let select: web_sys::HtmlSelectElement = event_target(&ev);
+                            let value = select.value();
+                            if !value.is_empty() {
+                                dispatch.apply(
+                                    BoardAction::SetType {
+                                        ids: selected_ids(),
+                                        node_type: value.parse().unwrap_or_default(),
+                                    },
+                                    None,
+                                );
+                            }
+                            select.set_value("");
+                        }
+                    >
+                        <option value="">"type…"</option>
+                        {TYPES
+                            .iter()
+                            .map(|t| view! { <option value=t.as_str()>{t.as_str()}</option> })
+                            .collect_view()}
+                    </select>
+                    <span style="position: relative; display: inline-flex;">
+                        <input
+                            class="modal-input"
+                            type="text"
+                            placeholder="+tag"
+                            style="width: 72px; font-family: var(--mono); font-size: 12px; padding: 2px 6px;"
+                            prop:value=move || tag_query.get()
+                            on:input=move |ev| tag_query.set(event_target_value(&ev))
+                            on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                match ev.key().as_str() {
+                                    "Enter" => add_tag(tag_query.get_untracked()),
+                                    "Escape" => tag_query.set(String::new()),
+                                    _ => {}
+                                }
+                            }
+                        />
+                        <TagSuggestions query=tag_query on_pick=Callback::new(add_tag) />
+                    </span>
+                    {ALIGNMENTS
+                        .iter()
+                        .map(|&(to, glyph, title)| {
+                            view! {
+                                <button
+                                    class="hud-btn"
+                                    style="padding: 2px 6px;"
+                                    title=title
+                                    on:click=move |_| {
+                                        dispatch.apply(
+                                            BoardAction::Align {
+                                                ids: selected_ids(),
+                                                to,
+                                            },
+                                            None,
+                                        );
+                                    }
+                                >
+                                    {glyph}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                    <button
+                        class="hud-btn"
+                        style="padding: 2px 8px;"
+                        title="Group (Cmd+G)"
+                        on:click=move |_| dispatch.group_nodes(selected_ids())
+                    >
+                        "Group"
+                    </button>
+                    <button
+                        class="hud-btn"
+                        style="padding: 2px 8px; color: var(--danger);"
+                        title="Delete (Del)"
+                        on:click=move |_| dispatch.delete_nodes(selected_ids())
+                    >
+                        "Delete"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
@@ -0,0 +1,476 @@
+use super::selection_toolbar::SWATCHES;
+use crate::app::{BoardDataCtx, EditingCtx, SettingsCtx};
+use crate::interaction::BoardAction;
+use crate::settings::{
+    EdgeStyle, EdgeTrigger, PasteMode, SelectionStyle, MAX_UNDO_DEPTH, MIN_UNDO_DEPTH,
+};
+use crate::state::{
+    clamp_preview_timeout_secs, MAX_PREVIEW_TIMEOUT_SECS, MIN_PREVIEW_TIMEOUT_SECS,
+};
+use leptos::prelude::*;
+
+/// HUD-anchored preferences panel. Shown while `settings_open` is set (toggled by
+/// the "Settings" HUD button); every control writes straight into the `settings`
+/// signal, which the app persists to localStorage on change. The "this board"
+/// controls are the exception: they edit the board's own settings through the
+/// dispatcher, so they're saved in `board.json` and undoable.
+#[component]
+pub fn SettingsPanel() -> impl IntoView {
+    let ctx = use_context::<SettingsCtx>().unwrap();
+    let board = use_context::<BoardDataCtx>().unwrap().board;
+    let dispatch = use_context::<EditingCtx>().unwrap().dispatch;
+
+    move || {
+        ctx.settings_open.get().then(|| {
+            // Controls read the settings reactively (not via one snapshot here) so
+            // a change doesn't rebuild the panel and interrupt a slider drag.
+            view! {
+                <div class="modal" style="position: fixed; top: 52px; right: 12px; z-index: 150; \
+                            padding: 10px 14px; display: flex; flex-direction: column; gap: 8px; \
+                            font-family: var(--mono); font-size: 12px; color: var(--text);">
+                    <span style="color: var(--text-dim);">"settings"</span>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().read_only
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.read_only = on);
+                            }
+                        />
+                        "Read-only (lock board)"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().pan_inertia
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.pan_inertia = on);
+                            }
+                        />
+                        "Pan inertia"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().copy_link_on_click
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.copy_link_on_click = on);
+                            }
+                        />
+                        "Copy link URL on click"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().auto_link_urls
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.auto_link_urls = on);
+                            }
+                        />
+                        "Auto-link URLs in text nodes"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().stack_new_nodes
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.stack_new_nodes = on);
+                            }
+                        />
+                        "Stack new nodes in a column"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Create edges with"
+                        <select
+                            class="modal-input"
+                            style="font-family: var(--mono); font-size: 12px; padding: 2px 4px;"
+                            prop:value=move || match ctx.settings.get().edge_trigger {
+                                EdgeTrigger::Shift => "shift",
+                                EdgeTrigger::Alt => "alt",
+                                EdgeTrigger::ConnectMode => "connect_mode",
+                            }
+                            on:change=move |ev| {
+                                let trigger = match event_target_value(&ev).as_str() {
+                                    "alt" => EdgeTrigger::Alt,
+                                    "connect_mode" => EdgeTrigger::ConnectMode,
+                                    _ => EdgeTrigger::Shift,
+                                };
+                                ctx.set_settings.update(|s| s.edge_trigger = trigger);
+                            }
+                        >
+                            <option value="shift">"Shift-drag"</option>
+                            <option value="alt">"Alt-drag"</option>
+                            <option value="connect_mode">"Connect mode (E)"</option>
+                        </select>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Paste nodes"
+                        <select
+                            class="modal-input"
+                            style="font-family: var(--mono); font-size: 12px; padding: 2px 4px;"
+                            prop:value=move || match ctx.settings.get().paste_mode {
+                                PasteMode::Cursor => "cursor",
+                                PasteMode::Offset => "offset",
+                            }
+                            on:change=move |ev| {
+                                let mode = match event_target_value(&ev).as_str() {
+                                    "offset" => PasteMode::Offset,
+                                    _ => PasteMode::Cursor,
+                                };
+                                ctx.set_settings.update(|s| s.paste_mode = mode);
+                            }
+                        >
+                            <option value="cursor">"At the cursor"</option>
+                            <option value="offset">"Offset from the originals"</option>
+                        </select>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Zoom sensitivity"
+                        <input
+                            type="range"
+                            min="0.25"
+                            max="3"
+                            step="0.25"
+                            prop:value=move || ctx.settings.get().zoom_sensitivity.to_string()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<f64>() {
+                                    ctx.set_settings.update(|s| s.zoom_sensitivity = v);
+                                }
+                            }
+                        />
+                        <span style="color: var(--text-dim); min-width: 4ch;">
+                            {move || format!("{:.2}", ctx.settings.get().zoom_sensitivity)}
+                        </span>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().natural_scroll
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.natural_scroll = on);
+                            }
+                        />
+                        "Natural scroll direction"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().reduce_motion
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.reduce_motion = on);
+                            }
+                        />
+                        "Reduce motion (no camera animation)"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().fit_text_on_resize
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.fit_text_on_resize = on);
+                            }
+                        />
+                        "Don't shrink text nodes below their text"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().grid_snap
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.grid_snap = on);
+                            }
+                        />
+                        "Snap to grid when dragging"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().resize_snap
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.resize_snap = on);
+                            }
+                        />
+                        "Snap to grid when resizing"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Corner radius"
+                        <input
+                            type="range"
+                            min="0"
+                            max="24"
+                            step="2"
+                            prop:value=move || ctx.settings.get().node_corner_radius.to_string()
+                            on:input=move |ev| {
+                                if let Ok(r) = event_target_value(&ev).parse::<f64>() {
+                                    ctx.set_settings.update(|s| s.node_corner_radius = r);
+                                }
+                            }
+                        />
+                        <span style="color: var(--text-dim); min-width: 2ch;">
+                            {move || ctx.settings.get().node_corner_radius}
+                        </span>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().rotate_edge_labels
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.rotate_edge_labels = on);
+                            }
+                        />
+                        "Rotate edge labels along edges"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Selected nodes"
+                        <select
+                            class="modal-input"
+                            style="font-family: var(--mono); font-size: 12px; padding: 2px 4px;"
+                            prop:value=move || match ctx.settings.get().selection_style {
+                                SelectionStyle::Glow => "glow",
+                                SelectionStyle::Shadow => "shadow",
+                                SelectionStyle::Outline => "outline",
+                            }
+                            on:change=move |ev| {
+                                let style = match event_target_value(&ev).as_str() {
+                                    "shadow" => SelectionStyle::Shadow,
+                                    "outline" => SelectionStyle::Outline,
+                                    _ => SelectionStyle::Glow,
+                                };
+                                ctx.set_settings.update(|s| s.selection_style = style);
+                            }
+                        >
+                            <option value="glow">"Glow"</option>
+                            <option value="shadow">"Drop shadow"</option>
+                            <option value="outline">"Thick outline"</option>
+                        </select>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Edges"
+                        <select
+                            class="modal-input"
+                            style="font-family: var(--mono); font-size: 12px; padding: 2px 4px;"
+                            prop:value=move || match ctx.settings.get().edge_style {
+                                EdgeStyle::Straight => "straight",
+                                EdgeStyle::Curved => "curved",
+                            }
+                            on:change=move |ev| {
+                                let style = match event_target_value(&ev).as_str() {
+                                    "curved" => EdgeStyle::Curved,
+                                    _ => EdgeStyle::Straight,
+                                };
+                                ctx.set_settings.update(|s| s.edge_style = style);
+                            }
+                        >
+                            <option value="straight">"Straight lines"</option>
+                            <option value="curved">"Curves"</option>
+                        </select>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Edge opacity"
+                        <input
+                            type="range"
+                            min="0.1"
+                            max="1"
+                            step="0.1"
+                            prop:value=move || ctx.settings.get().edge_opacity.to_string()
+                            on:input=move |ev| {
+                                if let Ok(opacity) = event_target_value(&ev).parse::<f64>() {
+                                    ctx.set_settings.update(|s| s.edge_opacity = opacity);
+                                }
+                            }
+                        />
+                        <span style="color: var(--text-dim); min-width: 3ch;">
+                            {move || format!("{:.1}", ctx.settings.get().edge_opacity)}
+                        </span>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().drag_ghost
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.drag_ghost = on);
+                            }
+                        />
+                        "Outline start position while dragging"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().board_thumbnail
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.board_thumbnail = on);
+                            }
+                        />
+                        "Board thumbnail (desktop)"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || !ctx.settings.get().hide_help_bar
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.hide_help_bar = !on);
+                            }
+                        />
+                        "Shortcut bar"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || ctx.settings.get().render_stats
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.render_stats = on);
+                            }
+                        />
+                        "Render stats (dev)"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Autosave delay"
+                        <input
+                            type="range"
+                            min="0"
+                            max="2000"
+                            step="20"
+                            prop:value=move || ctx.settings.get().autosave_ms.to_string()
+                            on:input=move |ev| {
+                                if let Ok(ms) = event_target_value(&ev).parse::<u32>() {
+                                    ctx.set_settings.update(|s| s.autosave_ms = ms);
+                                }
+                            }
+                        />
+                        <span style="color: var(--text-dim); min-width: 7ch;">
+                            {move || match ctx.settings.get().autosave_ms {
+                                0 => "instant".to_string(),
+                                ms => format!("{ms} ms"),
+                            }}
+                        </span>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Undo steps"
+                        <input
+                            type="range"
+                            min=MIN_UNDO_DEPTH
+                            max=MAX_UNDO_DEPTH
+                            prop:value=move || ctx.settings.get().undo_depth.to_string()
+                            on:input=move |ev| {
+                                if let Ok(depth) = event_target_value(&ev).parse::<u32>() {
+                                    ctx.set_settings.update(|s| s.undo_depth = depth);
+                                }
+                            }
+                        />
+                        <span style="color: var(--text-dim); min-width: 4ch;">
+                            {move || ctx.settings.get().undo_depth}
+                        </span>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:disabled=move || ctx.settings.get().read_only
+                            prop:checked=move || board.with(|b| b.settings.directed)
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                dispatch.apply(BoardAction::SetDirected(on), None);
+                            }
+                        />
+                        "Directed edges (this board)"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            prop:disabled=move || !board.with(|b| b.settings.directed)
+                            prop:checked=move || ctx.settings.get().prevent_cycles
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                ctx.set_settings.update(|s| s.prevent_cycles = on);
+                            }
+                        />
+                        "Prevent cycles (directed boards)"
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "New node color (this board)"
+                        <select
+                            class="modal-input"
+                            style="font-family: var(--mono); font-size: 12px; padding: 2px 4px;"
+                            prop:disabled=move || ctx.settings.get().read_only
+                            prop:value=move || {
+                                board.with(|b| b.settings.default_node_color.clone().unwrap_or_default())
+                            }
+                            on:change=move |ev| {
+                                let value = event_target_value(&ev);
+                                let color = (!value.is_empty()).then_some(value);
+                                dispatch.apply(BoardAction::SetDefaultColor(color), None);
+                            }
+                        >
+                            <option value="">"type default"</option>
+                            {SWATCHES
+                                .iter()
+                                .map(|&color| view! { <option value=color>{color}</option> })
+                                .collect_view()}
+                        </select>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Assets folder"
+                        <input
+                            class="modal-input"
+                            type="text"
+                            placeholder="assets/ next to board.json"
+                            style="width: 180px; font-family: var(--mono); font-size: 12px; padding: 2px 6px;"
+                            prop:value=move || ctx.settings.get().assets_dir
+                            on:change=move |ev| {
+                                let dir = event_target_value(&ev);
+                                ctx.set_settings.update(|s| s.assets_dir = dir);
+                            }
+                        />
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Preview timeout"
+                        <input
+                            class="modal-input"
+                            type="number"
+                            min=MIN_PREVIEW_TIMEOUT_SECS
+                            max=MAX_PREVIEW_TIMEOUT_SECS
+                            style="width: 56px; font-family: var(--mono); font-size: 12px; padding: 2px 6px;"
+                            prop:value=move || ctx.settings.get().preview_timeout_secs.to_string()
+                            on:change=move |ev| {
+                                if let Ok(secs) = event_target_value(&ev).parse::<u64>() {
+                                    ctx.set_settings.update(|s| {
+                                        s.preview_timeout_secs = clamp_preview_timeout_secs(secs)
+                                    });
+                                }
+                            }
+                        />
+                        <span style="color: var(--text-dim);">"s"</span>
+                    </label>
+                    <label style="display: flex; align-items: center; gap: 8px;">
+                        "Preview User-Agent"
+                        <input
+                            class="modal-input"
+                            type="text"
+                            placeholder="built-in browser agent"
+                            style="width: 180px; font-family: var(--mono); font-size: 12px; padding: 2px 6px;"
+                            prop:value=move || ctx.settings.get().preview_user_agent
+                            on:change=move |ev| {
+                                let agent = event_target_value(&ev);
+                                ctx.set_settings.update(|s| s.preview_user_agent = agent);
+                            }
+                        />
+                    </label>
+                </div>
+            }
+        })
+    }
+}
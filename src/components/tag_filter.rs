@@ -0,0 +1,92 @@
+use crate::app::BoardDataCtx;
+use crate::query::all_tags;
+use leptos::prelude::*;
+
+/// Tag toggles: a row of `#tag` buttons pinned top-left under the type filter,
+/// one per distinct tag on the board (most used first). With any tag toggled
+/// on, nodes carrying none of the active tags draw faded (see
+/// [`crate::canvas::tag_dimmed`]) — unlike the type filter they stay
+/// selectable, so the filter only shifts focus. `clear` drops every active
+/// tag and restores full opacity.
+///
+/// Active tags that no longer appear on any node stay listed until toggled
+/// off, so the filter can't get stuck dimming everything.
+#[component]
+pub fn TagFilter() -> impl IntoView {
+    let ctx = use_context::<BoardDataCtx>().unwrap();
+    let board = ctx.board;
+    let active_tags = ctx.active_tags;
+
+    let tags = move || {
+        let mut tags: Vec<String> =
+            board.with(|b| all_tags(b).into_iter().map(|(t, _)| t).collect());
+        active_tags.with(|active| {
+            let mut stale: Vec<&String> = active.iter().filter(|t| !tags.contains(t)).collect();
+            stale.sort();
+            tags.extend(stale.into_iter().cloned());
+        });
+        tags
+    };
+
+    let toggle = move |tag: String| {
+        active_tags.update(|active| {
+            if !active.remove(&tag) {
+                active.insert(tag);
+            }
+        });
+    };
+
+    view! {
+        <Show when=move || !tags().is_empty()>
+            <div class="hud" style="position: fixed; top: 44px; left: 12px; gap: 4px; max-width: 60vw;">
+                {move || {
+                    tags()
+                        .into_iter()
+                        .map(|tag| {
+                            let on = {
+                                let tag = tag.clone();
+                                move || active_tags.with(|a| a.contains(&tag))
+                            };
+                            let title_on = on.clone();
+                            let title_tag = tag.clone();
+                            let click_tag = tag.clone();
+                            view! {
+                                <button
+                                    class="hud-btn"
+                                    style=move || {
+                                        let base = "padding: 2px 6px; font-family: var(--mono); font-size: 11px;";
+                                        if on() {
+                                            format!("{base} color: var(--accent); border-color: var(--accent);")
+                                        } else {
+                                            base.to_string()
+                                        }
+                                    }
+                                    title=move || {
+                                        if title_on() {
+                                            format!("Stop highlighting #{title_tag}")
+                                        } else {
+                                            format!("Highlight nodes tagged #{title_tag}")
+                                        }
+                                    }
+                                    on:click=move |_| toggle(click_tag.clone())
+                                >
+                                    {format!("#{tag}")}
+                                </button>
+                            }
+                        })
+                        .collect_view()
+                }}
+                <Show when=move || active_tags.with(|a| !a.is_empty())>
+                    <button
+                        class="hud-btn"
+                        style="padding: 2px 6px; font-family: var(--mono); font-size: 11px;"
+                        title="Clear the tag filter"
+                        on:click=move |_| active_tags.update(|a| a.clear())
+                    >
+                        "clear"
+                    </button>
+                </Show>
+            </div>
+        </Show>
+    }
+}
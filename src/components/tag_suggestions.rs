@@ -0,0 +1,65 @@
+use crate::app::BoardDataCtx;
+use crate::query::{all_tags, fuzzy_rank};
+use leptos::prelude::*;
+
+/// Most suggestions the dropdown lists at once.
+const MAX_SUGGESTIONS: usize = 6;
+
+/// Autocomplete dropdown hung under a tag input: the board's existing tags
+/// ranked by [`fuzzy_rank`] against `query` (more-used tags first on ties), so
+/// typing `urg` offers the `urgent` already in use instead of minting a
+/// near-duplicate. Hidden while `query` is blank or nothing matches, and once
+/// the typed text already is an existing tag. Clicking a suggestion hands it to
+/// `on_pick`; the parent must be `position: relative`.
+#[component]
+pub fn TagSuggestions(query: RwSignal<String>, on_pick: Callback<String>) -> impl IntoView {
+    let board = use_context::<BoardDataCtx>().unwrap().board;
+
+    let suggestions = Memo::new(move |_| {
+        let typed = query.get();
+        if typed.trim().is_empty() {
+            return Vec::new();
+        }
+        let tags: Vec<String> = board.with(|b| all_tags(b).into_iter().map(|(t, _)| t).collect());
+        let mut ranked = fuzzy_rank(&tags, &typed);
+        if ranked.first().is_some_and(|t| t == typed.trim()) {
+            return Vec::new();
+        }
+        ranked.truncate(MAX_SUGGESTIONS);
+        ranked
+    });
+
+    move || {
+        let list = suggestions.get();
+        (!list.is_empty()).then(|| {
+            view! {
+                <div
+                    class="modal"
+                    style="position: absolute; top: 100%; left: 0; margin-top: 4px; z-index: 130; \
+                           min-width: 100%; padding: 2px 0; display: flex; flex-direction: column;"
+                >
+                    {list
+                        .into_iter()
+                        .map(|tag| {
+                            let label = tag.clone();
+                            view! {
+                                <button
+                                    class="hud-btn"
+                                    style="padding: 2px 8px; text-align: left; border: none; \
+                                           white-space: nowrap;"
+                                    // mousedown, not click: fires before the input loses focus.
+                                    on:mousedown=move |ev| {
+                                        ev.prevent_default();
+                                        on_pick.run(tag.clone());
+                                    }
+                                >
+                                    {label}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            }
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use crate::app::EditingCtx;
+use leptos::prelude::*;
+
+/// How long a toast stays up before clearing itself.
+const TOAST_MS: u32 = 1600;
+
+/// Show `msg` as a brief toast. It clears itself after [`TOAST_MS`] unless a
+/// newer message has replaced it in the meantime.
+pub fn show_toast(toast: RwSignal<Option<String>>, msg: impl Into<String>) {
+    let msg = msg.into();
+    toast.set(Some(msg.clone()));
+    gloo_timers::callback::Timeout::new(TOAST_MS, move || {
+        if toast.get_untracked().as_deref() == Some(msg.as_str()) {
+            toast.set(None);
+        }
+    })
+    .forget();
+}
+
+/// Transient bottom-center confirmation for quiet side effects (e.g. a link URL
+/// copied to the clipboard), so they're never silent. Reads `toast` from
+/// [`EditingCtx`]; set it through [`show_toast`].
+#[component]
+pub fn Toast() -> impl IntoView {
+    let ctx = use_context::<EditingCtx>().unwrap();
+    let toast = ctx.toast;
+
+    move || {
+        toast.get().map(|msg| {
+            view! {
+                <div style="position: fixed; bottom: 44px; left: 50%; transform: translateX(-50%); \
+                            z-index: 200; background: var(--bg-panel); \
+                            border: 1px solid var(--border-strong); border-radius: var(--radius); \
+                            padding: 6px 12px; color: var(--text); \
+                            font-family: var(--mono); font-size: 12px; \
+                            box-shadow: var(--panel-shadow); pointer-events: none;">
+                    {msg}
+                </div>
+            }
+        })
+    }
+}
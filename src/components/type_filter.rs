@@ -0,0 +1,91 @@
+use super::selection_toolbar::TYPES;
+use crate::app::{BoardDataCtx, SelectionCtx};
+use crate::canvas::visible_nodes_by_type;
+use crate::state::NodeType;
+use leptos::prelude::*;
+use std::collections::HashSet;
+
+/// Per-type visibility toggles: a row of `[TEXT] [IDEA] ... [LINK]` buttons
+/// pinned top-left. A type toggled off is neither drawn nor hit-tested (see
+/// [`visible_nodes_by_type`]) and edges touching its nodes hide with them — a
+/// quick way to declutter a mixed board or isolate one kind of content.
+///
+/// Hiding a type also drops its nodes (and any edge to them) from the
+/// selection, so nothing invisible can be moved or deleted by accident.
+#[component]
+pub fn TypeFilter() -> impl IntoView {
+    let ctx = use_context::<BoardDataCtx>().unwrap();
+    let selection = use_context::<SelectionCtx>().unwrap();
+    let board = ctx.board;
+    let hidden_types = ctx.hidden_types;
+
+    let toggle = move |kind: NodeType| {
+        let name = kind.as_str().to_string();
+        let hiding = !hidden_types.with_untracked(|h| h.contains(&name));
+        hidden_types.update(|h| {
+            if hiding {
+                h.insert(name);
+            } else {
+                h.remove(&name);
+            }
+        });
+        if !hiding {
+            return;
+        }
+        let shown: HashSet<String> = board.with_untracked(|b| {
+            visible_nodes_by_type(b, &hidden_types.get_untracked())
+                .iter()
+                .map(|n| n.id.clone())
+                .collect()
+        });
+        selection
+            .set_selected_nodes
+            .update(|s| s.retain(|id| shown.contains(id)));
+        let edge_hidden = selection.selected_edge.with_untracked(|id| {
+            id.as_ref().is_some_and(|id| {
+                board.with_untracked(|b| {
+                    b.edges.iter().any(|e| {
+                        &e.id == id && !(shown.contains(&e.from_node) && shown.contains(&e.to_node))
+                    })
+                })
+            })
+        });
+        if edge_hidden {
+            selection.set_selected_edge.set(None);
+        }
+    };
+
+    view! {
+        <div class="hud" style="position: fixed; top: 12px; left: 12px; gap: 4px;">
+            {TYPES
+                .iter()
+                .map(|&kind| {
+                    let shown = move || hidden_types.with(|h| !h.contains(kind.as_str()));
+                    view! {
+                        <button
+                            class="hud-btn"
+                            style=move || {
+                                let base = "padding: 2px 6px; font-family: var(--mono); font-size: 11px;";
+                                if shown() {
+                                    base.to_string()
+                                } else {
+                                    format!("{base} opacity: 0.45; text-decoration: line-through;")
+                                }
+                            }
+                            title=move || {
+                                if shown() {
+                                    format!("Hide {} nodes", kind.as_str())
+                                } else {
+                                    format!("Show {} nodes", kind.as_str())
+                                }
+                            }
+                            on:click=move |_| toggle(kind)
+                        >
+                            {kind.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
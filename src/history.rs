@@ -6,8 +6,18 @@ use std::collections::VecDeque;
 ///
 /// `None` (the default for one-shot actions) never coalesces, so distinct
 /// operations always remain separately undoable.
+///
+/// Kinds in use: `"cycle-type"` (`T`), `"cycle-status"` (`S`),
+/// `"cycle-priority"` (`P`) and `"keyboard-resize"` (Ctrl+Alt+Arrow). Each
+/// shortcut has its own kind, so interleaving them keeps every switch a
+/// separate step.
 pub type EditKind = Option<&'static str>;
 
+/// Maximum gap between two same-kind timed pushes for them to coalesce. A
+/// keypress after a longer pause starts a new undo step, so coming back to a
+/// node later doesn't fold into an earlier run.
+pub const COALESCE_WINDOW_MS: f64 = 1000.0;
+
 /// History stack for undo/redo functionality.
 /// Stores full state snapshots for simplicity.
 ///
@@ -22,6 +32,8 @@ pub struct History<T: Clone> {
     /// Used to coalesce successive same-kind edits. Reset to `None` whenever the
     /// timeline branches (undo/redo) so a coalesce never spans a navigation.
     last_kind: EditKind,
+    /// Timestamp (ms) of the last timed push, for [`COALESCE_WINDOW_MS`].
+    last_at: Option<f64>,
 }
 
 impl<T: Clone> History<T> {
@@ -31,6 +43,19 @@ impl<T: Clone> History<T> {
             future: VecDeque::new(),
             max_size,
             last_kind: None,
+            last_at: None,
+        }
+    }
+
+    /// Change how many undo (and redo) steps are kept. Shrinking drops the
+    /// oldest entries right away; growing only leaves room for later pushes.
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        while self.past.len() > max_size {
+            self.past.pop_front();
+        }
+        while self.future.len() > max_size {
+            self.future.pop_front();
         }
     }
 
@@ -46,12 +71,28 @@ impl<T: Clone> History<T> {
     /// appended: the prior snapshot already captures the pre-edit state for the
     /// whole run, so the run collapses to one undo step. `None` never coalesces.
     pub fn push_kind(&mut self, state: T, kind: EditKind) {
+        self.push_inner(state, kind, None);
+    }
+
+    /// Like [`push_kind`](Self::push_kind), but the run only continues while
+    /// each push lands within [`COALESCE_WINDOW_MS`] of the previous one.
+    pub fn push_kind_at(&mut self, state: T, kind: EditKind, now_ms: f64) {
+        self.push_inner(state, kind, Some(now_ms));
+    }
+
+    fn push_inner(&mut self, state: T, kind: EditKind, now_ms: Option<f64>) {
+        let within_window = match (self.last_at, now_ms) {
+            (Some(last), Some(now)) => now - last <= COALESCE_WINDOW_MS,
+            _ => true,
+        };
         // Coalesce: a same-kind run keeps only the snapshot taken before the run
         // began. The redo stack is still cleared (a new edit invalidates redo).
-        let coalesce = kind.is_some() && kind == self.last_kind && !self.past.is_empty();
+        let coalesce =
+            kind.is_some() && kind == self.last_kind && within_window && !self.past.is_empty();
 
         self.future.clear();
         self.last_kind = kind;
+        self.last_at = now_ms;
 
         if coalesce {
             return;
@@ -91,6 +132,14 @@ impl<T: Clone> History<T> {
         })
     }
 
+    /// Drop the most recent snapshot without navigating, for a gesture that was
+    /// cancelled after its deferred snapshot was taken (Escape mid-drag). The
+    /// redo stack the push cleared is not restored.
+    pub fn discard_last(&mut self) -> Option<T> {
+        self.last_kind = None;
+        self.past.pop_back()
+    }
+
     pub fn can_undo(&self) -> bool {
         !self.past.is_empty()
     }
@@ -177,6 +226,34 @@ mod tests {
         assert_eq!(history.undo(2), None);
     }
 
+    #[test]
+    fn shrinking_max_size_drops_oldest_now() {
+        let mut history: History<i32> = History::new(10);
+        for state in 1..=5 {
+            history.push(state);
+        }
+        history.set_max_size(2);
+
+        assert_eq!(history.undo(6), Some(5));
+        assert_eq!(history.undo(5), Some(4));
+        assert_eq!(history.undo(4), None);
+    }
+
+    #[test]
+    fn growing_max_size_keeps_more_later_pushes() {
+        let mut history: History<i32> = History::new(2);
+        history.push(1);
+        history.push(2);
+        history.set_max_size(4);
+        // Nothing already dropped comes back...
+        assert_eq!(history.past.len(), 2);
+        // ...but new pushes are no longer trimmed at the old limit.
+        history.push(3);
+        history.push(4);
+        history.push(5);
+        assert_eq!(history.past, [2, 3, 4, 5]);
+    }
+
     #[test]
     fn undo_on_empty_returns_none() {
         let mut history: History<i32> = History::new(100);
@@ -403,4 +480,50 @@ mod tests {
         history.push_kind(4, Some("cycle"));
         assert!(!history.can_redo());
     }
+
+    #[test]
+    fn timed_run_within_window_coalesces() {
+        let mut history: History<i32> = History::new(100);
+        history.push_kind_at(0, Some("cycle"), 0.0);
+        history.push_kind_at(1, Some("cycle"), 400.0);
+        history.push_kind_at(2, Some("cycle"), 400.0 + COALESCE_WINDOW_MS);
+
+        assert_eq!(history.undo(3), Some(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn pause_longer_than_window_starts_new_step() {
+        let mut history: History<i32> = History::new(100);
+        history.push_kind_at(0, Some("cycle"), 0.0);
+        history.push_kind_at(1, Some("cycle"), 200.0);
+        history.push_kind_at(2, Some("cycle"), 200.0 + COALESCE_WINDOW_MS + 1.0);
+
+        assert_eq!(history.undo(3), Some(2));
+        assert_eq!(history.undo(2), Some(0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn discard_last_drops_the_latest_snapshot() {
+        let mut history: History<i32> = History::new(100);
+        history.push(1);
+        history.push(2);
+
+        assert_eq!(history.discard_last(), Some(2));
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(5), Some(1));
+    }
+
+    #[test]
+    fn discard_last_breaks_a_coalescing_run() {
+        let mut history: History<i32> = History::new(100);
+        history.push_kind(1, Some("cycle-type"));
+        history.push_kind(2, Some("cycle-type"));
+        history.discard_last();
+        history.push_kind(3, Some("cycle-type"));
+
+        assert_eq!(history.undo(4), Some(3));
+        assert!(!history.can_undo());
+    }
 }
@@ -10,8 +10,16 @@
 //! The view layer (`app.rs`, components) builds an action from DOM input, calls a
 //! thin `apply` wrapper that snapshots history once and runs `reduce`, then sets the
 //! board signal and dispatches the returned side effects.
+//!
+//! Structural edits (adding/removing nodes, connecting) delegate to the
+//! frontend-agnostic [`ops`] module in `brainstorm-types`, which the CLI shares.
 
-use crate::state::{Board, Edge, Node, NodeType};
+use crate::settings::PasteMode;
+use crate::state::{
+    nodes_bounding_box, ops, touch, Board, Edge, Node, NodeType, Port, ResizeHandle,
+    MIN_NODE_HEIGHT, MIN_NODE_WIDTH,
+};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 /// How node type cycling progresses when the user presses `T`, expressed over the
@@ -57,13 +65,17 @@ pub enum BoardAction {
         width: f64,
         height: f64,
     },
-    /// Create a directed edge between two existing nodes.
+    /// Create a directed edge between two existing nodes, entering the target
+    /// at `to_port` when one was picked (see [`nearest_port`](crate::state::nearest_port)).
     CreateEdge {
         id: String,
         from_node: String,
         to_node: String,
+        to_port: Option<Port>,
     },
     /// Insert a fully-formed node (the caller pre-builds it with a fresh id).
+    /// The board's [`BoardSettings`](crate::state::BoardSettings) defaults (e.g.
+    /// its default node color) fill in whatever the node leaves unset.
     CreateNode(Node),
     /// Delete the given node ids and any edge touching them. The selected edge id
     /// (if any) is deleted as well. Asset paths flagged here become
@@ -71,9 +83,15 @@ pub enum BoardAction {
     DeleteSelected {
         node_ids: Vec<String>,
         edge_id: Option<String>,
+        /// The `assets_dir` setting, deciding which images count as local assets.
+        assets_dir: String,
     },
     /// Cycle the `node_type` of the given nodes one step forward.
     CycleType(Vec<String>),
+    /// Step the `status` of the given nodes through [`STATUS_CYCLE`] (`S`).
+    CycleStatus(Vec<String>),
+    /// Step the `priority` of the given nodes through none → 1 … 5 → none (`P`).
+    CyclePriority(Vec<String>),
     /// Paste a batch of pre-rewritten nodes and edges (ids already fresh).
     PasteNodes { nodes: Vec<Node>, edges: Vec<Edge> },
     /// Replace a node's text (plain text / markdown inline editor commit).
@@ -82,12 +100,771 @@ pub enum BoardAction {
     /// to [`BoardAction::EditText`] but kept distinct so undo entries and any future
     /// instrumentation can tell the two editors apart.
     EditMarkdown { id: String, text: String },
+    /// Replace a node's text with a URL and turn it into a `link` node, as one
+    /// undo step (auto-link on inline editor commit).
+    EditTextAsLink { id: String, text: String },
+    /// Replace an edge's label (inline edge label editor commit). Surrounding
+    /// whitespace is dropped and a blank label removes it.
+    EditEdgeLabel { id: String, label: String },
+    /// Put the given nodes into `group` (or ungroup them with `None`). A `Some`
+    /// color is applied to every node as a shared border color; `None` leaves each
+    /// node's existing color alone.
+    AssignGroup {
+        ids: Vec<String>,
+        group: Option<String>,
+        color: Option<String>,
+    },
+    /// Set (or clear with `None`) the border color of the given nodes.
+    SetColor {
+        ids: Vec<String>,
+        color: Option<String>,
+    },
+    /// Set (or clear with `None`) the color new nodes on this board start with.
+    SetDefaultColor(Option<String>),
+    /// Switch the board between directed and undirected edges. Existing edges
+    /// are kept as they are; only how they're drawn and compared changes.
+    SetDirected(bool),
+    /// Set the `node_type` of the given nodes.
+    SetType {
+        ids: Vec<String>,
+        node_type: NodeType,
+    },
+    /// Add `tag` to each of the given nodes that doesn't already carry it.
+    AddTag { ids: Vec<String>, tag: String },
+    /// Turn auto-stacking on or off for `group`. Switching it on stacks the
+    /// group's members into a column right away (see [`stack_layout`]).
+    SetGroupStacked { group: String, stacked: bool },
+    /// Line the given nodes up along one edge or center of their bounding box.
+    Align { ids: Vec<String>, to: Alignment },
+    /// Flip `render_markdown` on the given nodes: on for all of them unless every
+    /// one already has it, in which case off (`M`).
+    ToggleMarkdown(Vec<String>),
+    /// Replace the given nodes with one node holding `text`, rewiring their
+    /// outside edges to it (`C`). See [`ops::collapse_to_node`].
+    CollapseNodes { ids: Vec<String>, text: String },
+    /// Rotate the draw order of the nodes stacked under world point `(x, y)`:
+    /// the bottom one comes to the top (`]`), or with `reverse` the top one
+    /// drops to the bottom (`[`).
+    CycleStack { x: f64, y: f64, reverse: bool },
+    /// Move every node to its force-directed position (`Shift+L`). See
+    /// [`ops::layout_positions`]; the same board and `seed` always land the
+    /// nodes in the same places.
+    AutoLayout { seed: u64 },
+}
+
+/// Which side (or center line) of the selection's bounding box [`align_nodes`]
+/// lines nodes up against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    CenterX,
+    Right,
+    Top,
+    CenterY,
+    Bottom,
+}
+
+/// Does this path look like a deletable local asset, i.e. a pasted image inside
+/// the assets folder? `assets_dir` is the `assets_dir` setting; blank means the
+/// default `assets/` next to `board.json`. Pasted images are stored by absolute
+/// path, so the folder is matched as a run of path segments (a leading `~` or
+/// `./` dropped) rather than resolved; `delete_asset` re-checks the real folder
+/// before removing anything.
+fn is_local_asset(path: &str, assets_dir: &str) -> bool {
+    let dir = assets_dir
+        .trim()
+        .trim_start_matches('~')
+        .trim_start_matches("./")
+        .trim_matches('/');
+    let dir = if dir.is_empty() { "assets" } else { dir };
+    path.contains(&format!("/{dir}/"))
+}
+
+/// What [`delete_nodes`] removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteReport {
+    pub nodes_removed: usize,
+    /// Edges dropped because they touched a removed node.
+    pub edges_removed: usize,
+    /// Local image assets the removed nodes pointed at, in board order; the
+    /// caller deletes these from disk.
+    pub asset_paths: Vec<String>,
+}
+
+/// Remove the nodes in `ids` and every edge touching one of them, reporting
+/// the counts and which pasted image files (under `assets_dir`, see
+/// [`is_local_asset`]) are now orphaned. Unknown ids are ignored.
+pub fn delete_nodes(board: &mut Board, ids: &HashSet<String>, assets_dir: &str) -> DeleteReport {
+    let ids: Vec<String> = ids.iter().cloned().collect();
+    let edges_before = board.edges.len();
+    let removed = ops::remove_nodes(board, &ids);
+    DeleteReport {
+        nodes_removed: removed.len(),
+        edges_removed: edges_before - board.edges.len(),
+        asset_paths: removed
+            .into_iter()
+            .filter(|n| n.node_type == NodeType::Image && is_local_asset(&n.text, assets_dir))
+            .map(|n| n.text)
+            .collect(),
+    }
+}
+
+/// Set the `group` of every node in `ids` to `group_id`, or clear it when
+/// `group_id` is `None` (ungroup). Unknown ids are ignored. Grouping is pure
+/// metadata — the shared `group` value is what `draw_groups` encloses and what
+/// [`select_group`] matches on, and it persists because `group` already serializes.
+pub fn assign_group(board: &mut Board, ids: &[String], group_id: Option<&str>) {
+    for node in &mut board.nodes {
+        if ids.contains(&node.id) {
+            node.group = group_id.map(str::to_string);
+        }
+    }
+}
+
+/// Set (`Some`) or clear (`None`) the border color of every node in `ids`.
+pub fn set_color(board: &mut Board, ids: &[String], color: Option<&str>) {
+    for node in &mut board.nodes {
+        if ids.contains(&node.id) {
+            node.color = color.map(str::to_string);
+        }
+    }
+}
+
+/// Set the `node_type` of every node in `ids`.
+pub fn set_node_type(board: &mut Board, ids: &[String], node_type: NodeType) {
+    for node in &mut board.nodes {
+        if ids.contains(&node.id) {
+            node.node_type = node_type;
+        }
+    }
+}
+
+/// Statuses the `S` shortcut steps through, after which the status clears.
+pub const STATUS_CYCLE: [&str; 3] = ["todo", "in-progress", "done"];
+
+/// The status after `status` in [`STATUS_CYCLE`]. No status (or one outside the
+/// cycle) starts at `todo`; `done` wraps back to none.
+pub fn next_status(status: Option<&str>) -> Option<String> {
+    let next = match status.and_then(|s| STATUS_CYCLE.iter().position(|c| *c == s)) {
+        Some(i) => STATUS_CYCLE.get(i + 1),
+        None => STATUS_CYCLE.first(),
+    };
+    next.map(|s| s.to_string())
+}
+
+/// The priority after `priority`: none → 1 → … → 5 → none.
+pub fn next_priority(priority: Option<u8>) -> Option<u8> {
+    match priority {
+        None => Some(1),
+        Some(p) if p < 5 => Some(p + 1),
+        Some(_) => None,
+    }
+}
+
+/// Append `tag` to every node in `ids` that doesn't already have it. The tag is
+/// trimmed; a blank tag is ignored.
+pub fn add_tag(board: &mut Board, ids: &[String], tag: &str) {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return;
+    }
+    for node in &mut board.nodes {
+        if ids.contains(&node.id) && !node.tags.iter().any(|t| t == tag) {
+            node.tags.push(tag.to_string());
+        }
+    }
+}
+
+/// Move every node in `ids` so the chosen side (or center line) matches that of
+/// their combined bounding box; the other axis is untouched. Fewer than two
+/// matching nodes is a no-op.
+pub fn align_nodes(board: &mut Board, ids: &[String], to: Alignment) {
+    let targets: Vec<Node> = board
+        .nodes
+        .iter()
+        .filter(|n| ids.contains(&n.id))
+        .cloned()
+        .collect();
+    if targets.len() < 2 {
+        return;
+    }
+    let Some((min_x, min_y, max_x, max_y)) = nodes_bounding_box(&targets) else {
+        return;
+    };
+    for node in &mut board.nodes {
+        if !ids.contains(&node.id) {
+            continue;
+        }
+        match to {
+            Alignment::Left => node.x = min_x,
+            Alignment::CenterX => node.x = (min_x + max_x) / 2.0 - node.width / 2.0,
+            Alignment::Right => node.x = max_x - node.width,
+            Alignment::Top => node.y = min_y,
+            Alignment::CenterY => node.y = (min_y + max_y) / 2.0 - node.height / 2.0,
+            Alignment::Bottom => node.y = max_y - node.height,
+        }
+    }
+}
+
+/// Vertical gap [`stack_layout`] leaves between the members of a stacked group.
+pub const STACK_SPACING: f64 = 20.0;
+
+/// Top-left positions that stack `members` in one column at the left edge of
+/// `frame` (`(x, y, width, height)`), `spacing` apart, top to bottom in their
+/// current `y` order (ties by `x`, then id) — so dropping a node between two
+/// others slots it in there. A column taller than the frame runs past its
+/// bottom: a group's box is drawn around its members, so the frame grows to fit.
+pub fn stack_layout(
+    members: &[&Node],
+    frame: (f64, f64, f64, f64),
+    spacing: f64,
+) -> HashMap<String, (f64, f64)> {
+    let mut order = members.to_vec();
+    order.sort_by(|a, b| {
+        a.y.total_cmp(&b.y)
+            .then(a.x.total_cmp(&b.x))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let mut y = frame.1;
+    order
+        .into_iter()
+        .map(|n| {
+            let at = (frame.0, y);
+            y += n.height + spacing;
+            (n.id.clone(), at)
+        })
+        .collect()
+}
+
+/// The box `(x, y, width, height)` around `group`'s members, counting any
+/// member listed in `before` at that earlier `(x, y)`. Passing a drag's start
+/// positions keeps a stacked column anchored where it was while one of its
+/// members is dragged around to reorder it.
+pub fn group_frame(
+    board: &Board,
+    group: &str,
+    before: &HashMap<String, (f64, f64)>,
+) -> Option<(f64, f64, f64, f64)> {
+    let members: Vec<Node> = board
+        .nodes
+        .iter()
+        .filter(|n| n.group.as_deref() == Some(group))
+        .map(|n| {
+            let mut n = n.clone();
+            if let Some(&(x, y)) = before.get(&n.id) {
+                (n.x, n.y) = (x, y);
+            }
+            n
+        })
+        .collect();
+    let (min_x, min_y, max_x, max_y) = nodes_bounding_box(&members)?;
+    Some((min_x, min_y, max_x - min_x, max_y - min_y))
+}
+
+/// Lay `group`'s members out as a [`stack_layout`] column inside `frame`.
+pub fn restack_group(board: &mut Board, group: &str, frame: (f64, f64, f64, f64)) {
+    let members: Vec<&Node> = board
+        .nodes
+        .iter()
+        .filter(|n| n.group.as_deref() == Some(group))
+        .collect();
+    let positions = stack_layout(&members, frame, STACK_SPACING);
+    for node in &mut board.nodes {
+        if let Some(&(x, y)) = positions.get(&node.id) {
+            (node.x, node.y) = (x, y);
+        }
+    }
+}
+
+/// The stacked groups of the nodes `leaving` picks, other than `except`, each
+/// with its current frame. Taken before those nodes leave (regrouped or
+/// deleted) so [`close_stacked_gaps`] can close the columns up where they were.
+fn stacked_frames_of(
+    board: &Board,
+    leaving: impl Fn(&str) -> bool,
+    except: Option<&str>,
+) -> Vec<(String, (f64, f64, f64, f64))> {
+    let groups: HashSet<&str> = board
+        .nodes
+        .iter()
+        .filter(|n| leaving(&n.id))
+        .filter_map(|n| n.group.as_deref())
+        .filter(|&g| Some(g) != except && board.settings.is_stacked(g))
+        .collect();
+    groups
+        .into_iter()
+        .filter_map(|g| Some((g.to_string(), group_frame(board, g, &HashMap::new())?)))
+        .collect()
+}
+
+/// Restack each group in `frames` that still has members in its old frame, and
+/// drop stacked groups left without any member from the board settings.
+fn close_stacked_gaps(board: &mut Board, frames: Vec<(String, (f64, f64, f64, f64))>) {
+    for (group, frame) in frames {
+        restack_group(board, &group, frame);
+    }
+    let live: HashSet<String> = board.nodes.iter().filter_map(|n| n.group.clone()).collect();
+    board.settings.stacked_groups.retain(|g| live.contains(g));
+}
+
+/// Sticky multi-connect (`H`): after one edge is drawn, its source stays armed
+/// and each click on another node connects the source to it, so a hub can be
+/// wired up one click per spoke.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StickyConnect {
+    #[default]
+    Off,
+    /// On, but no source yet: the next edge drawn arms its source.
+    Waiting,
+    /// On with this node armed as the source.
+    Armed(String),
+}
+
+impl StickyConnect {
+    /// The `H` key: turn the mode on, or off from any on state.
+    pub fn toggle(&self) -> Self {
+        match self {
+            StickyConnect::Off => StickyConnect::Waiting,
+            _ => StickyConnect::Off,
+        }
+    }
+
+    /// An edge from `from` was just created. While the mode is on, `from`
+    /// becomes (or stays) the armed source.
+    pub fn connected(&self, from: &str) -> Self {
+        match self {
+            StickyConnect::Off => StickyConnect::Off,
+            _ => StickyConnect::Armed(from.to_string()),
+        }
+    }
+
+    /// The edge `(from, to)` a click on `node` should create: only while armed,
+    /// and never a self-loop from clicking the source itself.
+    pub fn click_edge(&self, node: &str) -> Option<(String, String)> {
+        match self {
+            StickyConnect::Armed(source) if source != node => {
+                Some((source.clone(), node.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Escape: disarm the source but stay in the mode; a second Escape (with
+    /// nothing armed) leaves it. Returns `None` when already off, so Escape
+    /// can fall through to its usual job.
+    pub fn escape(&self) -> Option<Self> {
+        match self {
+            StickyConnect::Off => None,
+            StickyConnect::Waiting => Some(StickyConnect::Off),
+            StickyConnect::Armed(_) => Some(StickyConnect::Waiting),
+        }
+    }
+
+    pub fn armed(&self) -> Option<&String> {
+        match self {
+            StickyConnect::Armed(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Ids of every node whose `group` equals `group_id`. Used by Alt+click to
+/// select a whole group from any one of its members.
+pub fn select_group(board: &Board, group_id: &str) -> HashSet<String> {
+    board
+        .nodes
+        .iter()
+        .filter(|n| n.group.as_deref() == Some(group_id))
+        .map(|n| n.id.clone())
+        .collect()
+}
+
+/// Insert `node_id` into the middle of edge `edge_id`: the edge is replaced by
+/// `from → node` and `node → to`, using `new_ids` for the two new edges. The
+/// original label stays on the incoming half (it described leaving the source);
+/// the outgoing half is unlabeled. Each outer end keeps its port, and the ends
+/// at the inserted node are left free. Returns `false` and leaves the board untouched
+/// if the edge or node is missing, or the node is already one of its endpoints.
+pub fn split_edge_with(
+    board: &mut Board,
+    edge_id: &str,
+    node_id: &str,
+    new_ids: (String, String),
+) -> bool {
+    let Some(index) = board.edges.iter().position(|e| e.id == edge_id) else {
+        return false;
+    };
+    let edge = &board.edges[index];
+    if edge.from_node == node_id
+        || edge.to_node == node_id
+        || !board.nodes.iter().any(|n| n.id == node_id)
+    {
+        return false;
+    }
+    let edge = board.edges.remove(index);
+    board.edges.push(Edge {
+        id: new_ids.0,
+        from_node: edge.from_node,
+        to_node: node_id.to_string(),
+        label: edge.label,
+        from_port: edge.from_port,
+        to_port: None,
+    });
+    board.edges.push(Edge {
+        id: new_ids.1,
+        from_node: node_id.to_string(),
+        to_node: edge.to_node,
+        label: None,
+        from_port: None,
+        to_port: edge.to_port,
+    });
+    true
+}
+
+/// Geometry `(x, y, width, height)` after dragging `handle` of a node whose
+/// geometry was `original` by `(dx, dy)` world units. The corner opposite the
+/// handle stays put and the size never drops below
+/// [`MIN_NODE_WIDTH`]/[`MIN_NODE_HEIGHT`]. Shared by handle drags and the
+/// keyboard resize shortcut.
+pub fn resize_rect(
+    original: (f64, f64, f64, f64),
+    handle: ResizeHandle,
+    dx: f64,
+    dy: f64,
+) -> (f64, f64, f64, f64) {
+    resize_rect_min(original, handle, dx, dy, MIN_NODE_WIDTH, |_| {
+        MIN_NODE_HEIGHT
+    })
+}
+
+/// [`resize_rect`] with a content-derived floor: the width never drops below
+/// `min_width` and the height never below `min_height(new_width)`, since text
+/// rewraps onto more lines as its node narrows. Both floors are themselves
+/// kept at least [`MIN_NODE_WIDTH`]/[`MIN_NODE_HEIGHT`].
+pub fn resize_rect_min(
+    original: (f64, f64, f64, f64),
+    handle: ResizeHandle,
+    dx: f64,
+    dy: f64,
+    min_width: f64,
+    min_height: impl Fn(f64) -> f64,
+) -> (f64, f64, f64, f64) {
+    let (x, y, width, height) = original;
+    let (grow_x, grow_y) = match handle {
+        ResizeHandle::TopLeft => (-dx, -dy),
+        ResizeHandle::TopRight => (dx, -dy),
+        ResizeHandle::BottomLeft => (-dx, dy),
+        ResizeHandle::BottomRight => (dx, dy),
+    };
+    let new_width = (width + grow_x).max(min_width).max(MIN_NODE_WIDTH);
+    let new_height = (height + grow_y)
+        .max(min_height(new_width))
+        .max(MIN_NODE_HEIGHT);
+    let new_x = match handle {
+        ResizeHandle::TopLeft | ResizeHandle::BottomLeft => x + width - new_width,
+        _ => x,
+    };
+    let new_y = match handle {
+        ResizeHandle::TopLeft | ResizeHandle::TopRight => y + height - new_height,
+        _ => y,
+    };
+    (new_x, new_y, new_width, new_height)
+}
+
+/// Which corner handle of the rect `(x, y, width, height)` world point
+/// `(px, py)` is on, if any; each handle is a `size`-wide square centered on
+/// its corner. The rect counterpart of `Node::resize_handle_at`, used for the
+/// multi-selection bounds.
+pub fn rect_handle_at(
+    (x, y, width, height): (f64, f64, f64, f64),
+    px: f64,
+    py: f64,
+    size: f64,
+) -> Option<ResizeHandle> {
+    let half = size / 2.0;
+    let near = |cx: f64, cy: f64| (px - cx).abs() <= half && (py - cy).abs() <= half;
+    [
+        (x, y, ResizeHandle::TopLeft),
+        (x + width, y, ResizeHandle::TopRight),
+        (x, y + height, ResizeHandle::BottomLeft),
+        (x + width, y + height, ResizeHandle::BottomRight),
+    ]
+    .into_iter()
+    .find(|&(cx, cy, _)| near(cx, cy))
+    .map(|(_, _, handle)| handle)
+}
+
+/// A node id with its `(x, y, width, height)`.
+pub type NodeRect = (String, (f64, f64, f64, f64));
+
+/// Group resize: each node's `(x, y, width, height)` in `originals` after their
+/// shared bounding box goes from `from` to `to` (both `(x, y, width, height)`).
+/// Offsets within the box and sizes scale by the box's factors, so the group
+/// keeps its layout; sizes never drop below [`MIN_NODE_WIDTH`]/[`MIN_NODE_HEIGHT`].
+pub fn scale_rects(
+    originals: &[NodeRect],
+    from: (f64, f64, f64, f64),
+    to: (f64, f64, f64, f64),
+) -> Vec<NodeRect> {
+    let factor = |new: f64, old: f64| if old > 0.0 { new / old } else { 1.0 };
+    let (sx, sy) = (factor(to.2, from.2), factor(to.3, from.3));
+    originals
+        .iter()
+        .map(|(id, (x, y, w, h))| {
+            let rect = (
+                to.0 + (x - from.0) * sx,
+                to.1 + (y - from.1) * sy,
+                (w * sx).max(MIN_NODE_WIDTH),
+                (h * sy).max(MIN_NODE_HEIGHT),
+            );
+            (id.clone(), rect)
+        })
+        .collect()
+}
+
+/// World-unit step between successive pastes in [`PasteMode::Offset`].
+pub const PASTE_OFFSET: f64 = 50.0;
+
+/// How far to move copied `nodes` when pasting them: centered on `cursor` in
+/// [`PasteMode::Cursor`], or [`PASTE_OFFSET`] down-right of the originals per
+/// paste in [`PasteMode::Offset`], where `repeat` counts earlier pastes of the
+/// same copy so each lands one step further along. Add the result to every
+/// node's position; the cluster's own layout is untouched.
+pub fn paste_offset(
+    nodes: &[Node],
+    mode: PasteMode,
+    cursor: (f64, f64),
+    repeat: u32,
+) -> (f64, f64) {
+    match mode {
+        PasteMode::Cursor if !nodes.is_empty() => {
+            let count = nodes.len() as f64;
+            let cx = nodes.iter().map(|n| n.x + n.width / 2.0).sum::<f64>() / count;
+            let cy = nodes.iter().map(|n| n.y + n.height / 2.0).sum::<f64>() / count;
+            (cursor.0 - cx, cursor.1 - cy)
+        }
+        PasteMode::Cursor => (0.0, 0.0),
+        PasteMode::Offset => {
+            let step = PASTE_OFFSET * (repeat + 1) as f64;
+            (step, step)
+        }
+    }
 }
 
-/// Does this path look like a deletable local asset (a pasted image under
-/// `/assets/`)? Mirrors the previous inline check in the keyboard handler.
-fn is_local_asset(path: &str) -> bool {
-    path.contains("/assets/")
+/// World-unit offset, down and right, of the copies Cmd/Ctrl+D makes.
+pub const DUPLICATE_OFFSET: f64 = 20.0;
+
+/// Copies of `nodes` and the `edges` between them, moved by `(dx, dy)`, with
+/// every node and edge given an id from `fresh_id` and the edges rewired to the
+/// copied nodes. Shared by paste and duplicate; `edges` must only join nodes in
+/// `nodes` (as [`ops::extract_subgraph`] guarantees).
+pub fn remap_copy(
+    nodes: &[Node],
+    edges: &[Edge],
+    (dx, dy): (f64, f64),
+    mut fresh_id: impl FnMut() -> String,
+) -> (Vec<Node>, Vec<Edge>) {
+    let id_map: HashMap<&str, String> = nodes.iter().map(|n| (n.id.as_str(), fresh_id())).collect();
+    let new_nodes = nodes
+        .iter()
+        .map(|n| Node {
+            id: id_map[n.id.as_str()].clone(),
+            x: n.x + dx,
+            y: n.y + dy,
+            ..n.clone()
+        })
+        .collect();
+    let new_edges = edges
+        .iter()
+        .map(|e| Edge {
+            id: fresh_id(),
+            from_node: id_map[e.from_node.as_str()].clone(),
+            to_node: id_map[e.to_node.as_str()].clone(),
+            ..e.clone()
+        })
+        .collect();
+    (new_nodes, new_edges)
+}
+
+/// Clearance [`pack_without_overlap`] keeps between imported and existing nodes.
+pub const PACK_GAP: f64 = 20.0;
+
+/// Grid step, in world units, of the positions [`pack_without_overlap`] tries.
+const PACK_STEP: f64 = 40.0;
+
+/// How many rings of [`PACK_STEP`] [`pack_without_overlap`] searches before it
+/// jumps the block past the existing nodes' bounding box instead.
+const PACK_MAX_RINGS: i32 = 10;
+
+/// Move `incoming` as one block so none of its nodes comes within [`PACK_GAP`]
+/// of an `existing` node. Nothing moves when the block is already clear (always
+/// the case on an empty target). Otherwise candidate shifts are tried in
+/// growing square rings of [`PACK_STEP`] around the current spot; the first ring
+/// with a clear shift wins, and within it the one leaving the block's center
+/// nearest `origin`. When [`PACK_MAX_RINGS`] rings find nothing, the block goes
+/// just past whichever side of the existing nodes' bounding box is nearest
+/// `origin`, which is always clear. The nodes keep their layout relative to
+/// each other.
+pub fn pack_without_overlap(existing: &[NodeRect], incoming: &mut [Node], origin: (f64, f64)) {
+    let Some((min_x, min_y, max_x, max_y)) = nodes_bounding_box(incoming) else {
+        return;
+    };
+    let center = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    let clear = |dx: f64, dy: f64| {
+        incoming.iter().all(|n| {
+            existing.iter().all(|&(_, (x, y, w, h))| {
+                n.x + dx + n.width + PACK_GAP <= x
+                    || x + w + PACK_GAP <= n.x + dx
+                    || n.y + dy + n.height + PACK_GAP <= y
+                    || y + h + PACK_GAP <= n.y + dy
+            })
+        })
+    };
+    if clear(0.0, 0.0) {
+        return;
+    }
+    let distance =
+        |&(dx, dy): &(f64, f64)| (center.0 + dx - origin.0).hypot(center.1 + dy - origin.1);
+    let nearest = |shifts: Vec<(f64, f64)>| {
+        shifts
+            .into_iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    };
+    let ring_shift = (1..=PACK_MAX_RINGS).find_map(|ring| {
+        nearest(
+            (-ring..=ring)
+                .flat_map(|i| (-ring..=ring).map(move |j| (i, j)))
+                .filter(|&(i, j)| i.abs() == ring || j.abs() == ring)
+                .map(|(i, j)| (i as f64 * PACK_STEP, j as f64 * PACK_STEP))
+                .filter(|&(dx, dy)| clear(dx, dy))
+                .collect(),
+        )
+    });
+    let shift = ring_shift.or_else(|| {
+        let (ex_min_x, ex_min_y, ex_max_x, ex_max_y) = existing.iter().fold(
+            (
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(a, b, c, d), &(_, (x, y, w, h))| (a.min(x), b.min(y), c.max(x + w), d.max(y + h)),
+        );
+        nearest(vec![
+            (ex_max_x + PACK_GAP - min_x, 0.0),
+            (ex_min_x - PACK_GAP - max_x, 0.0),
+            (0.0, ex_max_y + PACK_GAP - min_y),
+            (0.0, ex_min_y - PACK_GAP - max_y),
+        ])
+    });
+    if let Some((dx, dy)) = shift {
+        for n in incoming.iter_mut() {
+            n.x += dx;
+            n.y += dy;
+        }
+    }
+}
+
+/// Ids of every node containing world point `(x, y)`, in draw order (bottom
+/// first, topmost last).
+pub fn nodes_at_point(board: &Board, x: f64, y: f64) -> Vec<String> {
+    board
+        .nodes
+        .iter()
+        .filter(|n| n.contains_point(x, y))
+        .map(|n| n.id.clone())
+        .collect()
+}
+
+/// Rotate the stack of nodes under `(x, y)` by one step so a different one is
+/// drawn (and hit-tested) on top. Only the stacked nodes swap slots; everything
+/// else keeps its place in `board.nodes`. Returns `false` when fewer than two
+/// nodes overlap the point.
+pub fn cycle_stack(board: &mut Board, x: f64, y: f64, reverse: bool) -> bool {
+    let slots: Vec<usize> = board
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| n.contains_point(x, y))
+        .map(|(i, _)| i)
+        .collect();
+    if slots.len() < 2 {
+        return false;
+    }
+    let mut stack: Vec<Node> = slots.iter().map(|&i| board.nodes[i].clone()).collect();
+    if reverse {
+        stack.rotate_right(1);
+    } else {
+        stack.rotate_left(1);
+    }
+    for (&i, node) in slots.iter().zip(stack) {
+        board.nodes[i] = node;
+    }
+    true
+}
+
+/// Build a new node centered on `(x, y)` plus an edge into it from `from_node`,
+/// for an edge dragged out and released over empty canvas. `ids` are the
+/// `(node, edge)` ids. Applied together as one [`BoardAction::PasteNodes`] so the
+/// pair is a single undo step.
+pub fn connected_node_at(from_node: &str, x: f64, y: f64, ids: (String, String)) -> (Node, Edge) {
+    let mut node = Node::new(ids.0, x, y, "New Node".to_string());
+    node.x -= node.width / 2.0;
+    node.y -= node.height / 2.0;
+    let edge = Edge {
+        id: ids.1,
+        from_node: from_node.to_string(),
+        to_node: node.id.clone(),
+        label: None,
+        from_port: None,
+        to_port: None,
+    };
+    (node, edge)
+}
+
+/// Whether committing `new` over a node's `old` text is a real edit. Line
+/// endings are compared normalized (a textarea hands back `\n` for a stored
+/// `\r\n`), so reopening and closing an editor without typing records no undo
+/// step and doesn't touch the node.
+pub fn text_changed(old: &str, new: &str) -> bool {
+    old.replace("\r\n", "\n") != new.replace("\r\n", "\n")
+}
+
+/// [`touch`] every node in `after` whose text or metadata differs from its
+/// counterpart in `before`, plus any node that's new. Moves and resizes alone
+/// leave the timestamp alone.
+pub fn touch_changed(before: &Board, after: &mut Board, now_ms: i64) {
+    let previous: HashMap<&str, &Node> = before.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    for node in &mut after.nodes {
+        let changed = previous.get(node.id.as_str()).is_none_or(|old| {
+            old.text != node.text
+                || old.node_type != node.node_type
+                || old.color != node.color
+                || old.tags != node.tags
+                || old.status != node.status
+                || old.group != node.group
+                || old.priority != node.priority
+                || old.collapsed != node.collapsed
+                || old.render_markdown != node.render_markdown
+        });
+        if changed {
+            touch(node, now_ms);
+        }
+    }
+}
+
+/// A fresh, human-readable group id (`group-1`, `group-2`, …) not already used
+/// by any node on the board. The id doubles as the label `draw_groups` renders.
+pub fn next_group_id(board: &Board) -> String {
+    let used: HashSet<&str> = board
+        .nodes
+        .iter()
+        .filter_map(|n| n.group.as_deref())
+        .collect();
+    (1..)
+        .map(|n| format!("group-{}", n))
+        .find(|candidate| !used.contains(candidate.as_str()))
+        .expect("unbounded range always yields an unused id")
 }
 
 /// Apply `action` to `board`, returning the next board and the side effects the
@@ -125,38 +902,36 @@ pub fn reduce(mut board: Board, action: BoardAction) -> (Board, Vec<SideEffect>)
             id,
             from_node,
             to_node,
+            to_port,
         } => {
-            board.edges.push(Edge {
-                id,
-                from_node,
-                to_node,
-                label: None,
-            });
+            if ops::connect(&mut board, id, &from_node, &to_node) {
+                if let Some(edge) = board.edges.last_mut() {
+                    edge.to_port = to_port;
+                }
+            }
             (board, vec![SideEffect::RequestSave])
         }
-        BoardAction::CreateNode(node) => {
-            board.nodes.push(node);
+        BoardAction::CreateNode(mut node) => {
+            board.settings.apply_defaults(&mut node);
+            ops::add_node(&mut board, node);
             (board, vec![SideEffect::RequestSave])
         }
-        BoardAction::DeleteSelected { node_ids, edge_id } => {
-            let mut effects = Vec::new();
+        BoardAction::DeleteSelected {
+            node_ids,
+            edge_id,
+            assets_dir,
+        } => {
             if let Some(edge_id) = edge_id {
-                board.edges.retain(|e| e.id != edge_id);
-            }
-            if !node_ids.is_empty() {
-                for node in &board.nodes {
-                    if node_ids.contains(&node.id)
-                        && node.node_type == NodeType::Image
-                        && is_local_asset(&node.text)
-                    {
-                        effects.push(SideEffect::DeleteAsset(node.text.clone()));
-                    }
-                }
-                board.nodes.retain(|n| !node_ids.contains(&n.id));
-                board
-                    .edges
-                    .retain(|e| !node_ids.contains(&e.from_node) && !node_ids.contains(&e.to_node));
+                ops::remove_edge(&mut board, &edge_id);
             }
+            let ids: HashSet<String> = node_ids.into_iter().collect();
+            let frames = stacked_frames_of(&board, |id| ids.contains(id), None);
+            let mut effects: Vec<SideEffect> = delete_nodes(&mut board, &ids, &assets_dir)
+                .asset_paths
+                .into_iter()
+                .map(SideEffect::DeleteAsset)
+                .collect();
+            close_stacked_gaps(&mut board, frames);
             effects.push(SideEffect::RequestSave);
             (board, effects)
         }
@@ -168,6 +943,22 @@ pub fn reduce(mut board: Board, action: BoardAction) -> (Board, Vec<SideEffect>)
             }
             (board, vec![SideEffect::RequestSave])
         }
+        BoardAction::CycleStatus(ids) => {
+            for node in &mut board.nodes {
+                if ids.contains(&node.id) {
+                    node.status = next_status(node.status.as_deref());
+                }
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::CyclePriority(ids) => {
+            for node in &mut board.nodes {
+                if ids.contains(&node.id) {
+                    node.priority = next_priority(node.priority);
+                }
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
         BoardAction::PasteNodes { nodes, edges } => {
             board.nodes.extend(nodes);
             board.edges.extend(edges);
@@ -179,6 +970,118 @@ pub fn reduce(mut board: Board, action: BoardAction) -> (Board, Vec<SideEffect>)
             }
             (board, vec![SideEffect::RequestSave])
         }
+        BoardAction::EditTextAsLink { id, text } => {
+            if let Some(node) = board.nodes.iter_mut().find(|n| n.id == id) {
+                node.text = text;
+                node.node_type = NodeType::Link;
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::EditEdgeLabel { id, label } => {
+            if let Some(edge) = board.edges.iter_mut().find(|e| e.id == id) {
+                let label = label.trim();
+                edge.label = (!label.is_empty()).then(|| label.to_string());
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::AssignGroup { ids, group, color } => {
+            // Nodes joining a stacked group slot into its column where the
+            // existing members already are.
+            let stacked_frame = group
+                .as_deref()
+                .filter(|g| board.settings.is_stacked(g))
+                .and_then(|g| group_frame(&board, g, &HashMap::new()));
+            // Columns the nodes leave close up behind them.
+            let left =
+                stacked_frames_of(&board, |id| ids.iter().any(|i| i == id), group.as_deref());
+            assign_group(&mut board, &ids, group.as_deref());
+            close_stacked_gaps(&mut board, left);
+            if let Some(group) = group.as_deref().filter(|g| board.settings.is_stacked(g)) {
+                let frame = stacked_frame.or_else(|| group_frame(&board, group, &HashMap::new()));
+                if let Some(frame) = frame {
+                    restack_group(&mut board, group, frame);
+                }
+            }
+            if let Some(color) = color {
+                for node in &mut board.nodes {
+                    if ids.contains(&node.id) {
+                        node.color = Some(color.clone());
+                    }
+                }
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::SetColor { ids, color } => {
+            set_color(&mut board, &ids, color.as_deref());
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::SetDefaultColor(color) => {
+            board.settings.default_node_color = color;
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::SetDirected(directed) => {
+            board.settings.directed = directed;
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::SetGroupStacked { group, stacked } => {
+            board.settings.stacked_groups.retain(|g| g != &group);
+            if stacked {
+                if let Some(frame) = group_frame(&board, &group, &HashMap::new()) {
+                    restack_group(&mut board, &group, frame);
+                }
+                board.settings.stacked_groups.push(group);
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::SetType { ids, node_type } => {
+            set_node_type(&mut board, &ids, node_type);
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::AddTag { ids, tag } => {
+            add_tag(&mut board, &ids, &tag);
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::Align { ids, to } => {
+            align_nodes(&mut board, &ids, to);
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::ToggleMarkdown(ids) => {
+            let all_on = board
+                .nodes
+                .iter()
+                .filter(|n| ids.contains(&n.id))
+                .all(|n| n.render_markdown);
+            for node in board.nodes.iter_mut().filter(|n| ids.contains(&n.id)) {
+                node.render_markdown = !all_on;
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::CycleStack { x, y, reverse } => {
+            cycle_stack(&mut board, x, y, reverse);
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::AutoLayout { seed } => {
+            let positions: HashMap<String, (f64, f64)> = ops::layout_positions(&board, seed)
+                .into_iter()
+                .map(|(id, x, y)| (id, (x, y)))
+                .collect();
+            for node in &mut board.nodes {
+                if let Some(&(x, y)) = positions.get(&node.id) {
+                    node.x = x;
+                    node.y = y;
+                }
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
+        BoardAction::CollapseNodes { ids, text } => {
+            if let Some(id) = ops::collapse_to_node(&mut board, &ids, text) {
+                let settings = board.settings.clone();
+                if let Some(node) = board.nodes.iter_mut().find(|n| n.id == id) {
+                    settings.apply_defaults(node);
+                }
+            }
+            (board, vec![SideEffect::RequestSave])
+        }
     }
 }
 
@@ -195,6 +1098,18 @@ mod tests {
             version: None,
             nodes,
             edges,
+            settings: Default::default(),
+        }
+    }
+
+    fn edge(id: &str, from: &str, to: &str) -> Edge {
+        Edge {
+            id: id.to_string(),
+            from_node: from.to_string(),
+            to_node: to.to_string(),
+            label: None,
+            from_port: None,
+            to_port: None,
         }
     }
 
@@ -263,78 +1178,287 @@ mod tests {
                 id: "e1".into(),
                 from_node: "a".into(),
                 to_node: "b".into(),
+                to_port: None,
             },
         );
         assert_eq!(out.edges.len(), 1);
         assert_eq!(out.edges[0].from_node, "a");
         assert_eq!(out.edges[0].to_node, "b");
         assert_eq!(out.edges[0].label, None);
+        assert_eq!(out.edges[0].ports(), (None, None));
         assert_eq!(fx, vec![SideEffect::RequestSave]);
     }
 
     #[test]
-    fn create_node_appends() {
-        let board = board_with(vec![], vec![]);
-        let (out, fx) = reduce(board, BoardAction::CreateNode(node("new", 1.0, 2.0)));
-        assert_eq!(out.nodes.len(), 1);
-        assert_eq!(out.nodes[0].id, "new");
-        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    fn create_edge_records_the_drop_port() {
+        let board = board_with(vec![node("a", 0.0, 0.0), node("b", 300.0, 0.0)], vec![]);
+        let (out, _) = reduce(
+            board,
+            BoardAction::CreateEdge {
+                id: "e1".into(),
+                from_node: "a".into(),
+                to_node: "b".into(),
+                to_port: Some(Port::Left),
+            },
+        );
+        assert_eq!(out.edges[0].ports(), (None, Some(Port::Left)));
     }
 
     #[test]
-    fn delete_selected_removes_nodes_and_incident_edges() {
-        let board = board_with(
-            vec![
-                node("a", 0.0, 0.0),
-                node("b", 0.0, 0.0),
-                node("c", 0.0, 0.0),
-            ],
-            vec![
-                Edge {
-                    id: "ab".into(),
-                    from_node: "a".into(),
-                    to_node: "b".into(),
-                    label: None,
-                },
-                Edge {
-                    id: "bc".into(),
-                    from_node: "b".into(),
-                    to_node: "c".into(),
-                    label: None,
-                },
-            ],
+    fn resize_rect_keeps_the_opposite_corner_fixed() {
+        let original = (100.0, 100.0, 200.0, 100.0);
+        assert_eq!(
+            resize_rect(original, ResizeHandle::BottomRight, 30.0, 20.0),
+            (100.0, 100.0, 230.0, 120.0)
         );
-        let (out, fx) = reduce(
-            board,
-            BoardAction::DeleteSelected {
-                node_ids: vec!["a".into()],
-                edge_id: None,
-            },
+        assert_eq!(
+            resize_rect(original, ResizeHandle::TopLeft, 30.0, 20.0),
+            (130.0, 120.0, 170.0, 80.0)
+        );
+        assert_eq!(
+            resize_rect(original, ResizeHandle::TopRight, 30.0, 20.0),
+            (100.0, 120.0, 230.0, 80.0)
+        );
+        assert_eq!(
+            resize_rect(original, ResizeHandle::BottomLeft, -30.0, -20.0),
+            (70.0, 100.0, 230.0, 80.0)
         );
-        // Node a gone, edge ab (incident to a) gone, edge bc survives.
-        assert!(out.nodes.iter().all(|n| n.id != "a"));
-        assert_eq!(out.nodes.len(), 2);
-        assert!(out.edges.iter().any(|e| e.id == "bc"));
-        assert!(out.edges.iter().all(|e| e.id != "ab"));
-        assert_eq!(fx, vec![SideEffect::RequestSave]);
     }
 
     #[test]
-    fn delete_selected_only_edge() {
-        let board = board_with(
-            vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)],
-            vec![Edge {
-                id: "ab".into(),
-                from_node: "a".into(),
-                to_node: "b".into(),
-                label: None,
-            }],
+    fn resize_rect_clamps_to_minimum_size() {
+        let (x, y, w, h) = resize_rect(
+            (100.0, 100.0, 200.0, 100.0),
+            ResizeHandle::TopLeft,
+            1000.0,
+            1000.0,
         );
-        let (out, fx) = reduce(
+        assert_eq!((w, h), (MIN_NODE_WIDTH, MIN_NODE_HEIGHT));
+        // The bottom-right corner didn't move.
+        assert_eq!((x + w, y + h), (300.0, 200.0));
+    }
+
+    #[test]
+    fn toggle_markdown_turns_on_unless_all_on() {
+        let mut flagged = node("a", 0.0, 0.0);
+        flagged.render_markdown = true;
+        let start = board_with(
+            vec![flagged, node("b", 0.0, 0.0), node("c", 0.0, 0.0)],
+            vec![],
+        );
+
+        let (mixed, _) = reduce(
+            start,
+            BoardAction::ToggleMarkdown(vec!["a".into(), "b".into()]),
+        );
+        let flags = |b: &Board| {
+            b.nodes
+                .iter()
+                .map(|n| n.render_markdown)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(flags(&mixed), vec![true, true, false]);
+
+        let (off, _) = reduce(
+            mixed,
+            BoardAction::ToggleMarkdown(vec!["a".into(), "b".into()]),
+        );
+        assert_eq!(flags(&off), vec![false, false, false]);
+        assert_eq!(off.nodes[0].text, "n", "the raw text is untouched");
+    }
+
+    #[test]
+    fn nodes_at_point_lists_the_stack_bottom_first() {
+        let b = board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("far", 900.0, 900.0),
+                node("b", 20.0, 20.0),
+            ],
+            vec![],
+        );
+        assert_eq!(nodes_at_point(&b, 50.0, 50.0), vec!["a", "b"]);
+        assert!(nodes_at_point(&b, -10.0, -10.0).is_empty());
+    }
+
+    #[test]
+    fn cycle_stack_rotates_only_the_overlapping_nodes() {
+        let mut b = board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("far", 900.0, 900.0),
+                node("b", 10.0, 10.0),
+                node("c", 20.0, 20.0),
+            ],
+            vec![],
+        );
+        let order = |b: &Board| b.nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>();
+
+        assert!(cycle_stack(&mut b, 50.0, 50.0, false));
+        assert_eq!(order(&b), vec!["b", "far", "c", "a"]);
+        assert!(cycle_stack(&mut b, 50.0, 50.0, true));
+        assert_eq!(order(&b), vec!["a", "far", "b", "c"]);
+    }
+
+    #[test]
+    fn cycle_stack_needs_two_overlapping_nodes() {
+        let mut b = board_with(vec![node("a", 0.0, 0.0), node("far", 900.0, 900.0)], vec![]);
+        assert!(!cycle_stack(&mut b, 50.0, 50.0, false));
+        assert_eq!(b.nodes[0].id, "a");
+    }
+
+    #[test]
+    fn auto_layout_moves_nodes_to_the_seeded_positions() {
+        let start = board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("b", 0.0, 0.0),
+                node("c", 0.0, 0.0),
+            ],
+            vec![edge("e1", "a", "b")],
+        );
+        let expected = ops::layout_positions(&start, 7);
+        let (out, effects) = reduce(start.clone(), BoardAction::AutoLayout { seed: 7 });
+        let placed: Vec<_> = out.nodes.iter().map(|n| (n.id.clone(), n.x, n.y)).collect();
+        assert_eq!(placed, expected);
+        assert_eq!(effects, vec![SideEffect::RequestSave]);
+
+        let (again, _) = reduce(start, BoardAction::AutoLayout { seed: 7 });
+        assert_eq!(again.nodes, out.nodes);
+    }
+
+    #[test]
+    fn connected_node_is_centered_and_linked() {
+        let (n, e) = connected_node_at("a", 500.0, 300.0, ("n1".into(), "e1".into()));
+        assert_eq!(n.id, "n1");
+        assert_eq!(n.x + n.width / 2.0, 500.0);
+        assert_eq!(n.y + n.height / 2.0, 300.0);
+        assert_eq!((e.id.as_str(), e.from_node.as_str()), ("e1", "a"));
+        assert_eq!(e.to_node, "n1");
+
+        let start = board_with(vec![node("a", 0.0, 0.0)], vec![]);
+        let (out, _) = reduce(
+            start,
+            BoardAction::PasteNodes {
+                nodes: vec![n],
+                edges: vec![e],
+            },
+        );
+        assert_eq!(out.nodes.len(), 2);
+        assert_eq!(out.edges[0].to_node, "n1");
+    }
+
+    #[test]
+    fn create_node_appends() {
+        let board = board_with(vec![], vec![]);
+        let (out, fx) = reduce(board, BoardAction::CreateNode(node("new", 1.0, 2.0)));
+        assert_eq!(out.nodes.len(), 1);
+        assert_eq!(out.nodes[0].id, "new");
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    }
+
+    #[test]
+    fn create_node_takes_board_default_color() {
+        let mut board = board_with(vec![], vec![]);
+        let (out, _) = reduce(
+            board.clone(),
+            BoardAction::CreateNode(node("plain", 0.0, 0.0)),
+        );
+        assert_eq!(out.nodes[0].color, None);
+
+        board.settings.default_node_color = Some("#06b6d4".into());
+        let (out, _) = reduce(board, BoardAction::CreateNode(node("blue", 0.0, 0.0)));
+        assert_eq!(out.nodes[0].color.as_deref(), Some("#06b6d4"));
+    }
+
+    #[test]
+    fn collapse_nodes_takes_board_default_color() {
+        let mut board = board_with(vec![node("a", 0.0, 0.0), node("b", 300.0, 0.0)], vec![]);
+        board.settings.default_node_color = Some("#06b6d4".into());
+        let (out, fx) = reduce(
+            board,
+            BoardAction::CollapseNodes {
+                ids: vec!["a".into(), "b".into()],
+                text: "Both".into(),
+            },
+        );
+        assert_eq!(out.nodes.len(), 1);
+        assert_eq!(out.nodes[0].text, "Both");
+        assert_eq!(out.nodes[0].color.as_deref(), Some("#06b6d4"));
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    }
+
+    #[test]
+    fn set_default_color_leaves_existing_nodes() {
+        let board = board_with(vec![node("a", 0.0, 0.0)], vec![]);
+        let (out, fx) = reduce(board, BoardAction::SetDefaultColor(Some("#ef4444".into())));
+        assert_eq!(out.settings.default_node_color.as_deref(), Some("#ef4444"));
+        assert_eq!(out.nodes[0].color, None);
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    }
+
+    #[test]
+    fn delete_selected_removes_nodes_and_incident_edges() {
+        let board = board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("b", 0.0, 0.0),
+                node("c", 0.0, 0.0),
+            ],
+            vec![
+                Edge {
+                    id: "ab".into(),
+                    from_node: "a".into(),
+                    to_node: "b".into(),
+                    label: None,
+                    from_port: None,
+                    to_port: None,
+                },
+                Edge {
+                    id: "bc".into(),
+                    from_node: "b".into(),
+                    to_node: "c".into(),
+                    label: None,
+                    from_port: None,
+                    to_port: None,
+                },
+            ],
+        );
+        let (out, fx) = reduce(
+            board,
+            BoardAction::DeleteSelected {
+                node_ids: vec!["a".into()],
+                edge_id: None,
+                assets_dir: String::new(),
+            },
+        );
+        // Node a gone, edge ab (incident to a) gone, edge bc survives.
+        assert!(out.nodes.iter().all(|n| n.id != "a"));
+        assert_eq!(out.nodes.len(), 2);
+        assert!(out.edges.iter().any(|e| e.id == "bc"));
+        assert!(out.edges.iter().all(|e| e.id != "ab"));
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    }
+
+    #[test]
+    fn delete_selected_only_edge() {
+        let board = board_with(
+            vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)],
+            vec![Edge {
+                id: "ab".into(),
+                from_node: "a".into(),
+                to_node: "b".into(),
+                label: None,
+                from_port: None,
+                to_port: None,
+            }],
+        );
+        let (out, fx) = reduce(
             board,
             BoardAction::DeleteSelected {
                 node_ids: vec![],
                 edge_id: Some("ab".into()),
+                assets_dir: String::new(),
             },
         );
         assert!(out.edges.is_empty());
@@ -356,6 +1480,7 @@ mod tests {
             BoardAction::DeleteSelected {
                 node_ids: vec!["img".into(), "remote".into()],
                 edge_id: None,
+                assets_dir: String::new(),
             },
         );
         assert!(out.nodes.is_empty());
@@ -379,12 +1504,277 @@ mod tests {
             BoardAction::DeleteSelected {
                 node_ids: vec!["t".into()],
                 edge_id: None,
+                assets_dir: String::new(),
             },
         );
         // node_type is "text" so no DeleteAsset, just save.
         assert_eq!(fx, vec![SideEffect::RequestSave]);
     }
 
+    fn ids(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn delete_nodes_counts_every_incident_edge() {
+        let mut board = board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("b", 0.0, 0.0),
+                node("c", 0.0, 0.0),
+            ],
+            vec![
+                edge("e1", "a", "b"),
+                edge("e2", "c", "a"),
+                edge("e3", "b", "c"),
+            ],
+        );
+        let report = delete_nodes(&mut board, &ids(&["a"]), "");
+        assert_eq!(report.nodes_removed, 1);
+        assert_eq!(report.edges_removed, 2);
+        assert_eq!(board.edges.len(), 1);
+        assert_eq!(board.edges[0].id, "e3");
+    }
+
+    #[test]
+    fn delete_nodes_counts_an_edge_between_two_deleted_nodes_once() {
+        let mut both = board_with(
+            vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)],
+            vec![edge("e1", "a", "b")],
+        );
+        let report = delete_nodes(&mut both, &ids(&["a", "b"]), "");
+        assert_eq!((report.nodes_removed, report.edges_removed), (2, 1));
+        assert!(both.nodes.is_empty() && both.edges.is_empty());
+
+        let mut one = board_with(
+            vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)],
+            vec![edge("e1", "a", "b")],
+        );
+        let report = delete_nodes(&mut one, &ids(&["b", "ghost"]), "");
+        assert_eq!((report.nodes_removed, report.edges_removed), (1, 1));
+        assert_eq!(one.nodes[0].id, "a");
+    }
+
+    #[test]
+    fn delete_nodes_lists_only_local_image_assets() {
+        let mut img = node("img", 0.0, 0.0);
+        img.node_type = NodeType::Image;
+        img.text = "/proj/assets/pic.png".to_string();
+        let mut text = node("t", 0.0, 0.0);
+        text.text = "/proj/assets/notes.txt".to_string();
+        let mut board = board_with(vec![img, text], vec![]);
+        let report = delete_nodes(&mut board, &ids(&["img", "t"]), "");
+        assert_eq!(report.nodes_removed, 2);
+        assert_eq!(report.asset_paths, vec!["/proj/assets/pic.png".to_string()]);
+    }
+
+    #[test]
+    fn delete_nodes_follows_a_configured_assets_folder() {
+        let image = |id: &str, path: &str| {
+            let mut n = node(id, 0.0, 0.0);
+            n.node_type = NodeType::Image;
+            n.text = path.to_string();
+            n
+        };
+        let board = board_with(
+            vec![
+                image("moved", "/home/me/Pictures/brainstorm/pic.png"),
+                image("old", "/proj/assets/old.png"),
+            ],
+            vec![],
+        );
+        for dir in ["~/Pictures/brainstorm", "/home/me/Pictures/brainstorm/"] {
+            let mut b = board.clone();
+            let report = delete_nodes(&mut b, &ids(&["moved", "old"]), dir);
+            assert_eq!(
+                report.asset_paths,
+                vec!["/home/me/Pictures/brainstorm/pic.png".to_string()],
+                "{dir}"
+            );
+        }
+        let mut b = board.clone();
+        let report = delete_nodes(&mut b, &ids(&["moved", "old"]), "  ");
+        assert_eq!(report.asset_paths, vec!["/proj/assets/old.png".to_string()]);
+    }
+
+    #[test]
+    fn sticky_connect_stays_armed_until_escape() {
+        let sticky = StickyConnect::Off.toggle();
+        assert_eq!(sticky, StickyConnect::Waiting);
+        // Nothing to connect until the first edge arms a source.
+        assert_eq!(sticky.click_edge("b"), None);
+
+        let sticky = sticky.connected("hub");
+        assert_eq!(sticky.armed().map(String::as_str), Some("hub"));
+        assert_eq!(sticky.click_edge("b"), Some(("hub".into(), "b".into())));
+        // Still armed after connecting: the next click connects again.
+        assert_eq!(sticky.click_edge("c"), Some(("hub".into(), "c".into())));
+        assert_eq!(sticky.click_edge("hub"), None);
+
+        let sticky = sticky.escape().unwrap();
+        assert_eq!(sticky, StickyConnect::Waiting);
+        assert_eq!(sticky.click_edge("b"), None);
+        assert_eq!(sticky.escape(), Some(StickyConnect::Off));
+        assert_eq!(StickyConnect::Off.escape(), None);
+    }
+
+    #[test]
+    fn sticky_connect_off_ignores_edges() {
+        assert_eq!(StickyConnect::Off.connected("a"), StickyConnect::Off);
+        assert_eq!(
+            StickyConnect::Armed("a".into()).toggle(),
+            StickyConnect::Off
+        );
+        // A new drag from another node re-arms on that source.
+        assert_eq!(
+            StickyConnect::Armed("a".into()).connected("b"),
+            StickyConnect::Armed("b".into())
+        );
+    }
+
+    #[test]
+    fn rect_handle_at_finds_each_corner() {
+        let rect = (0.0, 0.0, 100.0, 50.0);
+        assert_eq!(
+            rect_handle_at(rect, 1.0, -1.0, 8.0),
+            Some(ResizeHandle::TopLeft)
+        );
+        assert_eq!(
+            rect_handle_at(rect, 100.0, 0.0, 8.0),
+            Some(ResizeHandle::TopRight)
+        );
+        assert_eq!(
+            rect_handle_at(rect, 0.0, 52.0, 8.0),
+            Some(ResizeHandle::BottomLeft)
+        );
+        assert_eq!(
+            rect_handle_at(rect, 97.0, 50.0, 8.0),
+            Some(ResizeHandle::BottomRight)
+        );
+        assert_eq!(rect_handle_at(rect, 50.0, 25.0, 8.0), None);
+    }
+
+    #[test]
+    fn remap_copy_offsets_and_rewires_to_the_copies() {
+        let nodes = vec![node("a", 0.0, 0.0), node("b", 300.0, 0.0)];
+        let mut link = edge("e", "a", "b");
+        link.label = Some("next".into());
+        let mut counter = 0;
+        let (copies, edges) = remap_copy(&nodes, &[link], (20.0, 20.0), || {
+            counter += 1;
+            format!("new{counter}")
+        });
+        assert_eq!(
+            copies
+                .iter()
+                .map(|n| (n.id.as_str(), n.x, n.y))
+                .collect::<Vec<_>>(),
+            [("new1", 20.0, 20.0), ("new2", 320.0, 20.0)]
+        );
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].id, "new3");
+        assert_eq!(
+            (edges[0].from_node.as_str(), edges[0].to_node.as_str()),
+            ("new1", "new2")
+        );
+        assert_eq!(edges[0].label.as_deref(), Some("next"));
+    }
+
+    #[test]
+    fn paste_offset_centers_on_cursor() {
+        // Two 200x100 nodes centered on (300, 50) in aggregate.
+        let nodes = vec![node("a", 0.0, 0.0), node("b", 400.0, 0.0)];
+        let (dx, dy) = paste_offset(&nodes, PasteMode::Cursor, (1_000.0, 500.0), 3);
+        assert_eq!((dx, dy), (700.0, 450.0));
+    }
+
+    #[test]
+    fn paste_offset_cascades_from_originals() {
+        let nodes = vec![node("a", 0.0, 0.0), node("b", 400.0, 0.0)];
+        let cursor = (1_000.0, 500.0);
+        assert_eq!(
+            paste_offset(&nodes, PasteMode::Offset, cursor, 0),
+            (50.0, 50.0)
+        );
+        assert_eq!(
+            paste_offset(&nodes, PasteMode::Offset, cursor, 2),
+            (150.0, 150.0)
+        );
+    }
+
+    #[test]
+    fn pack_without_overlap_leaves_an_empty_target_alone() {
+        let mut nodes = vec![node("a", 0.0, 0.0), node("b", 250.0, 0.0)];
+        pack_without_overlap(&[], &mut nodes, (1_000.0, 1_000.0));
+        assert_eq!((nodes[0].x, nodes[0].y), (0.0, 0.0));
+        assert_eq!((nodes[1].x, nodes[1].y), (250.0, 0.0));
+
+        // Existing nodes that are already clear of the block don't move it either.
+        let below = [("x".to_string(), (0.0, 500.0, 200.0, 100.0))];
+        pack_without_overlap(&below, &mut nodes, (0.0, 0.0));
+        assert_eq!((nodes[0].x, nodes[0].y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn pack_without_overlap_shifts_the_block_clear() {
+        let existing = [
+            ("x".to_string(), (0.0, 0.0, 200.0, 100.0)),
+            ("y".to_string(), (250.0, 200.0, 200.0, 100.0)),
+        ];
+        let mut nodes = vec![node("a", 0.0, 0.0), node("b", 250.0, 0.0)];
+        pack_without_overlap(&existing, &mut nodes, (225.0, 50.0));
+
+        // The nearest clear spot is straight up; the layout is kept.
+        assert_eq!((nodes[0].x, nodes[0].y), (0.0, -120.0));
+        assert_eq!(
+            (nodes[1].x - nodes[0].x, nodes[1].y - nodes[0].y),
+            (250.0, 0.0)
+        );
+        for n in &nodes {
+            for &(_, (x, y, w, h)) in &existing {
+                assert!(
+                    n.x + n.width + PACK_GAP <= x
+                        || x + w + PACK_GAP <= n.x
+                        || n.y + n.height + PACK_GAP <= y
+                        || y + h + PACK_GAP <= n.y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pack_without_overlap_jumps_past_a_crowded_area() {
+        // A node far bigger than the ring search reaches: the block lands just
+        // past its side nearest the origin.
+        let existing = [("big".to_string(), (-1_000.0, -1_000.0, 2_000.0, 2_000.0))];
+        let mut nodes = vec![node("a", 0.0, 0.0)];
+        pack_without_overlap(&existing, &mut nodes, (0.0, 2_000.0));
+        assert_eq!((nodes[0].x, nodes[0].y), (0.0, 1_000.0 + PACK_GAP));
+    }
+
+    #[test]
+    fn scale_rects_keeps_group_layout() {
+        let originals = vec![
+            ("a".to_string(), (0.0, 0.0, 100.0, 50.0)),
+            ("b".to_string(), (200.0, 100.0, 100.0, 50.0)),
+        ];
+        // Bounds (0,0)-(300,150) doubled from the top-left corner.
+        let out = scale_rects(
+            &originals,
+            (0.0, 0.0, 300.0, 150.0),
+            (0.0, 0.0, 600.0, 300.0),
+        );
+        assert_eq!(out[0], ("a".to_string(), (0.0, 0.0, 200.0, 100.0)));
+        assert_eq!(out[1], ("b".to_string(), (400.0, 200.0, 200.0, 100.0)));
+    }
+
+    #[test]
+    fn scale_rects_floors_node_size() {
+        let originals = vec![("a".to_string(), (10.0, 10.0, 60.0, 40.0))];
+        let out = scale_rects(&originals, (10.0, 10.0, 60.0, 40.0), (10.0, 10.0, 6.0, 4.0));
+        assert_eq!(out[0].1, (10.0, 10.0, MIN_NODE_WIDTH, MIN_NODE_HEIGHT));
+    }
+
     #[test]
     fn cycle_type_advances_only_selected() {
         let mut a = node("a", 0.0, 0.0);
@@ -405,6 +1795,148 @@ mod tests {
         assert_eq!(fx, vec![SideEffect::RequestSave]);
     }
 
+    #[test]
+    fn cycle_type_presses_within_window_undo_in_one_step() {
+        use crate::history::{History, COALESCE_WINDOW_MS};
+
+        let mut board = board_with(vec![node("a", 0.0, 0.0)], vec![]);
+        let mut history: History<Board> = History::new(100);
+        let step = COALESCE_WINDOW_MS / 2.0;
+        for press in 0..3 {
+            history.push_kind_at(board.clone(), Some("cycle-type"), press as f64 * step);
+            board = reduce(board, BoardAction::CycleType(vec!["a".into()])).0;
+        }
+        assert_eq!(board.nodes[0].node_type, NodeType::Image);
+
+        let undone = history.undo(board).unwrap();
+        assert_eq!(undone.nodes[0].node_type, NodeType::Text);
+        assert!(!history.can_undo());
+    }
+
+    fn split_board(label: Option<&str>) -> Board {
+        board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("b", 400.0, 0.0),
+                node("m", 200.0, 0.0),
+            ],
+            vec![Edge {
+                id: "e".into(),
+                from_node: "a".into(),
+                to_node: "b".into(),
+                label: label.map(str::to_string),
+                from_port: None,
+                to_port: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn split_edge_replaces_edge_with_two_directed_halves() {
+        let mut board = split_board(Some("calls"));
+        assert!(split_edge_with(
+            &mut board,
+            "e",
+            "m",
+            ("e1".into(), "e2".into())
+        ));
+        assert!(board.edges.iter().all(|e| e.id != "e"));
+        assert_eq!(board.edges.len(), 2);
+        let incoming = board.edges.iter().find(|e| e.id == "e1").unwrap();
+        assert_eq!(
+            (incoming.from_node.as_str(), incoming.to_node.as_str()),
+            ("a", "m")
+        );
+        assert_eq!(incoming.label.as_deref(), Some("calls"));
+        let outgoing = board.edges.iter().find(|e| e.id == "e2").unwrap();
+        assert_eq!(
+            (outgoing.from_node.as_str(), outgoing.to_node.as_str()),
+            ("m", "b")
+        );
+        assert_eq!(outgoing.label, None);
+    }
+
+    #[test]
+    fn split_edge_keeps_the_outer_ports() {
+        let mut board = split_board(None);
+        board.edges[0].from_port = Some(Port::Right);
+        board.edges[0].to_port = Some(Port::Top);
+        assert!(split_edge_with(
+            &mut board,
+            "e",
+            "m",
+            ("e1".into(), "e2".into())
+        ));
+        assert_eq!(board.edges[0].ports(), (Some(Port::Right), None));
+        assert_eq!(board.edges[1].ports(), (None, Some(Port::Top)));
+    }
+
+    #[test]
+    fn split_edge_rejects_endpoints_and_unknown_ids() {
+        let original = split_board(None);
+        let ids = || ("e1".to_string(), "e2".to_string());
+
+        let mut board = original.clone();
+        assert!(!split_edge_with(&mut board, "e", "a", ids()));
+        assert!(!split_edge_with(&mut board, "missing", "m", ids()));
+        assert!(!split_edge_with(&mut board, "e", "ghost", ids()));
+        assert_eq!(board, original);
+    }
+
+    #[test]
+    fn text_changed_ignores_no_op_commits() {
+        assert!(!text_changed("Idea", "Idea"));
+        assert!(!text_changed("a\r\nb", "a\nb"));
+        assert!(!text_changed("", ""));
+        assert!(text_changed("Idea", "Idea!"));
+        assert!(text_changed("Idea", "Idea "));
+        assert!(text_changed("a\nb", "a\n\nb"));
+    }
+
+    #[test]
+    fn touch_changed_stamps_edited_and_new_nodes_only() {
+        let before = board_with(vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)], vec![]);
+        let mut after = before.clone();
+        after.nodes[0].x = 50.0; // moved only
+        after.nodes[1].status = Some("done".into());
+        after.nodes.push(node("c", 0.0, 0.0));
+
+        touch_changed(&before, &mut after, 42);
+        assert_eq!(after.nodes[0].updated_at, None);
+        assert_eq!(after.nodes[1].updated_at, Some(42));
+        assert_eq!(after.nodes[2].updated_at, Some(42));
+    }
+
+    #[test]
+    fn status_cycles_through_list_then_clears() {
+        assert_eq!(next_status(None).as_deref(), Some("todo"));
+        assert_eq!(next_status(Some("todo")).as_deref(), Some("in-progress"));
+        assert_eq!(next_status(Some("in-progress")).as_deref(), Some("done"));
+        assert_eq!(next_status(Some("done")), None);
+        // A custom status re-enters the cycle at the start.
+        assert_eq!(next_status(Some("blocked")).as_deref(), Some("todo"));
+    }
+
+    #[test]
+    fn priority_cycles_one_to_five_then_clears() {
+        assert_eq!(next_priority(None), Some(1));
+        assert_eq!(next_priority(Some(4)), Some(5));
+        assert_eq!(next_priority(Some(5)), None);
+    }
+
+    #[test]
+    fn cycle_status_and_priority_touch_only_listed_nodes() {
+        let board = board_with(vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)], vec![]);
+        let (out, fx) = reduce(board, BoardAction::CycleStatus(vec!["a".into()]));
+        assert_eq!(out.nodes[0].status.as_deref(), Some("todo"));
+        assert_eq!(out.nodes[1].status, None);
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+
+        let (out, _) = reduce(out, BoardAction::CyclePriority(vec!["b".into()]));
+        assert_eq!(out.nodes[0].priority, None);
+        assert_eq!(out.nodes[1].priority, Some(1));
+    }
+
     #[test]
     fn paste_nodes_extends_board() {
         let board = board_with(vec![node("a", 0.0, 0.0)], vec![]);
@@ -417,6 +1949,8 @@ mod tests {
                     from_node: "p1".into(),
                     to_node: "p2".into(),
                     label: None,
+                    from_port: None,
+                    to_port: None,
                 }],
             },
         );
@@ -439,6 +1973,32 @@ mod tests {
         assert_eq!(fx, vec![SideEffect::RequestSave]);
     }
 
+    #[test]
+    fn edit_edge_label_trims_and_clears() {
+        let board = board_with(
+            vec![node("a", 0.0, 0.0), node("b", 300.0, 0.0)],
+            vec![edge("e", "a", "b")],
+        );
+        let (out, fx) = reduce(
+            board,
+            BoardAction::EditEdgeLabel {
+                id: "e".into(),
+                label: "  depends on ".into(),
+            },
+        );
+        assert_eq!(out.edges[0].label.as_deref(), Some("depends on"));
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+
+        let (out, _) = reduce(
+            out,
+            BoardAction::EditEdgeLabel {
+                id: "e".into(),
+                label: "   ".into(),
+            },
+        );
+        assert_eq!(out.edges[0].label, None);
+    }
+
     #[test]
     fn edit_markdown_replaces_node_text() {
         let board = board_with(vec![node("a", 0.0, 0.0)], vec![]);
@@ -453,6 +2013,21 @@ mod tests {
         assert_eq!(fx, vec![SideEffect::RequestSave]);
     }
 
+    #[test]
+    fn edit_text_as_link_sets_text_and_type() {
+        let board = board_with(vec![node("a", 0.0, 0.0)], vec![]);
+        let (out, fx) = reduce(
+            board,
+            BoardAction::EditTextAsLink {
+                id: "a".into(),
+                text: "https://example.com".into(),
+            },
+        );
+        assert_eq!(out.nodes[0].text, "https://example.com");
+        assert_eq!(out.nodes[0].node_type, NodeType::Link);
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    }
+
     #[test]
     fn edit_text_unknown_id_is_noop() {
         let board = board_with(vec![node("a", 0.0, 0.0)], vec![]);
@@ -487,4 +2062,347 @@ mod tests {
         assert_eq!(a.tags, vec!["keep".to_string()]);
         assert_eq!(a.text, "new");
     }
+
+    #[test]
+    fn assign_group_sets_only_listed_nodes() {
+        let mut board = board_with(
+            vec![
+                node("a", 0.0, 0.0),
+                node("b", 0.0, 0.0),
+                node("c", 0.0, 0.0),
+            ],
+            vec![],
+        );
+        assign_group(&mut board, &["a".into(), "b".into()], Some("g1"));
+        assert_eq!(board.nodes[0].group.as_deref(), Some("g1"));
+        assert_eq!(board.nodes[1].group.as_deref(), Some("g1"));
+        assert_eq!(board.nodes[2].group, None);
+    }
+
+    #[test]
+    fn assign_group_none_ungroups() {
+        let mut a = node("a", 0.0, 0.0);
+        a.group = Some("g1".into());
+        let mut board = board_with(vec![a], vec![]);
+        assign_group(&mut board, &["a".into()], None);
+        assert_eq!(board.nodes[0].group, None);
+    }
+
+    #[test]
+    fn select_group_returns_all_members() {
+        let mut a = node("a", 0.0, 0.0);
+        a.group = Some("g1".into());
+        let mut b = node("b", 0.0, 0.0);
+        b.group = Some("g1".into());
+        let mut c = node("c", 0.0, 0.0);
+        c.group = Some("g2".into());
+        let board = board_with(vec![a, b, c, node("d", 0.0, 0.0)], vec![]);
+        let sel = select_group(&board, "g1");
+        assert_eq!(sel, ["a", "b"].iter().map(|s| s.to_string()).collect());
+        assert!(select_group(&board, "missing").is_empty());
+    }
+
+    #[test]
+    fn next_group_id_skips_used_ids() {
+        let mut a = node("a", 0.0, 0.0);
+        a.group = Some("group-1".into());
+        let mut b = node("b", 0.0, 0.0);
+        b.group = Some("group-2".into());
+        let board = board_with(vec![a, b], vec![]);
+        assert_eq!(next_group_id(&board), "group-3");
+        assert_eq!(next_group_id(&board_with(vec![], vec![])), "group-1");
+    }
+
+    #[test]
+    fn assign_group_action_applies_optional_color() {
+        let mut c = node("c", 0.0, 0.0);
+        c.color = Some("#ff6600".into());
+        let board = board_with(vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0), c], vec![]);
+        let (out, fx) = reduce(
+            board,
+            BoardAction::AssignGroup {
+                ids: vec!["a".into(), "b".into()],
+                group: Some("g1".into()),
+                color: Some("#4c90f0".into()),
+            },
+        );
+        assert_eq!(out.nodes[0].color.as_deref(), Some("#4c90f0"));
+        assert_eq!(out.nodes[1].color.as_deref(), Some("#4c90f0"));
+        // Unlisted node keeps its own color and stays ungrouped.
+        assert_eq!(out.nodes[2].color.as_deref(), Some("#ff6600"));
+        assert_eq!(out.nodes[2].group, None);
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+
+        // Ungrouping without a color leaves the shared color in place.
+        let (out, _) = reduce(
+            out,
+            BoardAction::AssignGroup {
+                ids: vec!["a".into()],
+                group: None,
+                color: None,
+            },
+        );
+        assert_eq!(out.nodes[0].group, None);
+        assert_eq!(out.nodes[0].color.as_deref(), Some("#4c90f0"));
+    }
+
+    #[test]
+    fn set_color_sets_and_clears_listed_nodes() {
+        let mut board = board_with(vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)], vec![]);
+        set_color(&mut board, &["a".into()], Some("#22c55e"));
+        assert_eq!(board.nodes[0].color.as_deref(), Some("#22c55e"));
+        assert_eq!(board.nodes[1].color, None);
+
+        set_color(&mut board, &["a".into()], None);
+        assert_eq!(board.nodes[0].color, None);
+    }
+
+    #[test]
+    fn set_type_applies_to_listed_nodes_only() {
+        let board = board_with(vec![node("a", 0.0, 0.0), node("b", 0.0, 0.0)], vec![]);
+        let (out, fx) = reduce(
+            board,
+            BoardAction::SetType {
+                ids: vec!["b".into()],
+                node_type: NodeType::Note,
+            },
+        );
+        assert_eq!(out.nodes[0].node_type, NodeType::Text);
+        assert_eq!(out.nodes[1].node_type, NodeType::Note);
+        assert_eq!(fx, vec![SideEffect::RequestSave]);
+    }
+
+    #[test]
+    fn add_tag_skips_duplicates_and_blank_tags() {
+        let mut a = node("a", 0.0, 0.0);
+        a.tags = vec!["urgent".into()];
+        let mut board = board_with(vec![a, node("b", 0.0, 0.0)], vec![]);
+        let ids = vec!["a".to_string(), "b".to_string()];
+
+        add_tag(&mut board, &ids, " urgent ");
+        assert_eq!(board.nodes[0].tags, vec!["urgent".to_string()]);
+        assert_eq!(board.nodes[1].tags, vec!["urgent".to_string()]);
+
+        add_tag(&mut board, &ids, "   ");
+        assert_eq!(board.nodes[1].tags.len(), 1);
+    }
+
+    #[test]
+    fn align_left_and_bottom_use_the_selection_bounds() {
+        let mut a = node("a", 10.0, 0.0);
+        a.width = 100.0;
+        a.height = 50.0;
+        let mut b = node("b", 40.0, 200.0);
+        b.width = 20.0;
+        b.height = 80.0;
+        let mut board = board_with(vec![a, b, node("c", -500.0, -500.0)], vec![]);
+        let ids = vec!["a".to_string(), "b".to_string()];
+
+        align_nodes(&mut board, &ids, Alignment::Left);
+        assert_eq!((board.nodes[0].x, board.nodes[1].x), (10.0, 10.0));
+
+        align_nodes(&mut board, &ids, Alignment::Bottom);
+        assert_eq!(board.nodes[0].y, 230.0);
+        assert_eq!(board.nodes[1].y, 200.0);
+
+        // Nodes outside `ids` don't move and don't widen the bounds.
+        assert_eq!((board.nodes[2].x, board.nodes[2].y), (-500.0, -500.0));
+    }
+
+    #[test]
+    fn align_center_x_centers_on_the_bounds() {
+        let mut a = node("a", 0.0, 0.0);
+        a.width = 200.0;
+        let mut b = node("b", 300.0, 100.0);
+        b.width = 100.0;
+        let mut board = board_with(vec![a, b], vec![]);
+
+        align_nodes(&mut board, &["a".into(), "b".into()], Alignment::CenterX);
+
+        // Bounds span 0..400, center 200.
+        assert_eq!(board.nodes[0].x, 100.0);
+        assert_eq!(board.nodes[1].x, 150.0);
+        assert_eq!(board.nodes[1].y, 100.0);
+    }
+
+    #[test]
+    fn align_needs_two_nodes() {
+        let mut board = board_with(vec![node("a", 10.0, 20.0)], vec![]);
+        align_nodes(&mut board, &["a".into()], Alignment::Right);
+        assert_eq!((board.nodes[0].x, board.nodes[0].y), (10.0, 20.0));
+    }
+
+    #[test]
+    fn stack_layout_puts_one_node_at_the_frame_corner() {
+        let a = node("a", 37.0, 81.0);
+        let at = stack_layout(&[&a], (0.0, 50.0, 300.0, 400.0), STACK_SPACING);
+        assert_eq!(at["a"], (0.0, 50.0));
+    }
+
+    #[test]
+    fn stack_layout_orders_by_y_with_even_spacing() {
+        let (a, b, c) = (
+            node("a", 0.0, 300.0),
+            node("b", 40.0, 10.0),
+            node("c", 5.0, 120.0),
+        );
+        let at = stack_layout(&[&a, &b, &c], (0.0, 0.0, 200.0, 400.0), 20.0);
+        // 100-high nodes: b, c, a at 0, 120, 240.
+        assert_eq!(at["b"], (0.0, 0.0));
+        assert_eq!(at["c"], (0.0, 120.0));
+        assert_eq!(at["a"], (0.0, 240.0));
+    }
+
+    #[test]
+    fn stack_layout_overflows_and_the_group_frame_grows() {
+        let nodes: Vec<Node> = (0..4)
+            .map(|i| {
+                let mut n = node(&format!("n{i}"), 0.0, i as f64);
+                n.group = Some("col".into());
+                n
+            })
+            .collect();
+        let frame = (0.0, 0.0, 200.0, 150.0);
+        let members: Vec<&Node> = nodes.iter().collect();
+        let at = stack_layout(&members, frame, 20.0);
+        assert_eq!(at["n3"], (0.0, 360.0));
+
+        let mut board = board_with(nodes, vec![]);
+        restack_group(&mut board, "col", frame);
+        let grown = group_frame(&board, "col", &HashMap::new()).unwrap();
+        assert_eq!(grown, (0.0, 0.0, 200.0, 460.0));
+    }
+
+    #[test]
+    fn group_frame_counts_moved_members_at_their_start() {
+        let mut a = node("a", 0.0, 0.0);
+        a.group = Some("g".into());
+        let mut b = node("b", 500.0, -300.0);
+        b.group = Some("g".into());
+        let board = board_with(vec![a, b], vec![]);
+        let before = HashMap::from([("b".to_string(), (0.0, 120.0))]);
+        assert_eq!(
+            group_frame(&board, "g", &before),
+            Some((0.0, 0.0, 200.0, 220.0))
+        );
+    }
+
+    #[test]
+    fn stacking_a_group_lays_it_out_and_is_remembered() {
+        let mut a = node("a", 10.0, 200.0);
+        a.group = Some("g".into());
+        let mut b = node("b", 60.0, 0.0);
+        b.group = Some("g".into());
+        let c = node("c", 900.0, 900.0);
+        let (out, _) = reduce(
+            board_with(vec![a, b, c], vec![]),
+            BoardAction::SetGroupStacked {
+                group: "g".into(),
+                stacked: true,
+            },
+        );
+        assert!(out.settings.is_stacked("g"));
+        assert_eq!((out.nodes[1].x, out.nodes[1].y), (10.0, 0.0));
+        assert_eq!((out.nodes[0].x, out.nodes[0].y), (10.0, 120.0));
+        assert_eq!((out.nodes[2].x, out.nodes[2].y), (900.0, 900.0));
+
+        let (out, _) = reduce(
+            out,
+            BoardAction::SetGroupStacked {
+                group: "g".into(),
+                stacked: false,
+            },
+        );
+        assert!(out.settings.stacked_groups.is_empty());
+    }
+
+    #[test]
+    fn joining_a_stacked_group_slots_into_its_column() {
+        let mut a = node("a", 0.0, 0.0);
+        a.group = Some("g".into());
+        let mut b = node("b", 0.0, 120.0);
+        b.group = Some("g".into());
+        let mut board = board_with(vec![a, b, node("c", 400.0, 60.0)], vec![]);
+        board.settings.stacked_groups = vec!["g".into()];
+        let (out, _) = reduce(
+            board,
+            BoardAction::AssignGroup {
+                ids: vec!["c".into()],
+                group: Some("g".into()),
+                color: None,
+            },
+        );
+        let pos = |id: &str| {
+            let n = out.nodes.iter().find(|n| n.id == id).unwrap();
+            (n.x, n.y)
+        };
+        assert_eq!(pos("a"), (0.0, 0.0));
+        assert_eq!(pos("c"), (0.0, 120.0));
+        assert_eq!(pos("b"), (0.0, 240.0));
+    }
+
+    fn stacked_column() -> Board {
+        let members = ["a", "b", "c"].iter().enumerate().map(|(i, id)| {
+            let mut n = node(id, 0.0, i as f64 * 120.0);
+            n.group = Some("g".into());
+            n
+        });
+        let mut board = board_with(members.collect(), vec![]);
+        board.settings.stacked_groups = vec!["g".into()];
+        board
+    }
+
+    #[test]
+    fn leaving_a_stacked_group_closes_its_column() {
+        let (out, _) = reduce(
+            stacked_column(),
+            BoardAction::AssignGroup {
+                ids: vec!["a".into()],
+                group: None,
+                color: None,
+            },
+        );
+        let pos = |id: &str| {
+            let n = out.nodes.iter().find(|n| n.id == id).unwrap();
+            (n.x, n.y)
+        };
+        assert_eq!(pos("a"), (0.0, 0.0));
+        assert_eq!(pos("b"), (0.0, 0.0));
+        assert_eq!(pos("c"), (0.0, 120.0));
+        assert!(out.settings.is_stacked("g"));
+
+        let (out, _) = reduce(
+            stacked_column(),
+            BoardAction::DeleteSelected {
+                node_ids: vec!["b".into()],
+                edge_id: None,
+                assets_dir: String::new(),
+            },
+        );
+        assert_eq!((out.nodes[1].id.as_str(), out.nodes[1].y), ("c", 120.0));
+    }
+
+    #[test]
+    fn emptied_stacked_groups_are_forgotten() {
+        let all = vec!["a".into(), "b".into(), "c".into()];
+        let (out, _) = reduce(
+            stacked_column(),
+            BoardAction::AssignGroup {
+                ids: all.clone(),
+                group: Some("renamed".into()),
+                color: None,
+            },
+        );
+        assert!(out.settings.stacked_groups.is_empty());
+
+        let (out, _) = reduce(
+            stacked_column(),
+            BoardAction::DeleteSelected {
+                node_ids: all,
+                edge_id: None,
+                assets_dir: String::new(),
+            },
+        );
+        assert!(out.settings.stacked_groups.is_empty());
+    }
 }
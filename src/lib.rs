@@ -3,4 +3,7 @@ pub mod canvas;
 pub mod components;
 pub mod history;
 pub mod interaction;
+pub mod query;
+pub mod settings;
 pub mod state;
+pub mod tabs;
@@ -0,0 +1,506 @@
+//! Structured node queries for the search overlay.
+//!
+//! A query is whitespace-separated terms. `key:value` terms filter on node
+//! metadata — `type:idea`, `tag:urgent`, `status:todo`, `priority:1` — and every
+//! other term is free text. All terms must match (AND), so
+//! `type:idea tag:urgent roadmap` finds idea nodes tagged `urgent` that mention
+//! "roadmap".
+//!
+//! [`select_by_type`] and [`select_by_tag`] run a single filter over the whole
+//! board for the context menu's bulk-selection commands; [`search_hits`] and
+//! [`result_label`] order and label the search overlay's results list.
+//!
+//! The tag helpers at the bottom ([`all_tags`], [`fuzzy_rank`]) feed the tag
+//! input's autocomplete from the same board vocabulary.
+
+use crate::state::{Board, Node, NodeType};
+use std::collections::{HashMap, HashSet};
+
+/// One metadata condition. Values are stored lowercase; comparisons against the
+/// node are case-insensitive.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Type(NodeType),
+    /// Exact (case-insensitive) tag.
+    Tag(String),
+    /// Exact (case-insensitive) status.
+    Status(String),
+    Priority(u8),
+}
+
+/// A parsed query: metadata filters plus free-text terms, all ANDed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+    /// Lowercased free-text terms; each is a substring match.
+    pub text: Vec<String>,
+}
+
+impl Query {
+    /// True when the query has no terms at all (a blank search box).
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.text.is_empty()
+    }
+}
+
+/// Parse a query string. Keys are case-insensitive. Anything that isn't a
+/// well-formed filter — an unknown key (`foo:bar`), an empty value (`tag:`),
+/// an unknown type, or a priority outside `1..=5` — is kept as free text, so a
+/// URL like `https://x.io` still searches as typed.
+pub fn parse_query(s: &str) -> Query {
+    let mut query = Query::default();
+    for term in s.split_whitespace() {
+        let lower = term.to_lowercase();
+        match parse_filter(&lower) {
+            Some(filter) => query.filters.push(filter),
+            None => query.text.push(lower),
+        }
+    }
+    query
+}
+
+fn parse_filter(term: &str) -> Option<Filter> {
+    let (key, value) = term.split_once(':')?;
+    if value.is_empty() {
+        return None;
+    }
+    match key {
+        "type" => match value.parse::<NodeType>() {
+            Ok(NodeType::Unknown) | Err(_) => None,
+            Ok(t) => Some(Filter::Type(t)),
+        },
+        "tag" => Some(Filter::Tag(value.to_string())),
+        "status" => Some(Filter::Status(value.to_string())),
+        "priority" => value
+            .parse::<u8>()
+            .ok()
+            .filter(|p| (1..=5).contains(p))
+            .map(Filter::Priority),
+        _ => None,
+    }
+}
+
+/// Whether `node` satisfies every term of `q`. An empty query matches nothing
+/// (a blank search box shouldn't select the whole board). Free text matches the
+/// node's body text, and — as plain search always has — its tags and status.
+pub fn matches(node: &Node, q: &Query) -> bool {
+    if q.is_empty() {
+        return false;
+    }
+    let filters_match = q.filters.iter().all(|f| match f {
+        Filter::Type(t) => node.node_type == *t,
+        Filter::Tag(tag) => node.tags.iter().any(|t| t.to_lowercase() == *tag),
+        Filter::Status(status) => node
+            .status
+            .as_deref()
+            .is_some_and(|s| s.to_lowercase() == *status),
+        Filter::Priority(p) => node.priority == Some(*p),
+    });
+    filters_match && q.text.iter().all(|term| text_matches(node, term))
+}
+
+/// Most results the search overlay lists under its input.
+pub const SEARCH_RESULT_LIMIT: usize = 8;
+
+/// Longest result label, in characters, before it's cut with an ellipsis.
+const RESULT_LABEL_CHARS: usize = 48;
+
+/// Every node matching `query`, most recently edited first and otherwise in
+/// board order — the order Enter jumps to and the results list shows.
+pub fn search_hits<'a>(board: &'a Board, query: &Query) -> Vec<&'a Node> {
+    let mut hits: Vec<&Node> = board.nodes.iter().filter(|n| matches(n, query)).collect();
+    hits.sort_by_key(|n| std::cmp::Reverse(n.updated_at));
+    hits
+}
+
+/// One-line label for a search result: the node's title, else the first
+/// non-blank line of its text, shortened to [`RESULT_LABEL_CHARS`].
+pub fn result_label(node: &Node) -> String {
+    let line = node
+        .display_title()
+        .or_else(|| node.text.lines().map(str::trim).find(|l| !l.is_empty()))
+        .unwrap_or("(empty)");
+    if line.chars().count() <= RESULT_LABEL_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(RESULT_LABEL_CHARS - 1).collect();
+    format!("{}\u{2026}", cut.trim_end())
+}
+
+/// Ids of every node matching the one-filter query `filter`.
+fn select_matching(board: &Board, filter: Filter) -> HashSet<String> {
+    let query = Query {
+        filters: vec![filter],
+        text: Vec::new(),
+    };
+    board
+        .nodes
+        .iter()
+        .filter(|n| matches(n, &query))
+        .map(|n| n.id.clone())
+        .collect()
+}
+
+/// Ids of every node of type `node_type` ("Select all IDEA nodes").
+pub fn select_by_type(board: &Board, node_type: NodeType) -> HashSet<String> {
+    select_matching(board, Filter::Type(node_type))
+}
+
+/// Ids of every node carrying `tag`, compared case-insensitively like a
+/// `tag:` search term ("Select all #urgent").
+pub fn select_by_tag(board: &Board, tag: &str) -> HashSet<String> {
+    select_matching(board, Filter::Tag(tag.to_lowercase()))
+}
+
+fn text_matches(node: &Node, term: &str) -> bool {
+    node.text.to_lowercase().contains(term)
+        || node
+            .title
+            .as_deref()
+            .is_some_and(|t| t.to_lowercase().contains(term))
+        || node.tags.iter().any(|t| t.to_lowercase().contains(term))
+        || node
+            .status
+            .as_deref()
+            .is_some_and(|s| s.to_lowercase().contains(term))
+}
+
+/// Every tag used on the board with the number of nodes carrying it, most used
+/// first (ties alphabetical). Tags are distinct as written, so `urgent` and
+/// `Urgent` are listed separately — which is what the autocomplete exists to
+/// steer away from.
+pub fn all_tags(board: &Board) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for node in &board.nodes {
+        for tag in &node.tags {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tags
+}
+
+/// The `candidates` that fuzzily match `query`, best first. Matching is
+/// case-insensitive: an exact match ranks above a prefix match, then a
+/// substring match, then a subsequence match (`ugt` → `urgent`); anything else
+/// is dropped. Ties keep the candidates' order, so passing [`all_tags`] order
+/// puts more-used tags first. A blank query matches everything.
+pub fn fuzzy_rank(candidates: &[String], query: &str) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    let mut ranked: Vec<(u8, &String)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let rank = if lower == query {
+                0
+            } else if lower.starts_with(&query) {
+                1
+            } else if lower.contains(&query) {
+                2
+            } else if is_subsequence(&query, &lower) {
+                3
+            } else {
+                return None;
+            };
+            Some((rank, candidate))
+        })
+        .collect();
+    ranked.sort_by_key(|&(rank, _)| rank);
+    ranked.into_iter().map(|(_, c)| c.clone()).collect()
+}
+
+/// Whether the characters of `needle` appear in `haystack` in order.
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(text: &str) -> Node {
+        Node::new("n".to_string(), 0.0, 0.0, text.to_string())
+    }
+
+    mod parse_query_tests {
+        use super::*;
+
+        #[test]
+        fn empty_query_has_no_terms() {
+            assert!(parse_query("").is_empty());
+            assert!(parse_query("   \t ").is_empty());
+        }
+
+        #[test]
+        fn parses_multiple_filters() {
+            let q = parse_query("type:idea tag:urgent priority:1 status:todo");
+            assert_eq!(
+                q.filters,
+                vec![
+                    Filter::Type(NodeType::Idea),
+                    Filter::Tag("urgent".to_string()),
+                    Filter::Priority(1),
+                    Filter::Status("todo".to_string()),
+                ]
+            );
+            assert!(q.text.is_empty());
+        }
+
+        #[test]
+        fn keys_and_values_are_case_insensitive() {
+            let q = parse_query("TYPE:Note Tag:Urgent");
+            assert_eq!(
+                q.filters,
+                vec![
+                    Filter::Type(NodeType::Note),
+                    Filter::Tag("urgent".to_string())
+                ]
+            );
+        }
+
+        #[test]
+        fn mixes_filters_and_free_text() {
+            let q = parse_query("Roadmap tag:q3 launch");
+            assert_eq!(q.filters, vec![Filter::Tag("q3".to_string())]);
+            assert_eq!(q.text, vec!["roadmap", "launch"]);
+        }
+
+        #[test]
+        fn unknown_keys_are_text() {
+            let q = parse_query("owner:alice https://example.com");
+            assert!(q.filters.is_empty());
+            assert_eq!(q.text, vec!["owner:alice", "https://example.com"]);
+        }
+
+        #[test]
+        fn malformed_values_are_text() {
+            let q = parse_query("tag: type:bogus priority:9 priority:high");
+            assert!(q.filters.is_empty());
+            assert_eq!(
+                q.text,
+                vec!["tag:", "type:bogus", "priority:9", "priority:high"]
+            );
+        }
+    }
+
+    mod matches_tests {
+        use super::*;
+
+        fn tagged_idea() -> Node {
+            let mut n = node("Ship the roadmap");
+            n.node_type = NodeType::Idea;
+            n.tags = vec!["Urgent".to_string(), "q3".to_string()];
+            n.status = Some("todo".to_string());
+            n.priority = Some(1);
+            n
+        }
+
+        #[test]
+        fn empty_query_matches_nothing() {
+            assert!(!matches(&tagged_idea(), &parse_query("")));
+        }
+
+        #[test]
+        fn all_filters_must_match() {
+            let n = tagged_idea();
+            assert!(matches(
+                &n,
+                &parse_query("type:idea tag:urgent priority:1 status:todo")
+            ));
+            assert!(!matches(&n, &parse_query("type:idea priority:2")));
+            assert!(!matches(&n, &parse_query("type:note tag:urgent")));
+            assert!(!matches(&n, &parse_query("status:done")));
+        }
+
+        #[test]
+        fn tag_and_status_filters_are_exact() {
+            let n = tagged_idea();
+            assert!(!matches(&n, &parse_query("tag:urg")));
+            assert!(!matches(&n, &parse_query("status:to")));
+        }
+
+        #[test]
+        fn missing_metadata_never_matches_a_filter() {
+            let n = node("plain");
+            assert!(!matches(&n, &parse_query("priority:1")));
+            assert!(!matches(&n, &parse_query("status:todo")));
+            assert!(!matches(&n, &parse_query("tag:plain")));
+        }
+
+        #[test]
+        fn free_text_is_a_case_insensitive_substring() {
+            let n = tagged_idea();
+            assert!(matches(&n, &parse_query("ROADMAP")));
+            assert!(matches(&n, &parse_query("ship map")));
+            assert!(!matches(&n, &parse_query("ship launch")));
+        }
+
+        #[test]
+        fn free_text_also_searches_tags_and_status() {
+            let n = tagged_idea();
+            assert!(matches(&n, &parse_query("urg")));
+            assert!(matches(&n, &parse_query("todo")));
+        }
+
+        #[test]
+        fn free_text_searches_the_title() {
+            let mut n = node("https://example.com/q3-plan");
+            n.title = Some("Quarterly Plan".to_string());
+            assert!(matches(&n, &parse_query("quarterly")));
+        }
+
+        #[test]
+        fn filters_combine_with_free_text() {
+            let n = tagged_idea();
+            assert!(matches(&n, &parse_query("type:idea roadmap")));
+            assert!(!matches(&n, &parse_query("type:idea budget")));
+        }
+    }
+
+    mod search_result_tests {
+        use super::*;
+
+        #[test]
+        fn hits_put_recent_edits_first_then_board_order() {
+            let mut a = Node::new("a".into(), 0.0, 0.0, "plan one".into());
+            let b = Node::new("b".into(), 0.0, 0.0, "other".into());
+            let c = Node::new("c".into(), 0.0, 0.0, "Plan two".into());
+            let mut d = Node::new("d".into(), 0.0, 0.0, "plan three".into());
+            a.updated_at = None;
+            d.updated_at = Some(5);
+            let board = Board {
+                nodes: vec![a, b, c, d],
+                ..Board::default()
+            };
+            let ids: Vec<&str> = search_hits(&board, &parse_query("plan"))
+                .iter()
+                .map(|n| n.id.as_str())
+                .collect();
+            assert_eq!(ids, ["d", "a", "c"]);
+        }
+
+        #[test]
+        fn hits_find_tagged_nodes() {
+            let mut a = node("Untitled");
+            a.tags = vec!["Roadmap".into()];
+            let board = Board {
+                nodes: vec![a],
+                ..Board::default()
+            };
+            assert_eq!(search_hits(&board, &parse_query("roadmap")).len(), 1);
+        }
+
+        #[test]
+        fn label_prefers_title_then_first_line() {
+            let mut n = node("\n  First line  \nsecond");
+            assert_eq!(result_label(&n), "First line");
+            n.title = Some("Named".into());
+            assert_eq!(result_label(&n), "Named");
+            assert_eq!(result_label(&node("  ")), "(empty)");
+        }
+
+        #[test]
+        fn long_labels_are_cut_with_an_ellipsis() {
+            let label = result_label(&node(&"word ".repeat(20)));
+            assert_eq!(label.chars().count(), RESULT_LABEL_CHARS);
+            assert!(label.ends_with('\u{2026}'));
+        }
+    }
+
+    mod select_by_tests {
+        use super::*;
+
+        fn board() -> Board {
+            let mut a = Node::new("a".into(), 0.0, 0.0, "A".into());
+            a.node_type = NodeType::Image;
+            a.tags = vec!["Urgent".into()];
+            let mut b = Node::new("b".into(), 0.0, 0.0, "B".into());
+            b.tags = vec!["urgent".into(), "q3".into()];
+            let mut c = Node::new("c".into(), 0.0, 0.0, "C".into());
+            c.node_type = NodeType::Image;
+            Board {
+                nodes: vec![a, b, c],
+                ..Board::default()
+            }
+        }
+
+        fn ids(list: &[&str]) -> HashSet<String> {
+            list.iter().map(|id| id.to_string()).collect()
+        }
+
+        #[test]
+        fn selects_every_node_of_a_type() {
+            assert_eq!(select_by_type(&board(), NodeType::Image), ids(&["a", "c"]));
+            assert!(select_by_type(&board(), NodeType::Md).is_empty());
+        }
+
+        #[test]
+        fn selects_every_node_with_a_tag_ignoring_case() {
+            assert_eq!(select_by_tag(&board(), "URGENT"), ids(&["a", "b"]));
+            assert_eq!(select_by_tag(&board(), "q3"), ids(&["b"]));
+            assert!(select_by_tag(&board(), "urg").is_empty());
+        }
+    }
+
+    mod tag_suggestion_tests {
+        use super::*;
+
+        fn tags(list: &[&str]) -> Vec<String> {
+            list.iter().map(|t| t.to_string()).collect()
+        }
+
+        #[test]
+        fn all_tags_counts_usage_most_used_first() {
+            let mut a = node("A");
+            a.tags = tags(&["urgent", "q3"]);
+            let mut b = node("B");
+            b.tags = tags(&["urgent", "Urgent"]);
+            let board = Board {
+                nodes: vec![a, b],
+                ..Board::default()
+            };
+            assert_eq!(
+                all_tags(&board),
+                vec![
+                    ("urgent".to_string(), 2),
+                    ("Urgent".to_string(), 1),
+                    ("q3".to_string(), 1),
+                ]
+            );
+        }
+
+        #[test]
+        fn exact_prefix_ranks_before_substring() {
+            let candidates = tags(&["backlog", "log", "logistics"]);
+            assert_eq!(
+                fuzzy_rank(&candidates, "log"),
+                tags(&["log", "logistics", "backlog"])
+            );
+        }
+
+        #[test]
+        fn subsequence_matches_rank_last() {
+            let candidates = tags(&["ugly", "urgent", "gut"]);
+            assert_eq!(fuzzy_rank(&candidates, "ugt"), tags(&["urgent"]));
+            assert_eq!(fuzzy_rank(&candidates, "ug"), tags(&["ugly", "urgent"]));
+        }
+
+        #[test]
+        fn matching_is_case_insensitive() {
+            let candidates = tags(&["Urgent", "design"]);
+            assert_eq!(fuzzy_rank(&candidates, "URG"), tags(&["Urgent"]));
+            assert_eq!(fuzzy_rank(&candidates, "  Design "), tags(&["design"]));
+        }
+
+        #[test]
+        fn blank_query_keeps_every_candidate_in_order() {
+            let candidates = tags(&["b", "a"]);
+            assert_eq!(fuzzy_rank(&candidates, ""), candidates);
+        }
+    }
+}
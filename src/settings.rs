@@ -0,0 +1,388 @@
+//! User preferences — how the app behaves, as opposed to what's on the board.
+//!
+//! Settings live in `window.localStorage` under a single key (present in both
+//! the Tauri webview and a plain browser) and never touch `board.json`, so agents
+//! editing the board can't clobber them and they follow the user across boards.
+
+use crate::state::DEFAULT_PREVIEW_TIMEOUT_SECS;
+use serde::{Deserialize, Serialize};
+
+/// localStorage key holding the serialized [`Settings`].
+pub const SETTINGS_KEY: &str = "infinite-brainstorm-settings";
+
+/// Default autosave delay: how long after the last edit the board is written.
+pub const DEFAULT_AUTOSAVE_MS: u32 = 220;
+
+/// Longest autosave delay honoured; a larger stored value is clamped to it.
+pub const MAX_AUTOSAVE_MS: u32 = 5_000;
+
+/// Default number of undo steps kept.
+pub const DEFAULT_UNDO_DEPTH: u32 = 100;
+
+/// Fewest undo steps kept; a smaller stored value is raised to it.
+pub const MIN_UNDO_DEPTH: u32 = 1;
+
+/// Most undo steps honoured; a larger stored value is clamped to it. Each step
+/// is a full board snapshot, so this bounds undo memory.
+pub const MAX_UNDO_DEPTH: u32 = 1_000;
+
+/// Persisted user preferences. `#[serde(default)]` on the struct means a stored
+/// value missing a field (written by an older build) fills it from
+/// [`Settings::default`] instead of failing to parse.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// Keep gliding after a fast pan is released, decaying to a stop. Opt-in;
+    /// off by default.
+    pub pan_inertia: bool,
+    /// Node corner radius in world units. `0.0` keeps the square-cornered look.
+    pub node_corner_radius: f64,
+    /// Periodically write a downscaled `board-thumbnail.png` next to
+    /// `board.json` for OS file previews. Desktop app only; off by default.
+    pub board_thumbnail: bool,
+    /// Clicking a link node (without dragging it) copies its URL to the clipboard.
+    pub copy_link_on_click: bool,
+    /// Committing a plain text node whose whole content is a URL turns it into a
+    /// `link` node (so it gets a preview). Off by default — it changes the type.
+    pub auto_link_urls: bool,
+    /// Folder pasted images are stored in. Empty keeps the default `assets/`
+    /// next to `board.json`; a relative path resolves against the board's
+    /// directory. Desktop app only.
+    pub assets_dir: String,
+    /// Hide the shortcut hint bar along the bottom of the canvas. Set by its
+    /// close button; the Settings panel turns it back on.
+    pub hide_help_bar: bool,
+    /// Developer overlay with the last/average/max render time and the board's
+    /// node and edge counts.
+    pub render_stats: bool,
+    /// Wheel zoom speed multiplier; `1.0` zooms ×1.1 per mouse-wheel notch.
+    pub zoom_sensitivity: f64,
+    /// Invert the wheel zoom direction (scrolling down zooms in).
+    pub natural_scroll: bool,
+    /// Lock the board: no dragging, resizing, deleting, editing, creating or
+    /// pasting. Pan, zoom, selection and the view modals keep working.
+    pub read_only: bool,
+    /// Rotate edge labels to run along their edge instead of sitting level.
+    pub rotate_edge_labels: bool,
+    /// Double-clicking just below the node created last stacks the new node
+    /// under it in a column instead of centering it on the cursor.
+    pub stack_new_nodes: bool,
+    /// What turns a drag from a node into a new edge.
+    pub edge_trigger: EdgeTrigger,
+    /// While dragging, outline each dragged node's starting position.
+    pub drag_ghost: bool,
+    /// Milliseconds after the last edit before the board is saved; `0` saves
+    /// right away. Leaving the window or closing it always saves pending edits.
+    pub autosave_ms: u32,
+    /// Seconds a link-preview (or remote markdown) fetch may take before it
+    /// gives up. Desktop app only.
+    pub preview_timeout_secs: u32,
+    /// `User-Agent` sent when fetching link previews. Empty uses the built-in
+    /// browser-like default. Desktop app only.
+    pub preview_user_agent: String,
+    /// How many undo steps to keep ([`MIN_UNDO_DEPTH`]..=[`MAX_UNDO_DEPTH`]).
+    /// Lowering it drops the oldest steps right away.
+    pub undo_depth: u32,
+    /// Where Cmd/Ctrl+V puts copied nodes.
+    pub paste_mode: PasteMode,
+    /// Opacity of unselected edges, `0.1`..=`1.0`. Lowering it keeps dense
+    /// graphs from hiding the node text the edges run over.
+    pub edge_opacity: f64,
+    /// Jump the camera straight to its target (fit, focus, search, minimap)
+    /// instead of animating there.
+    pub reduce_motion: bool,
+    /// How selected nodes stand out from the rest.
+    pub selection_style: SelectionStyle,
+    /// Whether edges draw as straight lines or flowchart-style curves.
+    pub edge_style: EdgeStyle,
+    /// Dragging a text node's resize handle stops where its wrapped text would
+    /// start to be clipped.
+    pub fit_text_on_resize: bool,
+    /// On a directed board, refuse to draw an edge that would close a cycle,
+    /// keeping dependency graphs acyclic.
+    pub prevent_cycles: bool,
+    /// Snap dragged nodes to the 50px canvas grid.
+    pub grid_snap: bool,
+    /// Also land a resize's dragged corner on the grid. Off by default, so
+    /// turning on drag snapping doesn't change how resizing feels.
+    pub resize_snap: bool,
+}
+
+/// Emphasis drawn on selected nodes; see `canvas::selection_look` for the
+/// concrete border and shadow each one uses.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionStyle {
+    /// Soft glow in the border color.
+    #[default]
+    Glow,
+    /// Dark drop shadow that lifts the node off the canvas; reads on light
+    /// node colors where a glow washes out.
+    Shadow,
+    /// Thicker border only, no blur.
+    Outline,
+}
+
+/// Shape of an edge between two nodes; see `canvas::edge_bezier` for the
+/// curve.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeStyle {
+    /// Center-to-center straight line.
+    #[default]
+    Straight,
+    /// Smooth cubic curve leaving and entering the nodes' facing sides, with
+    /// its control points pulled out along that axis — less tangled on a
+    /// densely packed board.
+    Curved,
+}
+
+/// Placement of pasted nodes. Either way the copied cluster keeps its layout;
+/// the modes differ in where it lands.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteMode {
+    /// Centered on the pointer.
+    #[default]
+    Cursor,
+    /// A fixed step down-right of the originals, cascading with each repeat
+    /// paste of the same copy.
+    Offset,
+}
+
+/// How an edge drag is started. Shift-drag is the default; Alt-drag or connect
+/// mode free Shift for selection so a Shift-click meant to extend the selection
+/// can't start a stray edge instead.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeTrigger {
+    /// Shift-drag from a node.
+    #[default]
+    Shift,
+    /// Alt-drag from a node. Alt-click group selection and Alt-drag edge
+    /// splitting aren't available with this trigger.
+    Alt,
+    /// `E` toggles a connect mode in which a plain drag from a node connects.
+    ConnectMode,
+}
+
+impl EdgeTrigger {
+    /// Whether a mouse-down on a node with these modifiers starts an edge.
+    pub fn starts_edge(self, shift: bool, alt: bool, connect_mode: bool) -> bool {
+        match self {
+            EdgeTrigger::Shift => shift,
+            EdgeTrigger::Alt => alt,
+            EdgeTrigger::ConnectMode => connect_mode,
+        }
+    }
+
+    /// Connect mode after the toggle key is pressed in state `on`. It flips
+    /// under [`EdgeTrigger::ConnectMode`] and stays off under the others.
+    pub fn toggle_connect_mode(self, on: bool) -> bool {
+        self == EdgeTrigger::ConnectMode && !on
+    }
+
+    /// Shortcut-bar hint for creating an edge.
+    pub fn hint(self) -> &'static str {
+        match self {
+            EdgeTrigger::Shift => "[SHIFT+DRAG] connect",
+            EdgeTrigger::Alt => "[ALT+DRAG] connect",
+            EdgeTrigger::ConnectMode => "[E] connect mode",
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            pan_inertia: false,
+            node_corner_radius: 0.0,
+            board_thumbnail: false,
+            copy_link_on_click: true,
+            auto_link_urls: false,
+            assets_dir: String::new(),
+            hide_help_bar: false,
+            render_stats: false,
+            zoom_sensitivity: 1.0,
+            natural_scroll: false,
+            read_only: false,
+            rotate_edge_labels: false,
+            stack_new_nodes: false,
+            edge_trigger: EdgeTrigger::Shift,
+            drag_ghost: true,
+            autosave_ms: DEFAULT_AUTOSAVE_MS,
+            preview_timeout_secs: DEFAULT_PREVIEW_TIMEOUT_SECS,
+            preview_user_agent: String::new(),
+            undo_depth: DEFAULT_UNDO_DEPTH,
+            paste_mode: PasteMode::Cursor,
+            edge_opacity: 1.0,
+            reduce_motion: false,
+            selection_style: SelectionStyle::Glow,
+            edge_style: EdgeStyle::Straight,
+            fit_text_on_resize: false,
+            prevent_cycles: false,
+            grid_snap: true,
+            resize_snap: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Parse a stored settings blob, falling back to defaults when it's corrupt
+    /// so a bad localStorage value can never break startup.
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    /// [`Settings::undo_depth`] clamped to `MIN_UNDO_DEPTH..=MAX_UNDO_DEPTH`, as
+    /// the history capacity.
+    pub fn history_depth(&self) -> usize {
+        self.undo_depth.clamp(MIN_UNDO_DEPTH, MAX_UNDO_DEPTH) as usize
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+/// Load the persisted settings, or defaults when nothing is stored.
+pub fn load_settings() -> Settings {
+    local_storage()
+        .and_then(|s| s.get_item(SETTINGS_KEY).ok().flatten())
+        .map(|json| Settings::from_json(&json))
+        .unwrap_or_default()
+}
+
+/// Persist `settings`. Best-effort: a serialization or storage error (e.g. quota
+/// exceeded) is ignored rather than surfaced mid-interaction.
+pub fn save_settings(settings: &Settings) {
+    if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(settings)) {
+        let _ = storage.set_item(SETTINGS_KEY, &json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod settings_json_tests {
+        use super::*;
+
+        #[test]
+        fn round_trips() {
+            let settings = Settings {
+                pan_inertia: true,
+                node_corner_radius: 8.0,
+                board_thumbnail: true,
+                copy_link_on_click: false,
+                auto_link_urls: true,
+                assets_dir: "~/Pictures/brainstorm".to_string(),
+                hide_help_bar: true,
+                render_stats: true,
+                zoom_sensitivity: 1.5,
+                natural_scroll: true,
+                read_only: true,
+                rotate_edge_labels: true,
+                stack_new_nodes: true,
+                edge_trigger: EdgeTrigger::ConnectMode,
+                drag_ghost: false,
+                autosave_ms: 2_000,
+                preview_timeout_secs: 30,
+                preview_user_agent: "curl/8.5.0".to_string(),
+                undo_depth: 250,
+                paste_mode: PasteMode::Offset,
+                edge_opacity: 0.4,
+                reduce_motion: true,
+                selection_style: SelectionStyle::Shadow,
+                edge_style: EdgeStyle::Curved,
+                fit_text_on_resize: true,
+                prevent_cycles: true,
+                grid_snap: false,
+                resize_snap: true,
+            };
+            let json = serde_json::to_string(&settings).unwrap();
+            assert_eq!(Settings::from_json(&json), settings);
+        }
+
+        #[test]
+        fn missing_fields_take_defaults() {
+            assert_eq!(Settings::from_json("{}"), Settings::default());
+        }
+
+        #[test]
+        fn pan_inertia_is_opt_in() {
+            assert!(!Settings::default().pan_inertia);
+        }
+
+        #[test]
+        fn drags_snap_by_default_but_resizes_do_not() {
+            let settings = Settings::default();
+            assert!(settings.grid_snap);
+            assert!(!settings.resize_snap);
+        }
+
+        #[test]
+        fn corrupt_value_falls_back_to_defaults() {
+            assert_eq!(Settings::from_json("not json"), Settings::default());
+            assert_eq!(
+                Settings::from_json(r#"{"pan_inertia":"yes"}"#),
+                Settings::default()
+            );
+        }
+    }
+
+    mod history_depth_tests {
+        use super::*;
+
+        #[test]
+        fn clamps_the_stored_depth() {
+            let depth = |undo_depth| {
+                Settings {
+                    undo_depth,
+                    ..Settings::default()
+                }
+                .history_depth()
+            };
+            assert_eq!(depth(250), 250);
+            assert_eq!(depth(0), MIN_UNDO_DEPTH as usize);
+            assert_eq!(depth(u32::MAX), MAX_UNDO_DEPTH as usize);
+        }
+    }
+
+    mod edge_trigger_tests {
+        use super::*;
+
+        #[test]
+        fn connect_mode_toggles_on_and_off() {
+            let trigger = EdgeTrigger::ConnectMode;
+            let on = trigger.toggle_connect_mode(false);
+            assert!(on);
+            assert!(trigger.starts_edge(false, false, on));
+            let off = trigger.toggle_connect_mode(on);
+            assert!(!off);
+            assert!(!trigger.starts_edge(true, true, off));
+        }
+
+        #[test]
+        fn toggle_key_does_nothing_for_modifier_triggers() {
+            for trigger in [EdgeTrigger::Shift, EdgeTrigger::Alt] {
+                assert!(!trigger.toggle_connect_mode(false));
+            }
+        }
+
+        #[test]
+        fn modifier_triggers_ignore_the_other_key() {
+            assert!(EdgeTrigger::Shift.starts_edge(true, false, false));
+            assert!(!EdgeTrigger::Shift.starts_edge(false, true, true));
+            assert!(EdgeTrigger::Alt.starts_edge(false, true, false));
+            assert!(!EdgeTrigger::Alt.starts_edge(true, false, false));
+        }
+
+        #[test]
+        fn defaults_to_shift() {
+            assert_eq!(Settings::default().edge_trigger, EdgeTrigger::Shift);
+            let json = serde_json::to_string(&EdgeTrigger::ConnectMode).unwrap();
+            assert_eq!(json, r#""connect_mode""#);
+        }
+    }
+}
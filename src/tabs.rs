@@ -0,0 +1,273 @@
+//! Open-board tabs: the per-board state a window juggles when several boards
+//! are open at once.
+//!
+//! Each [`BoardTab`] owns everything that belongs to one board — its file path,
+//! `Board`, `Camera`, node selection and undo history — and [`Tabs`] keeps them
+//! in a keyed, ordered collection with one active tab. The container is pure
+//! (no signals, no DOM, no IPC), so open/close/switch and history isolation are
+//! unit-tested natively.
+//!
+//! How the app contexts map onto it: `BoardDataCtx`, `SelectionCtx` and the
+//! dispatcher's history keep describing *the active board only*, so no
+//! component has to learn about tabs. The `App` holds a `Tabs` in a
+//! `StoredValue` plus an `active_board` signal (the active index). Switching
+//! tabs first stashes the live `board`/`camera`/`selected_nodes` signals and the
+//! history `Rc` contents into the outgoing tab ([`Tabs::active_mut`]), then
+//! calls [`Tabs::switch_to`] and writes the incoming tab's values back into the
+//! same signals — one set of signals, swapped per tab, instead of one context
+//! per tab. Saving keeps going through `request_save`, which writes to the
+//! active tab's `path`; on desktop the switch also re-points the backend's board
+//! path and file watcher at that path so external edits to a background board
+//! never reload over the active one.
+
+use crate::app::Snapshot;
+use crate::history::History;
+use crate::state::{Board, Camera};
+use std::collections::HashSet;
+
+/// One open board and the view/edit state that travels with it.
+#[derive(Clone)]
+pub struct BoardTab {
+    /// Where the board is loaded from and saved to; also the tab's identity.
+    pub path: String,
+    pub board: Board,
+    pub camera: Camera,
+    pub selected: HashSet<String>,
+    pub history: History<Snapshot>,
+}
+
+impl BoardTab {
+    /// A freshly opened board: default camera, nothing selected, and an empty
+    /// history holding up to `history_depth` undo steps.
+    pub fn new(path: impl Into<String>, board: Board, history_depth: usize) -> Self {
+        Self {
+            path: path.into(),
+            board,
+            camera: Camera::default(),
+            selected: HashSet::new(),
+            history: History::new(history_depth),
+        }
+    }
+
+    /// Label for the tab strip: the board's folder name (boards are
+    /// `<folder>/board.json`), or the file name when there's no folder.
+    pub fn title(&self) -> &str {
+        let mut parts = self.path.rsplit(['/', '\\']).filter(|p| !p.is_empty());
+        let file = parts.next().unwrap_or(&self.path);
+        match parts.next() {
+            Some(folder) if file == "board.json" => folder,
+            _ => file,
+        }
+    }
+}
+
+/// Ordered open tabs with exactly one active. Never empty: the last tab can't
+/// be closed, so there's always a board to show.
+#[derive(Clone)]
+pub struct Tabs {
+    tabs: Vec<BoardTab>,
+    active: usize,
+}
+
+impl Tabs {
+    pub fn new(first: BoardTab) -> Self {
+        Self {
+            tabs: vec![first],
+            active: 0,
+        }
+    }
+
+    /// Open `tab` and make it active, returning its index. A board whose path
+    /// is already open isn't opened twice: its existing tab is activated and
+    /// `tab` is dropped.
+    pub fn open(&mut self, tab: BoardTab) -> usize {
+        let index = match self.tabs.iter().position(|t| t.path == tab.path) {
+            Some(existing) => existing,
+            None => {
+                self.tabs.push(tab);
+                self.tabs.len() - 1
+            }
+        };
+        self.active = index;
+        index
+    }
+
+    /// Close the tab at `index`, returning it. Refuses (returns `None`) for an
+    /// out-of-range index or the only remaining tab. Closing the active tab
+    /// activates its right neighbour (or the new last tab); closing one to its
+    /// left keeps the same tab active.
+    pub fn close(&mut self, index: usize) -> Option<BoardTab> {
+        if index >= self.tabs.len() || self.tabs.len() == 1 {
+            return None;
+        }
+        let closed = self.tabs.remove(index);
+        if index < self.active || self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+        Some(closed)
+    }
+
+    /// Make the tab at `index` active. Returns `false` (no change) when out of
+    /// range.
+    pub fn switch_to(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        self.active = index;
+        true
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> &BoardTab {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut BoardTab {
+        &mut self.tabs[self.active]
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Always `false` (a `Tabs` is never empty); provided alongside [`Tabs::len`].
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BoardTab> {
+        self.tabs.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Node;
+
+    fn tab(path: &str) -> BoardTab {
+        BoardTab::new(path, Board::default(), 10)
+    }
+
+    fn paths(tabs: &Tabs) -> Vec<&str> {
+        tabs.iter().map(|t| t.path.as_str()).collect()
+    }
+
+    mod open_tests {
+        use super::*;
+
+        #[test]
+        fn open_appends_and_activates() {
+            let mut tabs = Tabs::new(tab("/a/board.json"));
+            assert_eq!(tabs.open(tab("/b/board.json")), 1);
+            assert_eq!(tabs.active_index(), 1);
+            assert_eq!(paths(&tabs), ["/a/board.json", "/b/board.json"]);
+        }
+
+        #[test]
+        fn reopening_a_path_switches_to_it() {
+            let mut tabs = Tabs::new(tab("/a/board.json"));
+            tabs.open(tab("/b/board.json"));
+            tabs.active_mut().camera.zoom = 2.0;
+            assert_eq!(tabs.open(tab("/a/board.json")), 0);
+            assert_eq!(tabs.len(), 2);
+            // The already-open tab wasn't replaced by the fresh one.
+            tabs.switch_to(1);
+            assert_eq!(tabs.active().camera.zoom, 2.0);
+        }
+    }
+
+    mod close_tests {
+        use super::*;
+
+        fn three() -> Tabs {
+            let mut tabs = Tabs::new(tab("a"));
+            tabs.open(tab("b"));
+            tabs.open(tab("c"));
+            tabs
+        }
+
+        #[test]
+        fn last_tab_cannot_close() {
+            let mut tabs = Tabs::new(tab("a"));
+            assert!(tabs.close(0).is_none());
+            assert_eq!(tabs.len(), 1);
+        }
+
+        #[test]
+        fn closing_active_activates_right_neighbour() {
+            let mut tabs = three();
+            tabs.switch_to(1);
+            assert_eq!(tabs.close(1).map(|t| t.path), Some("b".into()));
+            assert_eq!(tabs.active().path, "c");
+        }
+
+        #[test]
+        fn closing_active_last_tab_activates_new_last() {
+            let mut tabs = three();
+            tabs.close(2);
+            assert_eq!(tabs.active().path, "b");
+        }
+
+        #[test]
+        fn closing_a_tab_to_the_left_keeps_the_active_one() {
+            let mut tabs = three();
+            tabs.close(0);
+            assert_eq!(tabs.active().path, "c");
+            assert_eq!(tabs.active_index(), 1);
+        }
+
+        #[test]
+        fn out_of_range_is_refused() {
+            let mut tabs = three();
+            assert!(tabs.close(7).is_none());
+            assert!(!tabs.switch_to(7));
+            assert_eq!(tabs.len(), 3);
+        }
+    }
+
+    mod independence_tests {
+        use super::*;
+
+        #[test]
+        fn each_tab_keeps_its_own_board_and_history() {
+            let mut tabs = Tabs::new(tab("a"));
+            let before = tabs.active().board.clone();
+            let a = tabs.active_mut();
+            a.history.push((before, HashSet::new()));
+            a.board
+                .nodes
+                .push(Node::new("n1".into(), 0.0, 0.0, "A".into()));
+
+            tabs.open(tab("b"));
+            assert!(tabs.active().board.nodes.is_empty());
+            assert!(!tabs.active().history.can_undo());
+
+            tabs.switch_to(0);
+            let a = tabs.active_mut();
+            assert!(a.history.can_undo());
+            let current = (a.board.clone(), a.selected.clone());
+            let (restored, _) = a.history.undo(current).unwrap();
+            assert!(restored.nodes.is_empty());
+        }
+    }
+
+    mod title_tests {
+        use super::*;
+
+        #[test]
+        fn uses_folder_for_board_json() {
+            assert_eq!(tab("/Users/me/ideas/board.json").title(), "ideas");
+            assert_eq!(tab("C:\\work\\plan\\board.json").title(), "plan");
+        }
+
+        #[test]
+        fn uses_file_name_otherwise() {
+            assert_eq!(tab("/tmp/other.json").title(), "other.json");
+            assert_eq!(tab("board.json").title(), "board.json");
+        }
+    }
+}